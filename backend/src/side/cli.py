@@ -4,7 +4,7 @@ import argparse
 from side.cli_handlers.auth import handle_login, handle_profile, handle_usage
 from side.cli_handlers.connect import handle_connect
 from side.cli_handlers.audit import handle_audit, handle_health
-from side.cli_handlers.intel import handle_index, handle_watch, handle_strategy, handle_maintenance
+from side.cli_handlers.intel import handle_index, handle_tests, handle_test_impact, handle_coverage, handle_watch, handle_strategy, handle_maintenance
 from side.cli_handlers.wizard import handle_wizard
 
 # Standard libs setup for fast start
@@ -47,6 +47,22 @@ def main():
     # Intelligence
     index_parser = subparsers.add_parser("index", help="Index the codebase")
     index_parser.add_argument("path", nargs="?", default=".", help="Project path to index")
+    index_parser.add_argument("--diff", nargs="+", metavar="REV", help="Compare the index of two git revisions instead: BASE [HEAD] (head defaults to HEAD)")
+    index_parser.add_argument("--migrate-store", action="store_true", help="Move per-directory .side/local.json indexes into one central store under .side/tree")
+    index_parser.add_argument("--import-scip", nargs="+", metavar="FILE", help="Import .scip indexes (rust-analyzer, scip-python, scip-typescript) as precise edges")
+    index_parser.add_argument("--export", nargs=2, metavar=("FORMAT", "OUTPUT"), help="Also export the index (scip | lsif) to OUTPUT")
+
+    tests_parser = subparsers.add_parser("tests", help="List the tests that exercise a function or method")
+    tests_parser.add_argument("symbol", help="Function or method (`add_item`, `Order.add_item`, `Order::add_item`)")
+    tests_parser.add_argument("--depth", type=int, default=2, help="Call hops allowed between a test and the symbol")
+
+    impact_parser = subparsers.add_parser("test-impact", help="List the tests affected by your changes, with commands to run them")
+    impact_parser.add_argument("--since", default="HEAD", metavar="REV", help="Revision to compare the working tree with")
+    impact_parser.add_argument("--depth", type=int, default=3, help="Call hops allowed between a test and a changed function")
+
+    coverage_parser = subparsers.add_parser("coverage", help="Ingest line coverage (lcov, Cobertura XML, llvm-cov JSON)")
+    coverage_parser.add_argument("reports", nargs="*", metavar="REPORT", help="Coverage reports (default: lcov.info, coverage.xml, ...)")
+    coverage_parser.add_argument("--commit", metavar="SHA", help="Commit the coverage was measured at (default: HEAD)")
 
     watch_parser = subparsers.add_parser("watch", help="Start the Real-time Watcher")
     watch_parser.add_argument("path", nargs="?", default=".", help="Project path to watch")
@@ -71,6 +87,9 @@ def main():
         "health": handle_health,
         "audit": handle_audit,
         "index": handle_index,
+        "tests": handle_tests,
+        "test-impact": handle_test_impact,
+        "coverage": handle_coverage,
         "watch": handle_watch,
         "strategy": handle_strategy,
         "maintenance": handle_maintenance,
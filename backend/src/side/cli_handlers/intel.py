@@ -5,12 +5,19 @@ from .utils import ux, get_engine
 
 def handle_index(args):
     """Manual Indexer."""
+    if getattr(args, "diff", None):
+        return handle_index_diff(args)
     ux.display_status("Analyzing Project Structure...", level="info")
     from side.intel.context_service import ContextService
     
     path = Path(args.path).resolve()
+    if getattr(args, "migrate_store", False):
+        from side.intel.index_store import migrate_to_central
+        moved = migrate_to_central(path)
+        ux.display_status(f"Moved {moved} directory indexes into {path / '.side' / 'tree'}.", level="success")
+
     intel = ContextService(path, engine=get_engine())
-    
+
     # Run the Feed
     graph = asyncio.run(intel.feed())
     
@@ -20,6 +27,126 @@ def handle_index(args):
     else:
         ux.display_status("Context Updated.", level="success")
     ux.display_status("Identity successfully projected to: .side/project.json", level="info")
+
+    for scip_file in getattr(args, "import_scip", None) or []:
+        from side.intel.scip_import import import_scip
+        try:
+            stats = import_scip(path, Path(scip_file).resolve(), intel.engine.schema)
+            ux.display_status(f"Imported {stats['definitions']} definitions and {stats['edges']} precise edges "
+                              f"from {scip_file} ({stats['tool']}).", level="success")
+        except (OSError, ValueError, IndexError) as e:
+            ux.display_status(f"Could not import {scip_file}: {e}", level="error")
+
+    if getattr(args, "export", None):
+        from side.intel.scip_export import export_index
+        fmt, output = args.export
+        try:
+            stats = export_index(path, intel.engine.schema, fmt.lower(), Path(output).resolve())
+            ux.display_status(f"Exported {stats['documents']} documents, {stats['symbols']} symbols and "
+                              f"{stats['references']} references to {output} ({fmt}).", level="success")
+        except ValueError as e:
+            ux.display_status(str(e), level="error")
+    ux.display_footer()
+
+def handle_index_diff(args):
+    """Index diff between two git revisions (no checkout)."""
+    from side.intel.index_diff import diff_revisions
+
+    if len(args.diff) > 2:
+        ux.display_status("Usage: side index [PATH] --diff BASE [HEAD]", level="error")
+        return
+    base, head = args.diff[0], args.diff[1] if len(args.diff) == 2 else "HEAD"
+    try:
+        report = diff_revisions(Path(args.path).resolve(), base, head)
+    except ValueError as e:
+        ux.display_status(str(e), level="error")
+        return
+
+    ux.display_header("Index Diff", subtitle=f"{base}..{head}")
+    summary = report["summary"]
+    ux.render_table("Summary", ["Added", "Removed", "Changed", "Signatures", "New Calls", "Removed Calls"], [[
+        summary["added"], summary["removed"], summary["changed"],
+        summary["signature_changes"], summary["new_calls"], summary["removed_calls"],
+    ]])
+    entity_rows = [["+", e["symbol"], e["type"], e["file"]] for e in report["added"]]
+    entity_rows += [["-", e["symbol"], e["type"], e["file"]] for e in report["removed"]]
+    entity_rows += [["~", e["symbol"], e["type"], e["file"]] for e in report["changed"]]
+    if entity_rows:
+        ux.render_table("Entities", ["", "Symbol", "Type", "File"], entity_rows)
+    if report["signature_changes"]:
+        ux.render_table("Signature Changes", ["Symbol", "Before", "After"],
+                        [[s["symbol"], s["before"] or "", s["after"] or ""] for s in report["signature_changes"]])
+    if report["new_calls"]:
+        ux.render_table("New Call Edges", ["Caller", "Callee", "Confidence"],
+                        [[f"{c['caller']} ({c['caller_file']})", f"{c['callee']} ({c['callee_file']})", c["confidence"]]
+                         for c in report["new_calls"]])
+    ux.display_footer()
+
+def handle_tests(args):
+    """Tests covering a symbol, from the indexed test map."""
+    from side.intel.graph_query import find_tests
+
+    engine = get_engine()
+    report = find_tests(engine.schema, engine.get_project_id(), args.symbol, depth=args.depth)
+    if not report["matches"]:
+        ux.display_status(f"No indexed function or method named `{args.symbol}`. Run `side index` first?", level="error")
+        return
+
+    ux.display_header("Tests", subtitle=args.symbol)
+    if report["tests"]:
+        ux.render_table("Covering Tests", ["Test", "File", "Depth", "Via", "Confidence"], [
+            [t["symbol"], t["file"], t["depth"], t["via"], t["confidence"]] for t in report["tests"]
+        ])
+    else:
+        ux.display_status(f"No test reaches `{args.symbol}` within {args.depth} call hop(s).", level="warning")
+    ux.display_footer()
+
+def handle_test_impact(args):
+    """Tests affected by the working tree's changes, and the commands that run only them."""
+    from side.intel.impacted_tests import affected_tests
+
+    try:
+        report = affected_tests(Path.cwd(), get_engine().schema, args.since, depth=args.depth)
+    except ValueError as e:
+        ux.display_status(str(e), level="error")
+        return
+
+    ux.display_header("Test Impact", subtitle=f"since {args.since}")
+    ux.display_status(report["summary"], level="info")
+    if report["tests"]:
+        ux.render_table("Affected Tests", ["Test", "Runner", "File", "Depth", "Via"], [
+            [t["test"], t["runner"], t["file"], t["depth"], t["via"] or ""] for t in report["tests"]
+        ])
+        ux.render_table("Run", ["Directory", "Command"], [[c["cwd"], c["command"]] for c in report["commands"]])
+    if report.get("warning"):
+        ux.display_status(report["warning"], level="warning")
+    ux.display_footer()
+
+def handle_coverage(args):
+    """Maps coverage reports onto functions and types, then flags edited code that stays untested."""
+    from side.intel.coverage import ingest_coverage, untested_changes
+
+    root = Path.cwd()
+    schema = get_engine().schema
+    try:
+        stats = ingest_coverage(root, schema, [Path(r) for r in args.reports] or None, commit=args.commit)
+    except ValueError as e:
+        ux.display_status(str(e), level="error")
+        return
+
+    ux.display_header("Coverage", subtitle=f"{', '.join(stats['formats'])} at {stats['commit'][:12]}")
+    ux.display_status(f"{stats['entities']} entities in {stats['files']} files ({stats['line_percent']}% of lines covered)",
+                      level="success")
+    if stats["unmatched"]:
+        ux.display_status(f"{len(stats['unmatched'])} report files are not part of this project", level="warning")
+    try:
+        flagged = untested_changes(root, schema)
+    except ValueError:
+        flagged = []    # Not a git checkout: nothing to compare with
+    if flagged:
+        ux.render_table("Untested Changes", ["Symbol", "Type", "File", "Coverage"], [
+            [w["symbol"], w["type"], w["file"], f"{w['percent']:g}% of {w['total_lines']} lines"] for w in flagged
+        ])
     ux.display_footer()
 
 def handle_watch(args):
@@ -66,6 +66,10 @@ class SideConfig:
     enable_cache_eviction: bool = field(default_factory=lambda: os.getenv("ENABLE_CACHE_EVICTION", "true").lower() == "true")
     enable_advanced_scavengers: bool = field(default_factory=lambda: os.getenv("ENABLE_ADVANCED_SCAVENGERS", "false").lower() == "true")
 
+    # Sparse index layout: "central" (one tree under <project>/.side) | "scattered" (per-directory .side/local.json)
+    # Empty: central once a project has been migrated, scattered otherwise
+    index_store: str = field(default_factory=lambda: os.getenv("SIDE_INDEX_STORE", ""))
+
     def __post_init__(self) -> None:
         """Initialize after creation."""
         # Get API keys from environment if not set
@@ -274,11 +274,49 @@ async def handle_ai_mistake_repeat(event: Event):
     pattern = payload.get("pattern", "")
     previous_rejection = payload.get("previous_rejection", {})
     
-    logger.warning(f"AI repeating rejected pattern: {pattern}")
-    
     from side.storage import get_activity_ledger
     ledger = get_activity_ledger()
 
+    # rustc errors from cargo runs: only a repeat once the same code was seen before
+    diagnostic = payload.get("diagnostic")
+    if diagnostic:
+        project_id = payload.get("project_id", "global")
+        recent = ledger.get_recent_activities(project_id, limit=50)
+        earlier = [
+            act for act in recent
+            if act.action == "rustc_error"
+            and act.payload.get("code") == diagnostic.get("code")
+            and act.payload.get("captured_at", 0) < diagnostic.get("captured_at", 0)
+        ]
+        if not earlier:
+            return
+
+        suggestion = diagnostic.get("suggestion") or {}
+        advice = suggestion.get("message") or "Avoid this pattern based on previous rejection."
+        if suggestion.get("replacement"):
+            advice += f" (e.g. `{suggestion['replacement']}`)"
+        if diagnostic.get("explain"):
+            advice += f" See `{diagnostic['explain']}`."
+
+        logger.warning(f"AI repeating rustc error {pattern} ({len(earlier) + 1} times)")
+        ledger.log_activity(
+            project_id=project_id,
+            tool="pattern_detector",
+            action="rejection_context_injected",
+            payload={
+                "pattern": pattern,
+                "reason": previous_rejection.get("reason", "Unknown"),
+                "advice": advice,
+                "occurrence_count": len(earlier) + 1,
+                "file": diagnostic.get("file"),
+                "line": diagnostic.get("line"),
+                "label": diagnostic.get("label"),
+            }
+        )
+        return
+
+    logger.warning(f"AI repeating rejected pattern: {pattern}")
+
     # Inject rejection context
     ledger.log_activity(
         project_id="global",
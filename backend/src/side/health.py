@@ -161,30 +161,29 @@ class SystemAwareness:
             "scale": "SMALL" # Default
         }
         
-        # 1. Scan Extensions (Tier-1 Polyglot)
-        for ext in [".py", ".js", ".ts", ".go", ".rs", ".swift", ".kt"]:
-            if list(Path.cwd().glob(f"**/*{ext}")):
-                fingerprint["languages"].add(ext[1:])
+        from side.intel.languages import registry as language_registry
+        root = Path.cwd()
+
+        # 1. Scan Extensions (Registered Languages)
+        for spec in language_registry.all():
+            if any(next(root.glob(f"**/*{ext}"), None) for ext in spec.extensions):
+                fingerprint["languages"].add(spec.name)
         
-        # 2. Project Markers (Ecosystem Identifiers)
+        # 2. Project Markers (Ecosystem Identifiers, from LanguageSpec manifests)
         markers = {
-            "Cargo.toml": ("rust", "cargo"),
-            "go.mod": ("go", "go_modules"),
-            "Package.swift": ("swift", "swiftpm"),
-            "Podfile": ("swift", "cocoapods"),
-            "build.gradle.kts": ("kotlin", "gradle"),
-            "AndroidManifest.xml": ("kotlin", "android"),
-            "requirements.txt": ("python", "pip"),
-            "package.json": ("javascript", "npm")
+            marker: (spec.name, infra)
+            for spec in language_registry.all()
+            for marker, infra in spec.manifests.items()
+            if infra and not any(ch in marker for ch in "*?[")
         }
         
         for marker, (lang, infra) in markers.items():
-            if (Path.cwd() / marker).exists():
+            if (root / marker).exists():
                 fingerprint["languages"].add(lang)
                 fingerprint["infra"].add(infra)
                 
                 # Deep Dive: Framework detection inside markers
-                content = (Path.cwd() / marker).read_text().lower()
+                content = (root / marker).read_text().lower()
                 
                 # Python
                 if marker == "requirements.txt":
@@ -204,8 +203,13 @@ class SystemAwareness:
                 
                 # Rust
                 if marker == "Cargo.toml":
-                    if "tokio" in content: fingerprint["frameworks"].add("tokio")
-                    if "serde" in content: fingerprint["frameworks"].add("serde")
+                    from side.intel.cargo_manifest import load_cargo_workspace
+                    workspace = load_cargo_workspace(root)
+                    crate_deps = workspace.dependency_names() if workspace else set()
+                    for fw in ["tokio", "serde", "axum", "actix-web", "rocket", "diesel", "sqlx"]:
+                        if fw in crate_deps: fingerprint["frameworks"].add(fw)
+                    if workspace and len(workspace.members()) > 1:
+                        fingerprint["infra"].add("cargo_workspace")
 
         # 3. Scan Scale (Heuristic - Non-Blocking)
         # OPTIMIZATION: Do NOT scan entire subtree in hot path.
@@ -6,25 +6,88 @@
 
 SIDE_SOCKET="/tmp/side.sock"
 
+# Sends one shell signal. python3 builds the JSON from its arguments, so commands
+# with quotes or backslashes stay valid (and nc -U varies across platforms).
+# Arguments: command [exit_code output_file]
+_side_emit() {
+    python3 -c '
+import json, socket, sys, time
+sock_path, command, cwd = sys.argv[1:4]
+payload = {"command": command, "cwd": cwd, "timestamp": int(time.time())}
+if len(sys.argv) > 5:
+    payload.update(exit_code=int(sys.argv[4]), output_file=sys.argv[5])
+signal = {"category": "shell", "tool": "shell", "action": "command", "payload": payload}
+s = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM)
+s.connect(sock_path)
+s.sendall((json.dumps(signal) + "\n").encode())
+s.close()
+' "$SIDE_SOCKET" "$1" "$(pwd)" "${@:2}" > /dev/null 2>&1 &
+}
+
 _side_send_signal() {
-    local cmd="$1"
-    local dir=$(pwd)
-    local ts=$(date +%s)
-    
     # Check if socket exists
     if [ -S "$SIDE_SOCKET" ]; then
-        # Construct JSON signal
-        local signal="{\"category\": \"shell\", \"tool\": \"shell\", \"action\": \"command\", \"payload\": {\"command\": \"$cmd\", \"cwd\": \"$dir\", \"timestamp\": $ts}}"
-        
-        # Send to socket (using python for portability since nc -U varies)
-        python3 -c "import socket, sys; s = socket.socket(socket.AF_UNIX, socket.SOCK_STREAM); s.connect('$SIDE_SOCKET'); s.sendall((sys.argv[1] + '\n').encode()); s.close()" "$signal" > /dev/null 2>&1 &
+        _side_emit "$1"
+    fi
+}
+
+# Cargo compiles: copy compiler output (human or --message-format=json) to a
+# private temp file so the listener can structure rustc errors.
+_side_send_cargo_signal() {
+    if [ -S "$SIDE_SOCKET" ]; then
+        _side_emit "$1" "$2" "$3"
+    else
+        rm -f "$3"
     fi
 }
 
+# True when `cargo()` below runs the command line, and so reports it itself:
+# `cargo <build|b|check|c|test|t|clippy> ...`, after any VAR=value assignments
+_side_cargo_wrapped() {
+    local line="$1"
+    while [ "${line%% *}" != "$line" ]; do
+        case "${line%% *}" in
+            *=*|time) line="${line#* }" ;;
+            *) break ;;
+        esac
+    done
+    case "$line " in
+        "cargo build "*|"cargo b "*|"cargo check "*|"cargo c "*|"cargo test "*|"cargo t "*|"cargo clippy "*) return 0 ;;
+    esac
+    return 1
+}
+
+cargo() {
+    case "$1" in
+        build|b|check|c|test|t|clippy)
+            local out
+            out=$(umask 077; mktemp "${TMPDIR:-/tmp}/side-cargo.XXXXXX") || { command cargo "$@"; return $?; }
+            # Piped output loses cargo's terminal detection: keep colors and the progress bar
+            local color=never progress=auto
+            if [ -t 2 ]; then
+                color=always
+                progress=always
+            fi
+            # Both tees finish inside the pipeline, so the capture is complete before the signal
+            (
+                set -o pipefail
+                export CARGO_TERM_COLOR="${CARGO_TERM_COLOR:-$color}"
+                export CARGO_TERM_PROGRESS_WHEN="${CARGO_TERM_PROGRESS_WHEN:-$progress}"
+                export CARGO_TERM_PROGRESS_WIDTH="${CARGO_TERM_PROGRESS_WIDTH:-${COLUMNS:-80}}"
+                { command cargo "$@" 2>&1 1>&3 3>&- | tee -a "$out" >&2; } 3>&1 | tee -a "$out"
+            )
+            local code=$?
+            _side_send_cargo_signal "cargo $*" "$code" "$out"
+            return $code
+            ;;
+    esac
+    command cargo "$@"
+}
+
 # Zsh Integration
 if [ -n "$ZSH_VERSION" ]; then
     preexec() {
-        _side_send_signal "$1"
+        _side_cargo_wrapped "$1" || _side_send_signal "$1"
     }
 fi
 
@@ -0,0 +1,441 @@
+"""
+API Surface - Public items per package, snapshotted per commit, and semver checks.
+
+The surface is what downstream users can name:
+- Rust: `pub` items of library crates whose modules are `pub` all the way up, `pub`
+  methods of public types and the items of public traits
+- TypeScript/JavaScript: exported declarations and public members of exported classes
+- Python: the names in a module's `__all__`, and the public methods of listed classes
+
+A commit's surface is built from the git object store like an index diff (nothing is
+checked out) and cached per commit under `.side/cache/api/` the first time a check needs it.
+`check_api_break` compares two surfaces: removed items, changed signatures, new required
+trait bounds and new required trait items are major, additions and relaxed bounds minor,
+and a diff that leaves the surface alone is a patch.
+"""
+
+import ast
+import hashlib
+import json
+import logging
+import re
+import tempfile
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Set, Tuple
+
+from side.intel import rust_cfg, rust_modules
+from side.intel.index_cache import CACHE_VERSION, IndexCache
+from side.intel.index_diff import git, materialize, resolve_tree
+from side.utils.crypto import shield
+
+logger = logging.getLogger(__name__)
+
+API_VERSION = 1
+LEVELS = ("patch", "minor", "major")
+SKIPPED_TYPES = {"impl", "file", "module", "macro"}     # Not API items of their own
+RUST_TYPE_KINDS = {"struct", "enum", "union", "trait", "type"}
+RUST_DECL_RE = re.compile(r"^(.*?\b(?:fn|struct|enum|union|trait|type)\s+(?:r#)?\w+)")
+TEST_FILE_RE = re.compile(r"(?:^|/)(?:tests?|__tests__)/|\.(?:test|spec)\.[cm]?[jt]sx?$|(?:^|/)test_[^/]*\.py$")
+MAX_HEADER_LINES = 12
+
+
+def _item(path: str, language: str, package: str, file: str, entity: Dict[str, Any], **extra) -> Dict[str, Any]:
+    item = {
+        "path": path,
+        "kind": entity.get("type", "name"),
+        "language": language,
+        "package": package,
+        "file": file,
+        "line": entity.get("line"),
+        "signature": entity.get("signature"),
+    }
+    item.update({k: v for k, v in extra.items() if v is not None})
+    return item
+
+
+def _entities(path: Path, cache: IndexCache) -> List[Dict[str, Any]]:
+    from side.intel.tree_indexer import get_file_dna
+    return get_file_dna(path, cache).get("semantics", {}).get("entities", [])
+
+
+# --- Rust ---
+
+def _has_body(lines: List[str], line: int) -> bool:
+    """Whether the declaration on `line` has a `{ ... }` body (a trait method with a default)."""
+    depth = 0
+    for text in lines[line - 1:line - 1 + MAX_HEADER_LINES]:
+        for ch in text.split("//", 1)[0]:
+            if ch in "([":
+                depth += 1
+            elif ch in ")]":
+                depth -= 1
+            elif depth == 0 and ch in "{;":
+                return ch == "{"
+    return True
+
+
+def _rust_items(root: Path, cache: IndexCache) -> Dict[str, Dict[str, Any]]:
+    from side.intel.cargo_manifest import parse_crate
+
+    items: Dict[str, Dict[str, Any]] = {}
+    # Fresh trees: `pub mod` items and targets decide what is reachable, and the indexer's may be stale
+    for tree in rust_modules.module_trees(root, cached=False):
+        try:
+            crate = parse_crate(tree.crate_dir / "Cargo.toml")
+        except Exception as e:
+            logger.debug(f"API surface: unreadable manifest in {tree.crate_dir}: {e}")
+            continue
+        lib = next((t for t in crate.targets if t.kind == "lib"), None) if crate else None
+        if lib is None:
+            continue  # Binaries have no downstream users
+        lib_name = lib.name.replace("-", "_")
+
+        public_types: Set[str] = set()
+        members = []
+        for file, file_module in tree.files.items():
+            if file_module.split("::")[0] != lib_name:
+                continue
+            rel = file.relative_to(root).as_posix()
+            lines = file.read_text(errors="ignore").splitlines()
+            for entity in _entities(file, cache):
+                line = entity.get("line") or 0
+                module = tree.module_at(file, line)
+                cfg = rust_cfg.combine([tree.cfg_at(file, line), entity.get("cfg")])
+                if not module or not tree.exported(module) or rust_cfg.is_test_only(cfg):
+                    continue
+                if entity.get("parent"):
+                    members.append((entity, module, rel, cfg, lines))
+                elif entity["type"] not in SKIPPED_TYPES and entity.get("visibility") == "pub":
+                    path = rust_modules.qualify(module, entity["name"])
+                    items[path] = _item(path, "rust", crate.name, rel, entity, cfg=cfg)
+                    if entity["type"] in RUST_TYPE_KINDS:
+                        public_types.add(entity["name"])
+
+        # Methods need their owner: inherent `pub fn`s of public types, every item of a public trait
+        for entity, module, rel, cfg, lines in members:
+            owner = entity["parent"]
+            required = None
+            if entity.get("parent_type") == "trait":
+                if rust_modules.qualify(module, owner) not in items:
+                    continue
+                required = not _has_body(lines, entity["line"])
+            elif entity.get("owner_kind") != "impl" or entity.get("visibility") != "pub" or owner not in public_types:
+                continue
+            path = rust_modules.qualify(module, entity["name"], owner)
+            items[path] = _item(path, "rust", crate.name, rel, entity, cfg=cfg, required=required)
+    return items
+
+
+def _closing_angle(text: str) -> int:
+    depth = 0
+    for i, ch in enumerate(text):
+        if ch == "<":
+            depth += 1
+        elif ch == ">" and text[i - 1:i] != "-":
+            depth -= 1
+            if depth == 0:
+                return i
+    return len(text)
+
+
+def _split_top(text: str, sep: str, limit: int = -1, keep_empty: bool = False) -> List[str]:
+    """Splits on `sep` outside brackets and generics."""
+    parts, depth, start, i = [], 0, 0, 0
+    while i < len(text):
+        ch = text[i]
+        if ch in "([{<":
+            depth += 1
+        elif ch in ")]}" or (ch == ">" and text[i - 1:i] != "-"):
+            depth -= 1
+        elif depth == 0 and text.startswith(sep, i) and limit != 0:
+            parts.append(text[start:i])
+            start = i = i + len(sep)
+            limit -= 1
+            continue
+        i += 1
+    parts.append(text[start:])
+    return [p.strip() for p in parts if keep_empty or p.strip()]
+
+
+def _split_colon(clause: str) -> Tuple[str, str]:
+    """`T: Clone + Send` -> ('T', 'Clone + Send'); `::` paths are not separators."""
+    for m in re.finditer(r"(?<!:):(?!:)", clause):
+        return clause[:m.start()].strip(), clause[m.end():].strip()
+    return clause.strip(), ""
+
+
+def split_bounds(signature: str) -> Tuple[str, Set[str]]:
+    """
+    (signature without bounds, {"T: Clone", "Self: Send", ...}) from generic parameters,
+    supertraits and `where` clauses, so bound changes can be told from other changes.
+    """
+    m = RUST_DECL_RE.match(signature)
+    if not m:
+        return signature, set()
+    head, rest = m.group(1), signature[m.end():].strip()
+    bounds: Set[str] = set()
+
+    def add(name: str, bound: str) -> None:
+        bounds.update(f"{name}: {' '.join(b.split())}" for b in _split_top(bound, "+"))
+
+    params = []
+    if rest.startswith("<"):
+        end = _closing_angle(rest)
+        for part in _split_top(rest[1:end], ","):
+            decl, *default = _split_top(part, "=", 1)
+            name, bound = _split_colon(decl)
+            params.append(" = ".join([name, *default]))
+            add(name, bound)
+        rest = rest[end + 1:].strip()
+
+    rest, *where = _split_top(f" {rest} ", " where ", 1, keep_empty=True)
+    for clause in _split_top(where[0], ",") if where else []:
+        add(*_split_colon(clause))
+    if re.search(r"\btrait\s", head) and rest.startswith(":"):
+        add("Self", rest[1:])
+        rest = ""
+
+    shape = head + (f"<{', '.join(params)}>" if params else "") + (f" {rest}" if rest else "")
+    return " ".join(shape.split()), bounds
+
+
+# --- TypeScript / JavaScript ---
+
+def _package_name(root: Path, path: Path, names: Dict[Path, str]) -> str:
+    """`name` of the nearest package.json at or above `path`, within `root`."""
+    directory = path.parent
+    while True:
+        if directory not in names:
+            manifest = directory / "package.json"
+            name = None
+            if manifest.is_file():
+                try:
+                    name = json.loads(manifest.read_text()).get("name")
+                except (OSError, ValueError):
+                    pass
+                name = name or directory.name
+            names[directory] = name
+        if names[directory] or directory == root or directory.parent == directory:
+            return names[directory] or root.name
+        directory = directory.parent
+
+
+def _ts_items(root: Path, cache: IndexCache) -> Dict[str, Dict[str, Any]]:
+    from side.intel.call_graph import iter_source_files
+
+    items: Dict[str, Dict[str, Any]] = {}
+    names: Dict[Path, str] = {}
+    for path in iter_source_files(root, {"typescript", "javascript"}):
+        rel = path.relative_to(root).as_posix()
+        if TEST_FILE_RE.search(rel):
+            continue
+        entities = _entities(path, cache)
+        exported = {e["name"] for e in entities if not e.get("parent") and e.get("visibility") == "export"}
+        package = _package_name(root, path, names)
+        language = "javascript" if path.suffix in (".js", ".jsx", ".mjs", ".cjs") else "typescript"
+        for entity in entities:
+            owner = entity.get("parent")
+            if owner and owner in exported and entity.get("visibility") == "public":
+                key = f"{rel}::{owner}.{entity['name']}"
+            elif not owner and entity["name"] in exported:
+                key = f"{rel}::{entity['name']}"
+            else:
+                continue
+            items[key] = _item(key, language, package, rel, entity)
+    return items
+
+
+# --- Python ---
+
+def _python_all(tree: ast.Module) -> Optional[List[str]]:
+    """Names listed in a module-level `__all__` (assignments and `+=`), or None without one."""
+    names = None
+    for node in tree.body:
+        targets = node.targets if isinstance(node, ast.Assign) else [node.target] if isinstance(node, ast.AugAssign) else []
+        if not any(isinstance(t, ast.Name) and t.id == "__all__" for t in targets):
+            continue
+        if isinstance(node.value, (ast.List, ast.Tuple)):
+            listed = [e.value for e in node.value.elts if isinstance(e, ast.Constant) and isinstance(e.value, str)]
+            names = (names or []) + listed if isinstance(node, ast.AugAssign) else listed
+    return names
+
+
+def _python_imports(tree: ast.Module, module: str, is_package: bool) -> Dict[str, Tuple[str, str]]:
+    """Bound name -> (source module, name there) for `from x import y` at module level."""
+    package = module.split(".") if is_package else module.split(".")[:-1]
+    imports = {}
+    for node in tree.body:
+        if not isinstance(node, ast.ImportFrom):
+            continue
+        if node.level:
+            base = package[:len(package) - (node.level - 1)] if node.level > 1 else package
+            source = ".".join(base + ([node.module] if node.module else []))
+        else:
+            source = node.module or ""
+        for alias in node.names:
+            imports[alias.asname or alias.name] = (source, alias.name)
+    return imports
+
+
+def _python_items(root: Path, cache: IndexCache) -> Dict[str, Dict[str, Any]]:
+    from side.intel.call_graph import iter_source_files, python_module
+
+    modules: Dict[str, Path] = {}
+    for path in iter_source_files(root, {"python"}):
+        rel = path.relative_to(root).as_posix()
+        if not TEST_FILE_RE.search(rel):
+            modules[python_module(rel).removeprefix("src.")] = path   # `src/` layouts import without it
+
+    items: Dict[str, Dict[str, Any]] = {}
+    for module, path in modules.items():
+        try:
+            tree = ast.parse(path.read_text(errors="ignore"))
+        except (SyntaxError, ValueError):
+            continue
+        listed = _python_all(tree)
+        if not listed:
+            continue
+        imports = _python_imports(tree, module, path.name == "__init__.py")
+        for name in listed:
+            source, original = path, name
+            if name in imports and imports[name][0] in modules:
+                source, original = modules[imports[name][0]], imports[name][1]
+            entities = _entities(source, cache)
+            entity = next((e for e in entities if e["name"] == original and not e.get("parent")), {})
+            rel = source.relative_to(root).as_posix()
+            key = f"{module}.{name}"
+            items[key] = _item(key, "python", module.split(".")[0], rel, entity)
+            if entity.get("type") == "class":
+                for method in entities:
+                    if method.get("parent") == original and method.get("visibility") == "public":
+                        member = f"{key}.{method['name']}"
+                        items[member] = _item(member, "python", module.split(".")[0], rel, method)
+    return items
+
+
+# --- Surface & snapshots ---
+
+def api_surface(root: Path, cache: Optional[IndexCache] = None) -> Dict[str, Dict[str, Any]]:
+    """Public items under `root`, keyed `shop::orders::Order::total`, `web/app.ts::render` or `pkg.Order`."""
+    cache = cache or IndexCache(root)
+    items: Dict[str, Dict[str, Any]] = {}
+    for collect in (_rust_items, _ts_items, _python_items):
+        items.update(collect(root, cache))
+    return items
+
+
+def resolve_commit(root: Path, rev: str) -> str:
+    try:
+        return git(root, "rev-parse", "--verify", "--quiet", f"{rev}^{{commit}}").decode().strip()
+    except ValueError:
+        raise ValueError(f"Unknown revision: {rev}") from None
+
+
+def _snapshot_path(root: Path, commit: str) -> Path:
+    prefix = git(root, "rev-parse", "--show-prefix").decode().strip()
+    key = hashlib.sha256(f"{commit}:{prefix}:{CACHE_VERSION}".encode()).hexdigest()[:8]
+    return root / ".side" / "cache" / "api" / f"v{API_VERSION}" / f"{commit}.{key}"
+
+
+def build_snapshot(root: Path, commit: str) -> Dict[str, Any]:
+    """Surface of a commit, read from the git object store into a scratch directory."""
+    with tempfile.TemporaryDirectory(prefix="side-api-") as scratch:
+        materialize(root, resolve_tree(root, commit), Path(scratch))
+        items = api_surface(Path(scratch), IndexCache(root))
+        rust_modules.forget(Path(scratch))
+    return {"commit": commit, "items": items}
+
+
+def load_snapshot(root: Path, rev: str = "HEAD") -> Dict[str, Any]:
+    """Stored surface of the revision's commit, building it on a miss."""
+    commit = resolve_commit(root, rev)
+    path = _snapshot_path(root, commit)
+    if path.exists():
+        try:
+            return json.loads(shield.unseal_file(path))
+        except Exception as e:
+            logger.debug(f"API snapshot unreadable {path}: {e}")
+
+    snapshot = build_snapshot(root, commit)
+    try:
+        path.parent.mkdir(parents=True, exist_ok=True)
+        shield.seal_file(path, json.dumps(snapshot))
+    except OSError as e:
+        logger.debug(f"API snapshot write failed {path}: {e}")
+    return snapshot
+
+
+# --- Semver ---
+
+def _change(item: Dict[str, Any], level: str, reason: str, **extra) -> Dict[str, Any]:
+    change = {k: item.get(k) for k in ("path", "kind", "package", "file", "line")}
+    change.update({"level": level, "reason": reason, **extra})
+    return change
+
+
+def _compare(before: Dict[str, Any], after: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+    old_sig, new_sig = (" ".join((x.get("signature") or "").split()) for x in (before, after))
+    if old_sig != new_sig:
+        if after.get("language") == "rust":
+            old_shape, old_bounds = split_bounds(old_sig)
+            new_shape, new_bounds = split_bounds(new_sig)
+            if old_shape == new_shape:
+                added = sorted(new_bounds - old_bounds)
+                relaxed = sorted(old_bounds - new_bounds)
+                if added:
+                    return _change(after, "major", "new required trait bound", before=old_sig, after=new_sig, bounds=added)
+                if relaxed:
+                    return _change(after, "minor", "relaxed trait bound", before=old_sig, after=new_sig, bounds=relaxed)
+                return None  # Same bounds, written differently
+        return _change(after, "major", "changed signature", before=old_sig, after=new_sig)
+    if after.get("required") and not before.get("required"):
+        return _change(after, "major", "trait item lost its default")
+    if after.get("cfg") != before.get("cfg"):
+        level = "major" if after.get("cfg") else "minor"   # Newly gated items vanish for default builds
+        return _change(after, level, "cfg changed", before=before.get("cfg"), after=after.get("cfg"))
+    return None
+
+
+def diff_surfaces(old: Dict[str, Dict[str, Any]], new: Dict[str, Dict[str, Any]]) -> Dict[str, Any]:
+    """Semver classification of the change from surface `old` to `new`."""
+    changes = [_change(old[k], "major", "removed") for k in sorted(old.keys() - new.keys())]
+    for key in sorted(new.keys() - old.keys()):
+        item = new[key]
+        owner = key.rpartition("::")[0]
+        if item.get("required") and owner in old:
+            changes.append(_change(item, "major", "new required trait item"))
+        else:
+            changes.append(_change(item, "minor", "added"))
+    for key in sorted(old.keys() & new.keys()):
+        change = _compare(old[key], new[key])
+        if change:
+            changes.append(change)
+
+    packages: Dict[str, str] = {}
+    for change in changes:
+        current = packages.get(change["package"], "patch")
+        packages[change["package"]] = max(current, change["level"], key=LEVELS.index)
+    level = max(packages.values(), key=LEVELS.index, default="patch")
+    changes.sort(key=lambda c: (-LEVELS.index(c["level"]), c["path"]))
+
+    report = {
+        "level": level,
+        "summary": {lvl: sum(1 for c in changes if c["level"] == lvl) for lvl in ("major", "minor")},
+        "packages": dict(sorted(packages.items())),
+        "changes": changes,
+    }
+    if level == "major":
+        broken = sorted(p for p, lvl in packages.items() if lvl == "major")
+        report["warning"] = (f"Breaking change for downstream users of {', '.join(broken)}: "
+                             f"{report['summary']['major']} incompatible change(s). "
+                             "Keep the old API (deprecate instead of removing) or plan a major version bump.")
+    return report
+
+
+def check_api_break(root: Path, base: str = "HEAD", head: Optional[str] = None) -> Dict[str, Any]:
+    """Classifies the API change from `base` to `head` (default: the working tree) as major/minor/patch."""
+    old = load_snapshot(root, base)["items"]
+    new = load_snapshot(root, head)["items"] if head else api_surface(root)
+    report = diff_surfaces(old, new)
+    logger.info(f"📜 [API CHECK]: {base}..{head or 'working tree'} is {report['level']} {report['summary']}")
+    return {"base": base, "head": head or "working tree", **report}
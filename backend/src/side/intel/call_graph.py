@@ -0,0 +1,946 @@
+"""
+Call Graph Resolution - Who calls what, across files.
+
+A per-file pass records definitions (with their owning class/impl/trait),
+imports (`import`/`from`, ES `import`/`require`, Rust `use`) and call sites
+attributed to their enclosing function or method. A project-wide pass then
+resolves every call through the file's imports, its own scope and finally the
+global symbol table, and stores `calls` edges whose `confidence` says how the
+target was found.
+
+Tree-sitter scopes are used when available. Without it, Python falls back to
+the stdlib `ast` and Rust/TypeScript to a brace-matching scan.
+"""
+
+import ast
+import logging
+import os
+import posixpath
+import re
+from collections import defaultdict
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Set, Tuple
+
+from side.intel.languages import registry as languages, RUST_REGEX
+from side.intel.tree_indexer import RUST_ITEMS, TS_AVAILABLE
+from side.intel import rust_modules
+from side.intel.ids import entity_id, file_qualified_name, python_module, relationship_id
+from side.intel.lexing import line_index, match_braces, rust_type_name, split_rust_impl, strip_rust_noise, strip_ts_noise
+
+if TS_AVAILABLE:
+    from tree_sitter_languages import get_parser
+
+logger = logging.getLogger(__name__)
+
+CALL_GRAPH_LANGUAGES = {"python", "typescript", "javascript", "rust"}
+
+# How a call target was found. SCIP/LSIF imports are the only source of 1.0.
+CONFIDENCE = {
+    "import": 0.9,      # Named by an explicit import / `use` path
+    "scope": 0.8,       # Defined in the same file, or a self/this/Self method of the caller's owner
+    "qualified": 0.7,   # Type- or path-qualified call (`Order::new`, `Order.create`) on a project type
+    "unique": 0.5,      # The only project function with that name
+    "receiver": 0.3,    # The only project method with that name, called on an unknown receiver
+    "ambiguous": 0.2,   # One of several same-named candidates
+}
+MAX_AMBIGUOUS = 3       # Above this many candidates a bare name says nothing
+
+SELF_RECEIVERS = {"self", "this", "cls", "Self"}
+
+KEYWORDS = {
+    "if", "else", "for", "while", "loop", "match", "return", "switch", "catch", "with",
+    "fn", "function", "typeof", "sizeof", "await", "yield", "new", "delete", "in", "of",
+    "super", "import", "require", "as", "where", "move", "async", "unsafe", "impl", "dyn",
+    "Some", "None", "Ok", "Err", "Box", "Vec", "String",
+}
+
+# `recv.name(`, `recv::name::<T>(`, `name(` over noise-stripped source
+CALL_RE = re.compile(
+    r"(?:(?P<recv>[A-Za-z_$][\w$]*(?:\s*(?:::|\.)\s*[A-Za-z_$][\w$]*)*)\s*(?P<sep>::|\.)\s*)?"
+    r"(?P<name>[A-Za-z_$][\w$]*)\s*(?:::\s*<[^>()]*>\s*)?(?:<[^>()=;]*>\s*)?\("
+)
+
+RUST_FN_RE = RUST_REGEX["def"]
+RUST_IMPL_RE = re.compile(RUST_ITEMS["impl"].pattern, re.MULTILINE)
+RUST_TRAIT_RE = re.compile(RUST_ITEMS["trait"].pattern, re.MULTILINE)
+RUST_USE_RE = re.compile(r"^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+([^;]+);", re.MULTILINE)
+
+TS_CLASS_RE = re.compile(r"^\s*(?:export\s+)?(?:default\s+)?(?:abstract\s+)?class\s+([\w$]+)", re.MULTILINE)
+TS_FUNCTION_RE = re.compile(r"\b(?:async\s+)?function\s*\*?\s*([\w$]+)\s*(?:<[^>]*>)?\s*\(")
+TS_ARROW_RE = re.compile(
+    r"\b(?:const|let|var)\s+([\w$]+)\s*(?::[^=;]+)?=\s*(?:async\s+)?"
+    r"(?:function\b[^(]*\(|(?:<[^>]*>\s*)?\([^()]*\)\s*(?::[^=;{]+)?=>|[\w$]+\s*=>)"
+)
+TS_METHOD_RE = re.compile(
+    r"^\s*(?:(?:public|private|protected|static|async|readonly|override|get|set)\s+)*\*?\s*"
+    r"([\w$]+)\s*(?:<[^>]*>)?\s*\([^;{]*\)\s*(?::\s*[^;{]+)?\{",
+    re.MULTILINE,
+)
+TS_FIELD_ARROW_RE = re.compile(
+    r"^\s*(?:(?:public|private|protected|static|readonly)\s+)*([\w$]+)\s*(?::[^=;]+)?=\s*"
+    r"(?:async\s+)?(?:\([^()]*\)|[\w$]+)\s*(?::[^=;{]+)?=>",
+    re.MULTILINE,
+)
+TS_IMPORT_RE = re.compile(r"\bimport\s+(?:type\s+)?([\w$*{}\s,]+?)\s+from\s+['\"]([^'\"]+)['\"]")
+TS_REQUIRE_RE = re.compile(r"\b(?:const|let|var)\s+([\w$]+|\{[^}]*\})\s*=\s*require\(\s*['\"]([^'\"]+)['\"]\s*\)")
+TS_DEFAULT_EXPORT_RE = re.compile(r"\bexport\s+default\s+(?:async\s+)?(?:function\s*\*?\s*|class\s+)?([\w$]+)")
+TS_SOURCE_SUFFIXES = (".ts", ".tsx", ".js", ".jsx", ".mjs", ".cjs")
+
+
+@dataclass
+class Definition:
+    name: str
+    kind: str                       # 'function', 'method'
+    owner: Optional[str] = None     # Class, impl target or trait
+    line: int = 0
+    end_line: int = 0
+
+    @property
+    def qualname(self) -> str:
+        return f"{self.owner}.{self.name}" if self.owner else self.name
+
+
+@dataclass
+class Import:
+    alias: str                      # Name bound in the importing file ('*' for globs)
+    module: str                     # As written: 'pkg.orders', '../orders', 'crate::orders'
+    symbol: Optional[str] = None    # Imported member; None for whole-module / namespace imports
+
+
+@dataclass
+class CallSite:
+    name: str
+    line: int
+    caller: Optional[Definition] = None     # None for module-level code
+    receiver: Optional[str] = None          # 'self', 'Order', 'crate::orders', '?' for expressions
+    separator: Optional[str] = None         # '.' or '::'
+
+
+@dataclass
+class FileSymbols:
+    path: str                       # Project-relative, POSIX separators
+    language: str
+    module: str                     # 'pkg.orders' | 'src/orders' | 'orders::model'
+    definitions: List[Definition] = field(default_factory=list)
+    classes: List[str] = field(default_factory=list)
+    imports: List[Import] = field(default_factory=list)
+    calls: List[CallSite] = field(default_factory=list)
+    default_export: Optional[str] = None
+    scopes: List[Tuple[int, int, str]] = field(default_factory=list)   # Rust inline `mod` blocks: (first, last line, module)
+
+    def top_level(self, name: str) -> List[Definition]:
+        return [d for d in self.definitions if d.name == name and d.owner is None]
+
+    def methods(self, owner: str, name: str) -> List[Definition]:
+        return [d for d in self.definitions if d.name == name and d.owner == owner]
+
+    def module_at(self, line: int) -> str:
+        inside = [s for s in self.scopes if s[0] < line <= s[1]]
+        return max(inside, key=lambda s: s[0])[2] if inside else self.module
+
+    def imported(self, alias: str) -> Optional[Import]:
+        return next((i for i in self.imports if i.alias == alias), None)
+
+
+@dataclass
+class ResolvedCall:
+    caller: Optional[Definition]
+    caller_file: str
+    target: Definition
+    target_file: str
+    confidence: float
+    line: int
+    reason: str
+
+
+# --- Imports ---
+
+def expand_rust_use(tree: str, prefix: Tuple[str, ...] = ()) -> List[Tuple[Tuple[str, ...], str]]:
+    """Expands `a::{b, c::d as e, f::*}` into (path, alias) pairs."""
+    tree = " ".join(tree.split())
+    if tree.startswith("::"):
+        tree = tree[2:]
+    brace = tree.find("{")
+    if brace != -1 and tree.endswith("}"):
+        head = tuple(p.strip() for p in tree[:brace].rstrip(":").split("::") if p.strip())
+        items, depth, start, body = [], 0, 0, tree[brace + 1:-1]
+        for i, ch in enumerate(body):
+            depth += 1 if ch == "{" else -1 if ch == "}" else 0
+            if ch == "," and depth == 0:
+                items.append(body[start:i])
+                start = i + 1
+        items.append(body[start:])
+        pairs = []
+        for item in filter(str.strip, items):
+            pairs.extend(expand_rust_use(item.strip(), prefix + head))
+        return pairs
+
+    alias = None
+    if " as " in tree:
+        tree, alias = (part.strip() for part in tree.rsplit(" as ", 1))
+    path = prefix + tuple(p.strip() for p in tree.split("::") if p.strip())
+    if path and path[-1] == "self":
+        path = path[:-1]
+    if not path or alias == "_":
+        return []
+    return [(path, alias or path[-1])]
+
+
+def _rust_imports(code: str) -> List[Import]:
+    imports = []
+    for m in RUST_USE_RE.finditer(code):
+        for path, alias in expand_rust_use(m.group(1)):
+            imports.append(Import(alias=alias, module="::".join(path[:-1]), symbol=path[-1]) if alias != "*"
+                           else Import(alias="*", module="::".join(path[:-1])))
+    return imports
+
+
+def _ts_imports(code: str) -> List[Import]:
+    imports = []
+    for m in TS_IMPORT_RE.finditer(code):
+        clause, source = m.group(1), m.group(2)
+        named = re.search(r"\{([^}]*)\}", clause)
+        if named:
+            for item in filter(None, (s.strip() for s in named.group(1).split(","))):
+                item = re.sub(r"^type\s+", "", item)
+                name, _, alias = item.partition(" as ")
+                imports.append(Import(alias=(alias or name).strip(), module=source, symbol=name.strip()))
+            clause = clause.replace(named.group(0), "")
+        if ns := re.search(r"\*\s*as\s+([\w$]+)", clause):
+            imports.append(Import(alias=ns.group(1), module=source))
+            clause = clause.replace(ns.group(0), "")
+        if default := re.match(r"\s*([\w$]+)", clause):
+            imports.append(Import(alias=default.group(1), module=source, symbol="default"))
+    for m in TS_REQUIRE_RE.finditer(code):
+        target, source = m.group(1), m.group(2)
+        if target.startswith("{"):
+            for item in filter(None, (s.strip() for s in target[1:-1].split(","))):
+                name, _, alias = item.partition(":")
+                imports.append(Import(alias=(alias or name).strip(), module=source, symbol=name.strip()))
+        else:
+            imports.append(Import(alias=target, module=source))
+    return imports
+
+
+def _python_imports(tree: ast.AST) -> List[Import]:
+    imports = []
+    for node in ast.walk(tree):
+        if isinstance(node, ast.Import):
+            for alias in node.names:
+                imports.append(Import(alias=alias.asname or alias.name, module=alias.name))
+        elif isinstance(node, ast.ImportFrom):
+            module = "." * node.level + (node.module or "")
+            for alias in node.names:
+                if alias.name == "*":
+                    imports.append(Import(alias="*", module=module))
+                else:
+                    imports.append(Import(alias=alias.asname or alias.name, module=module, symbol=alias.name))
+    return imports
+
+
+# --- Definitions and call sites ---
+
+def _extract_python(content: str, symbols: FileSymbols) -> None:
+    try:
+        tree = ast.parse(content)
+    except (SyntaxError, ValueError):
+        return
+    symbols.imports = _python_imports(tree)
+
+    def record_call(call: ast.Call, caller: Optional[Definition]):
+        func = call.func
+        if isinstance(func, ast.Name):
+            symbols.calls.append(CallSite(func.id, call.lineno, caller))
+        elif isinstance(func, ast.Attribute):
+            receiver = ast.unparse(func.value) if isinstance(func.value, (ast.Name, ast.Attribute)) else "?"
+            symbols.calls.append(CallSite(func.attr, call.lineno, caller, receiver, "."))
+
+    def visit(node: ast.AST, owner: Optional[str], caller: Optional[Definition]):
+        if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef)):
+            definition = Definition(node.name, "method" if owner else "function", owner,
+                                    node.lineno, getattr(node, "end_lineno", node.lineno))
+            symbols.definitions.append(definition)
+            for decorator in node.decorator_list:
+                visit(decorator, owner, caller)
+            for stmt in node.body:
+                visit(stmt, None, definition)
+            return
+        if isinstance(node, ast.ClassDef):
+            symbols.classes.append(node.name)
+            for expr in node.decorator_list + node.bases:
+                visit(expr, owner, caller)
+            for stmt in node.body:
+                visit(stmt, node.name, caller)
+            return
+        if isinstance(node, ast.Call):
+            record_call(node, caller)
+        for child in ast.iter_child_nodes(node):
+            visit(child, owner, caller)
+
+    visit(tree, None, None)
+
+
+def _scan_scopes(code: str, owner_matches, fn_matches, method_matches, symbols: FileSymbols) -> None:
+    """
+    Brace-matching scan shared by the Rust and TypeScript fallbacks.
+    Owner and function headers open at their next `{`; calls belong to the innermost function body.
+    """
+    braces = match_braces(code)
+    line_of = line_index(code)
+
+    def body_span(start: int) -> Optional[Tuple[int, int]]:
+        open_pos, stop = code.find("{", start), code.find(";", start)
+        if open_pos == -1 or (stop != -1 and stop < open_pos):
+            return None
+        return open_pos, braces[open_pos]
+
+    owners = []  # (open, close, name)
+    for pos, name in owner_matches:
+        span = body_span(pos)
+        if span:
+            owners.append((span[0], span[1], name))
+
+    def innermost(pos: int, spans):
+        inside = [s for s in spans if s[0] < pos <= s[1]]
+        return max(inside, key=lambda s: s[0]) if inside else None
+
+    functions = []  # (open, close, name position, Definition)
+    def_positions = set()
+
+    def add_function(pos: int, name: str, span: Optional[Tuple[int, int]], method_only: bool = False):
+        owner = innermost(pos, owners)
+        enclosing_fn = innermost(pos, [(f[0], f[1]) for f in functions])
+        if owner and enclosing_fn and enclosing_fn[0] > owner[0]:
+            owner = None  # Nested inside a method body
+        if method_only and not owner:
+            return
+        definition = Definition(name, "method" if owner else "function", owner[2] if owner else None, line_of(pos),
+                                line_of(span[1]) if span else line_of(pos))
+        symbols.definitions.append(definition)
+        def_positions.add(pos)
+        if span:
+            functions.append((span[0], span[1], pos, definition))
+
+    for pos, name, span in fn_matches:
+        add_function(pos, name, span if span is not None else body_span(pos))
+    for pos, name in method_matches:
+        if pos not in def_positions:
+            add_function(pos, name, body_span(pos), method_only=True)
+
+    for m in CALL_RE.finditer(code):
+        name = m.group("name")
+        name_pos = m.start("name")
+        if name_pos in def_positions or (name in KEYWORDS and not m.group("recv")):
+            continue
+        before = code[max(0, m.start() - 40):m.start()].rstrip()
+        if re.search(r"\b(?:fn|function|new|class|struct|enum|trait|impl|macro_rules!)$", before):
+            continue
+        receiver = re.sub(r"\s+", "", m.group("recv")) if m.group("recv") else None
+        separator = m.group("sep")
+        if receiver is None and before.endswith("."):
+            receiver, separator = "?", "."
+        elif receiver and before.endswith("."):
+            receiver = "?"  # foo().bar.baz() - receiver is an expression
+        enclosing = [f for f in functions if f[0] < m.start() <= f[1]]
+        caller = max(enclosing, key=lambda f: f[0])[3] if enclosing else None
+        symbols.calls.append(CallSite(name, line_of(name_pos), caller, receiver, separator))
+
+
+def _extract_rust(content: str, symbols: FileSymbols) -> None:
+    code = strip_rust_noise(content)
+    symbols.imports = _rust_imports(code)
+
+    owners = []
+    for m in RUST_IMPL_RE.finditer(code):
+        _, target = split_rust_impl(m.group(1))
+        if target:
+            owners.append((m.end(), target))
+    for m in RUST_TRAIT_RE.finditer(code):
+        owners.append((m.end(), m.group(1)))
+        symbols.classes.append(m.group(1))
+    for m in re.finditer(RUST_ITEMS["type"].pattern, code, re.MULTILINE):
+        symbols.classes.append(m.group(2))
+
+    fns = [(m.start(1), m.group(1), None) for m in RUST_FN_RE.finditer(code)]
+    _scan_scopes(code, owners, fns, [], symbols)
+
+
+def extract_ts(content: str, symbols: FileSymbols) -> None:
+    code = strip_ts_noise(content)
+    symbols.imports = _ts_imports(content)
+    if default := TS_DEFAULT_EXPORT_RE.search(code):
+        symbols.default_export = default.group(1)
+
+    owners = [(m.end(), m.group(1)) for m in TS_CLASS_RE.finditer(code)]
+    symbols.classes.extend(name for _, name in owners)
+
+    fns = [(m.start(1), m.group(1), None) for m in TS_FUNCTION_RE.finditer(code)]
+    for m in TS_ARROW_RE.finditer(code):
+        rest = code[m.end():].lstrip()
+        if not rest.startswith("{"):
+            # Expression body: runs to the end of the statement
+            stop = re.search(r";|\n\s*\n|$", code[m.end():])
+            fns.append((m.start(1), m.group(1), (m.end() - 1, m.end() + stop.start())))
+        else:
+            fns.append((m.start(1), m.group(1), None))
+    methods = [(m.start(1), m.group(1)) for m in TS_METHOD_RE.finditer(code) if m.group(1) not in KEYWORDS]
+    methods += [(m.start(1), m.group(1)) for m in TS_FIELD_ARROW_RE.finditer(code)]
+    _scan_scopes(code, owners, fns, sorted(methods), symbols)
+
+
+# Tree-sitter node types per grammar
+TS_FUNCTION_NODES = {
+    "python": {"function_definition"},
+    "rust": {"function_item", "function_signature_item"},
+    "typescript": {"function_declaration", "generator_function_declaration", "method_definition",
+                   "abstract_method_signature"},
+}
+TS_OWNER_NODES = {
+    "python": {"class_definition"},
+    "rust": {"impl_item", "trait_item"},
+    "typescript": {"class_declaration", "abstract_class_declaration", "class"},
+}
+TS_CALL_NODES = {"call", "call_expression"}
+
+
+def _ts_grammar_family(grammar: str) -> str:
+    return "typescript" if grammar in ("typescript", "tsx", "javascript") else grammar
+
+
+def _extract_tree_sitter(grammar: str, content: str, symbols: FileSymbols) -> bool:
+    """Scope walk over the tree-sitter AST. Returns False when the grammar is unavailable."""
+    try:
+        tree = get_parser(grammar).parse(content.encode("utf8"))
+    except Exception as e:
+        logger.debug(f"Tree-sitter call graph unavailable for {grammar}: {e}")
+        return False
+
+    family = _ts_grammar_family(grammar)
+    text = lambda node: node.text.decode("utf8") if node is not None else None
+
+    def owner_name(node) -> Optional[str]:
+        if node.type == "impl_item":
+            target = node.child_by_field_name("type")
+            return rust_type_name(text(target)) if target else None
+        return text(node.child_by_field_name("name"))
+
+    def call_target(fn_node) -> Tuple[Optional[str], Optional[str], Optional[str]]:
+        if fn_node is None:
+            return None, None, None
+        if fn_node.type == "generic_function":
+            return call_target(fn_node.child_by_field_name("function"))
+        if fn_node.type == "identifier":
+            return text(fn_node), None, None
+        if fn_node.type == "scoped_identifier":
+            return text(fn_node.child_by_field_name("name")), text(fn_node.child_by_field_name("path")), "::"
+        if fn_node.type in ("attribute", "member_expression", "field_expression"):
+            obj = fn_node.child_by_field_name("object") or fn_node.child_by_field_name("value")
+            prop = (fn_node.child_by_field_name("attribute") or fn_node.child_by_field_name("property")
+                    or fn_node.child_by_field_name("field"))
+            simple = obj is not None and obj.type in (
+                "identifier", "self", "this", "attribute", "member_expression", "field_expression")
+            return text(prop), re.sub(r"\s+", "", text(obj)) if simple else "?", "."
+        return None, None, None
+
+    def walk(node, owner: Optional[str], caller: Optional[Definition]):
+        kind = node.type
+        if kind in TS_OWNER_NODES.get(family, ()):
+            owner = owner_name(node)
+            if owner and kind != "impl_item":
+                symbols.classes.append(owner)
+        elif kind in TS_FUNCTION_NODES.get(family, ()) or (
+                kind == "variable_declarator" and node.child_by_field_name("value") is not None
+                and node.child_by_field_name("value").type in ("arrow_function", "function", "function_expression")):
+            name = text(node.child_by_field_name("name"))
+            if name:
+                caller = Definition(name, "method" if owner else "function", owner,
+                                    node.start_point[0] + 1, node.end_point[0] + 1)
+                symbols.definitions.append(caller)
+                owner = None
+        elif kind in ("struct_item", "enum_item", "union_item"):
+            symbols.classes.append(text(node.child_by_field_name("name")))
+        elif kind in TS_CALL_NODES:
+            name, receiver, separator = call_target(node.child_by_field_name("function"))
+            if name and (receiver or name not in KEYWORDS):
+                symbols.calls.append(CallSite(name, node.start_point[0] + 1, caller, receiver, separator))
+        for child in node.children:
+            walk(child, owner, caller)
+
+    walk(tree.root_node, None, None)
+    return True
+
+
+def extract_file_symbols(path: Path, content: str, rel_path: str, module: str) -> Optional[FileSymbols]:
+    """Definitions, imports and scoped call sites for one source file."""
+    spec = languages.for_path(path)
+    if not spec or spec.name not in CALL_GRAPH_LANGUAGES:
+        return None
+    symbols = FileSymbols(path=rel_path, language=spec.name, module=module)
+
+    grammar = "tsx" if path.suffix == ".tsx" else spec.grammar
+    if TS_AVAILABLE and grammar and _extract_tree_sitter(grammar, content, symbols):
+        if spec.name == "python":
+            try:
+                symbols.imports = _python_imports(ast.parse(content))
+            except (SyntaxError, ValueError):
+                pass
+        elif spec.name == "rust":
+            symbols.imports = _rust_imports(strip_rust_noise(content))
+        else:
+            symbols.imports = _ts_imports(content)
+            if default := TS_DEFAULT_EXPORT_RE.search(strip_ts_noise(content)):
+                symbols.default_export = default.group(1)
+        return symbols
+
+    if spec.name == "python":
+        _extract_python(content, symbols)
+    elif spec.name == "rust":
+        _extract_rust(content, symbols)
+    else:
+        extract_ts(content, symbols)
+    return symbols
+
+
+# --- Module naming ---
+
+def _ts_module(rel_path: str) -> str:
+    return posixpath.splitext(rel_path)[0]
+
+
+def _rust_module(root: Path, path: Path) -> str:
+    """`orders::model` for the file the `orders` crate loads as `mod model`; integration tests are their own crates."""
+    return rust_modules.module_path(root, path)
+
+
+def module_name(root: Path, path: Path, language: str) -> str:
+    rel_path = path.relative_to(root).as_posix()
+    if language == "python":
+        return python_module(rel_path)
+    if language == "rust":
+        return _rust_module(root, path)
+    return _ts_module(rel_path)
+
+
+# --- Project-wide resolution ---
+
+class ModuleIndex:
+    """
+    Project files by module name, and Python / TypeScript import sources resolved against them.
+    Works over any per-file record with `path`, `language` and `module` (FileSymbols, type_graph.FileTypes).
+    """
+
+    def __init__(self, files: Iterable[Any]):
+        self.files: Dict[str, Any] = {f.path: f for f in files}
+        self.modules: Dict[Tuple[str, str], List[Any]] = defaultdict(list)
+        for f in self.files.values():
+            family = "typescript" if f.language == "javascript" else f.language
+            self.modules[(family, f.module)].append(f)
+            if family == "typescript" and posixpath.basename(f.module) == "index":
+                self.modules[(family, posixpath.dirname(f.module))].append(f)
+
+    def _python_files(self, module: str, importer) -> List[Any]:
+        if module.startswith("."):
+            level = len(module) - len(module.lstrip("."))
+            package = importer.module if importer.path.endswith("__init__.py") else importer.module.rpartition(".")[0]
+            for _ in range(level - 1):
+                package = package.rpartition(".")[0]
+            rest = module.lstrip(".")
+            return self.modules.get(("python", f"{package}.{rest}".strip(".") if rest else package), [])
+        exact = self.modules.get(("python", module))
+        if exact:
+            return exact
+        # Absolute imports are rooted somewhere below the project root (src layouts, monorepos)
+        matches = [fs[0] for (lang, name), fs in self.modules.items()
+                   if lang == "python" and name.endswith("." + module)]
+        if len(matches) > 1:
+            shared = lambda f: len(os.path.commonprefix([f.module, importer.module]))
+            matches.sort(key=shared, reverse=True)
+        return matches[:1]
+
+    def _ts_files(self, source: str, importer) -> List[Any]:
+        if source.startswith("."):
+            base = posixpath.normpath(posixpath.join(posixpath.dirname(importer.path), source))
+        elif source.startswith(("@/", "~/")):
+            suffix = source[2:]
+            return next((fs for (lang, name), fs in self.modules.items()
+                         if lang == "typescript" and (name == suffix or name.endswith("/" + suffix))), [])
+        else:
+            return []  # Package import
+        for ext in TS_SOURCE_SUFFIXES:
+            if base.endswith(ext):
+                base = base[:-len(ext)]
+        return self.modules.get(("typescript", base), [])
+
+    def module_files(self, importer, module: str) -> List[Any]:
+        """Project files a Python module / TypeScript import source written in `importer` refers to."""
+        if importer.language == "python":
+            return self._python_files(module, importer)
+        return self._ts_files(module, importer)
+
+
+class CallGraph(ModuleIndex):
+    """Symbol tables over every extracted file, and call resolution against them."""
+
+    def __init__(self, files: Iterable[FileSymbols]):
+        super().__init__(files)
+        self.functions: Dict[str, List[Tuple[FileSymbols, Definition]]] = defaultdict(list)
+        self.methods: Dict[str, List[Tuple[FileSymbols, Definition]]] = defaultdict(list)
+        self.classes: Dict[str, List[FileSymbols]] = defaultdict(list)
+        self.rust_crates = set()
+
+        for f in self.files.values():
+            if f.language == "rust":
+                self.rust_crates.add(f.module.split("::")[0])
+            for d in f.definitions:
+                (self.methods if d.owner else self.functions)[d.name].append((f, d))
+            for name in f.classes:
+                self.classes[name].append(f)
+
+    def _rust_absolute(self, file: FileSymbols, segments: List[str], follow_imports: bool = True) -> Optional[List[str]]:
+        """Crate-absolute segments for a `use` or call path written in `file`, None outside the project."""
+        current = file.module.split("::")
+        head = segments[0]
+        if head == "crate":
+            return current[:1] + segments[1:]
+        if head == "self":
+            return current + segments[1:]
+        if head == "super":
+            i = 0
+            while i < len(segments) and segments[i] == "super":
+                i += 1
+            return current[:max(1, len(current) - i)] + segments[i:]
+        if follow_imports:
+            imp = file.imported(head)
+            if imp and imp.symbol:
+                base = self._rust_absolute(file, imp.module.split("::") + [imp.symbol], follow_imports=False)
+                return base + segments[1:] if base else None
+        if head in self.rust_crates:
+            return segments
+        if head in ("std", "core", "alloc") or file.imported(head):
+            return None
+        return current + segments  # Child module declared with `mod`
+
+    def _rust_lookup(self, path: List[str]) -> List[Tuple[FileSymbols, Definition]]:
+        """Resolves `krate::module::fn` or `krate::module::Type::method`."""
+        *module, name = path
+        found = [(f, d) for f in self.modules.get(("rust", "::".join(module)), []) for d in f.top_level(name)]
+        if found or len(module) < 2:
+            return found
+        *type_module, owner = module
+        found = [(f, d) for f in self.modules.get(("rust", "::".join(type_module)), []) for d in f.methods(owner, name)]
+        if found:
+            return found
+        # impl blocks can live anywhere in the crate
+        crate = path[0]
+        return [(f, d) for f, d in self.methods.get(name, [])
+                if d.owner == owner and f.language == "rust" and f.module.split("::")[0] == crate]
+
+    # Candidate search
+
+    def _owner_methods(self, file: FileSymbols, owner: str, name: str) -> List[Tuple[FileSymbols, Definition]]:
+        return [(f, d) for f, d in self.methods.get(name, []) if d.owner == owner and f.language == file.language]
+
+    def _by_name(self, file: FileSymbols, name: str, table) -> List[Tuple[FileSymbols, Definition, str]]:
+        family = lambda lang: "typescript" if lang == "javascript" else lang
+        candidates = [(f, d) for f, d in table.get(name, []) if family(f.language) == family(file.language)]
+        if not candidates or len(candidates) > MAX_AMBIGUOUS:
+            return []
+        if len(candidates) == 1:
+            return [(*candidates[0], "unique" if table is self.functions else "receiver")]
+        return [(f, d, "ambiguous") for f, d in candidates]
+
+    def _imported_modules(self, file: FileSymbols, imp: Import, rest: str = "") -> List[FileSymbols]:
+        """Project files an import binds as a module (`import pkg.orders`, `* as orders`, `from pkg import orders`)."""
+        if imp.symbol is None:
+            module = f"{imp.module}.{rest}" if rest else imp.module
+            return self.module_files(file, module)
+        if file.language == "python" and not rest:
+            separator = "" if imp.module.endswith(".") else "."
+            return self.module_files(file, f"{imp.module}{separator}{imp.symbol}")
+        return []
+
+    def _imported_symbol(self, file: FileSymbols, imp: Import) -> Tuple[List[FileSymbols], Optional[str]]:
+        """Files defining the member an import binds, and its name there."""
+        modules = self.module_files(file, imp.module)
+        symbol = imp.symbol
+        if symbol == "default":
+            symbol = next((f.default_export for f in modules if f.default_export), None)
+        return modules, symbol
+
+    def _resolve_rust_path(self, file: FileSymbols, segments: List[str], name: str):
+        path = self._rust_absolute(file, segments)
+        if path is None or path[0] not in self.rust_crates:
+            return []
+        explicit = file.imported(segments[0]) or segments[0] in ("crate", "self", "super")
+        found = self._rust_lookup(path + [name])
+        if not found and len(segments) == 1 and segments[0] in self.classes:
+            return [(f, d, "qualified") for f, d in self._owner_methods(file, segments[0], name)]
+        return [(f, d, "import" if explicit else "qualified") for f, d in found]
+
+    def resolve_call(self, file: FileSymbols, call: CallSite) -> List[Tuple[FileSymbols, Definition, str]]:
+        """Candidate targets for one call site, each tagged with a CONFIDENCE tier."""
+        receiver, name = call.receiver, call.name
+
+        if receiver is None:
+            imp = file.imported(name)
+            if imp and file.language == "rust":
+                path = self._rust_absolute(file, [name])
+                if path is None or path[0] not in self.rust_crates:
+                    return []
+                return [(f, d, "import") for f, d in self._rust_lookup(path)]
+            if imp:
+                modules, symbol = self._imported_symbol(file, imp)
+                return [(f, d, "import") for f in modules for d in f.top_level(symbol or "")]
+            local = file.top_level(name)
+            if local:
+                return [(file, d, "scope") for d in local]
+            for glob in (i for i in file.imports if i.alias == "*"):
+                if file.language == "rust":
+                    base = self._rust_absolute(file, glob.module.split("::"), follow_imports=False)
+                    found = self._rust_lookup(base + [name]) if base and base[0] in self.rust_crates else []
+                else:
+                    found = [(f, d) for f in self.module_files(file, glob.module) for d in f.top_level(name)]
+                if found:
+                    return [(f, d, "import") for f, d in found]
+            return self._by_name(file, name, self.functions)
+
+        if receiver in SELF_RECEIVERS:
+            owner = call.caller.owner if call.caller else None
+            if owner:
+                local = file.methods(owner, name)
+                if local:
+                    return [(file, d, "scope") for d in local]
+                elsewhere = self._owner_methods(file, owner, name)
+                if elsewhere:
+                    return [(f, d, "qualified") for f, d in elsewhere]
+            return self._by_name(file, name, self.methods)
+
+        if file.language == "rust" and call.separator == "::":
+            return self._resolve_rust_path(file, receiver.split("::"), name)
+
+        head, _, rest = receiver.partition(".")
+        imp = file.imported(head)
+        if imp:
+            modules = self._imported_modules(file, imp, rest)
+            if modules:
+                return [(f, d, "import") for f in modules for d in f.top_level(name)]
+            if rest or imp.symbol is None:
+                return []
+            modules, symbol = self._imported_symbol(file, imp)
+            return [(f, d, "import") for f in modules for d in f.methods(symbol or head, name)]
+        if not rest:
+            local = file.methods(receiver, name)
+            if local:
+                return [(file, d, "scope") for d in local]
+            if receiver in self.classes:
+                return [(f, d, "qualified") for f, d in self._owner_methods(file, receiver, name)]
+        return self._by_name(file, name, self.methods)
+
+    def resolve(self, paths: Optional[Set[str]] = None) -> List[ResolvedCall]:
+        """Resolved calls of every file, or only of the callers in `paths`."""
+        resolved = []
+        for file in self.files.values():
+            if paths is not None and file.path not in paths:
+                continue
+            for call in file.calls:
+                for target_file, target, tier in self.resolve_call(file, call):
+                    if call.caller is target and target_file is file:
+                        continue  # Recursion adds nothing to the graph
+                    resolved.append(ResolvedCall(call.caller, file.path, target, target_file.path,
+                                                 CONFIDENCE[tier], call.line, tier))
+        return resolved
+
+
+# --- Persistence ---
+
+def crate_path(graph: CallGraph, d: Definition, file_path: str) -> Optional[str]:
+    """Crate path of a Rust definition (`orders::Order::total`); None for other languages."""
+    file = graph.files.get(file_path)
+    return rust_modules.qualify(file.module_at(d.line), d.name, d.owner) if file and file.language == "rust" else None
+
+
+def qualified_name(graph: CallGraph, d: Definition, file_path: str) -> str:
+    """What keys a definition's entity: its crate path, else its module or file path (`ids.file_qualified_name`)."""
+    return crate_path(graph, d, file_path) or file_qualified_name(file_path, d.name, d.owner)
+
+
+def definition_id(graph: CallGraph, d: Definition, file_path: str, project_id: str) -> str:
+    """The entity id `call_graph_records` stores for a definition."""
+    return entity_id(project_id, d.name, d.kind, qualified_name(graph, d, file_path))
+
+
+def call_graph_records(graph: CallGraph, project_id: str,
+                       paths: Optional[Set[str]] = None) -> Tuple[List[Dict[str, Any]], List[Dict[str, Any]]]:
+    """SchemaStore entities (callers/callees) and `calls` edges for a resolved graph (callers in `paths` only)."""
+    entities: Dict[str, Dict[str, Any]] = {}
+
+    def node(name: str, entity_type: str, file_path: str, qualified_name: Optional[str] = None) -> str:
+        ent_id = entity_id(project_id, name, entity_type, qualified_name)
+        entities.setdefault(ent_id, {
+            "id": ent_id,
+            "project_id": project_id,
+            "name": name,
+            "entity_type": entity_type,
+            "file_path": file_path,
+            "qualified_name": qualified_name,
+        })
+        return ent_id
+
+    def definition(d: Definition, file_path: str) -> str:
+        return node(d.name, d.kind, file_path, qualified_name(graph, d, file_path))
+
+    edges: Dict[Tuple[str, str], Dict[str, Any]] = {}
+    for call in graph.resolve(paths):
+        if call.caller:
+            source_id = definition(call.caller, call.caller_file)
+        else:
+            source_id = node(call.caller_file, "file", call.caller_file)
+        target_id = definition(call.target, call.target_file)
+        if source_id == target_id:
+            continue
+        edge = edges.get((source_id, target_id))
+        if edge is None or edge["confidence"] < call.confidence:
+            edges[(source_id, target_id)] = {
+                "id": relationship_id(source_id, target_id, "calls"),
+                "project_id": project_id,
+                "source_id": source_id,
+                "target_id": target_id,
+                "relation_type": "calls",
+                "confidence": call.confidence,
+            }
+    return list(entities.values()), list(edges.values())
+
+
+# Parsed files per project root, keyed by absolute path and reused while (mtime, size) is unchanged.
+# Each build keeps only the files it walked, so deleted files don't linger.
+_symbol_cache: Dict[Path, Dict[Path, Tuple[Tuple[int, int], FileSymbols]]] = {}
+
+
+def iter_source_files(root: Path, language_names: Iterable[str] = CALL_GRAPH_LANGUAGES):
+    """Source files of the given languages under `root`, honouring .sideignore."""
+    from side.services.ignore import ProjectIgnore
+
+    ignore_service = ProjectIgnore(root)
+    extensions = {ext for lang in language_names if (spec := languages.get(lang)) for ext in spec.extensions}
+    for dirpath, dirnames, filenames in os.walk(root, topdown=True):
+        current = Path(dirpath)
+        dirnames[:] = [d for d in dirnames if d != ".side" and not ignore_service.should_ignore(current / d)]
+        for filename in filenames:
+            path = current / filename
+            if path.suffix.lower() in extensions and not ignore_service.should_ignore(path):
+                yield path
+
+
+def _cached_symbols(root: Path, path: Path, previous: Dict[Path, Tuple[Tuple[int, int], FileSymbols]]):
+    """(stat key, symbols) for `path`, re-extracted only when its (mtime, size) changed; None when unreadable."""
+    try:
+        stat = path.stat()
+        key = (stat.st_mtime_ns, stat.st_size)
+        cached = previous.get(path)
+        if cached and cached[0] == key:
+            return cached
+        spec = languages.for_path(path)
+        content = path.read_text(errors="ignore")
+        symbols = extract_file_symbols(path, content, path.relative_to(root).as_posix(),
+                                       module_name(root, path, spec.name))
+    except (OSError, ValueError) as e:
+        logger.debug(f"Call graph: skipping {path}: {e}")
+        return None
+    return (key, symbols) if symbols else None
+
+
+def _place_rust(root: Path, symbols: FileSymbols) -> None:
+    # The module tree can change without this file changing (a `mod` moved, a #[path] added)
+    path = root / symbols.path
+    symbols.module = rust_modules.module_path(root, path)
+    symbols.scopes = rust_modules.inline_modules(root, path)
+
+
+def build_call_graph(root: Path) -> CallGraph:
+    """Extracts (or reuses cached) symbols for every source file under `root`."""
+    previous, cache = _symbol_cache.get(root, {}), {}
+    for path in iter_source_files(root):
+        entry = _cached_symbols(root, path, previous)
+        if entry:
+            cache[path] = entry
+    _symbol_cache[root] = cache
+    files = [symbols for _, symbols in cache.values()]
+    for symbols in files:
+        if symbols.language == "rust":
+            _place_rust(root, symbols)
+    return CallGraph(files)
+
+
+def update_call_graph(root: Path, changed: Iterable[Path]) -> CallGraph:
+    """
+    The last graph built for `root` with only `changed` files re-extracted (or dropped when
+    deleted), without walking the project. The first call for a root builds it in full.
+    """
+    if root not in _symbol_cache:
+        return build_call_graph(root)
+    cache = _symbol_cache[root]
+    extensions = {ext for lang in CALL_GRAPH_LANGUAGES if (spec := languages.get(lang)) for ext in spec.extensions}
+    for path in changed:
+        entry = _cached_symbols(root, path, cache) if path.suffix.lower() in extensions and path.is_file() else None
+        if entry:
+            cache[path] = entry
+            if entry[1].language == "rust":
+                _place_rust(root, entry[1])
+        else:
+            cache.pop(path, None)
+    return CallGraph(symbols for _, symbols in cache.values())
+
+
+def relative_paths(root: Path, changed: Iterable[Path]) -> Set[str]:
+    """Project-relative posix paths of `changed`, as entities store them."""
+    paths = set()
+    for path in changed:
+        try:
+            paths.add(path.relative_to(root).as_posix())
+        except ValueError:
+            continue
+    return paths
+
+
+def affected_paths(graph: CallGraph, schema_store, project_id: str, paths: Set[str], relation_type: str) -> Set[str]:
+    """
+    `paths` plus the files whose calls may resolve differently once they changed: the callers
+    of anything they now define, and the sources of stored `relation_type` edges into them.
+    """
+    defined = {d.name for f in graph.files.values() if f.path in paths for d in f.definitions}
+    defined |= {name for f in graph.files.values() if f.path in paths for name in f.classes}
+    affected = set(paths) | {f.path for f in graph.files.values() if any(c.name in defined for c in f.calls)}
+    targets = [e["id"] for e in schema_store.list_entities_in_files(project_id, sorted(paths))]
+    into = schema_store.list_relationships_to(project_id, targets, relation_type=relation_type, origin="index")
+    callers = schema_store.get_entities_by_ids([e["source_id"] for e in into])
+    return affected | {e["file_path"] for e in callers.values() if e["file_path"]}
+
+
+def index_call_graph(root: Path, schema_store, changed: Optional[List[Path]] = None) -> Dict[str, int]:
+    """
+    Rebuilds the project's heuristic `calls` edges in SchemaStore (imported SCIP edges are kept).
+    With `changed` files, only the edges whose caller lives in them, or calls into them, are replaced.
+    """
+    from side.intel.scip_import import drop_superseded
+
+    project_id = schema_store.engine.get_project_id()
+    if changed is None:
+        graph = build_call_graph(root)
+        entities, edges = call_graph_records(graph, project_id)
+        sources = None
+    else:
+        graph = update_call_graph(root, changed)
+        paths = affected_paths(graph, schema_store, project_id, relative_paths(root, changed), "calls")
+        entities, edges = call_graph_records(graph, project_id, paths)
+        sources = [e["id"] for e in schema_store.list_entities_in_files(project_id, sorted(paths))]
+        sources += [e["id"] for e in entities if e["file_path"] in paths]
+    edges = drop_superseded(schema_store, project_id, edges)
+
+    schema_store.save_entities_batch(entities)
+    schema_store.delete_relationships(project_id, relation_type="calls", origin="index", source_ids=sources)
+    schema_store.save_relationships_batch(edges)
+
+    stats = {"files": len(graph.files), "entities": len(entities), "edges": len(edges)}
+    logger.info(f"🕸️ [CALL GRAPH]: {stats}")
+    return stats
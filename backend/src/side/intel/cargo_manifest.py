@@ -0,0 +1,359 @@
+"""
+Cargo Manifest Parser - Workspace-aware Rust project model.
+
+Resolves `[workspace] members` globs, per-crate dependencies, features and
+targets, then projects the result into SchemaStore as crate nodes linked by
+`depends_on` edges (`dev_depends_on` / `build_depends_on` for those tables).
+"""
+
+import logging
+import tomllib
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from side.intel.ids import entity_id, relationship_id
+
+logger = logging.getLogger(__name__)
+
+DEPENDENCY_TABLES = {
+    "dependencies": "normal",
+    "dev-dependencies": "dev",
+    "build-dependencies": "build",
+}
+# Each dependency kind is its own relation, so `cargo build` graphs can leave out dev-only edges
+DEPENDENCY_RELATIONS = {"normal": "depends_on", "dev": "dev_depends_on", "build": "build_depends_on"}
+
+
+@dataclass
+class CargoDependency:
+    name: str                      # Name used in code (after `package = ...` rename)
+    package: str                   # Real crate name on crates.io / in the workspace
+    kind: str = "normal"           # 'normal', 'dev', 'build'
+    version: Optional[str] = None
+    path: Optional[Path] = None    # Resolved absolute path for path dependencies
+    optional: bool = False
+    features: List[str] = field(default_factory=list)
+    target: Optional[str] = None   # `cfg(...)` / triple for target-specific deps
+
+
+@dataclass
+class CargoTarget:
+    kind: str                      # 'lib', 'bin'
+    name: str
+    path: Optional[str] = None
+
+
+@dataclass
+class CargoCrate:
+    name: str
+    manifest_path: Path
+    version: Optional[str] = None
+    edition: Optional[str] = None
+    dependencies: List[CargoDependency] = field(default_factory=list)
+    features: Dict[str, List[str]] = field(default_factory=dict)
+    targets: List[CargoTarget] = field(default_factory=list)
+    is_member: bool = True
+
+    @property
+    def root(self) -> Path:
+        return self.manifest_path.parent
+
+
+@dataclass
+class CargoWorkspace:
+    root: Path
+    crates: Dict[str, CargoCrate] = field(default_factory=dict)
+    is_virtual: bool = False
+
+    def members(self) -> List[CargoCrate]:
+        return [c for c in self.crates.values() if c.is_member]
+
+    def dependents_of(self, package: str) -> List[str]:
+        """Names of local crates that depend on `package`."""
+        return sorted(
+            c.name for c in self.crates.values()
+            if any(d.package == package for d in c.dependencies)
+        )
+
+    def dependency_names(self) -> set:
+        """Every package name referenced by any local crate."""
+        return {d.package for c in self.crates.values() for d in c.dependencies}
+
+
+def _read_manifest(path: Path) -> Dict[str, Any]:
+    with open(path, "rb") as f:
+        return tomllib.load(f)
+
+
+def _inherit(value: Any, workspace_table: Dict[str, Any], key: str) -> Any:
+    """Resolves `key.workspace = true` against `[workspace.package]`."""
+    if isinstance(value, dict) and value.get("workspace") is True:
+        return workspace_table.get(key)
+    return value
+
+
+def _parse_dependency(name: str, spec: Any, kind: str, crate_dir: Path,
+                      workspace_root: Path, workspace_deps: Dict[str, Any],
+                      target: Optional[str] = None) -> CargoDependency:
+    base_dir = crate_dir
+    if isinstance(spec, dict) and spec.get("workspace") is True:
+        # `foo = { workspace = true, features = [...] }` merges onto [workspace.dependencies]
+        inherited = workspace_deps.get(name, {})
+        if isinstance(inherited, str):
+            inherited = {"version": inherited}
+        extra_features = spec.get("features", [])
+        spec = {**inherited, **{k: v for k, v in spec.items() if k != "workspace"}}
+        spec["features"] = list(inherited.get("features", [])) + list(extra_features)
+        base_dir = workspace_root
+
+    if isinstance(spec, str):
+        return CargoDependency(name=name, package=name, kind=kind, version=spec, target=target)
+
+    spec = spec or {}
+    dep_path = (base_dir / spec["path"]).resolve() if "path" in spec else None
+    return CargoDependency(
+        name=name,
+        package=spec.get("package", name),
+        kind=kind,
+        version=spec.get("version"),
+        path=dep_path,
+        optional=bool(spec.get("optional", False)),
+        features=list(spec.get("features", [])),
+        target=target,
+    )
+
+
+def _parse_targets(data: Dict[str, Any], crate_dir: Path, package_name: str) -> List[CargoTarget]:
+    targets = []
+    lib = data.get("lib")
+    if lib is not None or (crate_dir / "src" / "lib.rs").exists():
+        lib = lib or {}
+        targets.append(CargoTarget(
+            kind="lib",
+            name=lib.get("name", package_name.replace("-", "_")),
+            path=lib.get("path", "src/lib.rs"),
+        ))
+
+    bins = data.get("bin", [])
+    for b in bins:
+        targets.append(CargoTarget(kind="bin", name=b.get("name", package_name), path=b.get("path")))
+    if not bins and (crate_dir / "src" / "main.rs").exists():
+        targets.append(CargoTarget(kind="bin", name=package_name, path="src/main.rs"))
+    return targets
+
+
+def parse_crate(manifest_path: Path, workspace_root: Optional[Path] = None,
+                workspace_data: Optional[Dict[str, Any]] = None) -> Optional[CargoCrate]:
+    """Parses a single `[package]` manifest. Returns None for virtual manifests."""
+    data = _read_manifest(manifest_path)
+    package = data.get("package")
+    if not package:
+        return None
+
+    crate_dir = manifest_path.parent
+    workspace_root = workspace_root or crate_dir
+    ws = (workspace_data or {}).get("workspace", {})
+    ws_package = ws.get("package", {})
+    ws_deps = ws.get("dependencies", {})
+
+    crate = CargoCrate(
+        name=package["name"],
+        manifest_path=manifest_path,
+        version=_inherit(package.get("version"), ws_package, "version"),
+        edition=_inherit(package.get("edition"), ws_package, "edition"),
+        features={k: list(v) for k, v in data.get("features", {}).items()},
+        targets=_parse_targets(data, crate_dir, package["name"]),
+    )
+
+    def collect(tables: Dict[str, Any], target: Optional[str] = None):
+        for table, kind in DEPENDENCY_TABLES.items():
+            for dep_name, spec in tables.get(table, {}).items():
+                crate.dependencies.append(
+                    _parse_dependency(dep_name, spec, kind, crate_dir, workspace_root, ws_deps, target)
+                )
+
+    collect(data)
+    for target_name, tables in data.get("target", {}).items():
+        collect(tables, target=target_name)
+    return crate
+
+
+def _expand_members(root: Path, patterns: List[str], excludes: List[str]) -> List[Path]:
+    excluded = {(root / e).resolve() for e in excludes}
+    dirs = []
+    for pattern in patterns:
+        matches = sorted(root.glob(pattern)) if any(c in pattern for c in "*?[") else [root / pattern]
+        for m in matches:
+            m = m.resolve()
+            if m in excluded or not (m / "Cargo.toml").is_file():
+                continue
+            if m not in dirs:
+                dirs.append(m)
+    return dirs
+
+
+def load_cargo_workspace(root: Path) -> Optional[CargoWorkspace]:
+    """
+    Builds the workspace model rooted at `root/Cargo.toml`.
+    Path dependencies outside the member list are loaded as non-member crates.
+    """
+    root = root.resolve()
+    root_manifest = root / "Cargo.toml"
+    if not root_manifest.is_file():
+        return None
+
+    try:
+        root_data = _read_manifest(root_manifest)
+    except (OSError, tomllib.TOMLDecodeError) as e:
+        logger.warning(f"⚠️ [CARGO]: Unreadable manifest {root_manifest}: {e}")
+        return None
+
+    ws_table = root_data.get("workspace")
+    workspace = CargoWorkspace(root=root, is_virtual="package" not in root_data)
+
+    manifests = []
+    if "package" in root_data:
+        manifests.append(root_manifest)
+    if ws_table is not None:
+        for member_dir in _expand_members(root, ws_table.get("members", []), ws_table.get("exclude", [])):
+            if member_dir != root:
+                manifests.append(member_dir / "Cargo.toml")
+
+    seen = set()
+    queue = [(m, True) for m in manifests]
+    while queue:
+        manifest, is_member = queue.pop(0)
+        if manifest in seen:
+            continue
+        seen.add(manifest)
+        try:
+            crate = parse_crate(manifest, root, root_data if ws_table is not None else None)
+        except (OSError, tomllib.TOMLDecodeError, KeyError) as e:
+            logger.warning(f"⚠️ [CARGO]: Skipping {manifest}: {e}")
+            continue
+        if not crate:
+            continue
+        crate.is_member = is_member
+        workspace.crates.setdefault(crate.name, crate)
+
+        for dep in crate.dependencies:
+            if dep.path and (dep.path / "Cargo.toml").is_file():
+                queue.append((dep.path / "Cargo.toml", False))
+
+    logger.info(f"📦 [CARGO]: Loaded {len(workspace.crates)} crates from {root}")
+    return workspace
+
+
+def persist_cargo_workspace(workspace: CargoWorkspace, schema_store, project_id: str) -> Dict[str, int]:
+    """
+    Stores crates, features and targets as entities and crate -> crate
+    `depends_on` edges (one relation per dependency kind), so "which crates
+    depend on X" is a graph query. Edges from an earlier run are replaced, and
+    crates, features and targets no longer in the workspace are dropped.
+    """
+    entities = []
+    relationships = []
+    crate_ids = {}
+    manifests = {}
+
+    def crate_node(name: str, file_path: Optional[str], signature: Optional[str]) -> str:
+        if name not in crate_ids:
+            crate_ids[name] = entity_id(project_id, name, "crate")
+            entities.append({
+                "id": crate_ids[name],
+                "project_id": project_id,
+                "name": name,
+                "entity_type": "crate",
+                "file_path": file_path,
+                "signature": signature,
+            })
+        return crate_ids[name]
+
+    for crate in workspace.crates.values():
+        try:
+            rel_manifest = str(crate.manifest_path.relative_to(workspace.root))
+        except ValueError:
+            rel_manifest = str(crate.manifest_path)
+        manifests[crate.name] = rel_manifest
+        crate_node(crate.name, rel_manifest, f"{crate.name} {crate.version or ''}".strip())
+
+    # Features and targets reference their crate through parent_id, so they go after all crates.
+    children = []
+    for crate in workspace.crates.values():
+        parent_id = crate_ids[crate.name]
+        for feature, enables in crate.features.items():
+            children.append({
+                "id": entity_id(project_id, f"{crate.name}/{feature}", "feature"),
+                "project_id": project_id,
+                "name": feature,
+                "entity_type": "feature",
+                "file_path": manifests[crate.name],
+                "signature": f"{feature} = [{', '.join(enables)}]",
+                "parent_id": parent_id,
+            })
+        for target in crate.targets:
+            children.append({
+                "id": entity_id(project_id, f"{crate.name}/{target.kind}:{target.name}", "target"),
+                "project_id": project_id,
+                "name": target.name,
+                "entity_type": "target",
+                "file_path": target.path,
+                "signature": target.kind,
+                "parent_id": parent_id,
+            })
+
+        for dep in crate.dependencies:
+            local = dep.package in workspace.crates
+            target_id = crate_node(dep.package, None, None if local else f"{dep.package} {dep.version or '*'}")
+            relation = DEPENDENCY_RELATIONS.get(dep.kind, "depends_on")
+            relationships.append({
+                "id": relationship_id(parent_id, target_id, relation),
+                "project_id": project_id,
+                "source_id": parent_id,
+                "target_id": target_id,
+                "relation_type": relation,
+                "confidence": 1.0,
+            })
+
+    current = {e["id"] for e in entities + children}
+    schema_store.delete_entities_by_ids([
+        e["id"] for entity_type in ("crate", "feature", "target")
+        for e in schema_store.list_entities(project_id, entity_type) if e["id"] not in current
+    ])
+    schema_store.save_entities_batch(entities)
+    schema_store.save_entities_batch(children)
+    for relation in set(DEPENDENCY_RELATIONS.values()):
+        schema_store.delete_relationships(project_id, relation_type=relation, origin="index")
+    schema_store.save_relationships_batch(relationships)
+    return {"crates": len(entities), "children": len(children), "edges": len(relationships)}
+
+
+def crates_depending_on(schema_store, project_id: str, package: str,
+                        kinds: Optional[List[str]] = None) -> List[str]:
+    """Answers "which crates depend on X" from the stored graph, optionally for some dependency kinds only."""
+    target_id = entity_id(project_id, package, "crate")
+    relations = {DEPENDENCY_RELATIONS[k] for k in (kinds or DEPENDENCY_RELATIONS)}
+    dependents = []
+    for rel in schema_store.list_relationships(target_id=target_id):
+        if rel["relation_type"] not in relations:
+            continue
+        source = schema_store.get_entity_by_id(rel["source_id"])
+        if source and source["name"] not in dependents:
+            dependents.append(source["name"])
+    return sorted(dependents)
+
+
+def index_cargo_workspace(root: Path, schema_store) -> Optional[CargoWorkspace]:
+    """Loads and persists the Cargo workspace at `root`, if there is one."""
+    if not (root / "Cargo.toml").is_file():
+        return None
+    try:
+        workspace = load_cargo_workspace(root)
+        if workspace:
+            stats = persist_cargo_workspace(workspace, schema_store, schema_store.engine.get_project_id())
+            logger.info(f"📦 [CARGO]: Persisted crate graph {stats}")
+        return workspace
+    except Exception as e:
+        logger.warning(f"⚠️ [CARGO]: Workspace indexing failed for {root}: {e}")
+        return None
@@ -1,7 +1,6 @@
 from __future__ import annotations
 import logging
 import time
-import json
 import os
 from pathlib import Path
 from typing import Dict, List, Optional, Any, TYPE_CHECKING
@@ -15,6 +14,7 @@ if TYPE_CHECKING:
 
 from side.intel.connector import Connector
 from side.intel.tree_indexer import run_context_scan
+from side.intel.index_store import open_index_store
 from side.intel.code_monitor import CodeMonitor
 from side.intel.session_analyzer import SessionAnalyzer
 from side.intel.handlers.topology import CodeIndexer
@@ -104,14 +104,7 @@ class ContextService(LifecycleComponent):
 
     async def sync_checkpoint(self):
         """Serializes current state to local telemetry."""
-        root_index_path = self.project_path / ".side" / "local.json"
-        local_data = {}
-        if root_index_path.exists():
-            try:
-                raw_data = shield.unseal_file(root_index_path)
-                local_data = json.loads(raw_data)
-            except Exception as e:
-                logger.warning(f"Failed to read local index: {e}")
+        local_data = open_index_store(self.project_path).read(self.project_path) or {}
         
         stats = self._get_project_stats()
         
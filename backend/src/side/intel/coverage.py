@@ -0,0 +1,317 @@
+"""
+Coverage Ingestion - Line coverage from lcov, Cobertura and llvm-cov reports.
+
+Reports from `cargo llvm-cov` (`--lcov` or the `--json` export) and `pytest-cov`
+(`--cov-report=xml` or `lcov`) are read into per-file line hits and mapped onto
+the call graph's functions and methods; types are credited with the lines of
+their methods. Results are stored in SchemaStore's `coverage` table keyed by
+the commit they were measured at, so history accumulates across runs.
+`coverage_warnings` and `untested_changes` turn stored coverage into the notes
+PromptBuilder and audits show when untested code is being edited.
+"""
+
+import json
+import logging
+import re
+import subprocess
+import xml.etree.ElementTree as ET
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from side.intel.call_graph import CallGraph, build_call_graph, definition_id
+
+logger = logging.getLogger(__name__)
+
+LineHits = Dict[int, int]           # Line -> execution count (instrumented lines only)
+
+# Where `cargo llvm-cov --lcov --output-path`, `pytest --cov-report=xml` and friends usually write
+DEFAULT_REPORTS = (
+    "lcov.info", "coverage/lcov.info", "target/llvm-cov/lcov.info",
+    "coverage.xml", "cobertura.xml", "coverage/cobertura-coverage.xml",
+    "llvm-cov.json", "target/llvm-cov/llvm-cov.json",
+)
+LOW_COVERAGE = 50.0                 # Percent below which edited code is flagged
+TYPE_TYPES = {"class", "struct", "enum", "union", "trait", "interface", "type"}
+# Dependency sources some reports include; their `src/lib.rs` must not suffix-match ours
+DEPENDENCY_PATH_RE = re.compile(r"/(?:\.cargo/(?:registry|git)|\.rustup|rustc/[0-9a-f]+|site-packages|dist-packages|node_modules)/")
+
+
+# --- Report formats ---
+
+def parse_lcov(text: str) -> Dict[str, LineHits]:
+    """`SF:` / `DA:line,hits` records (FN/BRDA lines are ignored)."""
+    files: Dict[str, LineHits] = {}
+    current: Optional[LineHits] = None
+    for raw in text.splitlines():
+        line = raw.strip()
+        if line.startswith("SF:"):
+            current = files.setdefault(line[3:], {})
+        elif line.startswith("DA:") and current is not None:
+            parts = line[3:].split(",")
+            try:
+                number, hits = int(parts[0]), int(float(parts[1]))
+            except (IndexError, ValueError):
+                continue
+            current[number] = max(current.get(number, 0), hits)
+        elif line == "end_of_record":
+            current = None
+    return files
+
+
+def parse_cobertura(text: str) -> Dict[str, LineHits]:
+    """`<class filename=...><lines><line number= hits=/>`, with filenames joined to the first `<source>`."""
+    root = ET.fromstring(text)
+    sources = [s.text.strip().rstrip("/") for s in root.iter("source") if s.text and s.text.strip()]
+    files: Dict[str, LineHits] = {}
+    for cls in root.iter("class"):
+        filename = cls.get("filename")
+        if not filename:
+            continue
+        name = filename if filename.startswith("/") or not sources else f"{sources[0]}/{filename}"
+        hits = files.setdefault(name, {})
+        for line in cls.iter("line"):
+            try:
+                number, count = int(line.get("number")), int(float(line.get("hits", 0)))
+            except (TypeError, ValueError):
+                continue
+            hits[number] = max(hits.get(number, 0), count)
+    return files
+
+
+def _llvm_line_hits(segments: List[list]) -> LineHits:
+    """
+    Line counts from llvm-cov segments ([line, col, count, has_count, is_region_entry, is_gap?]),
+    following llvm-cov's own rule: a line counts when a region starts on it or a counted region wraps it.
+    """
+    hits: LineHits = {}
+    if not segments:
+        return hits
+    segments = sorted(segments, key=lambda s: (s[0], s[1]))
+    wrapped: Optional[list] = None
+    i = 0
+    for line in range(segments[0][0], segments[-1][0] + 1):
+        starts = []
+        while i < len(segments) and segments[i][0] == line:
+            starts.append(segments[i])
+            i += 1
+        entries = [s for s in starts if s[3] and s[4] and not (len(s) > 5 and s[5])]
+        skipped = bool(starts) and not starts[0][3] and starts[0][4]    # Line opens a skipped (#[cfg]'d out) region
+        counts = [s[2] for s in entries] + ([wrapped[2]] if wrapped is not None and wrapped[3] else [])
+        if counts and not skipped:
+            hits[line] = max(counts)
+        if starts:
+            wrapped = starts[-1]
+    return hits
+
+
+def parse_llvm_cov(text: str) -> Dict[str, LineHits]:
+    """`llvm-cov export` / `cargo llvm-cov --json`: per-file region segments."""
+    data = json.loads(text)
+    files: Dict[str, LineHits] = {}
+    for export in data.get("data", []):
+        for entry in export.get("files", []):
+            if entry.get("filename"):
+                files[entry["filename"]] = _llvm_line_hits(entry.get("segments", []))
+    return files
+
+
+PARSERS = {"lcov": parse_lcov, "cobertura": parse_cobertura, "llvm-cov": parse_llvm_cov}
+
+
+def detect_format(text: str) -> str:
+    head = text.lstrip()[:200]
+    if head.startswith("<"):
+        return "cobertura"
+    if head.startswith("{"):
+        return "llvm-cov"
+    if "SF:" in text:
+        return "lcov"
+    raise ValueError("Unrecognized coverage report (expected lcov, Cobertura XML or llvm-cov JSON)")
+
+
+def read_report(path: Path) -> Tuple[str, Dict[str, LineHits]]:
+    """(format, file name -> line hits) of a report."""
+    text = path.read_text(errors="ignore")
+    fmt = detect_format(text)
+    try:
+        return fmt, PARSERS[fmt](text)
+    except (ET.ParseError, json.JSONDecodeError) as e:
+        raise ValueError(f"Unreadable {fmt} report {path}: {e}") from None
+
+
+def find_reports(root: Path) -> List[Path]:
+    return [root / rel for rel in DEFAULT_REPORTS if (root / rel).is_file()]
+
+
+# --- Mapping onto entities ---
+
+def _project_path(root: Path, name: str, known: Iterable[str]) -> Optional[str]:
+    """Report file name -> project-relative path; reports from CI checkouts match by longest suffix."""
+    name = name.replace("\\", "/")
+    known = set(known)
+    if name.startswith("/"):
+        try:
+            relative = Path(name).resolve().relative_to(root.resolve()).as_posix()
+            if relative in known:
+                return relative
+        except ValueError:
+            pass
+    if DEPENDENCY_PATH_RE.search(name):
+        return None
+    name = name.removeprefix("./")
+    if name in known:
+        return name
+    matches = [k for k in known if name.endswith("/" + k)]
+    return max(matches, key=len) if matches else None
+
+
+def _record(project_id: str, entity_id: str, lines: LineHits, commit: str, source: str) -> Dict[str, Any]:
+    covered = sum(1 for count in lines.values() if count > 0)
+    return {
+        "project_id": project_id,
+        "entity_id": entity_id,
+        "commit_sha": commit,
+        "covered_lines": covered,
+        "total_lines": len(lines),
+        "percent": round(100.0 * covered / len(lines), 1),
+        "source": source,
+    }
+
+
+def coverage_records(graph: CallGraph, files: Dict[str, LineHits], schema_store, project_id: str,
+                     commit: str, source: str) -> List[Dict[str, Any]]:
+    """Coverage rows for every function/method with instrumented lines, and for the types owning them."""
+    rows: Dict[str, Dict[str, Any]] = {}
+    owned: Dict[Tuple[str, str], LineHits] = {}
+    for path, hits in files.items():
+        file = graph.files.get(path)
+        if not file:
+            continue
+        for d in file.definitions:
+            lines = {n: c for n, c in hits.items() if d.line <= n <= d.end_line}
+            if not lines:
+                continue
+            ent_id = definition_id(graph, d, path, project_id)
+            rows[ent_id] = _record(project_id, ent_id, lines, commit, source)
+            if d.owner:
+                owned.setdefault((path, d.owner), {}).update(lines)
+
+    by_type: Dict[str, LineHits] = {}
+    for (path, owner), lines in owned.items():
+        types = [e for e in schema_store.find_entities(project_id, owner) if e["entity_type"] in TYPE_TYPES]
+        # `impl Order` may live away from `struct Order`; prefer a type declared in the same file
+        local = [e for e in types if e.get("file_path") == path]
+        for entity in local or (types if len(types) == 1 else []):
+            by_type.setdefault(entity["id"], {}).update(lines)
+    for ent_id, lines in by_type.items():
+        rows[ent_id] = _record(project_id, ent_id, lines, commit, source)
+    return list(rows.values())
+
+
+def head_commit(root: Path) -> str:
+    """Commit the coverage was measured at: HEAD, or 'unversioned' outside git."""
+    try:
+        result = subprocess.run(["git", "rev-parse", "--verify", "--quiet", "HEAD"], cwd=root,
+                                capture_output=True, text=True, timeout=10)
+    except (OSError, subprocess.SubprocessError):
+        return "unversioned"
+    return result.stdout.strip() if result.returncode == 0 and result.stdout.strip() else "unversioned"
+
+
+def ingest_coverage(root: Path, schema_store, reports: Optional[List[Path]] = None,
+                    commit: Optional[str] = None) -> Dict[str, Any]:
+    """Maps line coverage from `reports` (default: the usual report locations) onto entities and stores it."""
+    root = root.resolve()
+    reports = reports if reports is not None else find_reports(root)
+    if not reports:
+        raise ValueError("No coverage report found. Pass a report path or write one to " + ", ".join(DEFAULT_REPORTS[:4]))
+
+    project_id = schema_store.engine.get_project_id()
+    commit = commit or head_commit(root)
+    graph = build_call_graph(root)
+    rows: Dict[str, Dict[str, Any]] = {}
+    matched: Dict[str, LineHits] = {}
+    unmatched: List[str] = []
+    formats = []
+    for report in reports:
+        fmt, files = read_report(Path(report))
+        formats.append(fmt)
+        mapped: Dict[str, LineHits] = {}
+        for name, hits in files.items():
+            path = _project_path(root, name, graph.files)
+            if path is None:
+                unmatched.append(name)
+                continue
+            merged = mapped.setdefault(path, {})
+            for number, count in hits.items():
+                merged[number] = max(merged.get(number, 0), count)
+        for row in coverage_records(graph, mapped, schema_store, project_id, commit, fmt):
+            rows[row["entity_id"]] = row
+        for path, hits in mapped.items():
+            matched.setdefault(path, {}).update(hits)
+
+    schema_store.save_coverage_batch(list(rows.values()))
+    total = sum(len(h) for h in matched.values())
+    covered = sum(1 for h in matched.values() for count in h.values() if count > 0)
+    stats = {
+        "commit": commit,
+        "formats": formats,
+        "files": len(matched),
+        "entities": len(rows),
+        "line_percent": round(100.0 * covered / total, 1) if total else 0.0,
+        "unmatched": sorted(unmatched),
+    }
+    logger.info(f"🧪 [COVERAGE]: {len(rows)} entities in {len(matched)} files at {commit[:12]} "
+                f"({stats['line_percent']}% of lines)")
+    return stats
+
+
+# --- Consumers ---
+
+def _warning(entity: Dict[str, Any], row: Dict[str, Any]) -> Dict[str, Any]:
+    return {
+        "symbol": entity["name"],
+        "type": entity["entity_type"],
+        "file": entity.get("file_path"),
+        "line": entity.get("line"),
+        "percent": row["percent"],
+        "covered_lines": row["covered_lines"],
+        "total_lines": row["total_lines"],
+        "commit": row["commit_sha"],
+    }
+
+
+def coverage_warnings(schema_store, project_id: str, file_path: str,
+                      threshold: float = LOW_COVERAGE) -> List[Dict[str, Any]]:
+    """Entities in `file_path` whose latest coverage is below `threshold` percent, least covered first."""
+    entities = {e["id"]: e for e in schema_store.find_entities_in_file(project_id, file_path)}
+    latest = schema_store.get_latest_coverage(list(entities))
+    warnings = [_warning(entities[ent_id], row) for ent_id, row in latest.items() if row["percent"] < threshold]
+    return sorted(warnings, key=lambda w: (w["percent"], w["symbol"]))
+
+
+def untested_changes(root: Path, schema_store, since: str = "HEAD",
+                     threshold: float = LOW_COVERAGE) -> List[Dict[str, Any]]:
+    """Functions and methods edited since `since` whose latest coverage is below `threshold` percent."""
+    from side.intel.impacted_tests import changed_entities
+    from side.services.context_tracker import ContextTracker
+
+    root = root.resolve()
+    project_id = schema_store.engine.get_project_id()
+    changes = ContextTracker.get_changed_lines(root, since)
+    graph = build_call_graph(root)
+    roots = changed_entities(root, graph, [], changes, schema_store, project_id)["roots"]
+    latest = schema_store.get_latest_coverage([r["id"] for r in roots])
+    flagged = []
+    for r in roots:
+        row = latest.get(r["id"])
+        if row and row["percent"] < threshold:
+            flagged.append(_warning({"name": r["name"], "entity_type": r["type"], "file_path": r["file"],
+                                     "line": r.get("line")}, row))
+    return sorted(flagged, key=lambda w: (w["percent"], w["file"], w["symbol"]))
+
+
+def describe(warning: Dict[str, Any]) -> str:
+    """"`total` (method, src/order.rs): 0% of 4 lines covered as of abc1234"."""
+    return (f"`{warning['symbol']}` ({warning['type']}, {warning['file']}): {warning['percent']:g}% of "
+            f"{warning['total_lines']} lines covered as of {warning['commit'][:7]}")
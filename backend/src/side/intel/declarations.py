@@ -0,0 +1,200 @@
+"""
+Declarations - Signatures, visibility and doc comments for indexed entities.
+
+Works from each entity's declaration line, so the tree-sitter and regex
+extraction paths share it. Entities without a `line` are left untouched.
+Rust items also get the `#[cfg(...)]` gate written on them or on their
+`impl`/`trait` block; module-level gates are added by the indexer.
+"""
+
+import ast
+import re
+from typing import Any, Dict, List, Optional
+
+from side.intel import rust_cfg
+
+MAX_HEADER_LINES = 12
+MAX_DOC_CHARS = 1000
+
+RUST_VISIBILITY_RE = re.compile(r"^(pub(?:\s*\([^)]*\))?)\s")
+RUST_LEADING_ATTRS_RE = re.compile(r"\s*((?:#\[.*?\]\s*)*)")
+TS_MEMBER_VISIBILITY_RE = re.compile(r"^(?:(?:static|readonly|abstract|override|async|declare)\s+)*(private|protected|public)\b")
+
+
+def _strip_line_comment(line: str, language: str) -> str:
+    marker = "#" if language == "python" else "//"
+    # Good enough for headers: comment markers inside string defaults are rare
+    return line.split(marker, 1)[0]
+
+
+def declaration_header(lines: List[str], index: int, language: str) -> str:
+    """Declaration text from its first line up to the body: `{`/`;` (C-style) or `:` (Python)."""
+    openers, closers = ("([{", ")]}") if language == "python" else ("([", ")]")
+    depth = 0
+    parts = []
+    for line in lines[index:index + MAX_HEADER_LINES]:
+        code = _strip_line_comment(line, language)
+        for i, ch in enumerate(code):
+            if ch in openers:
+                depth += 1
+            elif ch in closers:
+                depth -= 1
+            elif depth == 0 and ((language == "python" and ch == ":") or (language != "python" and ch in "{;")):
+                parts.append(code[:i])
+                return _normalize(" ".join(parts))
+        parts.append(code)
+    return _normalize(lines[index] if index < len(lines) else "")
+
+
+def _normalize(header: str) -> str:
+    header = " ".join(header.split())
+    header = re.sub(r"([(\[<])\s+", r"\1", header)
+    header = re.sub(r",?\s+([)\]])", r"\1", header)
+    return header.rstrip(" =,")
+
+
+def _block_comment(lines: List[str], end: int) -> Optional[List[str]]:
+    """A `/** ... */` block ending on line `end`."""
+    if not lines[end].strip().endswith("*/"):
+        return None
+    start = end
+    while start >= 0 and "/**" not in lines[start]:
+        start -= 1
+    if start < 0:
+        return None
+    text = []
+    for line in lines[start:end + 1]:
+        line = line.strip()
+        line = re.sub(r"^/\*\*|\*/$", "", line).strip()
+        text.append(re.sub(r"^\*\s?", "", line))
+    return text
+
+
+def _leading_doc(lines: List[str], index: int, language: str) -> Optional[str]:
+    """`///` or `/** */` comments directly above a declaration, skipping attributes/decorators."""
+    i = index - 1
+    skip = "#[" if language == "rust" else "@"
+    while i >= 0 and lines[i].strip().startswith(skip):
+        i -= 1
+    if i < 0:
+        return None
+
+    doc = _block_comment(lines, i)
+    if doc is None and language == "rust":
+        doc = []
+        while i >= 0 and lines[i].strip().startswith("///") and not lines[i].strip().startswith("////"):
+            doc.insert(0, lines[i].strip()[3:].strip())
+            i -= 1
+            while i >= 0 and lines[i].strip().startswith("#["):
+                i -= 1
+    return _clean_doc(doc)
+
+
+def _rust_cfg(lines: List[str], index: int) -> Optional[str]:
+    """Gate from the `#[cfg(...)]` attributes above a declaration (or in front of it on its line)."""
+    attrs = [RUST_LEADING_ATTRS_RE.match(lines[index]).group(1)]
+    i = index - 1
+    while i >= 0:
+        stripped = lines[i].strip()
+        if stripped.startswith(("#[", "//")):
+            attrs.insert(0, lines[i])
+            i -= 1
+            continue
+        if stripped.endswith("]"):
+            # Last line of an attribute spread over several lines
+            start = i
+            while start > max(0, i - MAX_HEADER_LINES) and not lines[start].strip().startswith("#["):
+                start -= 1
+            if lines[start].strip().startswith("#["):
+                attrs[:0] = lines[start:i + 1]
+                i = start - 1
+                continue
+        break
+    return rust_cfg.combine(rust_cfg.cfg_predicates("\n".join(attrs)))
+
+
+def _inherit_owner_cfg(entities: List[Dict[str, Any]]) -> None:
+    """Methods are gated by their `impl`/`trait` block as well as their own attributes."""
+    owners = sorted((e for e in entities if e["type"] in ("impl", "trait") and e.get("line")), key=lambda e: e["line"])
+    for entity in entities:
+        if not entity.get("parent") or not entity.get("line"):
+            continue
+        owner = None
+        for candidate in owners:
+            if candidate["line"] >= entity["line"]:
+                break
+            if candidate["name"] == entity["parent"]:
+                owner = candidate   # Nearest block above the method
+        if owner and owner.get("cfg"):
+            entity["cfg"] = rust_cfg.combine([owner["cfg"], entity.get("cfg")])
+
+
+def _clean_doc(doc: Optional[List[str]]) -> Optional[str]:
+    if not doc:
+        return None
+    text = "\n".join(doc).strip()
+    return text[:MAX_DOC_CHARS] or None
+
+
+def _python_docstrings(content: str) -> Dict[int, str]:
+    """Docstrings keyed by the line of their `def`/`class`."""
+    try:
+        tree = ast.parse(content)
+    except (SyntaxError, ValueError):
+        return {}
+    docs = {}
+    for node in ast.walk(tree):
+        if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef, ast.ClassDef)):
+            doc = ast.get_docstring(node)
+            if doc:
+                docs[node.lineno] = doc[:MAX_DOC_CHARS]
+    return docs
+
+
+def _visibility(language: str, entity: Dict[str, Any], signature: str) -> Optional[str]:
+    name = entity["name"]
+    if language == "python":
+        if name.startswith("__") and name.endswith("__"):
+            return "public"
+        if name.startswith("__"):
+            return "private"
+        return "internal" if name.startswith("_") else "public"
+    if language == "rust":
+        if entity["type"] == "impl":
+            return None
+        m = RUST_VISIBILITY_RE.match(signature + " ")
+        if m:
+            return re.sub(r"\s+", "", m.group(1))
+        # Trait items and trait-impl methods take the trait's visibility
+        return "inherited" if entity.get("owner_kind") in ("trait", "trait_impl") else "private"
+    if language in ("typescript", "javascript"):
+        if entity.get("parent"):
+            if name.startswith("#"):
+                return "private"
+            m = TS_MEMBER_VISIBILITY_RE.match(signature)
+            return m.group(1) if m else "public"
+        return "export" if signature.startswith("export") else "module"
+    return None
+
+
+def annotate_entities(language: Optional[str], content: str, entities: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """Adds `signature`, `visibility`, `doc` (and Rust `cfg`) to entities that carry their declaration line."""
+    if language not in ("python", "rust", "typescript", "javascript"):
+        return entities
+    lines = content.splitlines()
+    python_docs = _python_docstrings(content) if language == "python" else {}
+
+    for entity in entities:
+        line = entity.get("line")
+        if not line or line > len(lines):
+            continue
+        index = line - 1
+        signature = declaration_header(lines, index, language)
+        entity["signature"] = signature
+        entity["visibility"] = _visibility(language, entity, signature)
+        entity["doc"] = python_docs.get(line) if language == "python" else _leading_doc(lines, index, language)
+        if language == "rust":
+            entity["cfg"] = _rust_cfg(lines, index)
+    if language == "rust":
+        _inherit_owner_cfg(entities)
+    return entities
@@ -0,0 +1,209 @@
+"""
+Graph Query - Callers, callees, implementations and blast radius.
+
+Walks SchemaStore `calls` (and `implements`/`inherits`/`tests`) edges transitively. A result's confidence is the
+product of edge confidences along its best path, so a low-confidence hop
+early on drags down everything reached through it.
+"""
+
+import logging
+import re
+from collections import deque
+from typing import Any, Dict, List, Optional
+
+from side.intel import rust_cfg
+
+logger = logging.getLogger(__name__)
+
+MAX_DEPTH = 6
+MAX_RESULTS = 50
+CALLABLE_TYPES = {"function", "method", "file"}
+TYPE_TYPES = {"class", "interface", "struct", "enum", "union", "trait", "type"}
+SOURCE_SUFFIXES = {"py", "rs", "ts", "tsx", "js", "jsx", "mjs", "cjs"}
+QUALIFIER_RE = re.compile(r"::|[./]")   # Crate, module and file path separators
+
+
+def resolve_symbol(schema_store, project_id: str, symbol: str, kinds=CALLABLE_TYPES) -> List[Dict[str, Any]]:
+    """
+    Entities for `name`, `Type.method`, `Type::method` or a path (`orders::Order::total`, `models.Order.total`).
+    Qualifiers narrow the match to entities whose qualified name ends with them, when any does.
+    """
+    parts = [p for p in symbol.strip().rstrip("()").replace("::", ".").split(".") if p]
+    if not parts:
+        return []
+    found = [e for e in schema_store.find_entities(project_id, parts[-1]) if e["entity_type"] in kinds]
+    if len(parts) > 1:
+        wanted = parts[1:] if parts[0] == "crate" else parts
+        qualified = [e for e in found
+                     if [p for p in QUALIFIER_RE.split(e.get("qualified_name") or "") if p][-len(wanted):] == wanted]
+        found = qualified or found
+    return found
+
+
+def resolve_target(schema_store, project_id: str, target: str) -> List[Dict[str, Any]]:
+    """A file path resolves to everything defined in it (and its module-level code); anything else is a symbol."""
+    looks_like_path = "/" in target or "\\" in target or ("." in target and target.rsplit(".", 1)[-1] in SOURCE_SUFFIXES)
+    if looks_like_path:
+        entities = schema_store.find_entities_in_file(project_id, target.replace("\\", "/").removeprefix("./"))
+        if entities:
+            return [e for e in entities if e["entity_type"] in CALLABLE_TYPES]
+    return resolve_symbol(schema_store, project_id, target)
+
+
+def _entry(entity: Dict[str, Any], depth: int, confidence: float, via: Optional[str]) -> Dict[str, Any]:
+    entry = {
+        "symbol": entity["name"],
+        "type": entity["entity_type"],
+        "file": entity.get("file_path"),
+        "depth": depth,
+        "confidence": round(confidence, 3),
+        "via": via,
+    }
+    if entity.get("cfg"):
+        # Conditionally compiled: "only exists with feature `postgres`"
+        entry["cfg"] = entity["cfg"]
+        entry["availability"] = rust_cfg.describe(entity["cfg"])
+    return entry
+
+
+def walk_edges(schema_store, roots: List[Dict[str, Any]], direction: str, depth: int,
+               relation_types=("calls",)) -> List[Dict[str, Any]]:
+    """
+    Breadth-first walk over edges of `relation_types` from `roots`.
+    direction='callers' follows edges backwards (who calls / implements me), 'callees' forwards.
+    """
+    depth = max(1, min(depth, MAX_DEPTH))
+    root_ids = {e["id"] for e in roots}
+    best: Dict[str, Dict[str, Any]] = {}
+    queue = deque((e["id"], e["name"], 0, 1.0) for e in roots)
+
+    while queue:
+        entity_id, name, level, score = queue.popleft()
+        if level >= depth:
+            continue
+        if direction == "callers":
+            edges = schema_store.list_relationships(target_id=entity_id)
+            next_key = "source_id"
+        else:
+            edges = schema_store.list_relationships(source_id=entity_id)
+            next_key = "target_id"
+        edges = [e for e in edges if e["relation_type"] in relation_types and e[next_key] not in root_ids]
+        # One lookup for every entity first reached from here
+        others = schema_store.get_entities_by_ids([e[next_key] for e in edges if e[next_key] not in best])
+
+        for edge in edges:
+            other_id = edge[next_key]
+            confidence = score * (edge.get("confidence") or 0.0)
+            seen = best.get(other_id)
+            if seen:
+                if seen["confidence"] < round(confidence, 3):
+                    seen.update(confidence=round(confidence, 3), via=name)
+                continue
+            other = others.get(other_id)
+            if not other:
+                continue  # Edge into an entity that was never stored
+            best[other_id] = _entry(other, level + 1, confidence, name)
+            queue.append((other_id, other["name"], level + 1, confidence))
+
+    return sorted(best.values(), key=lambda r: (-r["confidence"], r["depth"], r["symbol"]))[:MAX_RESULTS]
+
+
+def find_callers(schema_store, project_id: str, symbol: str, depth: int = 2) -> Dict[str, Any]:
+    """Functions that (transitively) call `symbol`, most certain first."""
+    roots = resolve_symbol(schema_store, project_id, symbol)
+    return {
+        "symbol": symbol,
+        "matches": [_entry(e, 0, 1.0, None) for e in roots],
+        "callers": walk_edges(schema_store, roots, "callers", depth),
+    }
+
+
+def find_callees(schema_store, project_id: str, symbol: str, depth: int = 2) -> Dict[str, Any]:
+    """Functions `symbol` (transitively) calls, most certain first."""
+    roots = resolve_symbol(schema_store, project_id, symbol)
+    return {
+        "symbol": symbol,
+        "matches": [_entry(e, 0, 1.0, None) for e in roots],
+        "callees": walk_edges(schema_store, roots, "callees", depth),
+    }
+
+
+def impact_of_change(schema_store, project_id: str, target: str, depth: int = 3) -> Dict[str, Any]:
+    """Blast radius of editing a file or symbol: everything that reaches it through calls, grouped by file."""
+    roots = resolve_target(schema_store, project_id, target)
+    affected = walk_edges(schema_store, roots, "callers", depth)
+
+    files: Dict[str, Dict[str, Any]] = {}
+    for item in affected:
+        path = item["file"] or "unknown"
+        summary = files.setdefault(path, {"file": path, "symbols": 0, "max_confidence": 0.0})
+        summary["symbols"] += 1
+        summary["max_confidence"] = max(summary["max_confidence"], item["confidence"])
+
+    root_files = {e.get("file_path") for e in roots}
+    return {
+        "target": target,
+        "roots": [_entry(e, 0, 1.0, None) for e in roots],
+        "affected": affected,
+        "files": sorted((f for f in files.values() if f["file"] not in root_files),
+                        key=lambda f: (-f["max_confidence"], -f["symbols"], f["file"])),
+        "risk": "high" if len(affected) >= 10 else "medium" if affected else "low",
+    }
+
+
+def find_implementations(schema_store, project_id: str, symbol: str, depth: int = 3) -> Dict[str, Any]:
+    """Types that implement or (transitively) inherit from a trait, interface or class."""
+    roots = resolve_symbol(schema_store, project_id, symbol, kinds=TYPE_TYPES)
+    return {
+        "symbol": symbol,
+        "matches": [_entry(e, 0, 1.0, None) for e in roots],
+        "implementations": walk_edges(schema_store, roots, "callers", depth, relation_types=("implements", "inherits")),
+    }
+
+
+def covering_tests(schema_store, roots: List[Dict[str, Any]], depth: int) -> Dict[str, Dict[str, Any]]:
+    """
+    Test entity id -> entry for every test reaching `roots`: ones calling a root directly (depth 1),
+    then ones reaching it through up to `depth - 1` intermediate callers (`via` names what the test calls).
+    """
+    depth = max(1, min(depth, MAX_DEPTH))
+    tests: Dict[str, Dict[str, Any]] = {}
+    seen = {e["id"] for e in roots}
+    queue = deque((e["id"], e["name"], 0, 1.0) for e in roots)
+
+    while queue:
+        entity_id, name, level, score = queue.popleft()
+        if level >= depth:
+            continue
+        edges = schema_store.list_relationships(target_id=entity_id)
+        others = schema_store.get_entities_by_ids([
+            e["source_id"] for e in edges
+            if e["relation_type"] == "tests" or (e["relation_type"] == "calls" and e["source_id"] not in seen)
+        ])
+        for edge in edges:
+            confidence = score * (edge.get("confidence") or 0.0)
+            other_id = edge["source_id"]
+            if edge["relation_type"] == "tests":
+                known = tests.get(other_id)
+                if known and known["confidence"] >= round(confidence, 3):
+                    continue
+                test = others.get(other_id)
+                if test:
+                    tests[other_id] = _entry(test, level + 1, confidence, name)
+            elif edge["relation_type"] == "calls" and other_id not in seen:
+                seen.add(other_id)
+                caller = others.get(other_id)
+                if caller:
+                    queue.append((other_id, caller["name"], level + 1, confidence))
+    return tests
+
+
+def find_tests(schema_store, project_id: str, symbol: str, depth: int = 2) -> Dict[str, Any]:
+    """Tests that exercise `symbol`, directly or through its callers; direct ones first."""
+    roots = resolve_symbol(schema_store, project_id, symbol)
+    tests = covering_tests(schema_store, roots, depth)
+    return {
+        "symbol": symbol,
+        "matches": [_entry(e, 0, 1.0, None) for e in roots],
+        "tests": sorted(tests.values(), key=lambda r: (r["depth"], -r["confidence"], r["file"] or "", r["symbol"]))[:MAX_RESULTS],
+    }
@@ -43,6 +43,7 @@ class PromptBuilder:
         # 3. ACTIVE FOCUS & SOURCE (Surgical)
         if active_file:
              context_parts.append(f"📍 [ACTIVE FOCUS]: User is working on '{active_file}'.")
+             self._add_coverage(context_parts, active_file)
              if include_code:
                  self._add_source_code(context_parts, active_file)
 
@@ -92,6 +93,20 @@ class PromptBuilder:
         except Exception as e:
             logger.debug(f"Failed to inject source code: {e}")
 
+    def _add_coverage(self, parts: List[str], active_file: str):
+        """Flags untested or barely tested symbols in the file being edited."""
+        try:
+            from side.intel.coverage import coverage_warnings, describe
+            path = Path(active_file)
+            if path.is_absolute() and path.is_relative_to(self.project_path):
+                path = path.relative_to(self.project_path)
+            warnings = coverage_warnings(self.engine.schema, self.engine.get_project_id(), path.as_posix())
+            if warnings:
+                lines = "\n".join(f"- {describe(w)}" for w in warnings[:10])
+                parts.append(f"🧪 [COVERAGE]: Editing code with little or no test coverage:\n{lines}")
+        except Exception as e:
+            logger.debug(f"Failed to inject coverage: {e}")
+
     def get_surgical_context(self, query: str, limit: int = 3) -> str:
         """
         Surgical Attention: Finds most relevant code files based on semantic query.
@@ -1,11 +1,10 @@
 import logging
 import uuid
-import json
 from pathlib import Path
 from typing import Optional
-from side.utils.crypto import shield
 from side.common.constants import Origin
 from side.utils.hashing import sparse_hasher
+from side.intel.index_store import open_index_store
 
 logger = logging.getLogger(__name__)
 
@@ -18,13 +17,11 @@ class CodeIndexer:
 
     def get_condensed_dna(self) -> str:
         """Extract a high-level architectural summary from the Index."""
-        root_index = self.project_path / ".side" / "local.json"
-        if not root_index.exists():
+        data = open_index_store(self.project_path).read(self.project_path)
+        if data is None:
             return "DNA Not Found. Run 'side feed' to initialize."
             
         try:
-            raw_data = shield.unseal_file(root_index)
-            data = json.loads(raw_data)
             signals = data.get("dna", {}).get("signals", [])
             summary = f"Architectural DNA: {', '.join(signals)}\n"
             summary += f"Context Path: {data.get('path', 'root')}\n"
@@ -40,14 +37,12 @@ class CodeIndexer:
         Recursively walks the distributed Context Index to build a tree view.
         """
         tree_out = ""
-        index_path = path / ".side" / "local.json"
+        data = open_index_store(self.project_path).read(path)
         
-        if not index_path.exists():
+        if data is None:
             return tree_out
             
         try:
-            raw = shield.unseal_file(index_path)
-            data = json.loads(raw)
             files = sorted(data.get("context", {}).get("files", []), key=lambda x: x['name'])
             children = sorted(data.get("context", {}).get("children", {}).items())
             
@@ -167,13 +162,11 @@ class CodeIndexer:
 
     def get_condensed_dna(self) -> str:
         """Extract a high-level architectural summary from the Fractal Index."""
-        root_index = self.project_path / ".side" / "local.json"
-        if not root_index.exists():
+        data = open_index_store(self.project_path).read(self.project_path)
+        if data is None:
             return "DNA Not Found. Run 'side feed' to initialize."
             
         try:
-            raw_data = shield.unseal_file(root_index)
-            data = json.loads(raw_data)
             signals = data.get("dna", {}).get("signals", [])
             summary = f"Architectural DNA: {', '.join(signals)}\n"
             summary += f"Context Path: {data.get('path', 'root')}\n"
@@ -189,14 +182,12 @@ class CodeIndexer:
         Recursively walks the distributed Context Index to build a Merkle Tree.
         """
         tree_out = ""
-        index_path = path / ".side" / "local.json"
+        data = open_index_store(self.project_path).read(path)
         
-        if not index_path.exists():
+        if data is None:
             return tree_out
             
         try:
-            raw = shield.unseal_file(index_path)
-            data = json.loads(raw)
             files = sorted(data.get("context", {}).get("files", []), key=lambda x: x['name'])
             children = sorted(data.get("context", {}).get("children", {}).items())
             
@@ -0,0 +1,44 @@
+"""
+Graph Ids - The one id scheme for SchemaStore entities and relationships.
+
+Every pass (tree indexer, call/type graphs, module tree, cfg, Cargo, SCIP,
+tests, routes) derives ids here, so an edge written by one pass lands on the
+entity another pass stored. Changing the scheme changes every stored id: bump
+`index_cache.CACHE_VERSION` with it.
+
+Project items are keyed by a qualified name, so same-named items of different
+owners, files or modules stay apart: a Rust crate path (`shop::orders::Order::total`),
+a Python module path (`pkg.models.Cart.total`) or, for other languages, the
+defining file (`web/app/orders/route.ts::GET`). Only items outside the project
+(std, site-packages, node_modules) are keyed by bare name.
+"""
+
+import hashlib
+from pathlib import PurePosixPath
+from typing import Optional
+
+
+def entity_id(project_id: str, name: str, entity_type: str, qualified_name: Optional[str] = None) -> str:
+    """Entities are keyed by their qualified name when they have one."""
+    return hashlib.sha256(f"{project_id}:{qualified_name or name}:{entity_type}".encode()).hexdigest()[:16]
+
+
+def relationship_id(source_id: str, target_id: str, relation_type: str) -> str:
+    return hashlib.sha256(f"{source_id}:{target_id}:{relation_type}".encode()).hexdigest()[:16]
+
+
+def python_module(rel_path: str) -> str:
+    """`pkg/models.py` -> `pkg.models`; a package's `__init__.py` is the package itself."""
+    parts = list(PurePosixPath(rel_path).with_suffix("").parts)
+    if parts and parts[-1] == "__init__":
+        parts.pop()
+    return ".".join(parts)
+
+
+def file_qualified_name(file_path: str, name: str, owner: Optional[str] = None) -> str:
+    """Qualified name of a non-Rust item defined in `file_path` (project-relative), under `owner` if a member."""
+    member = f"{owner}.{name}" if owner else name
+    if file_path.endswith(".py"):
+        module = python_module(file_path)
+        return f"{module}.{member}" if module else member
+    return f"{file_path}::{member}"
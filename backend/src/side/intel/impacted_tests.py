@@ -0,0 +1,200 @@
+"""
+Impacted Tests - The smallest set of tests a change can break.
+
+The working tree is compared with a revision (`ContextTracker.get_changed_lines`);
+changed lines map to the functions, methods and test blocks containing them in the
+current call graph, and `tests`/`calls` edges lead back to every test that reaches
+one of them. Selected tests are grouped per runner into ready-to-run command lines:
+`cargo test` filters, pytest node ids and vitest/jest `-t` patterns.
+"""
+
+import logging
+import re
+import shlex
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+from side.intel import rust_modules
+from side.intel.call_graph import CALL_GRAPH_LANGUAGES, CallGraph, build_call_graph, crate_path, definition_id
+from side.intel.languages import registry as languages
+from side.intel.graph_query import CALLABLE_TYPES, covering_tests
+from side.intel.test_links import DiscoveredTest, discover_tests, discovered_test_id
+
+logger = logging.getLogger(__name__)
+
+Ranges = Optional[List[Tuple[int, int]]]    # None: the whole file was added or deleted
+JS_REGEX_SPECIAL_RE = re.compile(r"[.*+?^${}()|[\]\\/]")
+
+
+def _touches(ranges: Ranges, first: int, last: int) -> bool:
+    return ranges is None or any(start <= last and first <= end for start, end in ranges)
+
+
+def _module_level(ranges: Ranges, spans: List[Tuple[int, int]]) -> bool:
+    """True when some changed line lies outside every span."""
+    if ranges is None:
+        return True
+    return any(not any(s <= line <= e for s, e in spans) for start, end in ranges for line in range(start, end + 1))
+
+
+def changed_entities(root: Path, graph: CallGraph, tests: List[DiscoveredTest], changes: Dict[str, Ranges],
+                     schema_store, project_id: str) -> Dict[str, Any]:
+    """Entities whose lines changed, tests edited directly and files no entity accounts for."""
+    roots: Dict[str, Dict[str, Any]] = {}
+    edited: Dict[str, DiscoveredTest] = {}
+    unmapped = []
+
+    for path, ranges in sorted(changes.items()):
+        file = graph.files.get(path)
+        if file is None:
+            # Deleted sources are gone from the graph but their entities (and edges) are still stored
+            stored = [e for e in schema_store.find_entities_in_file(project_id, path)
+                      if e["file_path"] == path and e["entity_type"] in CALLABLE_TYPES]
+            roots.update((e["id"], {"id": e["id"], "name": e["name"], "type": e["entity_type"], "file": path})
+                         for e in stored if e["entity_type"] != "file")
+            spec = languages.for_path(Path(path))
+            if not stored and spec and spec.name in CALL_GRAPH_LANGUAGES:
+                unmapped.append(path)   # Non-source files (manifests, docs) are not traced
+            continue
+
+        for d in file.definitions:
+            if _touches(ranges, d.line, d.end_line):
+                ent_id = definition_id(graph, d, path, project_id)
+                roots[ent_id] = {"id": ent_id, "name": d.name, "type": d.kind, "file": path, "line": d.line}
+        in_file = [t for t in tests if t.file == path]
+        for t in in_file:
+            if _touches(ranges, t.line, t.end_line):
+                edited[discovered_test_id(graph, t, project_id)] = t
+
+        spans = [(d.line, d.end_line) for d in file.definitions] + [(t.line, t.end_line) for t in in_file]
+        if _module_level(ranges, spans):
+            if in_file:
+                # Imports, fixtures and setup at module level can break every test in the file
+                edited.update((discovered_test_id(graph, t, project_id), t) for t in in_file)
+            elif not any(r["file"] == path for r in roots.values()):
+                unmapped.append(path)
+
+    return {"roots": list(roots.values()), "edited": edited, "unmapped": unmapped}
+
+
+# --- Runner selectors ---
+
+def _rust_selector(root: Path, graph: CallGraph, test: DiscoveredTest) -> Tuple[Tuple[str, ...], str]:
+    """(cargo arguments selecting the test binary, `--exact` filter)."""
+    qualified = crate_path(graph, test.definition, test.file) or test.name
+    segments = qualified.split("::")
+    filter_path = "::".join(segments[1:]) or test.name
+    crate_dir = rust_modules.find_crate_dir(root, root / test.file)
+    args: List[str] = []
+    if crate_dir and crate_dir != root:
+        args += ["--manifest-path", (crate_dir / "Cargo.toml").relative_to(root).as_posix()]
+    in_crate = (root / test.file).relative_to(crate_dir or root).as_posix()
+    if in_crate.startswith("tests/"):
+        args += ["--test", segments[0]]
+    return tuple(args), filter_path
+
+
+def _pytest_node(test: DiscoveredTest) -> str:
+    owner = test.definition.owner if test.definition else None
+    return "::".join(p for p in (test.file, owner, test.name) if p)
+
+
+def _js_package(root: Path, test: DiscoveredTest) -> Tuple[Path, str]:
+    """Nearest package.json directory and its runner (`vitest` unless only jest is configured)."""
+    directory = (root / test.file).parent
+    while directory != root and not (directory / "package.json").is_file():
+        directory = directory.parent
+    manifest = directory / "package.json"
+    text = manifest.read_text(errors="ignore") if manifest.is_file() else ""
+    runner = "jest" if "jest" in text and "vitest" not in text else "vitest"
+    return directory, runner
+
+
+def _command(runner: str, cwd: Path, root: Path, argv: List[str], count: int) -> Dict[str, Any]:
+    return {
+        "runner": runner,
+        "cwd": cwd.relative_to(root).as_posix() or ".",
+        "tests": count,
+        "command": shlex.join(argv),
+    }
+
+
+def runner_commands(root: Path, graph: CallGraph, tests: List[DiscoveredTest]) -> Tuple[List[Dict[str, Any]], Dict[int, str]]:
+    """Command lines running exactly `tests`, and each test's selector (filter, node id or full name)."""
+    selectors: Dict[int, str] = {}
+    cargo: Dict[Tuple[str, ...], List[str]] = {}
+    pytest_nodes: List[str] = []
+    js: Dict[Tuple[Path, str], Tuple[List[str], List[str]]] = {}
+
+    for t in tests:
+        if t.framework == "cargo":
+            args, filter_path = _rust_selector(root, graph, t)
+            cargo.setdefault(args, []).append(filter_path)
+            selectors[id(t)] = filter_path
+        elif t.framework == "pytest":
+            selectors[id(t)] = _pytest_node(t)
+            pytest_nodes.append(selectors[id(t)])
+        else:
+            directory, runner = _js_package(root, t)
+            files, names = js.setdefault((directory, runner), ([], []))
+            relative = (root / t.file).relative_to(directory).as_posix()
+            if relative not in files:
+                files.append(relative)
+            selectors[id(t)] = t.name.replace(" > ", " ")   # Runners join describe/it names with spaces
+            names.append(selectors[id(t)])
+
+    commands = []
+    for args, filters in sorted(cargo.items()):
+        commands.append(_command("cargo", root, root, ["cargo", "test", *args, "--", *sorted(set(filters)), "--exact"],
+                                 len(set(filters))))
+    if pytest_nodes:
+        commands.append(_command("pytest", root, root, ["pytest", *sorted(set(pytest_nodes))], len(set(pytest_nodes))))
+    for (directory, runner), (files, names) in sorted(js.items()):
+        pattern = "^(?:" + "|".join(JS_REGEX_SPECIAL_RE.sub(r"\\\g<0>", n) for n in sorted(set(names))) + ")$"
+        argv = ["npx", runner, *(["run"] if runner == "vitest" else []), *sorted(files), "-t", pattern]
+        commands.append(_command(runner, directory, root, argv, len(set(names))))
+    return commands, selectors
+
+
+def affected_tests(root: Path, schema_store, since: str = "HEAD", depth: int = 3,
+                   changes: Optional[Dict[str, Ranges]] = None) -> Dict[str, Any]:
+    """Tests affected by the working tree's changes since `since`, with the commands that run only them."""
+    from side.services.context_tracker import ContextTracker
+
+    root = root.resolve()
+    if changes is None:
+        changes = ContextTracker.get_changed_lines(root, since)
+    changes = {p: r for p, r in changes.items() if not p.startswith(".side/")}
+    project_id = schema_store.engine.get_project_id()
+    graph = build_call_graph(root)
+    tests = discover_tests(root, graph)
+    changed = changed_entities(root, graph, tests, changes, schema_store, project_id)
+
+    reached = covering_tests(schema_store, changed["roots"], depth)
+    by_id = {discovered_test_id(graph, t, project_id): t for t in tests}
+    selected: Dict[str, Tuple[DiscoveredTest, int, Optional[str], float]] = {}
+    for ent_id, t in changed["edited"].items():
+        selected[ent_id] = (t, 0, None, 1.0)
+    for ent_id, entry in reached.items():
+        if ent_id in by_id and ent_id not in selected:
+            selected[ent_id] = (by_id[ent_id], entry["depth"], entry["via"], entry["confidence"])
+
+    ordered = sorted(selected.values(), key=lambda s: (s[1], -s[3], s[0].file, s[0].line))
+    commands, selectors = runner_commands(root, graph, [s[0] for s in ordered])
+    report = {
+        "since": since,
+        "changed_files": sorted(changes),
+        "changed": [{"symbol": r["name"], "type": r["type"], "file": r["file"]} for r in changed["roots"]],
+        "tests": [
+            {"test": selectors[id(t)], "runner": t.framework, "file": t.file, "line": t.line,
+             "depth": level, "via": via, "confidence": round(confidence, 3)}
+            for t, level, via, confidence in ordered
+        ],
+        "commands": commands,
+        "summary": f"{len(ordered)} of {len(tests)} tests affected by {len(changed['roots'])} changed entities",
+    }
+    if changed["unmapped"]:
+        report["unmapped"] = changed["unmapped"]
+        report["warning"] = "Changes outside any function are not traced to tests: " + ", ".join(changed["unmapped"])
+    logger.info(f"🎯 [TEST IMPACT]: {report['summary']}")
+    return report
@@ -0,0 +1,187 @@
+"""
+Index Cache - Content-addressed file semantics for incremental indexing.
+
+Layout under `<root>/.side/cache/index/v<N>/`:
+- `objects/<ab>/<hash>.<language>`: lines + semantics for one file content (hash -> semantics)
+- `dirs/<dir key>`: per-directory stat manifest, name -> [mtime_ns, size, hash]
+- `stores`: the databases (path + project) a full scan has persisted every file to
+
+A stat match skips reading the file; a content match (renames, copies,
+reverted edits) skips parsing. Stat hits skip persistence only for a database
+listed in `stores`. Bump CACHE_VERSION when extraction output changes.
+"""
+
+import hashlib
+import json
+import logging
+import os
+import threading
+from pathlib import Path
+from typing import Any, Dict, Optional, Set
+
+from side.intel.languages import registry as languages
+from side.utils.crypto import shield
+
+logger = logging.getLogger(__name__)
+
+CACHE_VERSION = 4
+
+
+def _language(path: Path) -> str:
+    spec = languages.for_path(path)
+    return spec.name if spec else "plain"
+
+
+class IndexCache:
+    """Content-addressed semantics store plus per-directory stat manifests."""
+
+    def __init__(self, root: Path):
+        self.root = root
+        self.base = root / ".side" / "cache" / "index" / f"v{CACHE_VERSION}"
+        self._manifests: Dict[Path, Dict[str, list]] = {}
+        self._used: Set[str] = set()
+        self._stores: Optional[Set[str]] = None
+        self.hits = 0
+        self.misses = 0
+
+    # --- Content objects ---
+
+    def _object_path(self, content_hash: str, language: str) -> Path:
+        return self.base / "objects" / content_hash[:2] / f"{content_hash}.{language}"
+
+    def touch(self, content_hash: str, path: Path) -> None:
+        """Marks an object as live for prune() (e.g. when a worker process stored it)."""
+        self._used.add(self._object_path(content_hash, _language(path)).name)
+
+    def get_semantics(self, content_hash: str, path: Path) -> Optional[Dict[str, Any]]:
+        """Cached {lines, semantics} for this content, or None."""
+        obj = self._object_path(content_hash, _language(path))
+        self._used.add(obj.name)
+        if not obj.exists():
+            return None
+        try:
+            return json.loads(shield.unseal_file(obj))
+        except Exception as e:
+            logger.debug(f"Index cache entry unreadable {obj}: {e}")
+            return None
+
+    def put_semantics(self, content_hash: str, path: Path, lines: int, semantics: Dict[str, Any]) -> None:
+        obj = self._object_path(content_hash, _language(path))
+        self._used.add(obj.name)
+        try:
+            obj.parent.mkdir(parents=True, exist_ok=True)
+            # Write-then-rename: parallel workers may store the same content
+            tmp = obj.with_name(f"{obj.name}.{os.getpid()}.{threading.get_ident()}.tmp")
+            shield.seal_file(tmp, json.dumps({"lines": lines, "semantics": semantics}))
+            os.replace(tmp, obj)
+        except OSError as e:
+            logger.debug(f"Index cache write failed {obj}: {e}")
+
+    # --- Stat manifests ---
+
+    def _manifest_path(self, directory: Path) -> Path:
+        try:
+            rel = directory.relative_to(self.root).as_posix()
+        except ValueError:
+            rel = str(directory)
+        return self.base / "dirs" / hashlib.sha256(rel.encode()).hexdigest()[:16]
+
+    def _manifest(self, directory: Path) -> Dict[str, list]:
+        if directory not in self._manifests:
+            path = self._manifest_path(directory)
+            manifest = {}
+            if path.exists():
+                try:
+                    manifest = json.loads(shield.unseal_file(path))
+                except Exception:
+                    manifest = {}
+            self._manifests[directory] = manifest
+        return self._manifests[directory]
+
+    def lookup(self, path: Path) -> Optional[Dict[str, Any]]:
+        """Full file DNA when the file is unchanged since it was last indexed (no read, no parse)."""
+        entry = self._manifest(path.parent).get(path.name)
+        try:
+            stat = path.stat()
+        except OSError:
+            return None
+        if not entry or entry[0] != stat.st_mtime_ns or entry[1] != stat.st_size:
+            self.misses += 1
+            return None
+        cached = self.get_semantics(entry[2], path)
+        if cached is None:
+            self.misses += 1
+            return None
+        self.hits += 1
+        return {
+            "name": path.name,
+            "type": path.suffix,
+            "size": stat.st_size,
+            "lines": cached["lines"],
+            "hash": entry[2],
+            "semantics": cached["semantics"],
+        }
+
+    def update_directory(self, directory: Path, dnas: Dict[Path, Dict[str, Any]]) -> None:
+        """Replaces the directory's manifest with the files just indexed (drops deleted ones)."""
+        manifest = {}
+        for path, dna in dnas.items():
+            if "hash" not in dna:
+                continue
+            try:
+                stat = path.stat()
+            except OSError:
+                continue
+            manifest[path.name] = [stat.st_mtime_ns, stat.st_size, dna["hash"]]
+
+        if manifest == self._manifests.get(directory):
+            return
+        self._manifests[directory] = manifest
+        target = self._manifest_path(directory)
+        try:
+            target.parent.mkdir(parents=True, exist_ok=True)
+            shield.seal_file(target, json.dumps(manifest, sort_keys=True))
+        except OSError as e:
+            logger.debug(f"Index cache manifest write failed {target}: {e}")
+
+    # --- Persisted databases ---
+
+    def _load_stores(self) -> Set[str]:
+        if self._stores is None:
+            self._stores = set()
+            path = self.base / "stores"
+            if path.exists():
+                try:
+                    self._stores = set(json.loads(shield.unseal_file(path)))
+                except Exception:
+                    pass
+        return self._stores
+
+    def persisted_to(self, store_key: str) -> bool:
+        """True when a full scan already wrote every cached file's entities to this database."""
+        return store_key in self._load_stores()
+
+    def mark_persisted(self, store_key: str) -> None:
+        stores = self._load_stores()
+        if store_key in stores:
+            return
+        stores.add(store_key)
+        try:
+            self.base.mkdir(parents=True, exist_ok=True)
+            shield.seal_file(self.base / "stores", json.dumps(sorted(stores)))
+        except OSError as e:
+            logger.debug(f"Index cache store marker write failed: {e}")
+
+    def prune(self) -> int:
+        """Deletes objects and manifests not touched during this run. Only valid after a full scan."""
+        seen = {self._manifest_path(d).name for d in self._manifests}
+        removed = 0
+        for obj in list(self.base.glob("objects/*/*")) + list(self.base.glob("dirs/*")):
+            keep = self._used if obj.parent.name != "dirs" else seen
+            if obj.name not in keep:
+                try:
+                    obj.unlink()
+                    removed += 1
+                except OSError:
+                    pass
+        return removed
@@ -0,0 +1,204 @@
+"""
+Index Diff - What changed in the code index between two git revisions.
+
+Each revision is read from the git object store (`git ls-tree` + `git cat-file --batch`)
+into a scratch directory and indexed with the working-tree extractors, so nothing
+is checked out. Snapshots are cached by tree hash under `.side/cache/revisions/`,
+and unchanged file contents come from the shared content-addressed IndexCache.
+
+The report lists added/removed/changed entities, signature changes and
+call edges that appear (or disappear) between the two revisions.
+"""
+
+import hashlib
+import json
+import logging
+import subprocess
+import tempfile
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+from side.intel.index_cache import CACHE_VERSION, IndexCache
+from side.intel.languages import registry as languages
+from side.utils.crypto import shield
+
+logger = logging.getLogger(__name__)
+
+SNAPSHOT_VERSION = 1
+SKIPPED_TYPES = {"impl", "file"}        # Not identifiable across revisions on their own
+SUPPORT_FILES = {".gitignore", ".sideignore"}
+GIT_TIMEOUT = 60
+
+
+def git(root: Path, *args: str, stdin: Optional[bytes] = None) -> bytes:
+    result = subprocess.run(["git", *args], cwd=root, input=stdin, capture_output=True, timeout=GIT_TIMEOUT)
+    if result.returncode != 0:
+        raise ValueError(result.stderr.decode(errors="replace").strip() or f"git {args[0]} failed")
+    return result.stdout
+
+
+def resolve_tree(root: Path, rev: str) -> str:
+    """Tree hash of a revision (commit, branch, tag)."""
+    try:
+        return git(root, "rev-parse", "--verify", "--quiet", f"{rev}^{{tree}}").decode().strip()
+    except ValueError:
+        raise ValueError(f"Unknown revision: {rev}") from None
+
+
+def _wanted(path: str) -> bool:
+    name = path.rsplit("/", 1)[-1]
+    return bool(languages.for_path(path) or languages.for_manifest(name) or name in SUPPORT_FILES)
+
+
+def materialize(root: Path, tree: str, dest: Path) -> int:
+    """Writes the revision's source files and manifests under `dest`. Paths are relative to `root`."""
+    blobs: List[Tuple[str, str]] = []
+    for entry in git(root, "ls-tree", "-r", "-z", tree).split(b"\0"):
+        if not entry:
+            continue
+        meta, path = entry.decode(errors="replace").split("\t", 1)
+        mode, kind, sha = meta.split()
+        if kind == "blob" and mode != "120000" and _wanted(path):   # Symlinks could point outside `dest`
+            blobs.append((sha, path))
+    if not blobs:
+        return 0
+
+    out = git(root, "cat-file", "--batch", stdin="".join(f"{sha}\n" for sha, _ in blobs).encode())
+    pos = 0
+    for _, path in blobs:
+        header_end = out.index(b"\n", pos)
+        size = int(out[pos:header_end].split()[2])
+        content = out[header_end + 1:header_end + 1 + size]
+        pos = header_end + 2 + size                           # Content is followed by a newline
+        target = dest / path
+        target.parent.mkdir(parents=True, exist_ok=True)
+        target.write_bytes(content)
+    return len(blobs)
+
+
+def _span_digests(content: str, entities: List[Dict[str, Any]], ends: Dict[Tuple[str, int], int]) -> List[str]:
+    """Digest of each entity's source span: a function's body, or up to the next entity."""
+    lines = content.splitlines()
+    starts = sorted({e["line"] for e in entities if e.get("line")})
+    digests = []
+    for entity in entities:
+        start = entity.get("line") or 0
+        end = ends.get((entity["name"], start))
+        if end is None:
+            end = next((s - 1 for s in starts if s > start), len(lines))
+        body = "\n".join(line.rstrip() for line in lines[max(start - 1, 0):end]).strip()
+        digests.append(hashlib.sha256(body.encode()).hexdigest()[:16])
+    return digests
+
+
+def build_snapshot(root: Path, tree: str) -> Dict[str, Any]:
+    """Entities and resolved call edges of one tree, keyed `file::Owner.name`."""
+    from side.intel.call_graph import CallGraph, extract_file_symbols, iter_source_files, module_name
+    from side.intel.tree_indexer import get_file_dna
+
+    cache = IndexCache(root)
+    entities: Dict[str, Dict[str, Any]] = {}
+    files = []
+    with tempfile.TemporaryDirectory(prefix="side-rev-") as scratch:
+        base = Path(scratch)
+        materialize(root, tree, base)
+        for path in sorted(iter_source_files(base, [spec.name for spec in languages.all()])):
+            rel = path.relative_to(base).as_posix()
+            spec = languages.for_path(path)
+            content = path.read_text(errors="ignore")
+            dna = get_file_dna(path, cache)
+            symbols = extract_file_symbols(path, content, rel, module_name(base, path, spec.name))
+            if symbols:
+                files.append(symbols)
+
+            ends = {(d.name, d.line): d.end_line for d in symbols.definitions} if symbols else {}
+            found = [e for e in dna.get("semantics", {}).get("entities", []) if e.get("type") not in SKIPPED_TYPES]
+            for entity, digest in zip(found, _span_digests(content, found, ends)):
+                qualname = f"{entity['parent']}.{entity['name']}" if entity.get("parent") else entity["name"]
+                entities[f"{rel}::{qualname}"] = {
+                    "symbol": qualname,
+                    "type": entity["type"],
+                    "file": rel,
+                    "line": entity.get("line"),
+                    "signature": entity.get("signature"),
+                    "digest": digest,
+                }
+
+    calls: Dict[str, float] = {}
+    for call in CallGraph(files).resolve():
+        caller = call.caller.qualname if call.caller else "<module>"
+        edge = f"{call.caller_file}::{caller} -> {call.target_file}::{call.target.qualname}"
+        calls[edge] = max(calls.get(edge, 0.0), call.confidence)
+    return {"tree": tree, "entities": entities, "calls": calls}
+
+
+def load_snapshot(root: Path, rev: str) -> Dict[str, Any]:
+    """Cached snapshot for the revision's tree, building it on a miss."""
+    tree = resolve_tree(root, rev)
+    prefix = git(root, "rev-parse", "--show-prefix").decode().strip()
+    key = hashlib.sha256(f"{tree}:{prefix}:{CACHE_VERSION}".encode()).hexdigest()[:16]
+    path = root / ".side" / "cache" / "revisions" / f"v{SNAPSHOT_VERSION}" / key
+    if path.exists():
+        try:
+            return json.loads(shield.unseal_file(path))
+        except Exception as e:
+            logger.debug(f"Revision snapshot unreadable {path}: {e}")
+
+    snapshot = build_snapshot(root, tree)
+    try:
+        path.parent.mkdir(parents=True, exist_ok=True)
+        shield.seal_file(path, json.dumps(snapshot))
+    except OSError as e:
+        logger.debug(f"Revision snapshot write failed {path}: {e}")
+    return snapshot
+
+
+def _summary(entity: Dict[str, Any]) -> Dict[str, Any]:
+    return {k: entity[k] for k in ("symbol", "type", "file", "line")}
+
+
+def _edge(edge: str, confidence: float) -> Dict[str, Any]:
+    caller, callee = edge.split(" -> ", 1)
+    caller_file, caller_name = caller.split("::", 1)
+    callee_file, callee_name = callee.split("::", 1)
+    return {"caller": caller_name, "caller_file": caller_file, "callee": callee_name,
+            "callee_file": callee_file, "confidence": confidence}
+
+
+def diff_snapshots(base: Dict[str, Any], head: Dict[str, Any]) -> Dict[str, Any]:
+    old, new = base["entities"], head["entities"]
+    added = [_summary(new[k]) for k in sorted(new.keys() - old.keys())]
+    removed = [_summary(old[k]) for k in sorted(old.keys() - new.keys())]
+    changed, signatures = [], []
+    for key in sorted(old.keys() & new.keys()):
+        before, after = old[key], new[key]
+        signature_changed = (before.get("signature") or "") != (after.get("signature") or "")
+        if before["digest"] != after["digest"] or signature_changed:
+            changed.append({**_summary(after), "signature_changed": signature_changed})
+        if signature_changed:
+            signatures.append({**_summary(after), "before": before.get("signature"), "after": after.get("signature")})
+
+    old_calls, new_calls = base["calls"], head["calls"]
+    added_calls = sorted((_edge(e, c) for e, c in new_calls.items() if e not in old_calls),
+                         key=lambda e: (-e["confidence"], e["caller_file"], e["caller"], e["callee"]))
+    removed_calls = sorted((_edge(e, c) for e, c in old_calls.items() if e not in new_calls),
+                           key=lambda e: (-e["confidence"], e["caller_file"], e["caller"], e["callee"]))
+    return {
+        "summary": {
+            "added": len(added), "removed": len(removed), "changed": len(changed),
+            "signature_changes": len(signatures), "new_calls": len(added_calls), "removed_calls": len(removed_calls),
+        },
+        "added": added,
+        "removed": removed,
+        "changed": changed,
+        "signature_changes": signatures,
+        "new_calls": added_calls,
+        "removed_calls": removed_calls,
+    }
+
+
+def diff_revisions(root: Path, base: str, head: str = "HEAD") -> Dict[str, Any]:
+    """Index-level diff of two revisions of the repository containing `root`."""
+    report = diff_snapshots(load_snapshot(root, base), load_snapshot(root, head))
+    logger.info(f"🔀 [INDEX DIFF]: {base}..{head} {report['summary']}")
+    return {"base": base, "head": head, **report}
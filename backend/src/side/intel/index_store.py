@@ -0,0 +1,141 @@
+"""
+Index Store - Where the sparse Merkle tree of directory indexes is kept.
+
+- `scattered` (legacy): `<dir>/.side/local.json` next to the code it describes
+- `central`: every node under `<root>/.side/tree/<dir key>`, nothing written into the working copy
+
+Both layouts hold the same v3.sparse nodes (checksum over files + child checksums),
+so the scan and its readers are layout-agnostic. Select with SIDE_INDEX_STORE
+(`central` | `scattered`); once a project has a central tree it stays central.
+`migrate_to_central` collects existing per-directory files into the central tree.
+"""
+
+import hashlib
+import json
+import logging
+import os
+import shutil
+from pathlib import Path
+from typing import Any, Dict, Optional, Set
+
+from side.utils.crypto import shield
+
+logger = logging.getLogger(__name__)
+
+LEGACY_NAME = "local.json"
+CENTRAL_DIR = "tree"
+
+
+def _legacy_path(directory: Path) -> Path:
+    return directory / ".side" / LEGACY_NAME
+
+
+class ScatteredIndexStore:
+    """One sealed `local.json` per complex directory, inside the working copy."""
+
+    layout = "scattered"
+
+    def __init__(self, root: Path):
+        self.root = root
+
+    def node_path(self, directory: Path) -> Path:
+        return _legacy_path(directory)
+
+    def read(self, directory: Path) -> Optional[Dict[str, Any]]:
+        path = self.node_path(directory)
+        if not path.exists():
+            return None
+        try:
+            return json.loads(shield.unseal_file(path))
+        except Exception as e:
+            logger.debug(f"Index node unreadable {path}: {e}")
+            return None
+
+    def write(self, directory: Path, data: Dict[str, Any]) -> None:
+        path = self.node_path(directory)
+        path.parent.mkdir(parents=True, exist_ok=True)
+        shield.seal_file(path, json.dumps(data, indent=2))
+
+    def remove(self, directory: Path) -> None:
+        side_dir = directory / ".side"
+        if side_dir.exists() and directory != self.root:
+            shutil.rmtree(side_dir)
+
+    def prune(self, kept: Set[Path]) -> int:
+        """Nodes of vanished directories vanish with them."""
+        return 0
+
+
+class CentralIndexStore(ScatteredIndexStore):
+    """All nodes under `<root>/.side/tree`, keyed by directory path relative to the root."""
+
+    layout = "central"
+
+    def __init__(self, root: Path):
+        super().__init__(root)
+        self.base = root / ".side" / CENTRAL_DIR
+
+    def node_path(self, directory: Path) -> Path:
+        try:
+            rel = directory.relative_to(self.root).as_posix()
+        except ValueError:
+            rel = str(directory)
+        return self.base / hashlib.sha256(rel.encode()).hexdigest()[:16]
+
+    def write(self, directory: Path, data: Dict[str, Any]) -> None:
+        super().write(directory, data)
+        self._drop_legacy(directory)
+
+    def remove(self, directory: Path) -> None:
+        self.node_path(directory).unlink(missing_ok=True)
+        self._drop_legacy(directory)
+
+    def prune(self, kept: Set[Path]) -> int:
+        """Deletes nodes not written during a full scan (deleted or now-ignored directories)."""
+        live = {self.node_path(d).name for d in kept}
+        removed = 0
+        for node in self.base.glob("*"):
+            if node.name not in live:
+                node.unlink(missing_ok=True)
+                removed += 1
+        return removed
+
+    def _drop_legacy(self, directory: Path) -> None:
+        """Leftover scattered files are removed as their directories are re-indexed."""
+        legacy = _legacy_path(directory)
+        if legacy.exists():
+            legacy.unlink()
+            if directory != self.root and not any(legacy.parent.iterdir()):
+                legacy.parent.rmdir()
+
+
+def open_index_store(root: Path) -> ScatteredIndexStore:
+    """The configured layout; a project with a central tree stays central unless told otherwise."""
+    from side.config import config
+
+    layout = (config.index_store or "").lower()
+    if layout == "central" or (not layout and (root / ".side" / CENTRAL_DIR).is_dir()):
+        return CentralIndexStore(root)
+    return ScatteredIndexStore(root)
+
+
+def migrate_to_central(root: Path) -> int:
+    """Moves every `<dir>/.side/local.json` under `root` into the central tree. Returns nodes moved."""
+    from side.services.ignore import ProjectIgnore
+
+    ignore_service = ProjectIgnore(root)
+    store = CentralIndexStore(root)
+    store.base.mkdir(parents=True, exist_ok=True)
+    moved = 0
+    for dirpath, dirnames, _ in os.walk(root, topdown=True):
+        current = Path(dirpath)
+        dirnames[:] = [d for d in dirnames if d != ".side" and not ignore_service.should_ignore(current / d)]
+        if not _legacy_path(current).exists():
+            continue
+        data = ScatteredIndexStore(root).read(current)
+        if data is None:
+            continue
+        store.write(current, data)
+        moved += 1
+    logger.info(f"📦 [INDEX STORE]: Migrated {moved} directory indexes into {store.base}")
+    return moved
@@ -6,12 +6,15 @@ import logging
 from pathlib import Path
 from typing import List, Set
 
+from side.intel.languages import registry
+
 logger = logging.getLogger(__name__)
 
 def detect_primary_languages(project_path: Path) -> Set[str]:
     """
     Detects which languages are used in the project based on file patterns.
     Returns a set of normalized language identifiers (e.g., 'python', 'javascript').
+    Manifests and extensions come from the LanguageSpec registry.
     """
     languages = set()
     EXCLUDE_DIRS = {".git", "node_modules", "venv", ".venv", "dist", "build", "target"}
@@ -24,45 +27,13 @@ def detect_primary_languages(project_path: Path) -> Set[str]:
         dirs[:] = [d for d in dirs if d not in EXCLUDE_DIRS]
         
         for name in files:
-            if name == "requirements.txt" or name == "setup.py" or name == "pyproject.toml":
-                languages.add("python")
-            elif name == "package.json":
-                languages.add("javascript")
-            elif name == "tsconfig.json" or name == "jsconfig.json":
-                languages.add("typescript")
-            elif name == "go.mod":
-                languages.add("go")
-            elif name == "Cargo.toml":
-                languages.add("rust")
-            elif name == "pom.xml" or name == "build.gradle" or name == "settings.gradle":
-                languages.add("java")
-            elif name == "Gemfile" or name == "Rakefile":
-                languages.add("ruby")
-            elif name == "composer.json":
-                languages.add("php")
-            elif name.endswith(".csproj") or name.endswith(".sln"):
-                languages.add("dotnet")
-            elif name == "pubspec.yaml":
-                languages.add("dart")
-            elif name == "angular.json":
-                languages.add("angular")
-                
-            ext = os.path.splitext(name)[1]
-            if ext == ".py": languages.add("python")
-            elif ext == ".js": languages.add("javascript")
-            elif ext == ".ts" or ext == ".tsx": languages.add("typescript")
-            elif ext == ".go": languages.add("go")
-            elif ext == ".rs": languages.add("rust")
-            elif ext == ".swift": languages.add("swift")
-            elif ext == ".kt" or ext == ".kts": languages.add("kotlin")
-            elif ext == ".java": languages.add("java")
-            elif ext == ".rb": languages.add("ruby")
-            elif ext == ".php": languages.add("php")
-            elif ext == ".cs": languages.add("dotnet")
-            elif ext == ".dart": languages.add("dart")
-            elif ext == ".vue": languages.add("vue")
-            elif ext == ".svelte": languages.add("svelte")
-            elif ext == ".astro": languages.add("astro")
+            manifest_spec = registry.for_manifest(name)
+            if manifest_spec:
+                languages.add(manifest_spec.name)
+
+            source_spec = registry.for_path(name)
+            if source_spec:
+                languages.add(source_spec.name)
 
     logger.info(f"🔍 [DETECTOR] Detected languages: {list(languages)}")
     return languages
@@ -0,0 +1,344 @@
+"""
+Language Registry - Single source of truth for polyglot support.
+
+Every language Sidelith understands is described by a `LanguageSpec`:
+extensions, manifest files, tree-sitter grammar and structural query,
+regex fallback, audit adapters and runtime log signatures. The indexer,
+language detector, fingerprint, file watcher and audit tools all read from
+here instead of keeping their own extension tables.
+
+Third-party packages can add languages through the `side.languages`
+entry point group. An entry point may resolve to a `LanguageSpec`, an
+iterable of specs, or a callable returning either:
+
+    [project.entry-points."side.languages"]
+    zig = "side_zig:LANGUAGE"
+"""
+
+import fnmatch
+import importlib
+import logging
+import re
+import threading
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Pattern, Tuple
+
+logger = logging.getLogger(__name__)
+
+ENTRY_POINT_GROUP = "side.languages"
+
+
+@dataclass(frozen=True)
+class LanguageSpec:
+    name: str                                       # Normalized id ('python', 'rust', ...)
+    extensions: Tuple[str, ...] = ()                # Source suffixes, with the dot
+    manifests: Dict[str, Optional[str]] = field(default_factory=dict)  # File name/glob -> package manager
+    grammar: Optional[str] = None                   # tree_sitter_languages id
+    query: str = ""                                 # tree-sitter structural query (class./func./call. tags)
+    regex: Optional[Dict[str, Pattern]] = None      # Fallback {"class": ..., "def": ...}
+    audit_adapters: Tuple[str, ...] = ()            # "module:Class" AuditAdapter references
+    log_label: Optional[str] = None                 # Error family reported by LogMonitor
+    log_patterns: Tuple[str, ...] = ()              # Case-insensitive runtime error signatures
+
+    def matches_manifest(self, file_name: str) -> bool:
+        return any(fnmatch.fnmatchcase(file_name, pattern) for pattern in self.manifests)
+
+
+# --- BUILT-IN STRUCTURAL PATTERNS ---
+# Pre-compiled regex for micro-second parsing
+C_STYLE_REGEX = {
+    "class": re.compile(r'\bclass\s+(\w+)', re.MULTILINE),
+    "def": re.compile(r'\bfunction\s+(\w+)', re.MULTILINE)
+}
+LINE_START_REGEX = {
+    "class": re.compile(r'^class\s+(\w+)', re.MULTILINE),
+    "def": re.compile(r'^def\s+(\w+)', re.MULTILINE)
+}
+
+RUST_REGEX = {
+    "class": re.compile(r'^\s*(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?(?:struct|enum|union|trait)\s+(\w+)', re.MULTILINE),
+    "def": re.compile(r'^\s*(?:pub(?:\([^)]*\))?\s+)?(?:default\s+)?(?:const\s+)?(?:async\s+)?(?:unsafe\s+)?(?:extern\s+"[^"]*"\s+)?fn\s+(\w+)', re.MULTILINE)
+}
+
+PYTHON_QUERY = """
+(class_definition name: (identifier) @class.name)
+(function_definition name: (identifier) @func.name)
+(call function: (identifier) @call.name)
+(call function: (attribute attribute: (identifier) @call.method))
+"""
+
+JS_QUERY = """
+(class_declaration name: (identifier) @class.name)
+(function_declaration name: (identifier) @func.name)
+(call_expression function: (identifier) @call.name)
+(call_expression function: (member_expression property: (property_identifier) @call.method))
+"""
+
+RUST_QUERY = """
+(struct_item name: (type_identifier) @class.struct)
+(enum_item name: (type_identifier) @class.enum)
+(union_item name: (type_identifier) @class.union)
+(trait_item name: (type_identifier) @class.trait)
+(impl_item type: (_) @impl.type)
+(function_item name: (identifier) @func.name)
+(function_signature_item name: (identifier) @func.name)
+(macro_definition name: (identifier) @macro.name)
+(mod_item name: (identifier) @module.name)
+(call_expression function: (identifier) @call.name)
+(call_expression function: (scoped_identifier name: (identifier) @call.name))
+(call_expression function: (field_expression field: (field_identifier) @call.method))
+"""
+
+# Order matters for LogMonitor: the first matching family wins
+# (PHP before JS_NODE, since "PHP Fatal error" also contains "fatal error").
+BUILTIN_LANGUAGES = [
+    LanguageSpec(
+        name="python",
+        extensions=(".py",),
+        manifests={"requirements.txt": "pip", "setup.py": "pip", "pyproject.toml": "pip"},
+        grammar="python",
+        query=PYTHON_QUERY,
+        regex=LINE_START_REGEX,
+        audit_adapters=("side.tools.audit_adapters.bandit:BanditAdapter",),
+        log_label="PYTHON",
+        log_patterns=("Traceback", "Exception", "RuntimeError", "NameError", "TypeError"),
+    ),
+    LanguageSpec(
+        name="php",
+        extensions=(".php",),
+        manifests={"composer.json": "composer"},
+        grammar="php",
+        regex=C_STYLE_REGEX,
+        log_label="PHP",
+        log_patterns=("PHP Fatal", "PHP Parse", "PHP Error"),
+    ),
+    LanguageSpec(
+        name="javascript",
+        extensions=(".js", ".jsx", ".mjs", ".cjs"),
+        manifests={"package.json": "npm"},
+        grammar="javascript",
+        query=JS_QUERY,
+        regex=C_STYLE_REGEX,
+        audit_adapters=("side.tools.audit_adapters.eslint:ESLintAdapter",),
+        log_label="JS_NODE",
+        log_patterns=("ReferenceError", "SyntaxError", "FATAL ERROR", "Uncaught Exception"),
+    ),
+    LanguageSpec(
+        name="typescript",
+        extensions=(".ts", ".tsx"),
+        manifests={"tsconfig.json": None, "jsconfig.json": None},
+        grammar="typescript",
+        query=JS_QUERY,
+        audit_adapters=("side.tools.audit_adapters.eslint:ESLintAdapter",),
+    ),
+    LanguageSpec(
+        name="go",
+        extensions=(".go",),
+        manifests={"go.mod": "go_modules"},
+        grammar="go",
+        regex={
+            "class": re.compile(r'^type\s+(\w+)\s+struct', re.MULTILINE),
+            "def": re.compile(r'^func\s+(?:\([^)]*\)\s+)?(\w+)', re.MULTILINE)
+        },
+        audit_adapters=("side.tools.audit_adapters.gosec:GosecAdapter",),
+        log_label="NATIVE",
+        log_patterns=("panic:",),
+    ),
+    LanguageSpec(
+        name="rust",
+        extensions=(".rs",),
+        manifests={"Cargo.toml": "cargo"},
+        grammar="rust",
+        query=RUST_QUERY,
+        regex=RUST_REGEX,
+        audit_adapters=(
+            "side.tools.audit_adapters.cargo_audit:CargoAuditAdapter",
+            "side.tools.audit_adapters.clippy:ClippyAdapter",
+        ),
+        log_label="NATIVE",
+        log_patterns=("segfault", "Segmentation fault"),
+    ),
+    LanguageSpec(
+        name="java",
+        extensions=(".java",),
+        manifests={"pom.xml": "maven", "build.gradle": "gradle", "settings.gradle": "gradle"},
+        grammar="java",
+        log_label="JAVA",
+        log_patterns=("java.lang.", "stack trace:"),
+    ),
+    LanguageSpec(
+        name="kotlin",
+        extensions=(".kt", ".kts"),
+        manifests={"build.gradle.kts": "gradle", "AndroidManifest.xml": "android"},
+        grammar="kotlin",
+        regex={
+            "class": re.compile(r'\b(?:class|interface|object)\s+(\w+)', re.MULTILINE),
+            "def": re.compile(r'\bfun\s+(\w+)', re.MULTILINE)
+        },
+        audit_adapters=("side.tools.audit_adapters.detekt:DetektAdapter",),
+    ),
+    LanguageSpec(
+        name="swift",
+        extensions=(".swift",),
+        manifests={"Package.swift": "swiftpm", "Podfile": "cocoapods"},
+        grammar="swift",
+        regex={
+            "class": re.compile(r'\b(?:class|struct|enum|protocol)\s+(\w+)', re.MULTILINE),
+            "def": re.compile(r'\bfunc\s+(\w+)', re.MULTILINE)
+        },
+        audit_adapters=("side.tools.audit_adapters.swiftlint:SwiftLintAdapter",),
+    ),
+    LanguageSpec(
+        name="ruby",
+        extensions=(".rb",),
+        manifests={"Gemfile": "bundler", "Rakefile": None},
+        grammar="ruby",
+        regex=LINE_START_REGEX,
+    ),
+    LanguageSpec(
+        name="dotnet",
+        extensions=(".cs",),
+        manifests={"*.csproj": "nuget", "*.sln": "nuget"},
+        grammar="c_sharp",
+        regex={
+            "class": re.compile(r'\b(?:class|struct|interface)\s+(\w+)', re.MULTILINE),
+            "def": re.compile(r'\b(?:public|private|protected|internal|static)?\s+(?:\w+)\s+(\w+)\s*\([^)]*\)\s*{', re.MULTILINE)
+        },
+    ),
+    LanguageSpec(name="dart", extensions=(".dart",), manifests={"pubspec.yaml": "pub"}),
+    LanguageSpec(name="vue", extensions=(".vue",)),
+    LanguageSpec(name="svelte", extensions=(".svelte",)),
+    LanguageSpec(name="astro", extensions=(".astro",)),
+    # Framework marker without sources of its own
+    LanguageSpec(name="angular", manifests={"angular.json": None}),
+]
+
+
+class LanguageRegistry:
+    """Ordered registry of LanguageSpecs with lazy entry-point plugin loading."""
+
+    def __init__(self, specs: Iterable[LanguageSpec] = ()):
+        self._specs: Dict[str, LanguageSpec] = {}
+        self._by_ext: Dict[str, LanguageSpec] = {}
+        self._by_manifest: Dict[str, LanguageSpec] = {}
+        self._manifest_globs: List[Tuple[str, LanguageSpec]] = []
+        self._plugins_loaded = False
+        self._lock = threading.Lock()
+        for spec in specs:
+            self.register(spec)
+
+    def register(self, spec: LanguageSpec, replace: bool = True) -> None:
+        """Adds (or replaces) a language. Later registrations win extension conflicts."""
+        if spec.name in self._specs and not replace:
+            raise ValueError(f"Language '{spec.name}' is already registered")
+        self._specs[spec.name] = spec
+        self._by_ext, self._by_manifest, self._manifest_globs = {}, {}, []
+        for s in self._specs.values():
+            for ext in s.extensions:
+                self._by_ext[ext.lower()] = s
+            for manifest in s.manifests:
+                if any(ch in manifest for ch in "*?["):
+                    self._manifest_globs.append((manifest, s))
+                else:
+                    self._by_manifest.setdefault(manifest, s)
+
+    def load_plugins(self) -> List[str]:
+        """Registers languages exposed through the `side.languages` entry point group."""
+        with self._lock:
+            if self._plugins_loaded:
+                return []
+            self._plugins_loaded = True
+
+        from importlib.metadata import entry_points
+
+        loaded = []
+        for ep in entry_points(group=ENTRY_POINT_GROUP):
+            try:
+                obj = ep.load()
+                if callable(obj) and not isinstance(obj, LanguageSpec):
+                    obj = obj()
+                specs = [obj] if isinstance(obj, LanguageSpec) else list(obj)
+                for spec in specs:
+                    if not isinstance(spec, LanguageSpec):
+                        raise TypeError(f"expected LanguageSpec, got {type(spec).__name__}")
+                    self.register(spec)
+                    loaded.append(spec.name)
+            except Exception as e:
+                logger.warning(f"⚠️ [LANGUAGES]: Plugin '{ep.name}' failed to load: {e}")
+        if loaded:
+            logger.info(f"🧩 [LANGUAGES]: Plugin languages registered: {loaded}")
+        return loaded
+
+    def _ensure_plugins(self) -> None:
+        if not self._plugins_loaded:
+            self.load_plugins()
+
+    def all(self) -> List[LanguageSpec]:
+        self._ensure_plugins()
+        return list(self._specs.values())
+
+    def get(self, name: str) -> Optional[LanguageSpec]:
+        self._ensure_plugins()
+        return self._specs.get(name)
+
+    def for_path(self, path: Path | str) -> Optional[LanguageSpec]:
+        """Language of a source file, by suffix."""
+        self._ensure_plugins()
+        return self._by_ext.get(Path(path).suffix.lower())
+
+    def for_manifest(self, file_name: str) -> Optional[LanguageSpec]:
+        """Language declared by a manifest file name (e.g. Cargo.toml -> rust)."""
+        self._ensure_plugins()
+        if file_name in self._by_manifest:
+            return self._by_manifest[file_name]
+        return next((s for pattern, s in self._manifest_globs if fnmatch.fnmatchcase(file_name, pattern)), None)
+
+    def source_extensions(self) -> frozenset:
+        """Every registered source suffix."""
+        self._ensure_plugins()
+        return frozenset(self._by_ext)
+
+    def log_error_patterns(self) -> Dict[str, List[str]]:
+        """LogMonitor families in registration order, patterns merged per label."""
+        patterns: Dict[str, List[str]] = {}
+        for spec in self.all():
+            if spec.log_patterns:
+                patterns.setdefault(spec.log_label or spec.name.upper(), []).extend(spec.log_patterns)
+        return patterns
+
+    def audit_adapter_classes(self, languages: Iterable[str]) -> List[Any]:
+        """Resolves the (deduplicated) AuditAdapter classes for the given languages."""
+        classes, seen = [], set()
+        for name in languages:
+            spec = self.get(name)
+            for ref in (spec.audit_adapters if spec else ()):
+                if ref in seen:
+                    continue
+                seen.add(ref)
+                module_name, _, attr = ref.partition(":")
+                try:
+                    classes.append(getattr(importlib.import_module(module_name), attr))
+                except (ImportError, AttributeError) as e:
+                    logger.warning(f"⚠️ [LANGUAGES]: Audit adapter '{ref}' unavailable: {e}")
+        return classes
+
+
+registry = LanguageRegistry(BUILTIN_LANGUAGES)
+
+
+def register_language(spec: LanguageSpec, replace: bool = True) -> None:
+    """Programmatic registration (tests, embedded plugins)."""
+    registry.register(spec, replace=replace)
+
+
+def get_language_spec(name: str) -> Optional[LanguageSpec]:
+    return registry.get(name)
+
+
+def language_for_path(path: Path | str) -> Optional[LanguageSpec]:
+    return registry.for_path(path)
+
+
+def source_extensions() -> frozenset:
+    return registry.source_extensions()
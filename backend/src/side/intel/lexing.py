@@ -0,0 +1,174 @@
+"""
+Lexing Helpers - Comment/literal stripping and bracket matching shared by the graph passes.
+
+The call, type, module, test and route passes scan source text with regexes;
+these keep them on real code (offsets and line structure preserved) and
+answer the small structural questions they share: matching brackets, offset
+to line, and the trait/type halves of a Rust `impl` header.
+"""
+
+import bisect
+import re
+from pathlib import Path
+from typing import Dict
+
+
+# --- Literal & comment stripping ---
+
+def strip_rust_noise(content: str) -> str:
+    """
+    Blanks out comments and string/char literals (keeping line structure),
+    so lexical patterns only match real code.
+    """
+    out = []
+    i, n = 0, len(content)
+    while i < n:
+        ch = content[i]
+        nxt = content[i + 1] if i + 1 < n else ""
+        if ch == "/" and nxt == "/":
+            end = content.find("\n", i)
+            end = n if end == -1 else end
+            out.append(" " * (end - i))
+            i = end
+        elif ch == "/" and nxt == "*":
+            depth, j = 1, i + 2
+            while j < n and depth:
+                if content.startswith("/*", j):
+                    depth, j = depth + 1, j + 2
+                elif content.startswith("*/", j):
+                    depth, j = depth - 1, j + 2
+                else:
+                    j += 1
+            out.append(re.sub(r"[^\n]", " ", content[i:j]))
+            i = j
+        elif ch in "br" and (m := re.match(r'b?r(#*)"', content[i:i + 260])) and (i == 0 or not (content[i - 1].isalnum() or content[i - 1] == "_")):
+            closing = '"' + m.group(1)
+            end = content.find(closing, i + m.end())
+            end = n if end == -1 else end + len(closing)
+            out.append('""' + re.sub(r"[^\n]", " ", content[i + 2:end]))
+            i = end
+        elif ch == '"':
+            j = i + 1
+            while j < n and content[j] != '"':
+                j += 2 if content[j] == "\\" else 1
+            out.append('"' + re.sub(r"[^\n]", " ", content[i + 1:j]) + '"')
+            i = j + 1
+        elif ch == "'" and (m := re.match(r"'(?:\\.[^']*|[^\\'])'", content[i:i + 12])):
+            out.append("' '" + " " * (m.end() - 3))
+            i += m.end()
+        else:
+            out.append(ch)
+            i += 1
+    return "".join(out)
+
+
+def strip_ts_noise(content: str) -> str:
+    """Blanks out comments and string/template literals, keeping offsets and line structure."""
+    out = []
+    i, n = 0, len(content)
+    while i < n:
+        ch = content[i]
+        nxt = content[i + 1] if i + 1 < n else ""
+        if ch == "/" and nxt == "/":
+            end = content.find("\n", i)
+            end = n if end == -1 else end
+            out.append(" " * (end - i))
+            i = end
+        elif ch == "/" and nxt == "*":
+            end = content.find("*/", i + 2)
+            end = n if end == -1 else end + 2
+            out.append(re.sub(r"[^\n]", " ", content[i:end]))
+            i = end
+        elif ch in "'\"`":
+            j = i + 1
+            while j < n and content[j] != ch and (ch == "`" or content[j] != "\n"):
+                j += 2 if content[j] == "\\" else 1
+            j = min(j, n - 1)
+            out.append(ch + re.sub(r"[^\n]", " ", content[i + 1:j]) + content[j])
+            i = j + 1
+        else:
+            out.append(ch)
+            i += 1
+    return "".join(out)
+
+
+# --- Structure ---
+
+def line_index(text: str):
+    """Maps a character offset to its 1-based line number."""
+    starts = [0] + [i + 1 for i, ch in enumerate(text) if ch == "\n"]
+    return lambda pos: bisect.bisect_right(starts, pos)
+
+
+def match_braces(code: str) -> Dict[int, int]:
+    """Maps every `{` offset to its matching `}` (unbalanced ones run to the end)."""
+    pairs, stack = {}, []
+    for i, ch in enumerate(code):
+        if ch == "{":
+            stack.append(i)
+        elif ch == "}" and stack:
+            pairs[stack.pop()] = i
+    for open_pos in stack:
+        pairs[open_pos] = len(code)
+    return pairs
+
+
+def closing_paren(code: str, open_at: int) -> int:
+    """Offset of the `)` matching the `(` at `open_at` (end of code when unbalanced)."""
+    depth = 0
+    for i in range(open_at, len(code)):
+        if code[i] == "(":
+            depth += 1
+        elif code[i] == ")":
+            depth -= 1
+            if depth == 0:
+                return i
+    return len(code)
+
+
+# --- Rust names ---
+
+def rust_type_name(type_text: str) -> str:
+    """Reduces a Rust type like `&'a mut crate::Order<T>` to its base name (`Order`)."""
+    depth = 0
+    base = []
+    for ch in type_text:
+        if ch == "<":
+            depth += 1
+        elif ch == ">":
+            depth -= 1
+        elif depth == 0:
+            base.append(ch)
+    text = re.sub(r"^(?:&\s*(?:'\w+\s+)?(?:mut\s+)?|dyn\s+|\*(?:const|mut)\s+)+", "", "".join(base).strip())
+    return text.split("::")[-1].strip()
+
+
+def split_rust_impl(header: str):
+    """Splits an impl header (text after `impl`) into (trait, target type)."""
+    header = header.strip()
+    if header.startswith("<"):
+        depth = 0
+        for i, ch in enumerate(header):
+            depth += 1 if ch == "<" else -1 if ch == ">" else 0
+            if depth == 0:
+                header = header[i + 1:].strip()
+                break
+    header = re.split(r"\bwhere\b", header, maxsplit=1)[0]
+    if re.search(r"\sfor\s", f" {header} "):
+        trait, target = re.split(r"\s+for\s+", header, maxsplit=1)
+        return rust_type_name(trait), rust_type_name(target)
+    return None, rust_type_name(header)
+
+
+def rust_module_path(src_root: Path, path: Path) -> str:
+    """Maps a source file to its module path (`src/a/b.rs` -> `a::b`, `src/lib.rs` -> `crate`)."""
+    try:
+        rel = path.relative_to(src_root)
+    except ValueError:
+        return path.stem
+    parts = list(rel.with_suffix("").parts)
+    if parts and parts[-1] == "mod":
+        parts.pop()
+    if parts in (["lib"], ["main"]):
+        return "crate"
+    return "::".join(parts) or "crate"
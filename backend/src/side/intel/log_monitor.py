@@ -19,6 +19,8 @@ from side.storage.modules.audit import AuditService
 from side.storage.modules.base import ContextEngine
 from side.intel.scavengers.mobile import AndroidScavenger
 from side.intel.scavengers.docker import DockerScavenger
+from side.intel.rust_panic import parse_rust_panics, parse_test_summary, link_panic_to_entity
+from side.intel.languages import registry as language_registry
 
 logger = logging.getLogger(__name__)
 
@@ -213,21 +215,18 @@ class LogMonitor:
     def _analyze_chunk(self, content: str, source: Path):
         """Analyzes text chunk for friction signals with High-Fidelity Causal Framing."""
         lines = content.splitlines()
+        consumed = self._analyze_rust(content, lines, source)
+        # Runtime error families come from the LanguageSpec registry
+        error_patterns = language_registry.log_error_patterns()
         for i, line in enumerate(lines):
             self.generic_buffer.append(line)
+            if i in consumed:
+                continue
             
             # Heuristics: Detecting deep runtime signatures
             is_error = False
             err_type = "GENERIC"
             
-            error_patterns = {
-                "PYTHON": ["Traceback", "Exception", "RuntimeError", "NameError", "TypeError"],
-                "PHP": ["PHP Fatal", "PHP Parse", "PHP Error"],
-                "JS_NODE": ["ReferenceError", "SyntaxError", "FATAL ERROR", "Uncaught Exception"],
-                "NATIVE": ["panic:", "segfault", "Segmentation fault"],
-                "JAVA": ["java.lang.", "stack trace:"]
-            }
-
             line_lower = line.lower()
             for lang, patterns in error_patterns.items():
                 if any(p.lower() in line_lower for p in patterns):
@@ -252,6 +251,57 @@ class LogMonitor:
                     }
                 )
 
+    def _analyze_rust(self, content: str, lines: list, source: Path) -> set:
+        """
+        Dedicated pass for Rust panics, backtraces and `cargo test` failures.
+        Returns the line indices it consumed so the generic heuristics skip them.
+        """
+        consumed = set()
+        if "panicked at" not in content and "test result: FAILED" not in content:
+            return consumed
+
+        project_id = ContextEngine.get_project_id(self.project_path)
+        for panic in parse_rust_panics(content):
+            consumed.update(range(panic.start, panic.end))
+            entity = None
+            try:
+                entity = link_panic_to_entity(panic, self.audits.engine.schema, project_id, self.project_path)
+            except Exception as e:
+                logger.debug(f"Panic entity linking skipped: {e}")
+
+            self._log_friction(
+                source.name.upper(),
+                "RUST_PANIC",
+                {
+                    "event": "RUST_PANIC",
+                    "snippet": lines[panic.start],
+                    "type": "RUST",
+                    "file": str(source),
+                    **panic.to_dict(),
+                    "entity": entity,
+                    "causal_frame": "\n".join(lines[panic.start:panic.end])
+                }
+            )
+
+        summary = parse_test_summary(content)
+        if summary:
+            consumed.update(range(summary.start, summary.end))
+            self._log_friction(
+                source.name.upper(),
+                "RUST_TEST_FAILURE",
+                {
+                    "event": "RUST_TEST_FAILURE",
+                    "snippet": lines[summary.end - 1],
+                    "type": "RUST",
+                    "file": str(source),
+                    "passed": summary.passed,
+                    "failed": summary.failed,
+                    "ignored": summary.ignored,
+                    "failures": summary.failures,
+                }
+            )
+        return consumed
+
     def _log_friction(self, source: str, event_type: str, payload: dict):
         """Persists friction to Ledger."""
         # [ECONOMY]: Charge 1 SU? 
@@ -0,0 +1,744 @@
+"""
+Route Map - HTTP routes and the functions that serve them.
+
+Routes are read from each framework's own declarations: FastAPI/Starlette/Flask
+decorators (`@router.get`, `@app.route`, `@mcp.custom_route`) and `Route(...)`
+tables, Next.js app-router files (`app/**/route.ts` method exports, `page.tsx`),
+Express `app.get(...)`/`router.route(...)` calls, and axum/actix `.route(...)`,
+`web::resource(...)` and `#[get("/...")]` handlers. Each route is stored as a
+`route` entity (`GET /users/{id}`) with a `handles` edge to its handler and
+`guarded_by` edges to the authentication in front of it: FastAPI dependencies,
+Express/Next.js middleware, axum layers and actix `wrap`s, auth extractor types
+and auth checks the handler itself calls. Generic words (`token`, `role`, `login`)
+only mark a guard in those guard positions, never in a handler's own calls or types.
+"""
+
+import ast
+import logging
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Set, Tuple
+
+from side.intel.call_graph import (
+    CallGraph, Definition, FileSymbols, build_call_graph, crate_path, qualified_name, relative_paths, update_call_graph,
+)
+from side.intel.ids import entity_id, relationship_id
+from side.intel.lexing import closing_paren, line_index, strip_rust_noise, strip_ts_noise
+
+logger = logging.getLogger(__name__)
+
+HTTP_METHODS = ("get", "post", "put", "patch", "delete", "head", "options", "trace")
+ROUTE_RELATIONS = ("handles", "guarded_by")
+
+# Words marking authentication/authorization in identifiers (`get_current_user`, `requireAuth`, `JwtLayer`)
+AUTH_WORDS = ("auth", "jwt", "guard", "permission", "protect", "bearer", "oauth", "credential", "claims",
+              "signin", "apikey", "clerk")
+# Whole words that only name auth in a guard position (`Depends(verify_token)`, `requireRole`, `@login_required`);
+# elsewhere they are `tokenize`, `CreateTokenRequest`, `LoginForm`, `RoleList`
+GUARD_WORDS = {"token", "tokens", "role", "roles", "login"}
+AUTH_PAIRS = {("current", "user"), ("api", "key"), ("sign", "in"), ("logged", "in"), ("server", "session"),
+              ("is", "admin")}
+# A handler's own calls only count as auth when they check something (`verify_jwt`, not `create_jwt`)
+CHECK_VERBS = {"auth", "authenticate", "authorize", "verify", "check", "require", "ensure", "validate", "assert",
+               "get", "current", "is", "has"}
+IDENT_WORD_RE = re.compile(r"[A-Z]+(?![a-z])|[A-Z]?[a-z]+|\d+")
+IDENT_RE = re.compile(r"[A-Za-z_$][\w$]*")
+
+PY_ROUTE_DECORATORS = set(HTTP_METHODS) | {"route", "api_route", "custom_route", "websocket", "websocket_route"}
+PY_APP_FACTORIES = {"FastAPI": "fastapi", "APIRouter": "fastapi", "Starlette": "starlette", "FastMCP": "starlette",
+                    "Flask": "flask", "Blueprint": "flask"}
+
+NEXT_APP_RE = re.compile(r"(?:^|/)app/((?:[^/]+/)*)(route|page)\.[cm]?[jt]sx?$")
+NEXT_METHODS = "GET|POST|PUT|PATCH|DELETE|HEAD|OPTIONS"
+NEXT_EXPORT_RE = re.compile(rf"\bexport\s+(?:(?:async\s+)?function\s+|(?:const|let|var)\s+)({NEXT_METHODS})\b")
+NEXT_EXPORT_LIST_RE = re.compile(r"\bexport\s*\{([^}]*)\}")
+NEXT_MIDDLEWARE_FILES = ("middleware.ts", "middleware.js", "proxy.ts", "proxy.js")
+NEXT_MATCHER_RE = re.compile(r"\bmatcher\s*:\s*(\[[^\]]*\]|(['\"])[^'\"]*\2)")
+
+EXPRESS_IMPORT_RE = re.compile(r"""(?:\bfrom\s+|\brequire\(\s*)['"]express['"]""")
+EXPRESS_OBJECT_RE = re.compile(r"\b(?:const|let|var)\s+([\w$]+)\s*=\s*(?:express\s*\(|(?:express\s*\.\s*)?Router\s*\()")
+EXPRESS_CALL_RE = re.compile(r"\b([\w$]+)\s*\.\s*(get|post|put|patch|delete|options|head|all|use|route)\s*\(")
+EXPRESS_CHAIN_RE = re.compile(r"\s*\.\s*(get|post|put|patch|delete|options|head|all)\s*\(")
+JS_INLINE_RE = re.compile(r"^(?:async\b|function\b|\(|[\w$]+\s*=>)")
+
+RUST_ROUTE_ATTR_RE = re.compile(
+    r'#\[\s*(?:actix_web::)?(get|post|put|patch|delete|head|options|trace|connect|route)\s*\(\s*"([^"]*)"([^\]]*)\]'
+)
+RUST_ROUTE_CALL_RE = re.compile(r"\.\s*route\s*\(")
+RUST_RESOURCE_RE = re.compile(r"\bweb::resource\s*\(")
+RUST_SCOPE_RE = re.compile(r"\bweb::scope\s*\(")
+RUST_NEST_RE = re.compile(r"\.\s*nest\s*\(")
+RUST_CHAIN_RE = re.compile(r"\s*\.\s*(\w+)\s*\(")
+RUST_LAYER_RE = re.compile(r"\.\s*(layer|route_layer|wrap|wrap_fn)\s*\(")
+AXUM_METHOD_RE = re.compile(r"\b(get|post|put|patch|delete|head|options|trace|any)(?:_service)?\s*\(\s*([\w:]+)?")
+ACTIX_METHOD_RE = re.compile(r"\bweb::(get|post|put|patch|delete|head|options|trace)\s*\(\s*\)")
+ACTIX_TO_RE = re.compile(r"\.\s*to\s*\(\s*([\w:]+)")
+
+
+@dataclass
+class Route:
+    method: str                     # 'GET', 'POST', ...; 'ANY' for every method, 'WS' for websockets
+    path: str
+    file: str
+    line: int
+    framework: str                  # 'fastapi', 'starlette', 'flask', 'nextjs', 'express', 'axum', 'actix'
+    handler: Optional[str] = None   # As written ('get_user', 'handlers::list'); None for inline closures
+    auth: List[str] = field(default_factory=list)
+    declaration: str = ""
+    definition: Optional[Definition] = None     # Handler, once resolved to a project function
+    handler_file: Optional[str] = None
+
+
+def is_auth(name: str, guard: bool = False) -> bool:
+    """
+    True for identifiers naming authentication or authorization (`requireAuth`, `get_current_user`).
+    `guard` names come from a guard position (dependency, middleware, layer) and may use GUARD_WORDS.
+    """
+    for segment in re.split(r"[.:]+", name):
+        words = [w.lower() for w in IDENT_WORD_RE.findall(segment)]
+        for word in words:
+            if word.startswith("author") and not word.startswith(("authoriz", "authoris")):
+                continue    # `author`, `authors`
+            if word.startswith(AUTH_WORDS) or (guard and word in GUARD_WORDS):
+                return True
+        if "require" in words[:-1] or any(pair in AUTH_PAIRS for pair in zip(words, words[1:])):
+            return True
+    return False
+
+
+def _is_check(name: str) -> bool:
+    words = [w.lower() for w in IDENT_WORD_RE.findall(re.split(r"[.:]+", name)[-1])]
+    return is_auth(name) and bool(words) and (len(words) == 1 or words[0] in CHECK_VERBS)
+
+
+def _auth(names: Iterable[Optional[str]], guard: bool = True) -> List[str]:
+    return list(dict.fromkeys(n for n in names if n and is_auth(n, guard)))
+
+
+def _join(prefix: str, path: str) -> str:
+    if not prefix:
+        return path or "/"
+    return prefix.rstrip("/") + (path if path.startswith("/") else f"/{path}" if path else "")
+
+
+def _split_args(code: str, start: int, end: int) -> List[Tuple[int, int]]:
+    """Top-level comma-separated argument spans of `code[start:end]`."""
+    spans, depth, begin = [], 0, start
+    for i in range(start, end):
+        ch = code[i]
+        if ch in "([{":
+            depth += 1
+        elif ch in ")]}":
+            depth -= 1
+        elif ch == "," and depth == 0:
+            spans.append((begin, i))
+            begin = i + 1
+    spans.append((begin, end))
+    return [(s, e) for s, e in spans if code[s:e].strip()]
+
+
+def _string_arg(content: str, code: str, span: Tuple[int, int], quotes: str = "'\"`") -> Optional[str]:
+    """Text of an argument that is exactly one string literal (read from `content`; `code` has it blanked)."""
+    s, e = span
+    text = code[s:e]
+    pos = s + len(text) - len(text.lstrip())
+    if pos >= e or code[pos] not in quotes:
+        return None
+    close = code.find(code[pos], pos + 1)
+    if close == -1 or close >= e or code[close + 1:e].strip():
+        return None
+    return content[pos + 1:close]
+
+
+def _line_text(content: str, line: int) -> str:
+    lines = content.splitlines()
+    return lines[line - 1].strip()[:200] if 0 < line <= len(lines) else ""
+
+
+# --- Python: FastAPI, Starlette, Flask ---
+
+def _py_name(node: Optional[ast.AST]) -> Optional[str]:
+    if isinstance(node, ast.Name):
+        return node.id
+    if isinstance(node, ast.Attribute):
+        base = _py_name(node.value)
+        return f"{base}.{node.attr}" if base else node.attr
+    if isinstance(node, ast.Call):
+        return _py_name(node.func)
+    return None
+
+
+def _py_str(node: Optional[ast.AST]) -> Optional[str]:
+    return node.value if isinstance(node, ast.Constant) and isinstance(node.value, str) else None
+
+
+def _py_kw(call: ast.Call, *names: str) -> Optional[ast.AST]:
+    return next((k.value for k in call.keywords if k.arg in names), None)
+
+
+def _py_methods(call: ast.Call) -> List[str]:
+    node = _py_kw(call, "methods")
+    if isinstance(node, (ast.List, ast.Tuple, ast.Set)):
+        methods = [s.upper() for s in map(_py_str, node.elts) if s]
+        if methods:
+            return methods
+    return ["GET"]
+
+
+def _py_dependencies(node: Optional[ast.AST]) -> List[str]:
+    """Callables behind `Depends(...)`/`Security(...)` anywhere under `node`."""
+    found = []
+    for call in ast.walk(node) if node is not None else ():
+        if isinstance(call, ast.Call) and (_py_name(call.func) or "").split(".")[-1] in ("Depends", "Security"):
+            target = call.args[0] if call.args else _py_kw(call, "dependency")
+            if name := _py_name(target):
+                found.append(name)
+    return found
+
+
+def _python_routes(file: FileSymbols, content: str) -> List[Route]:
+    try:
+        tree = ast.parse(content)
+    except (SyntaxError, ValueError):
+        return []
+
+    apps: Dict[str, Tuple[str, str, List[str]]] = {}    # Variable -> (framework, prefix, dependencies)
+    mounts: Dict[str, Tuple[str, List[str]]] = {}       # Router -> (`include_router` prefix, dependencies)
+    for node in ast.walk(tree):
+        if isinstance(node, ast.Assign) and isinstance(node.value, ast.Call):
+            factory = (_py_name(node.value.func) or "").split(".")[-1]
+            if factory in PY_APP_FACTORIES:
+                scope = (PY_APP_FACTORIES[factory], _py_str(_py_kw(node.value, "prefix", "url_prefix")) or "",
+                         _py_dependencies(_py_kw(node.value, "dependencies")))
+                apps.update((t.id, scope) for t in node.targets if isinstance(t, ast.Name))
+        elif (isinstance(node, ast.Call) and isinstance(node.func, ast.Attribute) and node.args
+              and node.func.attr in ("include_router", "register_blueprint")):
+            if child := _py_name(node.args[0]):
+                mounts[child] = (_py_str(_py_kw(node, "prefix", "url_prefix")) or "",
+                                 _py_dependencies(_py_kw(node, "dependencies")))
+
+    definitions = {d.line: d for d in file.definitions}
+    routes = []
+
+    def add(methods: List[str], path: str, obj: str, framework: str, line: int, handler: Optional[str],
+            guards: List[str], definition: Optional[Definition] = None):
+        _, prefix, app_guards = apps.get(obj, ("", "", []))
+        mount_prefix, mount_guards = mounts.get(obj, ("", []))
+        for method in methods:
+            routes.append(Route(method, _join(mount_prefix + prefix, path), file.path, line, framework, handler,
+                                _auth(mount_guards + app_guards + guards), _line_text(content, line),
+                                definition, file.path if definition else None))
+
+    for node in ast.walk(tree):
+        if isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef)):
+            route_decorators = [d for d in node.decorator_list if isinstance(d, ast.Call)
+                                and isinstance(d.func, ast.Attribute) and d.func.attr in PY_ROUTE_DECORATORS]
+            # `@login_required`-style decorators and `Depends(...)` parameters guard every route of the function
+            guards = [_py_name(d) for d in node.decorator_list if d not in route_decorators]
+            guards += _py_dependencies(node.args)
+            for dec in route_decorators:
+                path = _py_str(dec.args[0]) if dec.args else _py_str(_py_kw(dec, "path", "rule"))
+                if path is None:
+                    continue
+                obj, verb = _py_name(dec.func.value) or "", dec.func.attr
+                if verb in HTTP_METHODS:
+                    methods = [verb.upper()]
+                elif verb.startswith("websocket"):
+                    methods = ["WS"]
+                else:
+                    methods = _py_methods(dec)
+                framework = apps[obj][0] if obj in apps else (
+                    "starlette" if verb == "custom_route" else "flask" if verb == "route" else "fastapi")
+                add(methods, path, obj, framework, dec.lineno, node.name,
+                    _py_dependencies(_py_kw(dec, "dependencies")) + guards, definitions.get(node.lineno))
+        elif isinstance(node, ast.Call) and node.args and (path := _py_str(node.args[0])) is not None:
+            kind = (_py_name(node.func) or "").split(".")[-1]
+            endpoint = node.args[1] if len(node.args) > 1 else _py_kw(node, "endpoint")
+            if kind in ("Route", "WebSocketRoute"):
+                add(["WS"] if kind == "WebSocketRoute" else _py_methods(node), path, "", "starlette",
+                    node.lineno, _py_name(endpoint), [])
+            elif kind == "add_api_route" and isinstance(node.func, ast.Attribute):
+                obj = _py_name(node.func.value) or ""
+                add(_py_methods(node), path, obj, apps.get(obj, ("fastapi",))[0], node.lineno, _py_name(endpoint),
+                    _py_dependencies(_py_kw(node, "dependencies")))
+    return routes
+
+
+# --- Next.js app router ---
+
+def _matcher_regex(pattern: str) -> str:
+    """path-to-regexp matcher (`/dashboard/:path*`) as a Python regex."""
+    rx = re.sub(r"/:\w+\*", "(?:/.*)?", pattern)
+    rx = re.sub(r"/:\w+\+", "/.+", rx)
+    rx = re.sub(r"/:\w+\?", "(?:/[^/]+)?", rx)
+    return "^" + re.sub(r":\w+", "[^/]+", rx) + "$"
+
+
+def _next_middleware(root: Path, base: str, url: str) -> List[str]:
+    """The auth check in `middleware.ts` (or `proxy.ts`) when its matcher covers `url`."""
+    path = next((root / base / name for name in NEXT_MIDDLEWARE_FILES if (root / base / name).is_file()), None)
+    if path is None:
+        return []
+    content = path.read_text(errors="ignore")
+    code = strip_ts_noise(content)
+    callees = [m.group(1) for m in re.finditer(r"([A-Za-z_$][\w$]*)\s*\(", code)]
+    # Name the check by its wrapper/call (`withAuth`, `getToken`); a cookie test alone names the file
+    checks = _auth(callees)[:1] or ([path.stem] if _auth(IDENT_RE.findall(code)) else [])
+    if not checks:
+        return []
+    matcher = NEXT_MATCHER_RE.search(content)
+    patterns = re.findall(r"['\"](/[^'\"]*)['\"]", matcher.group(1)) if matcher else []
+    sample = re.sub(r"\[+\.*(\w+)\]+", r"\1", url)      # `/users/[id]` -> `/users/id`
+    for pattern in patterns or ["/.*"]:
+        try:
+            if re.match(_matcher_regex(pattern), sample):
+                return checks
+        except re.error:
+            return checks
+    return []
+
+
+def _next_routes(root: Path, file: FileSymbols, content: str) -> List[Route]:
+    m = NEXT_APP_RE.search(file.path)
+    if not m:
+        return []
+    segments = [s for s in m.group(1).split("/") if s]
+    if any(s.startswith("_") for s in segments):
+        return []   # Private folders are not routable
+    # Route groups `(admin)` and parallel-route slots `@modal` don't appear in the URL
+    url = "/" + "/".join(s for s in segments if not (s.startswith("(") and s.endswith(")")) and not s.startswith("@"))
+    base = file.path[:m.start()]
+    auth = _next_middleware(root, base, url)
+    code = strip_ts_noise(content)
+    line_of = line_index(code)
+
+    if m.group(2) == "page":
+        default = re.search(r"\bexport\s+default\b", code)
+        line = line_of(default.start()) if default else 1
+        return [Route("GET", url, file.path, line, "nextjs", file.default_export, list(auth), _line_text(content, line))]
+
+    exports = [(e.group(1), e.group(1), e.start()) for e in NEXT_EXPORT_RE.finditer(code)]
+    for listed in NEXT_EXPORT_LIST_RE.finditer(code):
+        for item in listed.group(1).split(","):
+            parts = item.split()        # `handler as GET` (NextAuth)
+            if len(parts) == 3 and parts[1] == "as" and re.fullmatch(NEXT_METHODS, parts[2]):
+                exports.append((parts[2], parts[0], listed.start()))
+    routes = []
+    for method, handler, pos in sorted(exports, key=lambda e: e[2]):
+        line = line_of(pos)
+        routes.append(Route(method, url, file.path, line, "nextjs", handler, list(auth), _line_text(content, line)))
+    return routes
+
+
+# --- Express ---
+
+def _js_names(text: str) -> List[str]:
+    """Middleware named by an argument: `auth`, `requireRole('admin')`, `[a, b]`; inline functions name none."""
+    text = text.strip()
+    if text.startswith("["):
+        inner = text[1:text.rfind("]")] if "]" in text else text[1:]
+        return [n for s, e in _split_args(inner, 0, len(inner)) for n in _js_names(inner[s:e])]
+    if JS_INLINE_RE.match(text):
+        return []
+    m = re.match(r"[\w$.]+", text)
+    return [m.group(0)] if m else []
+
+
+def _js_handler(text: str) -> Optional[str]:
+    """Handler named by the last argument; wrappers like `asyncHandler(listUsers)` name the inner function."""
+    text = text.strip()
+    wrapped = re.fullmatch(r"[\w$.]+\s*\(\s*([\w$.]+)\s*\)", text)
+    if wrapped:
+        return wrapped.group(1)
+    names = _js_names(text)
+    return names[0] if names else None
+
+
+def _express_routes(file: FileSymbols, content: str) -> List[Route]:
+    if not EXPRESS_IMPORT_RE.search(content):
+        return []
+    code = strip_ts_noise(content)
+    line_of = line_index(code)
+    objects = {m.group(1) for m in EXPRESS_OBJECT_RE.finditer(code)} | {"app", "router"}
+    mounts: Dict[str, Tuple[str, str, int]] = {}        # Router -> (prefix, parent, offset) from `app.use('/api', router)`
+    guards: Dict[str, List[Tuple[int, str, str]]] = {}  # Object -> (offset, path scope, middleware) from `use()`
+    declared = []                                       # (offset, object, verb, path, argument texts)
+
+    for m in EXPRESS_CALL_RE.finditer(code):
+        obj, verb = m.groups()
+        if obj not in objects:
+            continue
+        open_at = m.end() - 1
+        close = closing_paren(code, open_at)
+        args = _split_args(code, open_at + 1, close)
+        texts = [code[s:e] for s, e in args]
+        path = _string_arg(content, code, args[0]) if args else None
+        if verb == "use":
+            rest = texts[1:] if path is not None else texts
+            for text in rest:
+                name = text.strip()
+                if name in objects:
+                    mounts[name] = (path or "", obj, m.start())
+                else:
+                    guards.setdefault(obj, []).extend((m.start(), path or "", n) for n in _js_names(text))
+        elif verb == "route":
+            pos = close + 1
+            while path is not None and (chained := EXPRESS_CHAIN_RE.match(code, pos)):
+                chain_open = chained.end() - 1
+                chain_close = closing_paren(code, chain_open)
+                declared.append((m.start(), obj, chained.group(1), path,
+                                 [code[s:e] for s, e in _split_args(code, chain_open + 1, chain_close)]))
+                pos = chain_close + 1
+        elif path is not None and len(args) >= 2:     # `app.get('env')` reads a setting
+            declared.append((m.start(), obj, verb, path, texts[1:]))
+
+    def applied(obj: str, before: int, path: str) -> List[str]:
+        return [n for offset, scope, n in guards.get(obj, []) if offset < before and path.startswith(scope)]
+
+    routes = []
+    for offset, obj, verb, path, args in declared:
+        middleware = [n for text in args[:-1] for n in _js_names(text)] + applied(obj, offset, path)
+        prefix = ""
+        if obj in mounts:
+            prefix, parent, mounted_at = mounts[obj]
+            middleware += applied(parent, mounted_at, _join(prefix, path))
+        line = line_of(offset)
+        routes.append(Route("ANY" if verb == "all" else verb.upper(), _join(prefix, path), file.path, line,
+                            "express", _js_handler(args[-1]), _auth(middleware), _line_text(content, line)))
+    return routes
+
+
+# --- Rust: axum, actix-web ---
+
+def _depths(code: str) -> List[int]:
+    """Bracket depth at every offset (an opening bracket sits at its outer depth)."""
+    depth, out = 0, []
+    for ch in code:
+        if ch in ")]}":
+            depth -= 1
+        out.append(depth)
+        if ch in "([{":
+            depth += 1
+    return out
+
+
+def _chain(code: str, depths: List[int], pos: int) -> Tuple[int, int]:
+    """Bounds of the method chain (`Router::new().route(..).layer(..)`) containing `pos`."""
+    level = depths[pos]
+    start = pos
+    while start > 0 and depths[start - 1] >= level and not (depths[start - 1] == level and code[start - 1] in ",;"):
+        start -= 1
+    end = pos
+    while end < len(code) and depths[end] >= level and not (depths[end] == level and code[end] in ",;"):
+        end += 1
+    return start, end
+
+
+def _rust_nested(content: str, code: str) -> Dict[str, Tuple[str, int]]:
+    """Routers bound to a variable and nested elsewhere: `.nest("/api", api)` -> {'api': ('/api', offset)}."""
+    nested = {}
+    for m in RUST_NEST_RE.finditer(code):
+        args = _split_args(code, m.end(), closing_paren(code, m.end() - 1))
+        if len(args) == 2 and re.fullmatch(r"\s*\w+\s*", code[args[1][0]:args[1][1]]):
+            nested[code[args[1][0]:args[1][1]].strip()] = (_string_arg(content, code, args[0], '"') or "", m.start())
+    return nested
+
+
+def _rust_context(content: str, code: str, depths: List[int], pos: int,
+                  nested: Dict[str, Tuple[str, int]]) -> Tuple[str, List[str]]:
+    """
+    Path prefix (`nest`, `web::scope`) and auth layers in effect at `pos`. axum layers wrap the
+    routes added before them; actix `wrap`s cover the whole chain. Enclosing chains are walked
+    outwards, and a router bound with `let` continues where it is nested.
+    """
+    prefixes: List[str] = []
+    auth: List[str] = []
+    seen = set()
+    while pos not in seen:
+        seen.add(pos)
+        level = depths[pos]
+        start, end = _chain(code, depths, pos)
+        for layer in RUST_LAYER_RE.finditer(code, start, end):
+            if depths[layer.start()] != level or (layer.group(1) in ("layer", "route_layer") and layer.start() < pos):
+                continue
+            open_at = layer.end() - 1
+            auth += _auth(IDENT_RE.findall(code[open_at + 1:closing_paren(code, open_at)]))[:1]
+        for scope in RUST_SCOPE_RE.finditer(code, start, end):
+            if depths[scope.start()] == level:
+                args = _split_args(code, scope.end(), closing_paren(code, scope.end() - 1))
+                prefixes.insert(0, (_string_arg(content, code, args[0], '"') or "") if args else "")
+
+        # Step out to the call this chain is an argument of (`.nest("/api", ..)`, `.service(..)`)
+        enclosing = start - 1
+        while enclosing >= 0 and not (depths[enclosing] == level - 1 and code[enclosing] in "([{"):
+            enclosing -= 1
+        if enclosing < 0 or code[enclosing] != "(":
+            bound = re.match(r"\s*let\s+(?:mut\s+)?(\w+)", code[start:end])
+            if bound and bound.group(1) in nested:
+                prefix, pos = nested[bound.group(1)]
+                prefixes.insert(0, prefix)
+                continue
+            break
+        owner = re.search(r"\.\s*(\w+)\s*$", code[max(0, enclosing - 40):enclosing])
+        if owner and owner.group(1) == "nest":
+            args = _split_args(code, enclosing + 1, closing_paren(code, enclosing))
+            if len(args) == 2 and args[1][0] <= pos:
+                prefixes.insert(0, _string_arg(content, code, args[0], '"') or "")
+        pos = enclosing
+    return "".join(p.rstrip("/") for p in prefixes), list(dict.fromkeys(auth))
+
+
+def _rust_attribute_routes(file: FileSymbols, lines: List[str]) -> List[Route]:
+    """actix-web `#[get("/users/{id}")]` / `#[route("/", method = "GET", method = "POST")]` handlers."""
+    routes = []
+    for d in file.definitions:
+        attrs = []
+        i = d.line - 2
+        while i >= 0 and len(attrs) < 8 and lines[i].strip().startswith(("#[", "///", "//")):
+            attrs.insert(0, lines[i].strip())
+            i -= 1
+        for m in RUST_ROUTE_ATTR_RE.finditer("\n".join(attrs)):
+            verb, path, rest = m.groups()
+            methods = re.findall(r'method\s*=\s*"(\w+)"', rest) if verb == "route" else [verb]
+            for method in methods or ["GET"]:
+                routes.append(Route(method.upper(), path, file.path, d.line, "actix", d.name,
+                                    declaration=m.group(0), definition=d, handler_file=file.path))
+    return routes
+
+
+def _rust_routes(file: FileSymbols, content: str) -> List[Route]:
+    lines = content.splitlines()
+    routes = _rust_attribute_routes(file, lines)
+    code = strip_rust_noise(content)
+    if ".route" not in code and "web::resource" not in code:
+        return routes
+    depths = _depths(code)
+    line_of = line_index(code)
+    nested = _rust_nested(content, code)
+
+    def add(method: str, path: str, handler: Optional[str], framework: str, pos: int):
+        prefix, auth = _rust_context(content, code, depths, pos, nested)
+        line = line_of(pos)
+        routes.append(Route(method.upper(), _join(prefix, path), file.path, line, framework,
+                            handler.rstrip(":") if handler else None, auth, lines[line - 1].strip()[:200]))
+
+    def actix_targets(start: int, end: int) -> List[Tuple[str, Optional[str]]]:
+        found = []
+        for m in ACTIX_METHOD_RE.finditer(code, start, end):
+            to = ACTIX_TO_RE.search(code, m.end(), end)
+            found.append((m.group(1), to.group(1) if to else None))
+        return found
+
+    # `.route("/users", get(list).post(create))` (axum) / `.route("/users", web::get().to(list))` (actix)
+    for m in RUST_ROUTE_CALL_RE.finditer(code):
+        open_at = m.end() - 1
+        args = _split_args(code, open_at + 1, closing_paren(code, open_at))
+        if len(args) != 2 or (path := _string_arg(content, code, args[0], '"')) is None:
+            continue    # actix `resource(..).route(web::get().to(h))` is read with its resource below
+        start, end = args[1]
+        if "web::" in code[start:end]:
+            for verb, handler in actix_targets(start, end):
+                add(verb, path, handler, "actix", m.start())
+            continue
+        for method in AXUM_METHOD_RE.finditer(code, start, end):
+            if depths[method.start()] == depths[start]:    # Not a `.get()` inside an inline closure
+                add("ANY" if method.group(1) == "any" else method.group(1), path, method.group(2), "axum", m.start())
+
+    # `web::resource("/users").route(web::get().to(list)).to(fallback)`
+    for m in RUST_RESOURCE_RE.finditer(code):
+        open_at = m.end() - 1
+        close = closing_paren(code, open_at)
+        args = _split_args(code, open_at + 1, close)
+        path = _string_arg(content, code, args[0], '"') if args else None
+        if path is None:
+            continue
+        pos = close + 1
+        while chained := RUST_CHAIN_RE.match(code, pos):
+            chain_open = chained.end() - 1
+            chain_close = closing_paren(code, chain_open)
+            if chained.group(1) == "route":
+                for verb, handler in actix_targets(chain_open, chain_close):
+                    add(verb, path, handler, "actix", m.start())
+            elif chained.group(1) == "to":
+                target = re.match(r"\s*([\w:]+)", code[chain_open + 1:chain_close])
+                add("ANY", path, target.group(1) if target else None, "actix", m.start())
+            pos = chain_close + 1
+    return routes
+
+
+# --- Handlers ---
+
+def _language_family(language: str) -> Tuple[str, ...]:
+    return ("typescript", "javascript") if language in ("typescript", "javascript") else (language,)
+
+
+def resolve_function(graph: CallGraph, file: FileSymbols, name: str) -> Tuple[Optional[Definition], Optional[str]]:
+    """A function named as written in `file` (`list_users`, `handlers::list`, `Views.show`): local first, then unique."""
+    segments = [s for s in re.split(r"::|\.", name) if s]
+    if not segments:
+        return None, None
+    last, owner = segments[-1], segments[-2] if len(segments) > 1 else None
+    local = [d for d in file.definitions if d.name == last and d.owner in (None, owner)]
+    if local:
+        return min(local, key=lambda d: d.owner is not None), file.path
+    family = _language_family(file.language)
+    candidates = [(d, f.path) for f in graph.files.values() if f.language in family
+                  for d in f.definitions if d.name == last and d.owner in (None, owner)]
+    if len(candidates) > 1 and len(segments) > 1 and file.language == "rust":
+        path = "::".join(segments)
+        candidates = [(d, p) for d, p in candidates if (crate_path(graph, d, p) or "").endswith(path)]
+    return candidates[0] if len(candidates) == 1 else (None, None)
+
+
+def _handler_auth(root: Path, graph: CallGraph, route: Route) -> List[str]:
+    """Auth checks the handler calls itself, and Rust extractor types (`claims: Claims`) it demands."""
+    file = graph.files[route.handler_file]
+    d = route.definition
+    names = [c.name for c in file.calls if c.caller is d and _is_check(c.name)]
+    if file.language == "rust":
+        try:
+            lines = (root / file.path).read_text(errors="ignore").splitlines()[d.line - 1:d.line + 11]
+        except OSError:
+            lines = []
+        signature = "\n".join(lines).split("{", 1)[0]
+        names += re.findall(r"\b[A-Z]\w*", signature.split("(", 1)[1] if "(" in signature else "")
+    return _auth(names, guard=False)
+
+
+def extract_routes(root: Path, graph: CallGraph, paths: Optional[Set[str]] = None) -> List[Route]:
+    """Every route declared in `graph`'s files (or only in `paths`), with handlers resolved and auth collected."""
+    routes = []
+    for file in graph.files.values():
+        if paths is not None and file.path not in paths:
+            continue
+        try:
+            content = (root / file.path).read_text(errors="ignore")
+        except OSError:
+            continue
+        if file.language == "python":
+            routes.extend(_python_routes(file, content))
+        elif file.language == "rust":
+            routes.extend(_rust_routes(file, content))
+        else:
+            routes.extend(_next_routes(root, file, content) + _express_routes(file, content))
+
+    for route in routes:
+        if route.definition is None and route.handler:
+            route.definition, route.handler_file = resolve_function(graph, graph.files[route.file], route.handler)
+        if route.definition is not None:
+            route.auth = list(dict.fromkeys(route.auth + _handler_auth(root, graph, route)))
+    return sorted(routes, key=lambda r: (r.file, r.line, r.method))
+
+
+# --- Persistence ---
+
+def route_map_records(graph: CallGraph, routes: List[Route],
+                      project_id: str) -> Tuple[List[Dict[str, Any]], List[Dict[str, Any]]]:
+    """`route` entities (plus handlers and external auth middleware) and `handles`/`guarded_by` edges."""
+    entities: Dict[str, Dict[str, Any]] = {}
+    edges: Dict[str, Dict[str, Any]] = {}
+
+    def node(name: str, entity_type: str, file_path: str, qualified: Optional[str], signature: Optional[str] = None) -> str:
+        ent_id = entity_id(project_id, name, entity_type, qualified)
+        entities.setdefault(ent_id, {
+            "id": ent_id,
+            "project_id": project_id,
+            "name": name,
+            "entity_type": entity_type,
+            "file_path": file_path,
+            "signature": signature,
+            "qualified_name": qualified,
+        })
+        return ent_id
+
+    def edge(source_id: str, target_id: str, relation: str):
+        rel_id = relationship_id(source_id, target_id, relation)
+        edges[rel_id] = {"id": rel_id, "project_id": project_id, "source_id": source_id, "target_id": target_id,
+                         "relation_type": relation, "confidence": 1.0}
+
+    for r in routes:
+        label = f"{r.method} {r.path}"
+        route_id = node(label, "route", r.file, f"{r.file}::{label}", r.declaration or None)
+        if r.definition is not None:
+            d = r.definition
+            edge(route_id, node(d.name, d.kind, r.handler_file, qualified_name(graph, d, r.handler_file)), "handles")
+        for name in r.auth:
+            guard, guard_file = resolve_function(graph, graph.files[r.file], name)
+            if guard is not None:
+                target_id = node(guard.name, guard.kind, guard_file, qualified_name(graph, guard, guard_file))
+            else:
+                target_id = node(name, "middleware", r.file, f"middleware::{name}")
+            edge(route_id, target_id, "guarded_by")
+    return list(entities.values()), list(edges.values())
+
+
+def index_route_map(root: Path, schema_store, changed: Optional[List[Path]] = None) -> Dict[str, int]:
+    """
+    Rebuilds the project's `route` entities and their `handles`/`guarded_by` edges.
+    With `changed` files, only the routes they declare are replaced; an edited Next.js
+    middleware guards routes elsewhere, so it rebuilds everything.
+    """
+    project_id = schema_store.engine.get_project_id()
+    if changed is not None and any(p.name in NEXT_MIDDLEWARE_FILES for p in changed):
+        changed = None
+    if changed is None:
+        graph = build_call_graph(root)
+        routes = extract_routes(root, graph)
+        entities, edges = route_map_records(graph, routes, project_id)
+
+        schema_store.delete_entities(project_id, "route")
+        schema_store.delete_entities(project_id, "middleware")
+        for relation in ROUTE_RELATIONS:
+            schema_store.delete_relationships(project_id, relation_type=relation, origin="index")
+        schema_store.save_entities_batch(entities)
+        schema_store.save_relationships_batch(edges)
+    else:
+        graph = update_call_graph(root, changed)
+        paths = sorted(relative_paths(root, changed))
+        routes = extract_routes(root, graph, set(paths))
+        entities, edges = route_map_records(graph, routes, project_id)
+
+        stale = [e["id"] for e in schema_store.list_entities_in_files(project_id, paths, "route")]
+        schema_store.delete_entities(project_id, "route", paths)
+        for relation in ROUTE_RELATIONS:
+            schema_store.delete_relationships(project_id, relation_type=relation, origin="index", source_ids=stale)
+        schema_store.save_entities_batch(entities)
+        schema_store.save_relationships_batch(edges)
+        schema_store.delete_unreferenced_entities(project_id, "middleware", "guarded_by")
+
+    stats = {
+        "routes": sum(1 for e in entities if e["entity_type"] == "route"),
+        "handled": sum(1 for e in edges if e["relation_type"] == "handles"),
+        "guarded": len({e["source_id"] for e in edges if e["relation_type"] == "guarded_by"}),
+    }
+    logger.info(f"🛣️ [ROUTE MAP]: {stats}")
+    return stats
+
+
+def list_routes(schema_store, project_id: str, method: Optional[str] = None,
+                prefix: Optional[str] = None) -> List[Dict[str, Any]]:
+    """Stored routes with their handler and auth, optionally filtered by method and path prefix."""
+    targets: Dict[str, Dict[str, List[str]]] = {}
+    for relation in ROUTE_RELATIONS:
+        for rel in schema_store.list_relationships(project_id=project_id, relation_type=relation):
+            targets.setdefault(rel["source_id"], {}).setdefault(relation, []).append(rel["target_id"])
+
+    results = []
+    for route in schema_store.list_entities(project_id, "route"):
+        verb, path = route["name"].split(" ", 1)
+        if (method and verb != method.upper()) or (prefix and not path.startswith(prefix)):
+            continue
+        linked = targets.get(route["id"], {})
+        handler = next((h for h in map(schema_store.get_entity_by_id, linked.get("handles", [])) if h), None)
+        guards = [g["name"] for g in map(schema_store.get_entity_by_id, linked.get("guarded_by", [])) if g]
+        results.append({
+            "method": verb,
+            "path": path,
+            "handler": handler["name"] if handler else None,
+            "handler_file": handler["file_path"] if handler else None,
+            "auth": sorted(guards),
+            "file": route["file_path"],
+            "declaration": route["signature"],
+        })
+    return sorted(results, key=lambda r: (r["path"], r["method"], r["file"]))
@@ -0,0 +1,300 @@
+"""
+Rust cfg Predicates - Conditional compilation on indexed items.
+
+`#[cfg(...)]` on an item, on its `impl`/`trait` block and on the modules that
+contain it (`#[cfg(test)] mod tests;`, inner `#![cfg(...)]`) are combined into one
+predicate per entity and stored in its `cfg` column, so always-compiled code can be
+told apart from `feature = "postgres"`, `test` or `target_os = "linux"` code.
+`index_cfg_features` cross-references feature predicates with the crate's
+`[features]` table: gated entities get a `requires_feature` edge to the feature
+entity, and features Cargo.toml does not declare are reported.
+"""
+
+import logging
+import re
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Set, Tuple, Union
+
+from side.intel.ids import entity_id, relationship_id
+
+logger = logging.getLogger(__name__)
+
+CFG_ATTR_RE = re.compile(r"#\[\s*cfg\s*\(")
+INNER_CFG_RE = re.compile(r"^\s*#!\[\s*cfg\s*\(", re.MULTILINE)
+TOKEN_RE = re.compile(r'\s*(?:(?P<word>[A-Za-z_]\w*)|(?P<string>"(?:[^"\\]|\\.)*")|(?P<punct>[(),=]))')
+OPERATORS = ("all", "any", "not")
+
+# ('option', name, value or None) | (operator, [children])
+Node = Union[Tuple[str, str, Optional[str]], Tuple[str, list]]
+
+
+# --- Extraction ---
+
+def _balanced(text: str, open_at: int) -> Optional[str]:
+    """Text between the `(` at `open_at` and its matching `)`, skipping string literals."""
+    depth, i, in_string = 0, open_at, False
+    while i < len(text):
+        ch = text[i]
+        if in_string:
+            if ch == "\\":
+                i += 1
+            elif ch == '"':
+                in_string = False
+        elif ch == '"':
+            in_string = True
+        elif ch == "(":
+            depth += 1
+        elif ch == ")":
+            depth -= 1
+            if depth == 0:
+                return text[open_at + 1:i]
+        i += 1
+    return None
+
+
+def _predicates(text: str, pattern: re.Pattern) -> List[str]:
+    found = []
+    for m in pattern.finditer(text):
+        inner = _balanced(text, m.end() - 1)
+        if inner is not None and inner.strip():
+            found.append(normalize(inner))
+    return found
+
+
+def cfg_predicates(attrs: str) -> List[str]:
+    """Predicates of the outer `#[cfg(...)]` attributes in `attrs` (`cfg_attr` is not a gate)."""
+    return _predicates(attrs, CFG_ATTR_RE)
+
+
+def inner_cfg_predicates(text: str) -> List[str]:
+    """Predicates of a file's inner `#![cfg(...)]` attributes, which gate the whole module."""
+    return _predicates(text, INNER_CFG_RE)
+
+
+# --- Predicates ---
+
+def _tokens(predicate: str) -> List[Tuple[str, str]]:
+    tokens, pos = [], 0
+    while pos < len(predicate):
+        m = TOKEN_RE.match(predicate, pos)
+        if not m or m.end() == pos:
+            if predicate[pos:].strip():
+                raise ValueError(f"Unexpected cfg syntax at {predicate[pos:]!r}")
+            break
+        kind = m.lastgroup
+        tokens.append((kind, m.group(kind)))
+        pos = m.end()
+    return tokens
+
+
+def parse(predicate: str) -> Node:
+    """`all(unix, feature = "x")` -> ('all', [('option', 'unix', None), ('option', 'feature', 'x')])."""
+    tokens = _tokens(predicate)
+    pos = 0
+
+    def expect(value: str) -> None:
+        nonlocal pos
+        if pos >= len(tokens) or tokens[pos][1] != value:
+            raise ValueError(f"Expected {value!r} in cfg({predicate})")
+        pos += 1
+
+    def node() -> Node:
+        nonlocal pos
+        if pos >= len(tokens) or tokens[pos][0] != "word":
+            raise ValueError(f"Expected a cfg option in cfg({predicate})")
+        name = tokens[pos][1]
+        pos += 1
+        if name in OPERATORS and pos < len(tokens) and tokens[pos][1] == "(":
+            pos += 1
+            children = []
+            while pos < len(tokens) and tokens[pos][1] != ")":
+                children.append(node())
+                if pos < len(tokens) and tokens[pos][1] == ",":
+                    pos += 1
+            expect(")")
+            return (name, children)
+        if pos < len(tokens) and tokens[pos][1] == "=":
+            pos += 1
+            if pos >= len(tokens) or tokens[pos][0] != "string":
+                raise ValueError(f"Expected a string value in cfg({predicate})")
+            value = tokens[pos][1][1:-1]
+            pos += 1
+            return ("option", name, value)
+        return ("option", name, None)
+
+    tree = node()
+    if pos != len(tokens):
+        raise ValueError(f"Trailing tokens in cfg({predicate})")
+    return tree
+
+
+def render(node: Node) -> str:
+    if node[0] == "option":
+        return node[1] if node[2] is None else f'{node[1]} = "{node[2]}"'
+    return f"{node[0]}({', '.join(render(c) for c in node[1])})"
+
+
+def normalize(predicate: str) -> str:
+    """Canonical spacing (`feature="x"` -> `feature = "x"`); unparseable text is kept verbatim."""
+    try:
+        return render(parse(predicate))
+    except ValueError:
+        return " ".join(predicate.split())
+
+
+def combine(predicates: List[Optional[str]]) -> Optional[str]:
+    """One predicate that holds when all of `predicates` do; None when nothing is gated."""
+    parts: List[str] = []
+    for predicate in predicates:
+        if not predicate:
+            continue
+        try:
+            node = parse(predicate)
+            children = [render(c) for c in node[1]] if node[0] == "all" else [render(node)]
+        except ValueError:
+            children = [predicate]
+        parts.extend(c for c in children if c not in parts)
+    if not parts:
+        return None
+    return parts[0] if len(parts) == 1 else f"all({', '.join(parts)})"
+
+
+def _required(node: Node) -> Set[Tuple[str, Optional[str]]]:
+    """Options that must be set for `node` to hold."""
+    if node[0] == "option":
+        return {(node[1], node[2])}
+    if node[0] == "all":
+        return set().union(*(_required(c) for c in node[1])) if node[1] else set()
+    if node[0] == "any" and node[1]:
+        return set.intersection(*(_required(c) for c in node[1]))
+    return set()
+
+
+def _mentioned(node: Node, negated: bool = False) -> List[str]:
+    if node[0] == "option":
+        return [node[2]] if node[1] == "feature" and node[2] and not negated else []
+    return [f for c in node[1] for f in _mentioned(c, negated or node[0] == "not")]
+
+
+def features(predicate: Optional[str]) -> List[str]:
+    """Features whose presence can enable the gated code (`not(feature = ...)` excluded)."""
+    if not predicate:
+        return []
+    try:
+        found = _mentioned(parse(predicate))
+    except ValueError:
+        return []
+    return list(dict.fromkeys(found))
+
+
+def is_test_only(predicate: Optional[str]) -> bool:
+    """True when the code is only compiled for `cargo test`."""
+    if not predicate:
+        return False
+    try:
+        return ("test", None) in _required(parse(predicate))
+    except ValueError:
+        return False
+
+
+def describe(predicate: Optional[str]) -> Optional[str]:
+    """`only exists with feature `postgres``, `only exists in test builds`, ..."""
+    if not predicate:
+        return None
+    try:
+        node = parse(predicate)
+    except ValueError:
+        return f"only exists when `cfg({predicate})` holds"
+    options = node[1] if node[0] == "all" else [node]
+    if any(o[0] != "option" for o in options):
+        return f"only exists when `cfg({predicate})` holds"
+
+    named = [o[2] for o in options if o[1] == "feature" and o[2]]
+    parts = []
+    if named:
+        label = "feature" if len(named) == 1 else "features"
+        parts.append(f"with {label} " + " and ".join(f"`{f}`" for f in named))
+    if any(o[1] == "test" and o[2] is None for o in options):
+        parts.append("in test builds")
+    others = [render(o) for o in options if o[1] not in ("feature", "test")]
+    if others:
+        parts.append("when " + " and ".join(f"`{o}`" for o in others))
+    return "only exists " + ", ".join(parts)
+
+
+# --- Cross-reference with Cargo.toml ---
+
+def declared_features(crate) -> Set[str]:
+    """`[features]` keys plus the implicit features of optional dependencies."""
+    return set(crate.features) | {d.name for d in crate.dependencies if d.optional}
+
+
+def index_cfg_features(root: Path, schema_store, changed: Optional[List[Path]] = None) -> Dict[str, Any]:
+    """
+    Links feature-gated entities to their crate's feature entities and reports undeclared features.
+    With `changed` files, only the edges of the entities they define are replaced.
+    """
+    from side.intel import rust_modules
+    from side.intel.call_graph import relative_paths
+    from side.intel.cargo_manifest import parse_crate
+
+    project_id = schema_store.engine.get_project_id()
+    crates: Dict[Path, Any] = {}
+    edges: Dict[str, Dict[str, Any]] = {}
+    unknown: Dict[Tuple[str, str], List[str]] = {}
+    gated = 0
+
+    entities = schema_store.list_qualified_entities(project_id)
+    sources: Optional[List[str]] = None
+    if changed is not None:
+        paths = relative_paths(root, changed)
+        entities = [e for e in entities if e.get("file_path") in paths]
+        sources = [e["id"] for e in entities]
+
+    for entity in entities:
+        names = features(entity.get("cfg"))
+        if not names or not entity.get("file_path"):
+            continue
+        gated += 1
+        crate_dir = rust_modules.find_crate_dir(root, root / entity["file_path"])
+        if crate_dir is None:
+            continue
+        if crate_dir not in crates:
+            try:
+                crates[crate_dir] = parse_crate(crate_dir / "Cargo.toml")
+            except Exception as e:
+                logger.debug(f"cfg: unreadable manifest in {crate_dir}: {e}")
+                crates[crate_dir] = None
+        crate = crates[crate_dir]
+        if not crate:
+            continue
+
+        declared = declared_features(crate)
+        for feature in names:
+            if feature not in declared:
+                unknown.setdefault((crate.name, feature), []).append(entity["qualified_name"])
+                continue
+            feature_id = entity_id(project_id, f"{crate.name}/{feature}", "feature")
+            if not schema_store.get_entity_by_id(feature_id):
+                continue  # Implicit feature of an optional dependency: no `[features]` entry to link to
+            edge_id = relationship_id(entity["id"], feature_id, "requires_feature")
+            edges[edge_id] = {
+                "id": edge_id,
+                "project_id": project_id,
+                "source_id": entity["id"],
+                "target_id": feature_id,
+                "relation_type": "requires_feature",
+                "confidence": 1.0,
+            }
+
+    schema_store.delete_relationships(project_id, relation_type="requires_feature", origin="index",
+                                      source_ids=sources)
+    schema_store.save_relationships_batch(list(edges.values()))
+
+    undeclared = [{"crate": c, "feature": f, "entities": sorted(e)} for (c, f), e in sorted(unknown.items())]
+    for item in undeclared:
+        logger.warning(f"⚠️ [CFG]: {item['crate']} gates {len(item['entities'])} item(s) on feature "
+                       f"`{item['feature']}`, which its Cargo.toml does not declare")
+    stats = {"gated": gated, "edges": len(edges), "undeclared": undeclared}
+    logger.info(f"🧩 [CFG]: {gated} feature-gated entities, {len(edges)} feature edges")
+    return stats
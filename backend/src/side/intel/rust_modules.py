@@ -0,0 +1,552 @@
+"""
+Rust Module Tree - Crate-qualified paths for Rust files, items and `use` statements.
+
+rustc's rules are followed from every crate root (`[lib]`/`[[bin]]` paths,
+`src/main.rs`, `src/bin/`, `tests/`, `examples/`, `benches/`): `mod foo;` loads
+`foo.rs` or `foo/mod.rs` next to a mod-rs file and below `self/` for a non-mod-rs
+`self.rs`, `#[path = "..."]` overrides the file, and inline `mod foo { ... }` blocks
+scope their items and shift where their own `mod` files live. Every file maps to
+its module path (`shop::orders::model`), inline blocks to the lines they cover.
+Files no crate root reaches fall back to the directory layout. Each module also
+carries the `#[cfg(...)]` gates of its `mod` item, its inner `#![cfg(...)]` and its
+ancestors, so items in `#[cfg(test)] mod tests;` are known to be test-only.
+
+Rust entities are identified by their qualified path (`shop::orders::Order::total`),
+so same-named items in different modules no longer share an id. `index_rust_modules`
+stores the tree as `module` entities and resolves each `use` to a `uses` edge from the
+importing module to the entity it names, following re-exports. A saved file whose
+`mod` items and inner gates are unchanged only refreshes its own spans and `use`
+edges (`refresh_file`); anything else rebuilds the tree.
+"""
+
+import logging
+import os
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+from side.intel import rust_cfg
+from side.intel.ids import entity_id, relationship_id
+from side.intel.lexing import line_index, rust_module_path, strip_rust_noise
+
+logger = logging.getLogger(__name__)
+
+# `mod name;` / `mod name {` with its attributes, or a brace (tracks where inline modules close)
+MOD_RE = re.compile(
+    r"(?P<attrs>(?:#\[[^\]]*\]\s*)*)(?P<vis>pub(?:\([^)]*\))?\s+)?\bmod\s+(?:r#)?(?P<name>\w+)\s*(?P<body>[;{])"
+    r"|(?P<brace>[{}])"
+)
+PATH_ATTR_RE = re.compile(r'#\[\s*path\s*=\s*"([^"]+)"\s*\]')
+TARGET_DIRS = ("src/bin", "tests", "examples", "benches")    # Each `*.rs` / `*/main.rs` is its own crate
+USE_CONFIDENCE = 0.9            # Same tier as an explicit import in the call graph
+MAX_REEXPORT_HOPS = 8
+SKIPPED_TYPES = {"impl", "file"}    # Not nameable by a `use` path
+
+
+@dataclass
+class RustModule:
+    path: str                                   # 'shop::orders::model'
+    file: Path                                  # File holding the module's items
+    declared_in: Optional[Path] = None          # File with the `mod` item; None for crate roots
+    line: int = 0                               # Line of the `mod` item
+    span: Optional[Tuple[int, int]] = None      # (`mod` line, closing line) of an inline block
+    cfg: Optional[str] = None                   # Gate of the module and its ancestors: 'test', 'feature = "x"'
+    public: bool = True                         # Declared `pub mod` (crate roots are public)
+
+    @property
+    def name(self) -> str:
+        return self.path.rsplit("::", 1)[-1]
+
+    @property
+    def parent(self) -> Optional[str]:
+        return self.path.rpartition("::")[0] or None
+
+
+@dataclass
+class ModuleTree:
+    crate_dir: Path
+    crates: Dict[str, Path] = field(default_factory=dict)                      # crate name -> root file
+    modules: Dict[str, RustModule] = field(default_factory=dict)
+    files: Dict[Path, str] = field(default_factory=dict)                       # file -> its top-level module
+    inline: Dict[Path, List[Tuple[int, int, str]]] = field(default_factory=dict)
+    mod_rs: Dict[Path, bool] = field(default_factory=dict)                    # file -> loaded as a mod-rs file
+    structure: Dict[Path, List[tuple]] = field(default_factory=dict)          # file -> its `mod` items and gates
+    uses: Dict[Path, List["UseDecl"]] = field(default_factory=dict)           # file -> its `use` declarations
+
+    def module_at(self, path: Path, line: int = 0) -> Optional[str]:
+        """Module of the item at `line` (innermost inline block), or of the file itself."""
+        spans = [s for s in self.inline.get(path, []) if s[0] < line <= s[1]]
+        if spans:
+            return max(spans, key=lambda s: s[0])[2]
+        return self.files.get(path)
+
+    def exported(self, path: str) -> bool:
+        """True when `path` and every module above it are `pub`, so other crates can name its items."""
+        while path:
+            module = self.modules.get(path)
+            if module is None or not module.public:
+                return False
+            path = module.parent or ""
+        return True
+
+    def cfg_at(self, path: Path, line: int = 0) -> Optional[str]:
+        """cfg gate inherited by the item at `line` from the modules around it."""
+        module = self.modules.get(self.module_at(path, line) or "")
+        return module.cfg if module else None
+
+
+def _normalize(path: Path) -> Path:
+    return Path(os.path.normpath(path))
+
+
+# --- Crate roots ---
+
+def crate_roots(crate_dir: Path) -> List[Tuple[str, Path]]:
+    """(crate name, root file) of every target in the package; [] for virtual or broken manifests."""
+    from side.intel.cargo_manifest import parse_crate
+
+    try:
+        crate = parse_crate(crate_dir / "Cargo.toml")
+    except Exception as e:
+        logger.debug(f"Rust modules: unreadable manifest in {crate_dir}: {e}")
+        return []
+    if not crate:
+        return []
+
+    roots: List[Tuple[str, Path]] = []
+    for target in crate.targets:
+        path = target.path
+        if target.kind == "bin" and not path:
+            path = "src/main.rs" if target.name == crate.name else f"src/bin/{target.name}.rs"
+        if path:
+            roots.append((target.name.replace("-", "_"), crate_dir / path))
+    for directory in TARGET_DIRS:
+        base = crate_dir / directory
+        roots.extend((p.stem.replace("-", "_"), p) for p in sorted(base.glob("*.rs")))
+        roots.extend((p.parent.name.replace("-", "_"), p) for p in sorted(base.glob("*/main.rs")))
+
+    seen, unique = set(), []
+    for name, path in roots:
+        path = _normalize(path)
+        if path not in seen and path.is_file():
+            seen.add(path)
+            unique.append((name, path))
+    return unique
+
+
+# --- Tree ---
+
+def _walk(tree: ModuleTree, file: Path, module: str, mod_rs: bool, follow: bool = True) -> None:
+    """Records `file` as `module` and follows its `mod` items (unless not `follow`), depth first."""
+    tree.files[file] = module
+    tree.mod_rs[file] = mod_rs
+    try:
+        text = file.read_text(errors="ignore")
+    except OSError as e:
+        logger.debug(f"Rust modules: skipping {file}: {e}")
+        return
+    own = tree.modules[module]
+    inner = rust_cfg.inner_cfg_predicates(text)
+    own.cfg = rust_cfg.combine([own.cfg, *inner])
+    structure: List[tuple] = [("cfg", *inner)]
+    tree.structure[file] = structure
+    code = strip_rust_noise(text)
+    line_of = line_index(code)
+
+    stack: List[Tuple[str, Path, int, int]] = []   # Open inline modules: (path, directory, brace depth, line)
+    depth = 0
+    for m in MOD_RE.finditer(code):
+        if m.group("brace"):
+            if m.group("brace") == "{":
+                depth += 1
+                continue
+            depth -= 1
+            if stack and depth < stack[-1][2]:
+                path, _, _, start = stack.pop()
+                tree.modules[path].span = (start, line_of(m.start()))
+                tree.inline.setdefault(file, []).append((start, line_of(m.start()), path))
+            continue
+
+        name, line = m.group("name"), line_of(m.start("name"))
+        parent = stack[-1][0] if stack else module
+        base = stack[-1][1] if stack else (file.parent if mod_rs else file.parent / file.stem)
+        child = f"{parent}::{name}"
+        attrs = text[m.start("attrs"):m.end("attrs")]
+        custom = PATH_ATTR_RE.search(attrs)
+        cfg = rust_cfg.combine([tree.modules[parent].cfg, *rust_cfg.cfg_predicates(attrs)])
+        public = (m.group("vis") or "").strip() == "pub"
+        structure.append((child, m.group("body"), cfg, public, custom.group(1) if custom else None))
+
+        if m.group("body") == "{":
+            depth += 1
+            directory = _normalize(base / custom.group(1)) if custom else base / name
+            stack.append((child, directory, depth, line))
+            tree.modules[child] = RustModule(child, file, file, line, cfg=cfg, public=public)
+            continue
+
+        if custom:
+            # Relative to the declaring file's directory, or the inline module's; loaded as a mod-rs file
+            target = _normalize((base if stack else file.parent) / custom.group(1))
+            target_mod_rs = True
+        else:
+            target = next((c for c in (base / f"{name}.rs", base / name / "mod.rs") if c.is_file()), None)
+            target_mod_rs = target is not None and target.name == "mod.rs"
+        structure.append(("target", child, target if target is not None and target.is_file() else None))
+        if not follow:
+            if child in tree.modules:
+                tree.modules[child].line = line
+            continue
+        if target is None or not target.is_file() or target in tree.files:
+            continue  # Missing (generated, cfg-gated) or already reached
+        tree.modules[child] = RustModule(child, target, file, line, cfg=cfg, public=public)
+        _walk(tree, target, child, target_mod_rs)
+
+    for path, _, _, start in stack:   # Unbalanced braces: the block runs to the end of the file
+        end = line_of(len(code))
+        tree.modules[path].span = (start, end)
+        tree.inline.setdefault(file, []).append((start, end, path))
+
+
+def build_module_tree(crate_dir: Path) -> Optional[ModuleTree]:
+    """Module tree of every target in the package at `crate_dir`."""
+    roots = crate_roots(crate_dir)
+    if not roots:
+        return None
+    tree = ModuleTree(crate_dir=crate_dir)
+    for crate, root_file in roots:
+        if root_file in tree.files:
+            continue  # A `#[path]` of another target already loaded it
+        tree.crates.setdefault(crate, root_file)
+        tree.modules.setdefault(crate, RustModule(crate, root_file))
+        _walk(tree, root_file, crate, mod_rs=True)
+    return tree
+
+
+# Trees keyed by crate directory, until `clear()` (a scan, or an edited .rs/Cargo.toml);
+# crate names also by the manifest's (mtime, size), so a renamed lib is picked up on its own
+_trees: Dict[Path, Optional[ModuleTree]] = {}
+_crate_names: Dict[Path, Tuple[Optional[Tuple[int, int]], Optional[str]]] = {}
+
+
+def clear() -> None:
+    _trees.clear()
+    _crate_names.clear()
+
+
+def forget(root: Path) -> None:
+    """Drops cached trees and crate names of packages under `root` (a deleted scratch checkout)."""
+    for cache in (_trees, _crate_names):
+        for crate_dir in [d for d in cache if d == root or root in d.parents]:
+            del cache[crate_dir]
+
+
+def crate_tree(crate_dir: Path) -> Optional[ModuleTree]:
+    if crate_dir not in _trees:
+        _trees[crate_dir] = build_module_tree(crate_dir)
+    return _trees[crate_dir]
+
+
+def refresh_file(root: Path, path: Path) -> bool:
+    """
+    Brings the cached tree up to date with an edited `path`. Returns True when its
+    module structure (`mod` items, `#[path]`, gates, visibility) changed or is unknown,
+    so every module path has to be rebuilt; otherwise only its inline spans move.
+    """
+    path = _normalize(path)
+    crate_dir = find_crate_dir(root, path)
+    tree = _trees.get(crate_dir) if crate_dir else None
+    if tree is None or path not in tree.files or not path.is_file():
+        return True
+    module = tree.files[path]
+    scratch = ModuleTree(crate_dir=crate_dir, modules={
+        p: RustModule(m.path, m.file, m.declared_in, m.line, m.span, m.cfg, m.public)
+        for p, m in tree.modules.items()
+    })
+    _walk(scratch, path, module, tree.mod_rs.get(path, True), follow=False)
+    if scratch.structure.get(path) != tree.structure.get(path):
+        return True
+    for name, fresh in scratch.modules.items():
+        tree.modules[name].line, tree.modules[name].span = fresh.line, fresh.span
+    tree.inline[path] = scratch.inline.get(path, [])
+    return False
+
+
+def crate_name(crate_dir: Path) -> Optional[str]:
+    """Library name (or package name) as written in paths: `my-crate` -> `my_crate`."""
+    try:
+        stat = (crate_dir / "Cargo.toml").stat()
+        key = (stat.st_mtime_ns, stat.st_size)
+    except OSError:
+        key = None
+    cached = _crate_names.get(crate_dir)
+    if cached is None or cached[0] != key:
+        from side.intel.cargo_manifest import parse_crate
+        name = None
+        try:
+            crate = parse_crate(crate_dir / "Cargo.toml")
+            if crate:
+                lib = next((t for t in crate.targets if t.kind == "lib"), None)
+                name = (lib.name if lib else crate.name).replace("-", "_")
+        except Exception as e:
+            logger.debug(f"Rust modules: unreadable manifest in {crate_dir}: {e}")
+        _crate_names[crate_dir] = (key, name)
+    return _crate_names[crate_dir][1]
+
+
+def find_crate_dir(root: Path, path: Path) -> Optional[Path]:
+    """Nearest package (not virtual workspace) manifest directory at or above `path`, within `root`."""
+    crate_dir = path.parent
+    while True:
+        if (crate_dir / "Cargo.toml").is_file() and crate_name(crate_dir):
+            return crate_dir
+        if crate_dir == root or crate_dir.parent == crate_dir:
+            return None
+        crate_dir = crate_dir.parent
+
+
+def _layout_module(crate_dir: Optional[Path], path: Path) -> str:
+    """Module path from the directory layout alone, for files no crate root reaches."""
+    if crate_dir is None:
+        return "crate" if path.stem in ("lib", "main", "mod") else f"crate::{path.stem}"
+    try:
+        rel = path.relative_to(crate_dir / "src")
+    except ValueError:
+        return path.stem  # tests/, benches/, examples/
+    if rel.parts[0] == "bin":
+        return path.stem if len(rel.parts) == 2 else rel.parts[1]
+    module = rust_module_path(crate_dir / "src", path)
+    crate = crate_name(crate_dir)
+    return crate if module == "crate" else f"{crate}::{module}"
+
+
+def module_path(root: Path, path: Path, line: int = 0) -> str:
+    """`shop::orders::model` for the item at `line` of `path` (the file's own module for line 0)."""
+    path = _normalize(path)
+    crate_dir = find_crate_dir(root, path)
+    tree = crate_tree(crate_dir) if crate_dir else None
+    module = tree.module_at(path, line) if tree else None
+    return module or _layout_module(crate_dir, path)
+
+
+def inline_modules(root: Path, path: Path) -> List[Tuple[int, int, str]]:
+    """(`mod` line, closing line, module path) of the inline module blocks in `path`."""
+    path = _normalize(path)
+    crate_dir = find_crate_dir(root, path)
+    tree = crate_tree(crate_dir) if crate_dir else None
+    return list(tree.inline.get(path, [])) if tree else []
+
+
+def module_cfg(root: Path, path: Path, line: int = 0) -> Optional[str]:
+    """cfg gate of the module holding the item at `line` of `path` (None when always compiled)."""
+    path = _normalize(path)
+    crate_dir = find_crate_dir(root, path)
+    tree = crate_tree(crate_dir) if crate_dir else None
+    return tree.cfg_at(path, line) if tree else None
+
+
+def qualify(module: Optional[str], name: str, owner: Optional[str] = None) -> Optional[str]:
+    """`module::Owner::name`; None without a module (non-Rust entities keep name-based ids)."""
+    if not module:
+        return None
+    return "::".join(p for p in (module, owner, name) if p)
+
+
+def module_trees(root: Path, cached: bool = True) -> List[ModuleTree]:
+    """Trees of every package with Rust sources under `root`; built for this call alone unless `cached`."""
+    from side.intel.call_graph import iter_source_files
+
+    trees: Dict[Path, Optional[ModuleTree]] = {}
+    for path in iter_source_files(root, {"rust"}):
+        crate_dir = find_crate_dir(root, path)
+        if crate_dir and crate_dir not in trees:
+            trees[crate_dir] = crate_tree(crate_dir) if cached else build_module_tree(crate_dir)
+    return [t for t in trees.values() if t]
+
+
+# --- `use` resolution ---
+
+@dataclass
+class UseDecl:
+    module: str                 # Module the `use` is written in
+    path: List[str]             # As written, expanded: ['crate', 'orders', 'Order']
+    alias: str                  # Name bound in `module` ('*' for globs)
+    file: Path
+    line: int
+
+
+def file_uses(tree: ModuleTree, file: Path) -> List[UseDecl]:
+    """`use` declarations of one file, read fresh and cached on the tree."""
+    from side.intel.call_graph import RUST_USE_RE, expand_rust_use
+
+    uses = []
+    try:
+        code = strip_rust_noise(file.read_text(errors="ignore"))
+    except OSError:
+        code = ""
+    line_of = line_index(code)
+    for m in RUST_USE_RE.finditer(code):
+        line = line_of(m.start(1))
+        module = tree.module_at(file, line)
+        for path, alias in expand_rust_use(m.group(1)):
+            uses.append(UseDecl(module, list(path), alias, file, line))
+    tree.uses[file] = uses
+    return uses
+
+
+def use_declarations(tree: ModuleTree) -> List[UseDecl]:
+    return [use for file in tree.files for use in (tree.uses[file] if file in tree.uses else file_uses(tree, file))]
+
+
+class UseResolver:
+    """Resolves `use` paths against qualified entity paths, through `use` aliases and glob re-exports."""
+
+    def __init__(self, trees: List[ModuleTree], known: Dict[str, List[str]]):
+        self.known = known                                      # qualified path -> entity ids
+        self.crates = {name for tree in trees for name in tree.crates}
+        self.aliases: Dict[str, str] = {}                       # 'shop::prelude::Order' -> 'shop::orders::Order'
+        self.globs: Dict[str, List[str]] = {}                   # module -> modules glob-imported into it
+
+    def absolute(self, module: str, segments: List[str]) -> Optional[List[str]]:
+        """Crate-absolute segments, or None for external crates (std, dependencies)."""
+        current = module.split("::")
+        head = segments[0]
+        if head == "crate":
+            return current[:1] + segments[1:]
+        if head == "self":
+            return current + segments[1:]
+        if head == "super":
+            i = 0
+            while i < len(segments) and segments[i] == "super":
+                i += 1
+            return current[:max(1, len(current) - i)] + segments[i:]
+        local = f"{module}::{head}"
+        if local in self.known or local in self.aliases:
+            return current + segments
+        if head in self.crates:
+            return segments
+        return None
+
+    def register(self, uses: List[UseDecl]) -> None:
+        for use in uses:
+            target = self.absolute(use.module, use.path)
+            if target is None:
+                continue
+            if use.alias == "*":
+                self.globs.setdefault(use.module, []).append("::".join(target))
+            else:
+                self.aliases[f"{use.module}::{use.alias}"] = "::".join(target)
+
+    def lookup(self, path: List[str], hops: int = 0) -> Optional[str]:
+        """Qualified path of the entity `path` names, following aliases and globs."""
+        key = "::".join(path)
+        if key in self.known:
+            return key
+        if hops >= MAX_REEXPORT_HOPS:
+            return None
+        for i in range(len(path), 0, -1):   # Longest aliased prefix first
+            target = self.aliases.get("::".join(path[:i]))
+            if target and target != "::".join(path[:i]):
+                return self.lookup(target.split("::") + path[i:], hops + 1)
+        for source in self.globs.get("::".join(path[:-1]), []):
+            found = self.lookup(source.split("::") + path[-1:], hops + 1)
+            if found:
+                return found
+        return None
+
+    def resolve(self, use: UseDecl) -> Optional[str]:
+        target = self.absolute(use.module, use.path)
+        return self.lookup(target) if target else None
+
+
+# --- Persistence ---
+
+def module_records(root: Path, trees: List[ModuleTree], project_id: str) -> List[Dict[str, Any]]:
+    """`module` entities for every tree, parents before children."""
+    entities = []
+    for tree in trees:
+        for module in sorted(tree.modules.values(), key=lambda m: m.path.count("::")):
+            declared = module.declared_in or module.file
+            try:
+                file_path = declared.relative_to(root).as_posix()
+            except ValueError:
+                file_path = str(declared)
+            parent = tree.modules.get(module.parent or "")
+            entities.append({
+                "id": entity_id(project_id, module.name, "module", module.path),
+                "project_id": project_id,
+                "name": module.name,
+                "entity_type": "module",
+                "file_path": file_path,
+                "qualified_name": module.path,
+                "cfg": module.cfg,
+                "parent_id": entity_id(project_id, parent.name, "module", parent.path) if parent else None,
+            })
+    return entities
+
+
+def index_rust_modules(root: Path, schema_store, changed: Optional[List[Path]] = None) -> Dict[str, int]:
+    """
+    Stores the module tree and rebuilds `uses` edges from each `use` to the entity it names.
+    With `changed` files whose structure `refresh_file` kept, only the edges of the modules
+    they hold are replaced, unless their `use` lines (which other files resolve through) moved.
+    """
+    project_id = schema_store.engine.get_project_id()
+    trees = module_trees(root)
+    sources: Optional[List[str]] = None
+    if changed is None:
+        modules = module_records(root, trees, project_id)
+        if modules:
+            schema_store.save_entities_batch(modules)
+    else:
+        modules = []
+        files = {_normalize(p) for p in changed}
+        sources, moved = [], False
+        for tree in trees:
+            for file in files & set(tree.files):
+                before = [(u.module, u.path, u.alias) for u in tree.uses.get(file, [])]
+                moved |= before != [(u.module, u.path, u.alias) for u in file_uses(tree, file)]
+                sources += [entity_id(project_id, m.name, "module", m.path)
+                            for m in tree.modules.values() if m.file == file]
+        if moved:
+            sources = None  # Re-exports may have moved: every `use` resolves anew
+
+    known: Dict[str, List[str]] = {}
+    for entity in schema_store.list_qualified_entities(project_id):
+        if entity["entity_type"] not in SKIPPED_TYPES:
+            known.setdefault(entity["qualified_name"], []).append(entity["id"])
+
+    uses = [use for tree in trees for use in use_declarations(tree)]
+    resolver = UseResolver(trees, known)
+    resolver.register(uses)
+
+    edges: Dict[str, Dict[str, Any]] = {}
+    resolved = 0
+    for use in uses:
+        if use.alias == "*" or (sources is not None and use.file not in files):
+            continue
+        target = resolver.resolve(use)
+        if not target:
+            continue
+        resolved += 1
+        source_id = entity_id(project_id, use.module.rsplit("::", 1)[-1], "module", use.module)
+        for target_id in known[target]:
+            if target_id == source_id:
+                continue
+            edge_id = relationship_id(source_id, target_id, "uses")
+            edges.setdefault(edge_id, {
+                "id": edge_id,
+                "project_id": project_id,
+                "source_id": source_id,
+                "target_id": target_id,
+                "relation_type": "uses",
+                "confidence": USE_CONFIDENCE,
+            })
+
+    schema_store.delete_relationships(project_id, relation_type="uses", origin="index", source_ids=sources)
+    schema_store.save_relationships_batch(list(edges.values()))
+
+    stats = {"crates": sum(len(t.crates) for t in trees), "modules": len(modules),
+             "uses": len(uses), "resolved": resolved, "edges": len(edges)}
+    logger.info(f"🌳 [RUST MODULES]: {stats}")
+    return stats
@@ -0,0 +1,263 @@
+"""
+Rust Panic Parser - Structured capture of panics, backtraces and `cargo test` failures.
+
+Understands both panic header formats (pre- and post-1.73 rustc), `RUST_BACKTRACE=1`
+and `=full` frames, and the `cargo test` failure summary. Panic locations are linked
+back to SchemaStore entities so friction events point at the real function.
+"""
+
+import re
+import logging
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+logger = logging.getLogger(__name__)
+
+# thread 'main' panicked at 'called `Option::unwrap()` on a `None` value', src/main.rs:4:37
+PANIC_LEGACY_RE = re.compile(r"thread '(?P<thread>[^']*)' panicked at '(?P<message>.*)', (?P<file>\S+?):(?P<line>\d+):(?P<column>\d+)\s*$")
+# thread 'main' panicked at src/main.rs:4:37:
+# called `Option::unwrap()` on a `None` value
+PANIC_RE = re.compile(r"thread '(?P<thread>[^']*)' panicked at (?P<file>\S+?):(?P<line>\d+):(?P<column>\d+):\s*(?P<message>.*)$")
+
+#    3: orders::Order::add_item
+#    3:     0x55d1c2a4b1e0 - orders::Order::add_item::h5f3c0a4b2d1e9f87
+FRAME_RE = re.compile(r"^\s*(?P<index>\d+):\s+(?:0x[0-9a-f]+ - )?(?P<function>.+?)\s*$")
+FRAME_AT_RE = re.compile(r"^\s+at (?P<file>.+?):(?P<line>\d+)(?::(?P<column>\d+))?\s*$")
+HASH_SUFFIX_RE = re.compile(r"::h[0-9a-f]{16}$")
+
+TEST_SECTION_RE = re.compile(r"^---- (?P<name>\S+) stdout ----$")
+TEST_LINE_RE = re.compile(r"^test (?P<name>\S+) \.\.\. (?P<status>ok|FAILED|ignored)")
+TEST_RESULT_RE = re.compile(
+    r"^test result: (?P<status>ok|FAILED)\. (?P<passed>\d+) passed; (?P<failed>\d+) failed; (?P<ignored>\d+) ignored"
+)
+
+# Lines that terminate a multi-line panic message
+MESSAGE_TERMINATORS = ("note:", "stack backtrace:", "thread '", "---- ", "failures:", "test result:", "error:")
+
+# Frames from the runtime, never the user's crate
+RUNTIME_PREFIXES = (
+    "std::", "core::", "alloc::", "test::", "rust_begin_unwind", "__rust", "_start", "__libc",
+    "backtrace::", "panic_unwind::", "<alloc::", "<core::", "<std::",
+)
+RUNTIME_SYMBOLS = {"main", "<unknown>", "start_thread", "clone", "clone3"}
+FN_RE = re.compile(r"\bfn\s+(\w+)")
+
+
+@dataclass
+class BacktraceFrame:
+    index: int
+    function: str
+    file: Optional[str] = None
+    line: Optional[int] = None
+    column: Optional[int] = None
+
+    @property
+    def symbol_path(self) -> List[str]:
+        """Path segments of the function, without generics, closures or trait qualification."""
+        name = self.function
+        # <orders::Order as core::fmt::Display>::fmt -> orders::Order::fmt
+        name = re.sub(r"^<(.+?) as [^>]+>", r"\1", name)
+        name = re.sub(r"<[^<>]*>", "", name)
+        return [p for p in name.split("::") if p and not p.startswith("{{")]
+
+
+@dataclass
+class RustPanic:
+    thread: str
+    message: str
+    file: str
+    line: int
+    column: int
+    frames: List[BacktraceFrame] = field(default_factory=list)
+    first_crate_frame: Optional[BacktraceFrame] = None
+    test: Optional[str] = None
+    start: int = 0                 # Line span of the panic block in the parsed text
+    end: int = 0
+
+    def to_dict(self) -> Dict[str, Any]:
+        frame = self.first_crate_frame
+        return {
+            "thread": self.thread,
+            "message": self.message,
+            "location": {"file": self.file, "line": self.line, "column": self.column},
+            "frame": {
+                "function": frame.function,
+                "file": frame.file,
+                "line": frame.line,
+            } if frame else None,
+            "backtrace_depth": len(self.frames),
+            "test": self.test,
+        }
+
+
+@dataclass
+class CargoTestSummary:
+    passed: int
+    failed: int
+    ignored: int
+    failures: List[str] = field(default_factory=list)
+    start: int = 0
+    end: int = 0
+
+
+def _is_crate_path(file: str) -> bool:
+    """True for locations inside the user's project rather than the toolchain or registry."""
+    normalized = file.replace("\\", "/")
+    if normalized.startswith("/rustc/") or "/.cargo/registry/" in normalized or "/.rustup/" in normalized:
+        return False
+    if "/library/std/" in normalized or "/library/core/" in normalized:
+        return False
+    return True
+
+
+def _is_crate_frame(frame: BacktraceFrame) -> bool:
+    if frame.file:
+        return _is_crate_path(frame.file)
+    return frame.function not in RUNTIME_SYMBOLS and not frame.function.startswith(RUNTIME_PREFIXES)
+
+
+def _parse_backtrace(lines: List[str], start: int) -> Tuple[List[BacktraceFrame], int]:
+    """Parses frames following a `stack backtrace:` header. Returns frames and the next line index."""
+    frames: List[BacktraceFrame] = []
+    i = start
+    while i < len(lines):
+        at = FRAME_AT_RE.match(lines[i])
+        if at and frames:
+            frame = frames[-1]
+            frame.file = at.group("file").removeprefix("./")
+            frame.line = int(at.group("line"))
+            frame.column = int(at.group("column")) if at.group("column") else None
+            i += 1
+            continue
+        m = FRAME_RE.match(lines[i])
+        if not m:
+            break
+        frames.append(BacktraceFrame(int(m.group("index")), HASH_SUFFIX_RE.sub("", m.group("function"))))
+        i += 1
+    return frames, i
+
+
+def parse_rust_panics(text: str) -> List[RustPanic]:
+    """Extracts every panic (with its backtrace, if present) from a log or test output."""
+    lines = text.splitlines()
+    panics: List[RustPanic] = []
+    current_test = None
+
+    i = 0
+    while i < len(lines):
+        line = lines[i]
+        section = TEST_SECTION_RE.match(line.strip())
+        if section or line.strip() == "failures:":
+            current_test = section.group("name") if section else None
+            i += 1
+            continue
+
+        m = PANIC_LEGACY_RE.search(line) or PANIC_RE.search(line)
+        if not m:
+            i += 1
+            continue
+
+        panic = RustPanic(
+            thread=m.group("thread"),
+            message=m.group("message").strip(),
+            file=m.group("file").removeprefix("./"),
+            line=int(m.group("line")),
+            column=int(m.group("column")),
+            start=i,
+        )
+        i += 1
+
+        # Post-1.73 format: the message follows the header on its own line(s)
+        if m.re is PANIC_RE and not panic.message:
+            message_lines = []
+            while i < len(lines) and lines[i].strip() and not lines[i].startswith(MESSAGE_TERMINATORS):
+                message_lines.append(lines[i])
+                i += 1
+            panic.message = "\n".join(message_lines).strip()
+
+        while i < len(lines) and lines[i].startswith("note:"):
+            i += 1
+        if i < len(lines) and lines[i].strip() == "stack backtrace:":
+            panic.frames, i = _parse_backtrace(lines, i + 1)
+            while i < len(lines) and lines[i].startswith("note:"):
+                i += 1
+
+        panic.first_crate_frame = next((f for f in panic.frames if _is_crate_frame(f)), None)
+        # cargo test names the panicking thread after the test
+        panic.test = current_test or (panic.thread if "::" in panic.thread else None)
+        panic.end = i
+        panics.append(panic)
+
+    return panics
+
+
+def parse_test_summary(text: str) -> Optional[CargoTestSummary]:
+    """Parses the `failures:` list and `test result:` line of `cargo test` output."""
+    lines = text.splitlines()
+    summary = None
+    failed_tests: List[str] = []
+    listed: List[str] = []
+    failures_start = None
+
+    for i, line in enumerate(lines):
+        stripped = line.strip()
+        status = TEST_LINE_RE.match(stripped)
+        if status and status.group("status") == "FAILED":
+            failed_tests.append(status.group("name"))
+        elif stripped == "failures:":
+            failures_start = i
+            listed = []
+        elif failures_start is not None and line.startswith("    ") and stripped:
+            listed.append(stripped)
+        result = TEST_RESULT_RE.match(stripped)
+        if result and result.group("status") == "FAILED":
+            summary = CargoTestSummary(
+                passed=int(result.group("passed")),
+                failed=int(result.group("failed")),
+                ignored=int(result.group("ignored")),
+                failures=listed or failed_tests,
+                start=failures_start if failures_start is not None else i,
+                end=i + 1,
+            )
+            failed_tests, failures_start = [], None
+    return summary
+
+
+def _enclosing_fn(project_root: Path, file: str, line: int) -> Optional[str]:
+    """Name of the nearest `fn` at or above `line` in a project source file."""
+    path = Path(file) if Path(file).is_absolute() else project_root / file
+    try:
+        source = path.read_text(errors="ignore").splitlines()
+    except OSError:
+        return None
+    for text in reversed(source[:line]):
+        m = FN_RE.search(text)
+        if m:
+            return m.group(1)
+    return None
+
+
+def link_panic_to_entity(panic: RustPanic, schema_store, project_id: str, project_root: Path) -> Optional[Dict[str, Any]]:
+    """Resolves the panic to a SchemaStore function/method entity."""
+    candidates: List[Tuple[str, Optional[str]]] = []
+    frame = panic.first_crate_frame
+    if frame and frame.symbol_path:
+        candidates.append((frame.symbol_path[-1], frame.file or panic.file))
+    enclosing = _enclosing_fn(project_root, panic.file, panic.line)
+    if enclosing:
+        candidates.append((enclosing, panic.file))
+
+    for name, file in candidates:
+        try:
+            matches = [e for e in schema_store.find_entities(project_id, name)
+                       if e.get("entity_type") in ("function", "method")]
+        except Exception as e:
+            logger.debug(f"Entity lookup failed for {name}: {e}")
+            return None
+        if not matches:
+            continue
+        file_name = Path(file).name if file else None
+        best = next((e for e in matches if e.get("file_path") and Path(e["file_path"]).name == file_name), matches[0])
+        return {k: best.get(k) for k in ("id", "name", "entity_type", "file_path")}
+    return None
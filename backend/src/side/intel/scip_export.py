@@ -0,0 +1,436 @@
+"""
+SCIP / LSIF Export - Serializes the index for code search and review tooling.
+
+Definitions and their ranges come from the per-file semantics pass (served by
+the IndexCache), `implements`/`inherits` edges from SchemaStore, and reference
+occurrences from resolved call sites. SCIP is written as protobuf with a small
+wire encoder (no protobuf runtime needed); LSIF as JSON lines.
+
+Symbols follow SCIP syntax: `side <manager> <package> . <descriptors>`, built
+from the module path, owner and name so they are stable across runs.
+"""
+
+import json
+import logging
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+from side.intel import rust_modules
+from side.intel.ids import entity_id, file_qualified_name
+from side.intel.languages import registry as languages
+
+logger = logging.getLogger(__name__)
+
+SCHEME = "side"
+TOOL_NAME = "sidelith"
+TOOL_VERSION = "1.0.0"
+MIN_REFERENCE_CONFIDENCE = 0.5   # Calls below the "unique" tier are too speculative to publish as references
+
+MANAGERS = {"rust": "cargo", "python": "pip", "typescript": "npm", "javascript": "npm", "go": "gomod"}
+DOCUMENT_LANGUAGES = {
+    "rust": "Rust", "python": "Python", "typescript": "TypeScript", "javascript": "JavaScript",
+    "go": "Go", "java": "Java", "csharp": "CSharp", "php": "PHP", "ruby": "Ruby",
+}
+
+# scip.proto SymbolInformation.Kind
+SCIP_KINDS = {
+    "class": 7, "enum": 11, "function": 17, "interface": 21, "macro": 25,
+    "method": 26, "module": 29, "struct": 49, "trait": 53, "union": 59,
+}
+TYPE_KINDS = {"class", "struct", "enum", "union", "trait", "interface"}
+ROLE_DEFINITION = 1
+UTF8 = 1
+
+IDENTIFIER_RE = re.compile(r"^[\w+$-]+$")
+
+
+@dataclass
+class Occurrence:
+    line: int                  # 0-based
+    start: int
+    end: int
+    symbol: str
+    roles: int = 0
+
+
+@dataclass
+class SymbolInfo:
+    symbol: str
+    kind: str
+    display_name: str
+    documentation: List[str] = field(default_factory=list)
+    enclosing: Optional[str] = None
+    implements: List[str] = field(default_factory=list)
+
+
+@dataclass
+class ExportDocument:
+    relative_path: str
+    language: str
+    occurrences: List[Occurrence] = field(default_factory=list)
+    symbols: List[SymbolInfo] = field(default_factory=list)
+
+
+@dataclass
+class ExportIndex:
+    project_root: str
+    documents: List[ExportDocument] = field(default_factory=list)
+    external_symbols: List[SymbolInfo] = field(default_factory=list)
+
+
+# --- Symbols ---
+
+def _escape(name: str) -> str:
+    return name if IDENTIFIER_RE.match(name) else "`" + name.replace("`", "``") + "`"
+
+
+def _namespace(module: str, language: str) -> Tuple[str, str]:
+    """(package, namespace descriptors) for a module path from call_graph.module_name."""
+    if language == "rust":
+        parts = module.split("::")
+        return parts[0], "".join(f"{_escape(p)}/" for p in parts[1:])
+    separator = "." if language == "python" else "/"
+    return "", "".join(f"{_escape(p)}/" for p in module.split(separator) if p)
+
+
+def _module(root: Path, path: Path, language: str, line: int = 0) -> str:
+    """Module of the file; for Rust, of the (possibly inline) module enclosing `line`."""
+    from side.intel.call_graph import module_name
+    try:
+        if language == "rust":
+            return rust_modules.module_path(root, path, line)
+        return module_name(root, path, language)
+    except (OSError, ValueError):
+        return path.relative_to(root).with_suffix("").as_posix()
+
+
+def _symbol(language: str, package: str, descriptors: str) -> str:
+    return f"{SCHEME} {MANAGERS.get(language, '.')} {_escape(package) if package else '.'} . {descriptors}"
+
+
+def _descriptor(name: str, kind: str, disambiguator: int = 0) -> str:
+    if kind in TYPE_KINDS:
+        return f"{_escape(name)}#"
+    if kind in ("function", "method"):
+        return f"{_escape(name)}({'+' + str(disambiguator) if disambiguator else ''})."
+    if kind == "macro":
+        return f"{_escape(name)}!"
+    if kind == "module":
+        return f"{_escape(name)}/"
+    return f"{_escape(name)}."
+
+
+def _name_range(line_text: str, name: str, call: bool = False) -> Tuple[int, int]:
+    """Columns of `name` on a line; for call sites, prefer the occurrence followed by `(`."""
+    escaped = re.escape(name)
+    m = None
+    if call:
+        m = re.search(rf"(?<![\w$]){escaped}\s*(?:::\s*)?(?:<[^>()]*>\s*)?\(", line_text)
+    m = m or re.search(rf"(?<![\w$]){escaped}(?![\w$])", line_text)
+    return (m.start(), m.start() + len(name)) if m else (0, 0)
+
+
+# --- Collection ---
+
+@dataclass
+class _Definition:
+    file: str
+    language: str
+    entity: Dict[str, Any]
+    prefix: str                 # package-level symbol prefix incl. namespaces
+    symbol: str = ""
+    qualified_name: Optional[str] = None    # As the indexer keys the entity (crate, module or file path)
+
+
+def build_export(root: Path, schema_store=None) -> ExportIndex:
+    """Collects documents, definitions, relationships and references for `root`."""
+    from side.intel.call_graph import build_call_graph, iter_source_files
+    from side.intel.index_cache import IndexCache
+    from side.intel.tree_indexer import get_file_dna
+
+    cache = IndexCache(root)
+    index = ExportIndex(project_root=root.resolve().as_uri())
+    language_names = [spec.name for spec in languages.all()]
+
+    documents: Dict[str, ExportDocument] = {}
+    sources: Dict[str, List[str]] = {}
+    definitions: List[_Definition] = []
+
+    for path in sorted(iter_source_files(root, language_names)):
+        spec = languages.for_path(path)
+        rel_path = path.relative_to(root).as_posix()
+        try:
+            dna = cache.lookup(path) or get_file_dna(path, cache)
+            text = path.read_text(errors="ignore")
+        except OSError as e:
+            logger.debug(f"Export: skipping {path}: {e}")
+            continue
+        entities = [e for e in dna.get("semantics", {}).get("entities", []) if e.get("line") and e["type"] != "impl"]
+        if not entities:
+            continue
+        documents[rel_path] = ExportDocument(rel_path, DOCUMENT_LANGUAGES.get(spec.name, spec.name))
+        sources[rel_path] = text.splitlines()
+
+        for e in entities:
+            module = _module(root, path, spec.name, e["line"])
+            package, namespaces = _namespace(module, spec.name)
+            if not package or package == "crate":
+                package = root.resolve().name
+            qualified = (rust_modules.qualify(module, e["name"], e.get("parent")) if spec.name == "rust" else None) or \
+                file_qualified_name(rel_path, e["name"], e.get("parent"))
+            definitions.append(_Definition(rel_path, spec.name, e, _symbol(spec.name, package, namespaces), qualified_name=qualified))
+
+    # Types first, so methods can hang off their type even when the impl lives in another file
+    types: Dict[Tuple[str, str], List[_Definition]] = {}
+    for d in definitions:
+        if d.entity["type"] in TYPE_KINDS:
+            d.symbol = d.prefix + _descriptor(d.entity["name"], d.entity["type"])
+            types.setdefault((d.language, d.entity["name"]), []).append(d)
+
+    def owner_symbol(d: _Definition) -> str:
+        parent = d.entity["parent"]
+        candidates = types.get((d.language, parent), [])
+        local = [t for t in candidates if t.file == d.file]
+        if local or len(candidates) == 1:
+            return (local or candidates)[0].symbol
+        return d.prefix + _descriptor(parent, "trait" if d.entity.get("parent_type") == "trait" else "struct")
+
+    seen: Dict[str, int] = {}
+    for d in definitions:
+        if d.symbol:
+            continue
+        owner = owner_symbol(d) if d.entity.get("parent") else d.prefix
+        base = owner + _descriptor(d.entity["name"], d.entity["type"])
+        count = seen.get(base, 0)
+        seen[base] = count + 1
+        d.symbol = owner + _descriptor(d.entity["name"], d.entity["type"], count)
+
+    # Declared / structural type relationships from SchemaStore
+    implements: Dict[str, List[str]] = {}
+    externals: Dict[str, SymbolInfo] = {}
+    if schema_store is not None:
+        project_id = schema_store.engine.get_project_id()
+        by_id: Dict[str, List[_Definition]] = {}
+        for d in definitions:
+            by_id.setdefault(entity_id(project_id, d.entity["name"], d.entity["type"], d.qualified_name), []).append(d)
+        for relation in ("implements", "inherits"):
+            for edge in schema_store.list_relationships(project_id=project_id, relation_type=relation):
+                origins = by_id.get(edge["source_id"], [])
+                targets = [t.symbol for t in by_id.get(edge["target_id"], [])]
+                if origins and not targets:
+                    target = schema_store.get_entity_by_id(edge["target_id"])
+                    if not target:
+                        continue
+                    symbol = _symbol(origins[0].language, "", _descriptor(target["name"], target["entity_type"]))
+                    externals.setdefault(symbol, SymbolInfo(symbol, target["entity_type"], target["name"]))
+                    targets = [symbol]
+                for origin in origins:
+                    known = implements.setdefault(origin.symbol, [])
+                    known.extend(t for t in targets if t not in known)
+
+    by_line: Dict[Tuple[str, int, str], str] = {}
+    for d in definitions:
+        entity = d.entity
+        line_text = sources[d.file][entity["line"] - 1]
+        start, end = _name_range(line_text, entity["name"])
+        doc = documents[d.file]
+        doc.occurrences.append(Occurrence(entity["line"] - 1, start, end, d.symbol, ROLE_DEFINITION))
+
+        documentation = []
+        if entity.get("signature"):
+            documentation.append(f"```{d.language}\n{entity['signature']}\n```")
+        if entity.get("doc"):
+            documentation.append(entity["doc"])
+        enclosing = owner_symbol(d) if entity.get("parent") else None
+        doc.symbols.append(SymbolInfo(d.symbol, entity["type"], entity["name"], documentation,
+                                      enclosing, implements.get(d.symbol, [])))
+        by_line[(d.file, entity["line"], entity["name"])] = d.symbol
+
+    # Reference occurrences from the resolved call graph
+    for call in build_call_graph(root).resolve():
+        if call.confidence < MIN_REFERENCE_CONFIDENCE or call.caller_file not in documents:
+            continue
+        target = by_line.get((call.target_file, call.target.line, call.target.name))
+        lines = sources[call.caller_file]
+        if not target or not 0 < call.line <= len(lines):
+            continue
+        start, end = _name_range(lines[call.line - 1], call.target.name, call=True)
+        if end:
+            documents[call.caller_file].occurrences.append(Occurrence(call.line - 1, start, end, target))
+
+    for doc in documents.values():
+        doc.occurrences.sort(key=lambda o: (o.line, o.start, -o.roles))
+    index.documents = list(documents.values())
+    index.external_symbols = list(externals.values())
+    return index
+
+
+# --- SCIP protobuf ---
+
+def _varint(value: int) -> bytes:
+    out = bytearray()
+    while True:
+        bits = value & 0x7F
+        value >>= 7
+        if value:
+            out.append(bits | 0x80)
+        else:
+            out.append(bits)
+            return bytes(out)
+
+
+def _int_field(number: int, value: int) -> bytes:
+    return _varint(number << 3) + _varint(value) if value else b""
+
+
+def _bytes_field(number: int, payload: bytes) -> bytes:
+    return _varint(number << 3 | 2) + _varint(len(payload)) + payload
+
+
+def _str_field(number: int, value: Optional[str]) -> bytes:
+    return _bytes_field(number, value.encode("utf8")) if value else b""
+
+
+def _packed_field(number: int, values: List[int]) -> bytes:
+    return _bytes_field(number, b"".join(_varint(v) for v in values))
+
+
+def _scip_symbol(info: SymbolInfo) -> bytes:
+    out = _str_field(1, info.symbol)
+    for text in info.documentation:
+        out += _str_field(3, text)
+    for target in info.implements:
+        out += _bytes_field(4, _str_field(1, target) + _int_field(3, 1))          # Relationship.is_implementation
+    out += _int_field(5, SCIP_KINDS.get(info.kind, 0))
+    out += _str_field(6, info.display_name)
+    out += _str_field(8, info.enclosing)
+    return out
+
+
+def _scip_occurrence(occ: Occurrence) -> bytes:
+    return (_packed_field(1, [occ.line, occ.start, occ.end])
+            + _str_field(2, occ.symbol)
+            + _int_field(3, occ.roles))
+
+
+def encode_scip(index: ExportIndex) -> bytes:
+    """Serializes an ExportIndex as a `scip.Index` protobuf message."""
+    tool_info = _str_field(1, TOOL_NAME) + _str_field(2, TOOL_VERSION)
+    metadata = _bytes_field(2, tool_info) + _str_field(3, index.project_root) + _int_field(4, UTF8)
+    out = _bytes_field(1, metadata)
+    for doc in index.documents:
+        payload = _str_field(1, doc.relative_path)
+        for occ in doc.occurrences:
+            payload += _bytes_field(2, _scip_occurrence(occ))
+        for info in doc.symbols:
+            payload += _bytes_field(3, _scip_symbol(info))
+        payload += _str_field(4, doc.language)
+        out += _bytes_field(2, payload)
+    for info in index.external_symbols:
+        out += _bytes_field(3, _scip_symbol(info))
+    return out
+
+
+# --- LSIF JSON lines ---
+
+def encode_lsif(index: ExportIndex) -> str:
+    """Serializes an ExportIndex as LSIF 0.5 JSON lines (definitions, references, hovers, monikers)."""
+    lines: List[str] = []
+    next_id = 0
+
+    def emit(element: str, label: str, props: Dict[str, Any]) -> int:
+        nonlocal next_id
+        next_id += 1
+        lines.append(json.dumps({"id": next_id, "type": element, "label": label, **props}))
+        return next_id
+
+    def vertex(label: str, **props) -> int:
+        return emit("vertex", label, props)
+
+    def edge(label: str, **props) -> int:
+        return emit("edge", label, props)
+
+    vertex("metaData", version="0.5.0", projectRoot=index.project_root, positionEncoding="utf-16",
+           toolInfo={"name": TOOL_NAME, "version": TOOL_VERSION})
+    project = vertex("project", kind="polyglot")
+
+    result_sets: Dict[str, int] = {}
+    definitions: Dict[str, Tuple[int, int]] = {}          # symbol -> (document, range)
+    references: Dict[str, List[Tuple[int, int]]] = {}
+    infos = {info.symbol: info for doc in index.documents for info in doc.symbols}
+    document_ids = []
+
+    def result_set(symbol: str) -> int:
+        if symbol not in result_sets:
+            result_sets[symbol] = vertex("resultSet")
+            moniker = vertex("moniker", scheme=SCHEME, identifier=symbol, kind="export")
+            edge("moniker", outV=result_sets[symbol], inV=moniker)
+            info = infos.get(symbol)
+            if info and info.documentation:
+                contents = [{"language": "markdown", "value": text} for text in info.documentation]
+                hover = vertex("hoverResult", result={"contents": contents})
+                edge("textDocument/hover", outV=result_sets[symbol], inV=hover)
+        return result_sets[symbol]
+
+    for doc in index.documents:
+        uri = f"{index.project_root.rstrip('/')}/{doc.relative_path}"
+        document = vertex("document", uri=uri, languageId=doc.language.lower())
+        document_ids.append(document)
+        ranges = []
+        for occ in doc.occurrences:
+            rng = vertex("range", start={"line": occ.line, "character": occ.start},
+                         end={"line": occ.line, "character": occ.end})
+            edge("next", outV=rng, inV=result_set(occ.symbol))
+            ranges.append(rng)
+            if occ.roles & ROLE_DEFINITION:
+                definitions[occ.symbol] = (document, rng)
+            else:
+                references.setdefault(occ.symbol, []).append((document, rng))
+        if ranges:
+            edge("contains", outV=document, inVs=ranges)
+
+    for symbol, (document, rng) in definitions.items():
+        result = vertex("definitionResult")
+        edge("textDocument/definition", outV=result_sets[symbol], inV=result)
+        edge("item", outV=result, inVs=[rng], document=document)
+    for symbol, refs in references.items():
+        result = vertex("referenceResult")
+        edge("textDocument/references", outV=result_sets[symbol], inV=result)
+        if symbol in definitions:
+            document, rng = definitions[symbol]
+            edge("item", outV=result, inVs=[rng], document=document, property="definitions")
+        by_document: Dict[int, List[int]] = {}
+        for document, rng in refs:
+            by_document.setdefault(document, []).append(rng)
+        for document, rngs in by_document.items():
+            edge("item", outV=result, inVs=rngs, document=document, property="references")
+
+    if document_ids:
+        edge("contains", outV=project, inVs=document_ids)
+    return "\n".join(lines) + "\n"
+
+
+EXPORT_FORMATS = ("scip", "lsif")
+
+
+def export_index(root: Path, schema_store, fmt: str, output: Path) -> Dict[str, int]:
+    """Writes the project index to `output` as SCIP protobuf or LSIF JSON lines."""
+    if fmt not in EXPORT_FORMATS:
+        raise ValueError(f"Unknown export format '{fmt}' (expected one of: {', '.join(EXPORT_FORMATS)})")
+    index = build_export(root, schema_store)
+    output.parent.mkdir(parents=True, exist_ok=True)
+    if fmt == "scip":
+        output.write_bytes(encode_scip(index))
+    else:
+        output.write_text(encode_lsif(index))
+
+    stats = {
+        "documents": len(index.documents),
+        "symbols": sum(len(d.symbols) for d in index.documents),
+        "references": sum(1 for d in index.documents for o in d.occurrences if not o.roles & ROLE_DEFINITION),
+        "external_symbols": len(index.external_symbols),
+    }
+    logger.info(f"📦 [EXPORT]: {fmt} -> {output} {stats}")
+    return stats
@@ -0,0 +1,521 @@
+"""
+SCIP Import - Compiler-grade definitions, references and relationships.
+
+Ingests `.scip` indexes produced locally by `rust-analyzer scip`, `scip-python`
+or `scip-typescript` and upserts them into SchemaStore with confidence 1.0 and
+origin 'scip'. These resolve what the heuristic passes cannot (trait method
+dispatch, re-exports, aliased imports):
+
+- definitions -> entities (signature/doc/parent from SymbolInformation)
+- references  -> `calls` (callable targets) or `references` edges from the enclosing definition
+- relationships (is_implementation) -> `implements` / `inherits` edges
+
+A SCIP edge supersedes heuristic edges of the same relation from the same
+source, both at import time and in later heuristic rebuilds (`drop_superseded`).
+"""
+
+import logging
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+from urllib.parse import unquote, urlparse
+
+from side.intel.ids import entity_id, file_qualified_name, relationship_id
+from side.intel.languages import registry as languages
+
+logger = logging.getLogger(__name__)
+
+ORIGIN = "scip"
+PRECISE = 1.0
+ROLE_DEFINITION = 1
+IMPORTED_RELATIONS = ("calls", "references", "implements", "inherits")
+
+# scip.proto SymbolInformation.Kind -> entity type (kinds not listed are not indexed)
+SCIP_KIND_TYPES = {
+    7: "class", 9: "method", 11: "enum", 17: "function", 21: "interface", 25: "macro",
+    26: "method", 29: "module", 30: "module", 49: "struct", 53: "trait", 59: "union",
+    66: "method", 67: "method", 68: "method", 69: "method", 70: "method", 71: "method",
+    80: "method",
+}
+TYPE_TYPES = {"class", "struct", "enum", "union", "trait", "interface"}
+ABSTRACT_TYPES = {"trait", "interface"}
+CALLABLE_TYPES = {"function", "method"}
+
+NAME_RE = re.compile(r"[\w+$-]+")
+
+
+# --- Protobuf wire decoding ---
+
+def _fields(data: bytes) -> Dict[int, List[Any]]:
+    """Field number -> raw values (ints for varints, bytes for length-delimited)."""
+    fields: Dict[int, List[Any]] = {}
+    pos = 0
+
+    def varint() -> int:
+        nonlocal pos
+        shift = value = 0
+        while True:
+            byte = data[pos]
+            pos += 1
+            value |= (byte & 0x7F) << shift
+            shift += 7
+            if not byte & 0x80:
+                return value
+
+    while pos < len(data):
+        key = varint()
+        number, wire = key >> 3, key & 7
+        if wire == 0:
+            value = varint()
+        elif wire == 2:
+            length = varint()
+            value = data[pos:pos + length]
+            pos += length
+        elif wire == 1:
+            value, pos = data[pos:pos + 8], pos + 8
+        elif wire == 5:
+            value, pos = data[pos:pos + 4], pos + 4
+        else:
+            raise ValueError(f"Unsupported protobuf wire type {wire}")
+        fields.setdefault(number, []).append(value)
+    return fields
+
+
+def _text(fields: Dict[int, List[Any]], number: int) -> str:
+    values = fields.get(number)
+    return values[0].decode("utf8", errors="replace") if values else ""
+
+
+def _texts(fields: Dict[int, List[Any]], number: int) -> List[str]:
+    return [v.decode("utf8", errors="replace") for v in fields.get(number, [])]
+
+
+def _int(fields: Dict[int, List[Any]], number: int) -> int:
+    values = fields.get(number)
+    return values[0] if values and isinstance(values[0], int) else 0
+
+
+def _ints(fields: Dict[int, List[Any]], number: int) -> List[int]:
+    """Repeated int32, packed or not."""
+    out = []
+    for value in fields.get(number, []):
+        if isinstance(value, int):
+            out.append(value)
+        else:
+            out.extend(_packed_ints(value))
+    return out
+
+
+def _packed_ints(data: bytes) -> Iterable[int]:
+    pos = 0
+    while pos < len(data):
+        shift = value = 0
+        while True:
+            byte = data[pos]
+            pos += 1
+            value |= (byte & 0x7F) << shift
+            shift += 7
+            if not byte & 0x80:
+                break
+        yield value
+
+
+@dataclass
+class ScipRelationship:
+    symbol: str
+    is_implementation: bool = False
+    is_reference: bool = False
+
+
+@dataclass
+class ScipSymbol:
+    symbol: str
+    kind: int = 0
+    display_name: str = ""
+    documentation: List[str] = field(default_factory=list)
+    signature: str = ""
+    enclosing: str = ""
+    relationships: List[ScipRelationship] = field(default_factory=list)
+
+
+@dataclass
+class ScipOccurrence:
+    symbol: str
+    line: int                              # 0-based
+    roles: int = 0
+    enclosing: Optional[Tuple[int, int]] = None   # 0-based (start line, end line) of the definition body
+
+
+@dataclass
+class ScipDocument:
+    path: str
+    language: str = ""
+    occurrences: List[ScipOccurrence] = field(default_factory=list)
+    symbols: List[ScipSymbol] = field(default_factory=list)
+
+
+@dataclass
+class ScipIndex:
+    project_root: str = ""
+    tool: str = ""
+    documents: List[ScipDocument] = field(default_factory=list)
+    external_symbols: List[ScipSymbol] = field(default_factory=list)
+
+
+def _line_span(range_: List[int]) -> Optional[Tuple[int, int]]:
+    if len(range_) == 3:
+        return range_[0], range_[0]
+    if len(range_) == 4:
+        return range_[0], range_[2]
+    return None
+
+
+def _symbol_info(data: bytes) -> ScipSymbol:
+    f = _fields(data)
+    signature = ""
+    if 7 in f:
+        signature = _text(_fields(f[7][0]), 5)   # signature_documentation.text
+    relationships = []
+    for raw in f.get(4, []):
+        r = _fields(raw)
+        relationships.append(ScipRelationship(_text(r, 1), bool(_int(r, 3)), bool(_int(r, 2))))
+    return ScipSymbol(_text(f, 1), _int(f, 5), _text(f, 6), _texts(f, 3), signature, _text(f, 8), relationships)
+
+
+def parse_scip(data: bytes) -> ScipIndex:
+    """Decodes a `scip.Index` protobuf message."""
+    f = _fields(data)
+    index = ScipIndex()
+    if 1 in f:
+        metadata = _fields(f[1][0])
+        index.project_root = _text(metadata, 3)
+        if 2 in metadata:
+            index.tool = _text(_fields(metadata[2][0]), 1)
+    for raw in f.get(2, []):
+        d = _fields(raw)
+        doc = ScipDocument(_text(d, 1), _text(d, 4).lower())
+        for occ_raw in d.get(2, []):
+            o = _fields(occ_raw)
+            span = _line_span(_ints(o, 1))
+            if span is None:
+                continue
+            doc.occurrences.append(ScipOccurrence(_text(o, 2), span[0], _int(o, 3), _line_span(_ints(o, 7))))
+        doc.symbols = [_symbol_info(raw) for raw in d.get(3, [])]
+        index.documents.append(doc)
+    index.external_symbols = [_symbol_info(raw) for raw in f.get(3, [])]
+    return index
+
+
+# --- Symbol syntax ---
+
+@dataclass
+class Descriptor:
+    name: str
+    suffix: str        # 'namespace' | 'type' | 'term' | 'method' | 'macro' | 'meta' | 'parameter' | 'type_parameter'
+    end: int           # Offset just past this descriptor in the descriptor text
+
+
+def _read_name(text: str, i: int) -> Tuple[str, int]:
+    if text.startswith("`", i):
+        out, i = [], i + 1
+        while i < len(text):
+            if text[i] == "`":
+                if text.startswith("``", i):
+                    out.append("`")
+                    i += 2
+                    continue
+                return "".join(out), i + 1
+            out.append(text[i])
+            i += 1
+        return "".join(out), i
+    m = NAME_RE.match(text, i)
+    return (m.group(0), m.end()) if m else ("", i)
+
+
+SUFFIXES = {"/": "namespace", "#": "type", ".": "term", ":": "meta", "!": "macro"}
+
+
+def parse_symbol(symbol: str) -> Optional[Tuple[str, str, List[Descriptor]]]:
+    """(header incl. trailing space, descriptor text, descriptors); None for local symbols."""
+    if not symbol or symbol.startswith("local "):
+        return None
+    fields, i = 0, 0
+    while fields < 4 and i < len(symbol):
+        if symbol[i] == " ":
+            if symbol.startswith("  ", i):
+                i += 2                      # Escaped space inside a header field
+                continue
+            fields += 1
+        i += 1
+    header, text = symbol[:i], symbol[i:]
+
+    descriptors, pos = [], 0
+    while pos < len(text):
+        if text[pos] in "[(":
+            close = text.find("]" if text[pos] == "[" else ")", pos)
+            if close == -1:
+                break
+            suffix = "type_parameter" if text[pos] == "[" else "parameter"
+            descriptors.append(Descriptor(text[pos + 1:close], suffix, close + 1))
+            pos = close + 1
+            continue
+        name, pos = _read_name(text, pos)
+        if pos >= len(text):
+            break
+        if text[pos] == "(":
+            close = text.find(")", pos)
+            if close == -1:
+                break
+            pos = close + 1 + (text[close + 1:close + 2] == ".")
+            descriptors.append(Descriptor(name, "method", pos))
+        elif text[pos] in SUFFIXES:
+            pos += 1
+            descriptors.append(Descriptor(name, SUFFIXES[text[pos - 1]], pos))
+        else:
+            break
+    return header, text, descriptors
+
+
+def _entity_type(descriptors: List[Descriptor], info: Optional[ScipSymbol], language: str) -> Optional[str]:
+    if info and info.kind:
+        return SCIP_KIND_TYPES.get(info.kind)
+    if not descriptors:
+        return None
+    last = descriptors[-1]
+    if last.suffix == "type":
+        return "struct" if language == "rust" else "class"
+    if last.suffix == "method":
+        return "method" if len(descriptors) > 1 and descriptors[-2].suffix == "type" else "function"
+    if last.suffix == "macro":
+        return "macro"
+    if last.suffix == "namespace":
+        return "module"
+    return None
+
+
+def _split_docs(info: Optional[ScipSymbol]) -> Tuple[Optional[str], Optional[str]]:
+    """(signature, doc): a leading fenced block is the signature, the rest is prose."""
+    if not info:
+        return None, None
+    docs = list(info.documentation)
+    signature = info.signature or None
+    if docs and docs[0].lstrip().startswith("```"):
+        block = docs.pop(0).strip().strip("`")
+        block = block.split("\n", 1)[1] if "\n" in block else block   # Drop the language tag
+        signature = signature or block.strip() or None
+    doc = "\n\n".join(d.strip() for d in docs if d.strip())
+    return signature, doc or None
+
+
+def _qualified_name(header: str, descriptors: List[Descriptor]) -> Optional[str]:
+    """`shop::repo::Store::get` for rust-analyzer's `... shop 0.1.0 repo/impl#[Store][Repository]get().`"""
+    fields = header.replace("  ", "\0").split()
+    if len(fields) < 3 or not descriptors:
+        return None
+    parts = [fields[2].replace("\0", " ").replace("-", "_")]
+    for i, d in enumerate(descriptors):
+        if d.suffix == "type" and d.name == "impl" and i + 1 < len(descriptors) \
+                and descriptors[i + 1].suffix == "type_parameter":
+            parts.append(descriptors[i + 1].name)       # Methods of an impl block hang off its self type
+        elif d.suffix in ("namespace", "type", "term", "method", "macro"):
+            parts.append(d.name)
+    return "::".join(parts)
+
+
+def _path_prefix(root: Path, project_root: str) -> str:
+    """Where the SCIP project root sits inside `root` (indexers may run in a sub-crate/package)."""
+    if not project_root:
+        return ""
+    parsed = urlparse(project_root)
+    path = Path(unquote(parsed.path)) if parsed.scheme == "file" else Path(project_root)
+    try:
+        rel = path.resolve().relative_to(root.resolve()).as_posix()
+    except (ValueError, OSError):
+        return ""
+    return "" if rel == "." else f"{rel}/"
+
+
+# --- Import ---
+
+class _Importer:
+    def __init__(self, root: Path, index: ScipIndex, project_id: str):
+        self.root = root
+        self.index = index
+        self.project_id = project_id
+        self.prefix = _path_prefix(root, index.project_root)
+        self.infos: Dict[str, ScipSymbol] = {s.symbol: s for s in index.external_symbols}
+        for doc in index.documents:
+            self.infos.update((s.symbol, s) for s in doc.symbols)
+        self.entities: Dict[str, Dict[str, Any]] = {}      # entity id -> record
+        self.defined: Dict[str, Dict[str, Any]] = {}       # symbol -> record (defined in this index)
+        self.edges: Dict[str, Dict[str, Any]] = {}
+        self.covered: set = set()                           # entity ids whose edges this index owns
+
+    def language(self, doc: ScipDocument) -> str:
+        spec = languages.for_path(doc.path)
+        return spec.name if spec else doc.language
+
+    def entity(self, symbol: str, language: str, file_path: Optional[str]) -> Optional[Dict[str, Any]]:
+        parsed = parse_symbol(symbol)
+        if not parsed:
+            return None
+        header, text, descriptors = parsed
+        info = self.infos.get(symbol)
+        entity_type = _entity_type(descriptors, info, language)
+        name = (info.display_name if info else "") or (descriptors[-1].name if descriptors else "")
+        if not entity_type or not name:
+            return None
+        signature, doc = _split_docs(info)
+
+        parent = info.enclosing if info and info.enclosing else None
+        if not parent and len(descriptors) > 1 and descriptors[-2].suffix == "type":
+            parent = header + text[:descriptors[-2].end]
+
+        # Project items share the indexer's qualified ids; external ones stay name-keyed like its std targets
+        qualified = None
+        if file_path and language == "rust":
+            qualified = _qualified_name(header, descriptors)
+        elif file_path:
+            owner = descriptors[-2].name if len(descriptors) > 1 and descriptors[-2].suffix == "type" else None
+            qualified = file_qualified_name(file_path, name, owner)
+        ent_id = entity_id(self.project_id, name, entity_type, qualified)
+        record = self.entities.setdefault(ent_id, {
+            "id": ent_id, "project_id": self.project_id, "name": name, "entity_type": entity_type,
+            "file_path": file_path, "signature": signature, "doc": doc, "qualified_name": qualified,
+            "parent_symbol": parent, "language": language,
+        })
+        if file_path and not record["file_path"]:
+            record["file_path"] = file_path
+        return record
+
+    def file_entity(self, path: str) -> str:
+        ent_id = entity_id(self.project_id, path, "file")
+        self.entities.setdefault(ent_id, {"id": ent_id, "project_id": self.project_id, "name": path,
+                                          "entity_type": "file", "file_path": path})
+        return ent_id
+
+    def edge(self, source_id: str, target_id: str, relation: str) -> None:
+        if source_id == target_id:
+            return
+        edge_id = relationship_id(source_id, target_id, relation)
+        self.edges.setdefault(edge_id, {
+            "id": edge_id, "project_id": self.project_id, "source_id": source_id, "target_id": target_id,
+            "relation_type": relation, "confidence": PRECISE, "origin": ORIGIN,
+        })
+
+    def heuristic_spans(self, path: str) -> List[Tuple[int, int, str]]:
+        """(start, end, name) of function bodies from the heuristic extractor, 0-based lines."""
+        from side.intel.call_graph import extract_file_symbols
+
+        file = self.root / path
+        if not file.is_file():
+            return []
+        try:
+            symbols = extract_file_symbols(file, file.read_text(errors="ignore"), path, "")
+        except (OSError, ValueError):
+            return []
+        return [(d.line - 1, d.end_line - 1, d.name) for d in symbols.definitions] if symbols else []
+
+    def run(self) -> Dict[str, int]:
+        # Pass 1: every definition, so references can point across documents
+        spans: Dict[str, List[Tuple[int, int, str]]] = {}
+        for doc in self.index.documents:
+            path = self.prefix + doc.path
+            language = self.language(doc)
+            self.covered.add(self.file_entity(path))
+            doc_spans, defs_by_line = [], {}
+            for occ in doc.occurrences:
+                if not occ.roles & ROLE_DEFINITION:
+                    continue
+                record = self.entity(occ.symbol, language, path)
+                if not record:
+                    continue
+                self.defined[occ.symbol] = record
+                self.covered.add(record["id"])
+                defs_by_line[(occ.line, record["name"])] = record["id"]
+                if occ.enclosing and record["entity_type"] in CALLABLE_TYPES:
+                    doc_spans.append((occ.enclosing[0], occ.enclosing[1], record["id"]))
+            if not doc_spans:
+                # Older indexers omit enclosing_range: borrow body spans from the heuristic extractor
+                for start, end, name in self.heuristic_spans(path):
+                    if (start, name) in defs_by_line:
+                        doc_spans.append((start, end, defs_by_line[(start, name)]))
+            spans[path] = doc_spans
+
+        # Pass 2: references from the innermost enclosing definition (or the file itself)
+        references = 0
+        for doc in self.index.documents:
+            path = self.prefix + doc.path
+            file_id = self.file_entity(path)
+            for occ in doc.occurrences:
+                target = self.defined.get(occ.symbol)
+                if occ.roles & ROLE_DEFINITION or target is None:
+                    continue
+                inside = [s for s in spans[path] if s[0] <= occ.line <= s[1]]
+                source_id = max(inside, key=lambda s: s[0])[2] if inside else file_id
+                relation = "calls" if target["entity_type"] in CALLABLE_TYPES else "references"
+                self.edge(source_id, target["id"], relation)
+                references += 1
+
+        # Implementation relationships; external targets (std traits, base classes) get entities too
+        for doc in self.index.documents:
+            language = self.language(doc)
+            for info in doc.symbols:
+                source = self.defined.get(info.symbol)
+                if not source:
+                    continue
+                for rel in info.relationships:
+                    if not rel.is_implementation:
+                        continue
+                    target = self.defined.get(rel.symbol) or self.entity(rel.symbol, language, None)
+                    if not target:
+                        continue
+                    abstract = source["entity_type"] in ABSTRACT_TYPES and target["entity_type"] in ABSTRACT_TYPES
+                    classes = source["entity_type"] == target["entity_type"] == "class"
+                    self.edge(source["id"], target["id"], "inherits" if abstract or classes else "implements")
+
+        return {"documents": len(self.index.documents), "definitions": len(self.defined),
+                "references": references}
+
+    def records(self) -> List[Dict[str, Any]]:
+        """Entities with parent_id resolved, owners before members."""
+        out = []
+        for record in self.entities.values():
+            entity = {k: v for k, v in record.items() if k not in ("parent_symbol", "language")}
+            parent = self.defined.get(record.get("parent_symbol") or "")
+            entity["parent_id"] = parent["id"] if parent and parent["entity_type"] in TYPE_TYPES else None
+            out.append(entity)
+        out.sort(key=lambda e: e["parent_id"] is not None)
+        return out
+
+
+def import_scip(root: Path, scip_path: Path, schema_store) -> Dict[str, int]:
+    """Upserts a SCIP index into SchemaStore, replacing heuristic edges for the symbols it covers."""
+    index = parse_scip(scip_path.read_bytes())
+    project_id = schema_store.engine.get_project_id()
+    importer = _Importer(root, index, project_id)
+    stats = importer.run()
+    edges = list(importer.edges.values())
+
+    schema_store.save_entities_batch(importer.records())
+    # A re-import owns everything previously imported for these sources...
+    schema_store.delete_relationships(project_id, origin=ORIGIN, source_ids=sorted(importer.covered))
+    # ...and supersedes heuristic edges of each relation it now knows precisely
+    for relation in IMPORTED_RELATIONS:
+        sources = sorted({e["source_id"] for e in edges if e["relation_type"] == relation})
+        schema_store.delete_relationships(project_id, relation_type=relation, origin="index", source_ids=sources)
+    schema_store.save_relationships_batch(edges)
+
+    stats.update({"edges": len(edges), "tool": index.tool or "unknown"})
+    logger.info(f"🎯 [SCIP IMPORT]: {scip_path.name} {stats}")
+    return stats
+
+
+def drop_superseded(schema_store, project_id: str, edges: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """Heuristic edges whose source already has imported SCIP edges of the same relation."""
+    precise = {(r["source_id"], r["relation_type"])
+               for r in schema_store.list_relationships(project_id=project_id, origin=ORIGIN)}
+    if not precise:
+        return edges
+    return [e for e in edges if (e["source_id"], e["relation_type"]) not in precise]
@@ -0,0 +1,199 @@
+"""
+Test Links - Which tests exercise which code.
+
+Tests are discovered per language: Rust functions under a `#[test]`-style
+attribute (`#[tokio::test]`, `#[rstest]`, ...), pytest `test_*` functions and
+`Test*` class methods in test files, and vitest/jest `it()`/`test()` blocks
+(named by their description, nested under `describe()`). The call-graph pass
+already resolves what every function calls; calls made inside a test become
+`tests` edges from the test entity to the code it calls, so "which tests cover
+`Order::add_item`" is a walk back over `tests` and `calls` edges.
+"""
+
+import logging
+import re
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Set, Tuple
+
+from side.intel.call_graph import (
+    CallGraph, Definition, FileSymbols, affected_paths, build_call_graph, definition_id, relative_paths,
+    update_call_graph,
+)
+from side.intel.ids import entity_id, file_qualified_name, relationship_id
+from side.intel.lexing import closing_paren, line_index, strip_ts_noise
+
+logger = logging.getLogger(__name__)
+
+RUST_TEST_ATTR_RE = re.compile(r"#\[\s*(?:[\w:]+::)?(?:test|rstest)\b")
+PYTEST_FILE_RE = re.compile(r"(?:^|/)(?:test_[^/]*|[^/]*_test)\.py$")
+JS_TEST_RE = re.compile(r"\b(?P<kind>describe|it|test)(?:\.(?:only|skip|todo|concurrent))?\s*\(\s*(?P<quote>['\"`])")
+JS_TEST_FILE_RE = re.compile(r"(?:\.(?:test|spec)\.[cm]?[jt]sx?$|(?:^|/)__tests__/)")
+
+
+@dataclass
+class DiscoveredTest:
+    name: str                       # Function name, or `describe > it` description for JS blocks
+    file: str
+    framework: str                  # 'cargo', 'pytest', 'vitest'
+    line: int
+    end_line: int
+    definition: Optional[Definition] = None     # Rust/pytest tests are ordinary functions
+
+
+# --- Discovery ---
+
+def _rust_tests(file: FileSymbols, content: str) -> List[DiscoveredTest]:
+    lines = content.splitlines()
+    found = []
+    for d in file.definitions:
+        attrs = []
+        i = d.line - 2
+        while i >= 0 and len(attrs) < 8:
+            text = lines[i].strip()
+            if not text or text.endswith(("}", ";", "{")) and not text.startswith("#["):
+                break
+            attrs.insert(0, text)
+            i -= 1
+        if RUST_TEST_ATTR_RE.search("\n".join(attrs)):
+            found.append(DiscoveredTest(d.name, file.path, "cargo", d.line, d.end_line, d))
+    return found
+
+
+def _pytest_tests(file: FileSymbols) -> List[DiscoveredTest]:
+    if not PYTEST_FILE_RE.search(file.path):
+        return []
+    return [
+        DiscoveredTest(d.name, file.path, "pytest", d.line, d.end_line, d)
+        for d in file.definitions
+        if d.name.startswith("test") and (d.owner is None or d.owner.startswith("Test"))
+    ]
+
+
+def _js_tests(file: FileSymbols, content: str) -> List[DiscoveredTest]:
+    if not JS_TEST_FILE_RE.search(file.path):
+        return []
+    code = strip_ts_noise(content)
+    line_of = line_index(code)
+    blocks = []  # (start, end, kind, description)
+    for m in JS_TEST_RE.finditer(code):
+        quote_at = m.end("quote") - 1
+        close_quote = code.find(m.group("quote"), quote_at + 1)
+        if close_quote == -1:
+            continue
+        open_paren = code.index("(", m.end("kind"))
+        blocks.append((m.start(), closing_paren(code, open_paren), m.group("kind"),
+                       " ".join(content[quote_at + 1:close_quote].split())))
+
+    found = []
+    for start, end, kind, description in blocks:
+        if kind == "describe":
+            continue
+        suites = [b[3] for b in blocks if b[2] == "describe" and b[0] < start <= b[1]]
+        found.append(DiscoveredTest(" > ".join(suites + [description]), file.path, "vitest",
+                              line_of(start), line_of(end)))
+    return found
+
+
+def discover_tests(root: Path, graph: CallGraph, paths: Optional[Set[str]] = None) -> List[DiscoveredTest]:
+    """Test cases in every file of `graph` (or only those in `paths`), in file order."""
+    tests = []
+    for file in graph.files.values():
+        if paths is not None and file.path not in paths:
+            continue
+        if file.language == "python":
+            tests.extend(_pytest_tests(file))
+            continue
+        try:
+            content = (root / file.path).read_text(errors="ignore")
+        except OSError:
+            continue
+        tests.extend(_rust_tests(file, content) if file.language == "rust" else _js_tests(file, content))
+    return tests
+
+
+# --- Persistence ---
+
+def discovered_test_id(graph: CallGraph, test: DiscoveredTest, project_id: str) -> str:
+    """Rust/pytest tests share their function's entity; JS blocks are `test` entities keyed by file and name."""
+    if test.definition:
+        return definition_id(graph, test.definition, test.file, project_id)
+    return entity_id(project_id, test.name, "test", file_qualified_name(test.file, test.name))
+
+
+def test_link_records(graph: CallGraph, tests: List[DiscoveredTest], project_id: str,
+                     paths: Optional[Set[str]] = None) -> Tuple[List[Dict[str, Any]], List[Dict[str, Any]]]:
+    """Entities for JS test blocks and `tests` edges from every test (in `paths`) to what it calls."""
+    by_definition = {id(t.definition): t for t in tests if t.definition}
+    blocks: Dict[str, List[DiscoveredTest]] = {}
+    for t in tests:
+        if not t.definition:
+            blocks.setdefault(t.file, []).append(t)
+
+    entities: Dict[str, Dict[str, Any]] = {}
+
+    def test_id(t: DiscoveredTest) -> str:
+        ent_id = discovered_test_id(graph, t, project_id)
+        if not t.definition:
+            entities.setdefault(ent_id, {
+                "id": ent_id,
+                "project_id": project_id,
+                "name": t.name,
+                "entity_type": "test",
+                "file_path": t.file,
+                "qualified_name": file_qualified_name(t.file, t.name),
+            })
+        return ent_id
+
+    edges: Dict[Tuple[str, str], Dict[str, Any]] = {}
+    for call in graph.resolve(paths):
+        test = by_definition.get(id(call.caller)) if call.caller else None
+        if test is None:
+            # it() callbacks are not definitions: their calls belong to the innermost enclosing block
+            inside = [t for t in blocks.get(call.caller_file, []) if t.line <= call.line <= t.end_line]
+            test = max(inside, key=lambda t: t.line) if inside else None
+        if test is None:
+            continue
+        source_id = test_id(test)
+        target_id = definition_id(graph, call.target, call.target_file, project_id)
+        if source_id == target_id:
+            continue
+        edge = edges.get((source_id, target_id))
+        if edge is None or edge["confidence"] < call.confidence:
+            edges[(source_id, target_id)] = {
+                "id": relationship_id(source_id, target_id, "tests"),
+                "project_id": project_id,
+                "source_id": source_id,
+                "target_id": target_id,
+                "relation_type": "tests",
+                "confidence": call.confidence,
+            }
+    return list(entities.values()), list(edges.values())
+
+
+def index_test_links(root: Path, schema_store, changed: Optional[List[Path]] = None) -> Dict[str, int]:
+    """
+    Rebuilds the project's `tests` edges (and JS test block entities) from the call graph.
+    With `changed` files, only the tests they contain, or that call into them, are relinked.
+    """
+    project_id = schema_store.engine.get_project_id()
+    paths = sources = None
+    if changed is None:
+        graph = build_call_graph(root)
+    else:
+        graph = update_call_graph(root, changed)
+        paths = affected_paths(graph, schema_store, project_id, relative_paths(root, changed), "tests")
+        sources = [e["id"] for e in schema_store.list_entities_in_files(project_id, sorted(paths))]
+    tests = discover_tests(root, graph, paths)
+    entities, edges = test_link_records(graph, tests, project_id, paths)
+
+    if paths is not None:
+        sources += [discovered_test_id(graph, t, project_id) for t in tests]
+        schema_store.delete_entities(project_id, "test", sorted(paths))   # Renamed or removed blocks
+    schema_store.save_entities_batch(entities)
+    schema_store.delete_relationships(project_id, relation_type="tests", origin="index", source_ids=sources)
+    schema_store.save_relationships_batch(edges)
+
+    stats = {"tests": len(tests), "covered": len({e["target_id"] for e in edges}), "edges": len(edges)}
+    logger.info(f"🧪 [TEST MAP]: {stats}")
+    return stats
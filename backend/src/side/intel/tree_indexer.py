@@ -8,53 +8,37 @@ Architectural Principles:
 3. Zero-Trust Localism: All telemetry remains within the Merkle root.
 """
 
+import ast
 import os
 import json
 import hashlib
 import re
 import logging
+import time
 from pathlib import Path
-from typing import Dict, Any, List
+from typing import Dict, Any, List, Optional, Set, Tuple
 from concurrent.futures import ThreadPoolExecutor
-from side.utils.crypto import shield
-
-# --- PERFORMANCE CONFIG ---
-# Pre-compiled regex for micro-second parsing
-REGEX_STRUCTURAL = {
-    "python": {
-        "class": re.compile(r'^class\s+(\w+)', re.MULTILINE),
-        "def": re.compile(r'^def\s+(\w+)', re.MULTILINE)
-    },
-    "javascript": {
-        "class": re.compile(r'\bclass\s+(\w+)', re.MULTILINE),
-        "def": re.compile(r'\bfunction\s+(\w+)', re.MULTILINE)
-    },
-    "ruby": {
-        "class": re.compile(r'^class\s+(\w+)', re.MULTILINE),
-        "def": re.compile(r'^def\s+(\w+)', re.MULTILINE)
-    },
-    "php": {
-        "class": re.compile(r'\bclass\s+(\w+)', re.MULTILINE),
-        "def": re.compile(r'\bfunction\s+(\w+)', re.MULTILINE)
-    },
-    "go": {
-        "class": re.compile(r'^type\s+(\w+)\s+struct', re.MULTILINE),
-        "def": re.compile(r'^func\s+(?:\([^)]*\)\s+)?(\w+)', re.MULTILINE)
-    },
-    "dotnet": {
-        "class": re.compile(r'\b(?:class|struct|interface)\s+(\w+)', re.MULTILINE),
-        "def": re.compile(r'\b(?:public|private|protected|internal|static)?\s+(?:\w+)\s+(\w+)\s*\([^)]*\)\s*{', re.MULTILINE)
-    },
-    "swift": {
-        "class": re.compile(r'\b(?:class|struct|enum|protocol)\s+(\w+)', re.MULTILINE),
-        "def": re.compile(r'\bfunc\s+(\w+)', re.MULTILINE)
-    },
-    "kotlin": {
-        "class": re.compile(r'\b(?:class|interface|object)\s+(\w+)', re.MULTILINE),
-        "def": re.compile(r'\bfun\s+(\w+)', re.MULTILINE)
-    }
+from side.intel import rust_cfg, rust_modules
+from side.intel.languages import registry as languages, RUST_REGEX
+from side.intel.declarations import annotate_entities
+from side.intel.ids import entity_id, file_qualified_name
+from side.intel.index_cache import IndexCache
+from side.intel.index_store import open_index_store
+from side.intel.lexing import rust_type_name, split_rust_impl
+
+# Rust item patterns for the line-based fallback (keeps impl/trait ownership of methods)
+RUST_ITEMS = {
+    "type": re.compile(r'^\s*(?:pub(?:\([^)]*\))?\s+)?(struct|enum|union)\s+(\w+)'),
+    "trait": re.compile(r'^\s*(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?(?:auto\s+)?trait\s+(\w+)'),
+    "impl": re.compile(r'^\s*(?:unsafe\s+)?impl\b([^{;]*)'),
+    "fn": RUST_REGEX["def"],
+    "macro": re.compile(r'^\s*macro_rules!\s*(\w+)'),
+    "module": re.compile(r'^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)'),
 }
 
+# Entity types this pass stores per file (other passes own 'route', 'test', 'interface', 'crate', ...)
+ITEM_TYPES = {"class", "struct", "enum", "union", "trait", "impl", "function", "method", "macro", "module"}
+
 # Signals to watch for (Technology Detection)
 # These allow the indexer to 'smell' the architecture of a file without deep parsing.
 SIGNALS = {
@@ -109,6 +93,9 @@ ALLOWED_FILES = {
     "pyproject.toml", "Dockerfile", "go.mod", "Gemfile", "composer.json"
 }
 
+# Below this many files to parse, process start-up costs more than the GIL does
+PROCESS_POOL_MIN_FILES = 256
+
 try:
     from tree_sitter_languages import get_language, get_parser
     TS_AVAILABLE = True
@@ -119,6 +106,124 @@ from side.services.ignore import ProjectIgnore
 
 logger = logging.getLogger(__name__)
 
+def _rust_owner(name_node):
+    """Returns (owner, kind) for a Rust fn node inside an impl or trait; kind is 'impl', 'trait_impl' or 'trait'."""
+    fn_node = name_node.parent
+    body = fn_node.parent if fn_node else None
+    if body is None or body.type != "declaration_list" or body.parent is None:
+        return None, None
+    item = body.parent
+    if item.type == "impl_item":
+        target = item.child_by_field_name("type")
+        kind = "trait_impl" if item.child_by_field_name("trait") else "impl"
+        return (rust_type_name(target.text.decode("utf8")), kind) if target else (None, None)
+    if item.type == "trait_item":
+        name = item.child_by_field_name("name")
+        return (name.text.decode("utf8"), "trait") if name else (None, None)
+    return None, None
+
+def _python_owner(name_node) -> str | None:
+    """Returns the class that directly owns a Python function node, if any."""
+    fn_node = name_node.parent
+    if fn_node and fn_node.parent is not None and fn_node.parent.type == "decorated_definition":
+        fn_node = fn_node.parent
+    body = fn_node.parent if fn_node else None
+    if body is None or body.type != "block" or body.parent is None or body.parent.type != "class_definition":
+        return None
+    name = body.parent.child_by_field_name("name")
+    return name.text.decode("utf8") if name else None
+
+def _method(name: str, line: int, owner: str, owner_kind: str) -> Dict[str, Any]:
+    """Method entity linked to its owner (impl target / class stored as 'impl'/'class', trait as 'trait')."""
+    parent_type = {"trait": "trait", "class": "class"}.get(owner_kind, "impl")
+    return {"name": name, "type": "method", "line": line, "parent": owner,
+            "parent_type": parent_type, "owner_kind": owner_kind}
+
+def _scan_rust_items(content: str) -> List[Dict[str, Any]]:
+    """
+    Line-based Rust item scan for when tree-sitter is unavailable.
+    Tracks brace depth so methods are attached to their impl target or trait.
+    """
+    entities = []
+    owners = []  # (owner name, owner kind, brace depth of its body)
+    pending_owner = None
+    depth = 0
+
+    for lineno, line in enumerate(content.splitlines(), 1):
+        code = line.split("//", 1)[0]
+        in_owner_body = bool(owners) and owners[-1][2] == depth
+
+        if m := RUST_ITEMS["type"].match(code):
+            entities.append({"name": m.group(2), "type": m.group(1), "line": lineno})
+        elif m := RUST_ITEMS["trait"].match(code):
+            entities.append({"name": m.group(1), "type": "trait", "line": lineno})
+            pending_owner = (m.group(1), "trait")
+        elif m := RUST_ITEMS["impl"].match(code):
+            trait, target = split_rust_impl(m.group(1))
+            if target:
+                entities.append({"name": target, "type": "impl", "line": lineno})
+                pending_owner = (target, "trait_impl" if trait else "impl")
+        elif m := RUST_ITEMS["fn"].match(code):
+            if in_owner_body:
+                entities.append(_method(m.group(1), lineno, owners[-1][0], owners[-1][1]))
+            else:
+                entities.append({"name": m.group(1), "type": "function", "line": lineno})
+        elif m := RUST_ITEMS["macro"].match(code):
+            entities.append({"name": m.group(1), "type": "macro", "line": lineno})
+        elif m := RUST_ITEMS["module"].match(code):
+            entities.append({"name": m.group(1), "type": "module", "line": lineno})
+
+        opens, closes = code.count("{"), code.count("}")
+        if pending_owner and opens:
+            owners.append((*pending_owner, depth + 1))
+            pending_owner = None
+        depth += opens - closes
+        while owners and depth < owners[-1][2]:
+            owners.pop()
+
+    return entities
+
+def _scan_python_items(content: str) -> List[Dict[str, Any]]:
+    """Classes, functions and methods (attached to their class) via the stdlib parser."""
+    try:
+        tree = ast.parse(content)
+    except (SyntaxError, ValueError):
+        return []
+    entities = []
+
+    def visit(body, owner):
+        for node in body:
+            if isinstance(node, ast.ClassDef):
+                entities.append({"name": node.name, "type": "class", "line": node.lineno})
+                visit(node.body, node.name)
+            elif isinstance(node, (ast.FunctionDef, ast.AsyncFunctionDef)):
+                if owner:
+                    entities.append(_method(node.name, node.lineno, owner, "class"))
+                else:
+                    entities.append({"name": node.name, "type": "function", "line": node.lineno})
+            elif isinstance(node, (ast.If, ast.Try)):
+                visit(node.body + node.orelse, owner)
+
+    visit(tree.body, None)
+    return entities
+
+def _scan_ts_items(path: Path, content: str) -> List[Dict[str, Any]]:
+    """Classes, functions and class members for JS/TS, reusing the call graph's brace-matching scan."""
+    from side.intel.call_graph import TS_CLASS_RE, FileSymbols, extract_ts
+    from side.intel.lexing import strip_ts_noise
+
+    code = strip_ts_noise(content)
+    entities = [{"name": m.group(1), "type": "class", "line": code.count("\n", 0, m.start(1)) + 1}
+                for m in TS_CLASS_RE.finditer(code)]
+    symbols = FileSymbols(path=path.name, language="typescript", module="")
+    extract_ts(content, symbols)
+    for d in symbols.definitions:
+        if d.owner:
+            entities.append(_method(d.name, d.line, d.owner, "class"))
+        else:
+            entities.append({"name": d.name, "type": "function", "line": d.line})
+    return entities
+
 def get_file_semantics(path: Path, content: str) -> Dict[str, Any]:
     """Semantic extraction from file content."""
     semantics = {
@@ -130,22 +235,8 @@ def get_file_semantics(path: Path, content: str) -> Dict[str, Any]:
     }
     
     # 1. Structural Extraction (Universal)
-    ext_to_lang = {
-        ".py": "python",
-        ".js": "javascript",
-        ".jsx": "javascript",
-        ".ts": "typescript",
-        ".tsx": "typescript",
-        ".rb": "ruby",
-        ".php": "php",
-        ".go": "go",
-        ".cs": "c_sharp",
-        ".swift": "swift",
-        ".kt": "kotlin",
-        ".java": "java"
-    }
-    
-    lang_id = ext_to_lang.get(path.suffix)
+    spec = languages.for_path(path)
+    lang_id = spec.grammar if spec else None
     # print(f"🔍 [SEMANTICS]: {path.name} -> {lang_id}")
     
     # --- TREE-SITTER DEEP SCAN (IF AVAILABLE) ---
@@ -155,22 +246,8 @@ def get_file_semantics(path: Path, content: str) -> Dict[str, Any]:
             language = get_language(lang_id)
             tree = parser.parse(bytes(content, "utf8"))
             
-            # Simple queries for classes and functions
-            query_str = ""
-            if lang_id == "python":
-                query_str = """
-                (class_definition name: (identifier) @class.name)
-                (function_definition name: (identifier) @func.name)
-                (call function: (identifier) @call.name)
-                (call function: (attribute attribute: (identifier) @call.method))
-                """
-            elif lang_id in ["javascript", "typescript"]:
-                query_str = """
-                (class_declaration name: (identifier) @class.name)
-                (function_declaration name: (identifier) @func.name)
-                (call_expression function: (identifier) @call.name)
-                (call_expression function: (member_expression property: (property_identifier) @call.method))
-                """
+            # Structural query (classes, functions, calls) from the LanguageSpec
+            query_str = spec.query
             
             if query_str:
                 query = language.query(query_str)
@@ -178,29 +255,56 @@ def get_file_semantics(path: Path, content: str) -> Dict[str, Any]:
                 
                 for node, tag in captures:
                     name = node.text.decode("utf8")
+                    line = node.start_point[0] + 1
                     if "class.name" in tag:
                         semantics["classes"].append(name)
-                        semantics["entities"].append({"name": name, "type": "class"})
+                        semantics["entities"].append({"name": name, "type": "class", "line": line})
+                    elif tag.startswith("class."):
+                        # Rust: struct / enum / union / trait
+                        semantics["classes"].append(name)
+                        semantics["entities"].append({"name": name, "type": tag.split(".", 1)[1], "line": line})
+                    elif "impl.type" in tag:
+                        semantics["entities"].append({"name": rust_type_name(name), "type": "impl", "line": line})
                     elif "func.name" in tag:
                         semantics["functions"].append(name)
-                        semantics["entities"].append({"name": name, "type": "function"})
+                        owner, owner_kind = None, None
+                        if lang_id == "rust":
+                            owner, owner_kind = _rust_owner(node)
+                        elif lang_id == "python":
+                            owner, owner_kind = _python_owner(node), "class"
+                        if owner:
+                            semantics["entities"].append(_method(name, line, owner, owner_kind))
+                        else:
+                            semantics["entities"].append({"name": name, "type": "function", "line": line})
+                    elif "macro.name" in tag:
+                        semantics["entities"].append({"name": name, "type": "macro", "line": line})
+                    elif "module.name" in tag:
+                        semantics["entities"].append({"name": name, "type": "module", "line": line})
                     elif "call.name" in tag or "call.method" in tag:
                         # Relationship candidates
                         semantics["relationships"].append({"target": name, "type": "calls"})
                 
                 # If we have AST results, we can skip regex
                 if semantics["classes"] or semantics["functions"]:
+                    annotate_entities(spec.name, content, semantics["entities"])
                     return semantics
         except Exception as e:
             logger.debug(f"Tree-sitter scan failed for {path}: {e}")
 
     # --- REGEX FALLBACK ---
-    lang = lang_id # Normalize for REGEX_STRUCTURAL mapping if needed
-    if lang == "c_sharp": lang = "dotnet"
+    lang = spec.name if spec else None
     
-    if lang and lang in REGEX_STRUCTURAL:
-        semantics["classes"] = REGEX_STRUCTURAL[lang]["class"].findall(content)
-        semantics["functions"] = REGEX_STRUCTURAL[lang]["def"].findall(content)
+    if spec and spec.regex:
+        semantics["classes"] = spec.regex["class"].findall(content)
+        semantics["functions"] = spec.regex["def"].findall(content)
+
+    if lang == "rust":
+        semantics["entities"] = _scan_rust_items(content)
+    elif lang == "python":
+        semantics["entities"] = _scan_python_items(content)
+    elif lang in ("typescript", "javascript"):
+        semantics["entities"] = _scan_ts_items(path, content)
+    annotate_entities(lang, content, semantics["entities"])
     
     # 2. Signal Extraction (Cross-Language)
     for signal_name, regex in SIGNALS.items():
@@ -209,20 +313,27 @@ def get_file_semantics(path: Path, content: str) -> Dict[str, Any]:
             
     return semantics
 
-def get_file_dna(path: Path) -> Dict[str, Any]:
-    """Extracts deep DNA from a file (Structure + Semantics)."""
+def get_file_dna(path: Path, cache=None) -> Dict[str, Any]:
+    """Extracts deep DNA from a file (Structure + Semantics). Known content is served from `cache`."""
     try:
         content = path.read_text(errors='ignore')
         size = path.stat().st_size
-        
+        content_hash = hashlib.sha256(content.encode()).hexdigest()[:16]
+
+        cached = cache.get_semantics(content_hash, path) if cache else None
+        if cached is None:
+            cached = {"lines": len(content.splitlines()), "semantics": get_file_semantics(path, content)}
+            if cache:
+                cache.put_semantics(content_hash, path, cached["lines"], cached["semantics"])
+
         # Build DNA
         dna = {
             "name": path.name,
             "type": path.suffix,
             "size": size,
-            "lines": len(content.splitlines()),
-            "hash": hashlib.sha256(content.encode()).hexdigest()[:16],
-            "semantics": get_file_semantics(path, content)
+            "lines": cached["lines"],
+            "hash": content_hash,
+            "semantics": cached["semantics"]
         }
         
         # [PURGE]: 'Strategic Capture' removed. Files are only indexed for Structure, not Text.
@@ -233,8 +344,97 @@ def get_file_dna(path: Path) -> Dict[str, Any]:
     except Exception:
         return {"name": path.name, "error": "unreadable"}
 
-def generate_local_index(directory: Path, schema_store=None) -> Dict[str, Any]:
-    """Generates the Context Index for a single directory."""
+def index_candidates(directory: Path, ignore_service) -> Tuple[List[Path], List[Path]]:
+    """(entries to consider, files to index) for one directory."""
+    items_to_scan = [item for item in directory.iterdir() if not ignore_service.should_ignore(item)]
+
+    file_items = []
+    for i in items_to_scan:
+        if not i.is_file():
+            continue
+
+        # [CONTEXT FILTER]: Apply the "Signal Only" logic
+        # 1. Allow Exception List explicitly
+        if i.name in ALLOWED_FILES:
+            file_items.append(i)
+            continue
+
+        # 2. Deny Noise Extensions
+        if i.suffix in DENIED_EXTENSIONS:
+            continue
+
+        file_items.append(i)
+    return items_to_scan, file_items
+
+
+def _parse_chunk(chunk) -> List[Tuple[str, Dict[str, Any]]]:
+    """Process-pool worker: DNA for a batch of files, storing new content in the shared IndexCache."""
+    root, paths = chunk.payload
+    cache = IndexCache(Path(root))
+    return [(p, get_file_dna(Path(p), cache)) for p in paths]
+
+
+def prepare_files(root: Path, paths: List[Path], cache=None, workers: Optional[int] = None) -> Dict[Path, Tuple[Dict[str, Any], bool]]:
+    """
+    path -> (DNA, unchanged) for every file. Stat hits come from `cache`; the rest are parsed,
+    across processes when there are enough of them to repay the pool start-up.
+    """
+    prepared = {}
+    for path in paths:
+        if cache and (dna := cache.lookup(path)) is not None:
+            prepared[path] = (dna, True)
+    to_parse = [p for p in paths if p not in prepared]
+
+    workers = workers or os.cpu_count() or 1
+    if workers > 1 and len(to_parse) >= PROCESS_POOL_MIN_FILES:
+        from side.parallel.task_decomposer import TaskDecomposer
+
+        decomposer = TaskDecomposer(max_workers=workers, use_processes=True)
+        try:
+            # Several chunks per worker so one slow batch doesn't leave the rest idle
+            chunk_size = max(1, min(256, -(-len(to_parse) // (workers * 4))))
+            chunks = decomposer.decompose_by_files([str(p) for p in to_parse], chunk_size=chunk_size)
+            for chunk in chunks:
+                chunk.payload = (str(root), chunk.payload)
+            result = decomposer.execute_parallel(chunks, _parse_chunk)
+        finally:
+            decomposer.shutdown()
+        for batch in result.results:
+            for p, dna in batch:
+                path = Path(p)
+                prepared[path] = (dna, False)
+                if cache and "hash" in dna:
+                    cache.touch(dna["hash"], path)
+        to_parse = [p for p in to_parse if p not in prepared]  # Failed chunks fall back to threads
+
+    if to_parse:
+        with ThreadPoolExecutor(max_workers=8) as executor:
+            for path, dna in zip(to_parse, executor.map(lambda p: get_file_dna(p, cache), to_parse)):
+                prepared[path] = (dna, False)
+    return prepared
+
+
+def store_key(schema_store) -> str:
+    """Identifies the database (and project in it) entities are persisted to, for IndexCache."""
+    engine = schema_store.engine
+    path = Path(engine.db_path).resolve()
+    try:
+        inode = path.stat().st_ino   # A deleted and recreated database starts empty
+    except OSError:
+        inode = 0
+    return f"{path}:{inode}:{engine.get_project_id()}"
+
+
+def generate_local_index(directory: Path, schema_store=None, cache=None, store=None,
+                         prepared=None, entity_sink: Optional[List[Dict[str, Any]]] = None) -> Dict[str, Any]:
+    """
+    Generates the Context Index for a single directory.
+    With an IndexCache, files unchanged since the last run are not read, nor re-persisted
+    once a full scan has stored them in this `schema_store`'s database.
+    Child checksums are read from `store` (the project's configured IndexStore by default).
+    `prepared` (from prepare_files) supplies already-parsed DNA; entities are appended to
+    `entity_sink` for one batched write instead of being saved per file.
+    """
     files = []
     children_checksums = {}
     aggregated_signals = set()
@@ -253,86 +453,94 @@ def generate_local_index(directory: Path, schema_store=None) -> Dict[str, Any]:
     except:
         ignore_service = ProjectIgnore(directory)
 
+    store = store or open_index_store(project_root_path)
     has_subdirs = False
     
-    # [PERFORMANCE SPRINT I] Parallel file scanning
-    with ThreadPoolExecutor(max_workers=8) as executor:
-        items_to_scan = [item for item in directory.iterdir() if not ignore_service.should_ignore(item)]
-        
-        file_items = []
-        for i in items_to_scan:
-            if not i.is_file():
-                continue
-                
-            # [CONTEXT FILTER]: Apply the "Signal Only" logic
-            # 1. Allow Exception List explicitly
-            if i.name in ALLOWED_FILES:
-                file_items.append(i)
-                continue
-                
-            # 2. Deny Noise Extensions
-            if i.suffix in DENIED_EXTENSIONS:
-                continue
-                
-            file_items.append(i)
+    items_to_scan, file_items = index_candidates(directory, ignore_service)
+    prepared = prepared or {}
+    missing = [i for i in file_items if i not in prepared]
+    if missing:
+        prepared = {**prepared, **prepare_files(project_root_path, missing, cache, workers=1)}
+    dna_by_path = {i: prepared[i][0] for i in file_items}
+    unchanged = {i for i in file_items if prepared[i][1]}
+    persisted = bool(cache and schema_store and cache.persisted_to(store_key(schema_store)))
+    if cache:
+        cache.update_directory(directory, dna_by_path)
+    repersisted: List[str] = []
+    kept: Set[str] = set()
 
-        dna_results = list(executor.map(get_file_dna, file_items))
-        
-        for dna in dna_results:
-            files.append(dna)
-            if "semantics" in dna:
-                sem = dna["semantics"]
-                aggregated_signals.update(sem.get("signals", []))
-                total_classes += len(sem.get("classes", []))
-                total_functions += len(sem.get("functions", []))
-
-                # 🧬 SCHEMA PERSISTENCE
-                if schema_store:
+    for item, dna in dna_by_path.items():
+        files.append(dna)
+        if "semantics" in dna:
+            sem = dna["semantics"]
+            aggregated_signals.update(sem.get("signals", []))
+            total_classes += len(sem.get("classes", []))
+            total_functions += len(sem.get("functions", []))
+
+            # 🧬 SCHEMA PERSISTENCE (unchanged files are already stored, if in this database)
+            if schema_store and (item not in unchanged or not persisted):
+                try:
+                    file_rel_path = (directory / dna["name"]).relative_to(project_root_path).as_posix()
+                except ValueError:
                     file_rel_path = str(dna["name"])
-                    entities_to_save = []
-                    for ent in sem.get("entities", []):
-                        ent_id = hashlib.sha256(f"{project_id}:{ent['name']}:{ent['type']}".encode()).hexdigest()[:16]
-                        entities_to_save.append({
-                            "id": ent_id,
-                            "project_id": project_id,
-                            "name": ent["name"],
-                            "entity_type": ent["type"],
-                            "file_path": file_rel_path
-                        })
-                    if entities_to_save:
+
+                # Rust items are identified by their crate path (`shop::orders::Order::total`), the rest by module or file
+                spec = languages.for_path(item)
+                is_rust = bool(spec and spec.name == "rust")
+                entities_to_save = []
+                for ent in sem.get("entities", []):
+                    module = rust_modules.module_path(project_root_path, item, ent.get("line", 0)) if is_rust else None
+                    module_cfg = rust_modules.module_cfg(project_root_path, item, ent.get("line", 0)) if is_rust else None
+                    qualified = rust_modules.qualify(module, ent["name"], ent.get("parent")) or \
+                        file_qualified_name(file_rel_path, ent["name"], ent.get("parent"))
+                    parent_qualified = (rust_modules.qualify(module, ent["parent"]) or
+                                        file_qualified_name(file_rel_path, ent["parent"])) if ent.get("parent") else None
+                    entities_to_save.append({
+                        "id": entity_id(project_id, ent["name"], ent["type"], qualified),
+                        "project_id": project_id,
+                        "name": ent["name"],
+                        "entity_type": ent["type"],
+                        "file_path": file_rel_path,
+                        "signature": ent.get("signature"),
+                        "visibility": ent.get("visibility"),
+                        "doc": ent.get("doc") or "",
+                        "qualified_name": qualified,
+                        "cfg": rust_cfg.combine([module_cfg, ent.get("cfg")]),
+                        "parent_id": entity_id(project_id, ent["parent"], ent["parent_type"], parent_qualified) if ent.get("parent") else None,
+                    })
+                repersisted.append(file_rel_path)
+                kept.update(e["id"] for e in entities_to_save)
+                if is_rust:  # A crate root is its own module, stored by the module tree pass
+                    own = rust_modules.module_path(project_root_path, item)
+                    kept.add(entity_id(project_id, own.rsplit("::", 1)[-1], "module", own))
+                if entities_to_save:
+                    # Owners first so parent_id references resolve; unknown owners stay unlinked
+                    known = {e["id"] for e in entities_to_save}
+                    for e in entities_to_save:
+                        if e["parent_id"] not in known:
+                            e["parent_id"] = None
+                    if entity_sink is not None:
+                        entity_sink.extend(entities_to_save)
+                    else:
+                        entities_to_save.sort(key=lambda e: e["parent_id"] is not None)
                         schema_store.save_entities_batch(entities_to_save)
-                    
-                    # Relationships (Simplified logic: entities in same file 'reference' each other)
-                    # Future: Use Tree-sitter 'calls' for high-precision
-                    relationships_to_save = []
-                    for rel in sem.get("relationships", []):
-                        # Find target entity ID (if already indexed or known)
-                        target_id = hashlib.sha256(f"{project_id}:{rel['target']}:function".encode()).hexdigest()[:16]
-                        # Source ID would be the current function/class if we tracked the traversal
-                        # For now, we link the FILE to the entity
-                        file_id = hashlib.sha256(f"{project_id}:{file_rel_path}:file".encode()).hexdigest()[:16]
-                        relationships_to_save.append({
-                            "id": hashlib.sha256(f"{file_id}:{target_id}:{rel['type']}".encode()).hexdigest()[:16],
-                            "project_id": project_id,
-                            "source_id": file_id,
-                            "target_id": target_id,
-                            "relation_type": rel["type"]
-                        })
-                    if relationships_to_save:
-                        schema_store.save_relationships_batch(relationships_to_save)
-
-        for item in items_to_scan:
-            if item.is_dir() and item.name != ".side":
-                has_subdirs = True
-                child_index = item / ".side" / "local.json"
-                if child_index.exists():
-                    try:
-                        raw_data = shield.unseal_file(child_index)
-                        data = json.loads(raw_data)
-                        children_checksums[item.name] = data.get("checksum", "unknown")
-                    except:
-                        pass
-    
+                
+                # 'calls' edges need the whole project's symbols; see call_graph.index_call_graph
+
+    if repersisted:
+        # Items gone from a re-persisted file are dropped, with their edges
+        stale = [e["id"] for e in schema_store.list_entities_in_files(project_id, repersisted)
+                 if e["entity_type"] in ITEM_TYPES and e["id"] not in kept]
+        if stale:
+            schema_store.delete_entities_by_ids(stale)
+
+    for item in items_to_scan:
+        if item.is_dir() and item.name != ".side":
+            has_subdirs = True
+            child = store.read(item)
+            if child is not None:
+                children_checksums[item.name] = child.get("checksum", "unknown")
+
     # --- HEURISTIC (Complexity Analysis) ---
     is_complex = (
         len(files) >= 5 or 
@@ -362,14 +570,25 @@ def generate_local_index(directory: Path, schema_store=None) -> Dict[str, Any]:
     
     return index_data
 
-def run_context_scan(root: Path, schema_store=None):
+def run_context_scan(root: Path, schema_store=None, workers: Optional[int] = None) -> Dict[str, Any]:
     """
     Runs a pruning Top-Down scan to gather directories, 
     then processes them Bottom-Up to ensure Merkle integrity.
+    Parsing is sharded across `workers` processes (default: all cores) before the
+    bottom-up pass; entities are written in one transaction. Returns throughput stats.
     """
+    started = time.perf_counter()
     ignore_service = ProjectIgnore(root)
+    cache = IndexCache(root)
+    store = open_index_store(root)
+    rust_modules.clear()
     dirs_to_process = []
 
+    # 0. Project Model: Cargo workspaces are indexed as a crate graph
+    if schema_store:
+        from side.intel.cargo_manifest import index_cargo_workspace
+        index_cargo_workspace(root, schema_store)
+
     # 1. Top-Down Pruning Pass
     for dirpath, dirnames, filenames in os.walk(root, topdown=True):
         current_dir = Path(dirpath)
@@ -383,25 +602,75 @@ def run_context_scan(root: Path, schema_store=None):
             
         dirs_to_process.append(current_dir)
 
-    # 2. Bottom-Up Processing Pass (Reverse order)
+    # 2. Parallel Parse Pass: every file of every directory, before any Merkle node is built
+    all_files = []
+    for current_dir in dirs_to_process:
+        all_files.extend(index_candidates(current_dir, ignore_service)[1])
+    workers = workers or os.cpu_count() or 1
+    prepared = prepare_files(root, all_files, cache, workers=workers)
+    parsed = sum(1 for _, unchanged in prepared.values() if not unchanged)
+    parse_seconds = time.perf_counter() - started
+
+    # 3. Bottom-Up Processing Pass (Reverse order)
+    written = set()
+    entities: List[Dict[str, Any]] = []
     for current_dir in reversed(dirs_to_process):
         # Skip .git and root is handled explicitly if needed, 
         # but reversed(dirs_to_process) includes root at the end.
         
         print(f"🔮 Deep Indexing: {current_dir}")
-        index_data = generate_local_index(current_dir, schema_store=schema_store)
+        index_data = generate_local_index(current_dir, schema_store=schema_store, cache=cache, store=store,
+                                          prepared=prepared, entity_sink=entities)
         
         # SPARSE WRITE LOGIC
         is_root = current_dir == root
-        side_dir = current_dir / ".side"
         
         if index_data["is_complex"] or is_root:
-            side_dir.mkdir(exist_ok=True)
-            shield.seal_file(side_dir / "local.json", json.dumps(index_data, indent=2))
+            store.write(current_dir, index_data)
+            written.add(current_dir)
         else:
-            import shutil
-            if side_dir.exists():
-                shutil.rmtree(side_dir)
+            store.remove(current_dir)
+
+    if schema_store and entities:
+        # One transaction for the whole scan; owners first so parent_id references resolve
+        entities.sort(key=lambda e: e["parent_id"] is not None)
+        schema_store.save_entities_batch(entities)
+    if schema_store:
+        cache.mark_persisted(store_key(schema_store))
+
+    # Every live file was looked up during the pass, so anything else in the cache is stale
+    store.prune(written)
+    removed = cache.prune()
+    logger.info(f"Index cache: {cache.hits} unchanged, {cache.misses} re-indexed, {removed} stale entries pruned")
+
+    # 4. Module Tree, Call, Type, Test & Route Graph: resolve `use`s, calls, type relationships, tested code and HTTP routes once every directory is indexed
+    if schema_store:
+        from side.intel.call_graph import index_call_graph
+        from side.intel.type_graph import index_type_graph
+        from side.intel.test_links import index_test_links
+        from side.intel.route_map import index_route_map
+        rust_modules.index_rust_modules(root, schema_store)
+        rust_cfg.index_cfg_features(root, schema_store)
+        index_call_graph(root, schema_store)
+        index_type_graph(root, schema_store)
+        index_test_links(root, schema_store)
+        index_route_map(root, schema_store)
+
+    seconds = time.perf_counter() - started
+    stats = {
+        "directories": len(dirs_to_process),
+        "files": len(all_files),
+        "parsed": parsed,
+        "cached": len(all_files) - parsed,
+        "entities": len(entities),
+        "workers": workers,
+        "parse_seconds": round(parse_seconds, 3),
+        "seconds": round(seconds, 3),
+        "files_per_second": round(len(all_files) / seconds, 1) if seconds else 0.0,
+    }
+    logger.info(f"⚡ [INDEX]: {stats['files']} files ({stats['parsed']} parsed) in {stats['seconds']}s "
+                f"= {stats['files_per_second']} files/s on {workers} workers")
+    return stats
 
 def update_branch(root: Path, changed_path: Path, schema_store=None):
     """
@@ -412,6 +681,20 @@ def update_branch(root: Path, changed_path: Path, schema_store=None):
 
     ignore_service = ProjectIgnore(root)
 
+    cache = IndexCache(root)
+    store = open_index_store(root)
+
+    if schema_store and changed_path.name == "Cargo.toml":
+        from side.intel.cargo_manifest import index_cargo_workspace
+        index_cargo_workspace(root, schema_store)
+    rust_changed = changed_path.suffix == ".rs" or changed_path.name == "Cargo.toml"
+    # `mod` items and targets decide every file's module path: a structural edit rebuilds the graphs
+    structural = changed_path.name == "Cargo.toml" or (
+        changed_path.suffix == ".rs" and rust_modules.refresh_file(root, changed_path)
+    )
+    if structural:
+        rust_modules.clear()
+
     # Start from the parent directory of the changed file
     current_dir = changed_path.parent if changed_path.is_file() else changed_path
     
@@ -427,23 +710,35 @@ def update_branch(root: Path, changed_path: Path, schema_store=None):
             break
             
         print(f"⚡ Context Update: {current_dir}")
-        index_data = generate_local_index(current_dir, schema_store=schema_store)
+        index_data = generate_local_index(current_dir, schema_store=schema_store, cache=cache, store=store)
         
-        side_dir = current_dir / ".side"
         is_root = current_dir == root
         
         if index_data["is_complex"] or is_root:
-            side_dir.mkdir(exist_ok=True)
-            shield.seal_file(side_dir / "local.json", json.dumps(index_data, indent=2))
+            store.write(current_dir, index_data)
         else:
-            import shutil
-            if side_dir.exists():
-                shutil.rmtree(side_dir)
+            store.remove(current_dir)
         
         if current_dir == root:
             break
         current_dir = current_dir.parent
 
+    # Only the edited file is re-parsed, and only the edges from it or its callers are replaced;
+    # a structural Rust edit moves module paths (and so ids) everywhere and rebuilds them all
+    if schema_store and (structural or not ignore_service.should_ignore(changed_path)):
+        from side.intel.call_graph import index_call_graph
+        from side.intel.type_graph import index_type_graph
+        from side.intel.test_links import index_test_links
+        from side.intel.route_map import index_route_map
+        changed = None if structural else [changed_path]
+        if rust_changed:
+            rust_modules.index_rust_modules(root, schema_store, changed)
+            rust_cfg.index_cfg_features(root, schema_store, changed)
+        index_call_graph(root, schema_store, changed)
+        index_type_graph(root, schema_store, changed)
+        index_test_links(root, schema_store, changed)
+        index_route_map(root, schema_store, changed)
+
 if __name__ == "__main__":
     import sys
     target = Path(sys.argv[1]) if len(sys.argv) > 1 else Path.cwd()
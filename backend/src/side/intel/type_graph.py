@@ -0,0 +1,450 @@
+"""
+Type Graph - `implements` / `inherits` edges between types.
+
+Covers Rust `impl Trait for Type`, `#[derive(...)]` and supertraits, Python
+base classes, TypeScript `extends`/`implements`, and Go's implicit interface
+satisfaction (a type implements an interface when its method set covers the
+interface's). Type headers are simple enough that a scan over
+comment-stripped source is exact in practice; Python uses the stdlib `ast`.
+A base or trait resolves to the definition in the same file, then the one the
+file imports, then the nearest same-language namesake.
+"""
+
+import ast
+import logging
+import re
+from collections import defaultdict
+from dataclasses import dataclass, field
+from pathlib import Path, PurePosixPath
+from typing import Any, Dict, Iterable, List, Optional, Set, Tuple
+
+from side.intel import rust_modules
+from side.intel.call_graph import Import, ModuleIndex, _python_imports, _ts_imports, _ts_module, iter_source_files, relative_paths
+from side.intel.ids import entity_id, file_qualified_name, python_module, relationship_id
+from side.intel.languages import registry as languages
+from side.intel.lexing import match_braces, rust_type_name, split_rust_impl, strip_rust_noise, strip_ts_noise
+from side.intel.tree_indexer import RUST_ITEMS
+
+logger = logging.getLogger(__name__)
+
+TYPE_GRAPH_LANGUAGES = {"python", "typescript", "javascript", "rust", "go"}
+TYPE_RELATIONS = ("implements", "inherits")
+TYPE_KINDS = {"class", "interface", "struct", "enum", "union", "trait", "type"}
+
+# Declared in source vs. inferred from Go method sets (names only, signatures unchecked)
+CONFIDENCE = {"declared": 1.0, "structural": 0.7}
+
+RUST_TYPE_RE = re.compile(RUST_ITEMS["type"].pattern, re.MULTILINE)
+RUST_IMPL_RE = re.compile(RUST_ITEMS["impl"].pattern, re.MULTILINE)
+RUST_TRAIT_RE = re.compile(
+    r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?(?:auto\s+)?trait\s+(\w+)\s*(?:<[^{;]*?>)?\s*(?::\s*([^{;]+?))?\s*(?:where\b[^{]*)?\{",
+    re.MULTILINE,
+)
+RUST_DERIVE_RE = re.compile(
+    r"#\[\s*derive\s*\(([^)]*)\)\s*\]((?:\s*#\[[^\]]*\])*)\s*(?:pub(?:\([^)]*\))?\s+)?(struct|enum|union)\s+(\w+)"
+)
+
+TS_CLASS_RE = re.compile(
+    r"\bclass\s+([\w$]+)\s*(?:<[^{]*?>)?\s*(?:extends\s+([\w$.]+)\s*(?:<[^{]*?>)?)?\s*(?:implements\s+([^{]+))?\{"
+)
+TS_INTERFACE_RE = re.compile(r"\binterface\s+([\w$]+)\s*(?:<[^{]*?>)?\s*(?:extends\s+([^{]+))?\{")
+
+GO_TYPE_RE = re.compile(r"^\s*(?:type\s+)?(\w+)(?:\[[^\]]*\])?\s+(struct|interface)\s*\{", re.MULTILINE)
+GO_NAMED_TYPE_RE = re.compile(r"^\s*type\s+(\w+)(?:\[[^\]]*\])?\s+(?!struct\b|interface\b)[\w.\[\]*]+\s*$", re.MULTILINE)
+GO_METHOD_RE = re.compile(r"^\s*func\s*\(\s*\w*\s*\*?\s*(\w+)(?:\[[^\]]*\])?\s*\)\s*(\w+)\s*(?:\[[^\]]*\])?\s*\(", re.MULTILINE)
+GO_INTERFACE_METHOD_RE = re.compile(r"^\s*(\w+)\s*\(", re.MULTILINE)
+GO_EMBED_RE = re.compile(r"^\s*\*?([\w.]+)\s*$", re.MULTILINE)
+
+
+@dataclass
+class TypeRelation:
+    source: str
+    target: str
+    relation: str                   # 'implements' | 'inherits'
+    target_kind: str                # Kind to assume when the target is not defined in the project
+    via: str = "declared"           # 'declared', 'derive', 'structural'
+    qualifier: Optional[str] = None # Python / TypeScript: what the target is written under ('models' in `models.Base`)
+
+
+@dataclass
+class FileTypes:
+    path: str
+    language: str
+    module: str = ""                                                 # Python / TypeScript: 'pkg.orders' | 'src/orders'
+    types: Dict[str, str] = field(default_factory=dict)              # name -> kind
+    lines: Dict[str, int] = field(default_factory=dict)              # Rust: name -> line of the definition
+    qualified: Dict[str, str] = field(default_factory=dict)          # Rust: name -> crate-qualified path
+    relations: List[TypeRelation] = field(default_factory=list)
+    methods: Dict[str, Set[str]] = field(default_factory=dict)       # Go: receiver type -> method names
+    interfaces: Dict[str, Set[str]] = field(default_factory=dict)    # Go: interface -> method names
+    embeds: Dict[str, List[str]] = field(default_factory=dict)       # Go: interface -> embedded interfaces
+    imports: Dict[str, Import] = field(default_factory=dict)         # Python / TypeScript: alias -> import
+
+    @property
+    def package(self) -> str:
+        return str(PurePosixPath(self.path).parent)
+
+
+def _split_top_level(text: str, separators: str = ",") -> List[str]:
+    """Splits on separators outside of `<...>` / `(...)`."""
+    parts, depth, start = [], 0, 0
+    for i, ch in enumerate(text):
+        depth += 1 if ch in "<(" else -1 if ch in ">)" else 0
+        if ch in separators and depth == 0:
+            parts.append(text[start:i])
+            start = i + 1
+    parts.append(text[start:])
+    return [p.strip() for p in parts if p.strip()]
+
+
+def _base_name(text: str) -> str:
+    """`models.Base`, `Repo<T>`, `crate::repo::Repo<T>` -> the bare type name."""
+    return re.split(r"[.:]", rust_type_name(text.strip()))[-1]
+
+
+def _ts_relation(source: str, text: str, relation: str, target_kind: str) -> TypeRelation:
+    """`ns.Base<T>` -> a relation to `Base` qualified by `ns`."""
+    qualifier, _, _ = rust_type_name(text.strip()).rpartition(".")
+    return TypeRelation(source, _base_name(text), relation, target_kind, qualifier=qualifier or None)
+
+
+def _shared_dirs(a: str, b: str) -> int:
+    """Number of leading directories two project-relative paths share."""
+    shared = 0
+    for x, y in zip(PurePosixPath(a).parent.parts, PurePosixPath(b).parent.parts):
+        if x != y:
+            break
+        shared += 1
+    return shared
+
+
+# --- Per-language extraction ---
+
+def _python_types(content: str, types: FileTypes) -> None:
+    try:
+        tree = ast.parse(content)
+    except (SyntaxError, ValueError):
+        return
+    types.imports = {i.alias: i for i in _python_imports(tree) if i.alias != "*"}
+    for node in ast.walk(tree):
+        if not isinstance(node, ast.ClassDef):
+            continue
+        types.types[node.name] = "class"
+        for base in node.bases:
+            while isinstance(base, ast.Subscript):  # Generic[T], Repository[Order]
+                base = base.value
+            if isinstance(base, ast.Name):
+                name, qualifier = base.id, None
+            elif isinstance(base, ast.Attribute):
+                name, qualifier = base.attr, ast.unparse(base.value)
+            else:
+                continue
+            if name != "object":
+                types.relations.append(TypeRelation(node.name, name, "inherits", "class", qualifier=qualifier))
+
+
+def _rust_types(content: str, types: FileTypes) -> None:
+    code = strip_rust_noise(content)
+    for m in RUST_TYPE_RE.finditer(code):
+        types.types[m.group(2)] = m.group(1)
+        types.lines[m.group(2)] = code.count("\n", 0, m.start(2)) + 1
+
+    for m in RUST_TRAIT_RE.finditer(code):
+        types.types[m.group(1)] = "trait"
+        types.lines[m.group(1)] = code.count("\n", 0, m.start(1)) + 1
+        for bound in _split_top_level(m.group(2) or "", "+"):
+            if not bound.startswith(("'", "?")):
+                types.relations.append(TypeRelation(m.group(1), _base_name(bound), "inherits", "trait"))
+
+    for m in RUST_IMPL_RE.finditer(code):
+        trait, target = split_rust_impl(m.group(1))
+        if trait and target and not m.group(1).lstrip().startswith("!") and "!" not in trait:
+            types.relations.append(TypeRelation(target, trait, "implements", "trait"))
+
+    for m in RUST_DERIVE_RE.finditer(code):
+        # Stacked #[derive] attributes before the same item are all captured in group 2
+        derives = [m.group(1)] + re.findall(r"derive\s*\(([^)]*)\)", m.group(2) or "")
+        for derive in derives:
+            for name in _split_top_level(derive):
+                types.relations.append(TypeRelation(m.group(4), _base_name(name), "implements", "trait", "derive"))
+
+
+def _ts_types(content: str, types: FileTypes) -> None:
+    code = strip_ts_noise(content)
+    types.imports = {i.alias: i for i in _ts_imports(content)}
+    for m in TS_CLASS_RE.finditer(code):
+        name, parent, implemented = m.groups()
+        types.types[name] = "class"
+        if parent:
+            types.relations.append(_ts_relation(name, parent, "inherits", "class"))
+        for iface in _split_top_level(implemented or ""):
+            types.relations.append(_ts_relation(name, iface, "implements", "interface"))
+    for m in TS_INTERFACE_RE.finditer(code):
+        name, parents = m.groups()
+        types.types[name] = "interface"
+        for parent in _split_top_level(parents or ""):
+            types.relations.append(_ts_relation(name, parent, "inherits", "interface"))
+
+
+def _go_types(content: str, types: FileTypes) -> None:
+    code = strip_ts_noise(content)
+    braces = match_braces(code)
+    for m in GO_TYPE_RE.finditer(code):
+        name, kind = m.groups()
+        types.types[name] = kind
+        if kind != "interface":
+            continue
+        open_pos = m.end() - 1
+        body = code[open_pos + 1:braces.get(open_pos, open_pos)]
+        types.interfaces[name] = set(GO_INTERFACE_METHOD_RE.findall(body))
+        types.embeds[name] = [e for e in GO_EMBED_RE.findall(body) if e[:1].isalpha() and "|" not in e]
+        for embedded in types.embeds[name]:
+            types.relations.append(TypeRelation(name, _base_name(embedded), "inherits", "interface"))
+    for m in GO_NAMED_TYPE_RE.finditer(code):
+        types.types.setdefault(m.group(1), "type")
+    for m in GO_METHOD_RE.finditer(code):
+        types.methods.setdefault(m.group(1), set()).add(m.group(2))
+        types.types.setdefault(m.group(1), "type")
+
+
+EXTRACTORS = {
+    "python": _python_types,
+    "rust": _rust_types,
+    "typescript": _ts_types,
+    "javascript": _ts_types,
+    "go": _go_types,
+}
+
+
+def extract_file_types(path: Path, content: str, rel_path: str) -> Optional[FileTypes]:
+    spec = languages.for_path(path)
+    if not spec or spec.name not in EXTRACTORS:
+        return None
+    types = FileTypes(path=rel_path, language=spec.name)
+    if spec.name == "python":
+        types.module = python_module(rel_path)
+    elif spec.name in ("typescript", "javascript"):
+        types.module = _ts_module(rel_path)
+    EXTRACTORS[spec.name](content, types)
+    return types
+
+
+# --- Project-wide pass ---
+
+def _go_satisfaction(files: List[FileTypes]) -> List[Tuple[FileTypes, TypeRelation]]:
+    """Go types whose method set covers an interface's (including embedded interfaces)."""
+    go_files = [f for f in files if f.language == "go"]
+    interfaces: Dict[Tuple[str, str], Tuple[Set[str], List[str]]] = {}
+    method_sets: Dict[Tuple[str, str], Set[str]] = defaultdict(set)
+    declaring: Dict[Tuple[str, str], FileTypes] = {}
+    for f in go_files:
+        for name, methods in f.interfaces.items():
+            interfaces[(f.package, name)] = (methods, f.embeds.get(name, []))
+        for name, methods in f.methods.items():
+            method_sets[(f.package, name)] |= methods
+        for name, kind in f.types.items():
+            if kind != "interface":
+                declaring.setdefault((f.package, name), f)
+
+    def full_method_set(key, seen=()) -> Optional[Set[str]]:
+        methods, embeds = interfaces[key]
+        result = set(methods)
+        for embedded in embeds:
+            name = embedded.rsplit(".", 1)[-1]
+            match = (key[0], name) if (key[0], name) in interfaces and "." not in embedded else \
+                next((k for k in interfaces if k[1] == name), None)
+            if match is None or match in seen:
+                return None  # Embeds something outside the project (io.Reader): can't decide
+            inner = full_method_set(match, seen + (key,))
+            if inner is None:
+                return None
+            result |= inner
+        return result
+
+    relations = []
+    for key in interfaces:
+        required = full_method_set(key)
+        if not required:
+            continue  # interface{} / any / undecidable
+        for type_key, methods in method_sets.items():
+            if type_key in declaring and required <= methods:
+                relations.append((declaring[type_key], TypeRelation(type_key[1], key[1], "implements", "interface", "structural")))
+    return relations
+
+
+def type_graph_records(files: List[FileTypes], project_id: str) -> Tuple[List[Dict[str, Any]], List[Dict[str, Any]]]:
+    """SchemaStore type entities and `implements`/`inherits` edges."""
+    defined: Dict[str, List[Tuple[FileTypes, str]]] = defaultdict(list)
+    for f in files:
+        for name, kind in f.types.items():
+            defined[name].append((f, kind))
+
+    entities: Dict[str, Dict[str, Any]] = {}
+
+    def node(name: str, kind: str, defining: Optional[FileTypes]) -> str:
+        qualified = (defining.qualified.get(name) or file_qualified_name(defining.path, name)) if defining else None
+        ent_id = entity_id(project_id, name, kind, qualified)
+        entities.setdefault(ent_id, {
+            "id": ent_id,
+            "project_id": project_id,
+            "name": name,
+            "entity_type": kind,
+            "file_path": defining.path if defining else None,
+            "qualified_name": qualified,
+        })
+        return ent_id
+
+    modules = ModuleIndex(f for f in files if f.module)
+
+    def imported(source_file: FileTypes, rel: TypeRelation) -> Optional[Tuple[str, List[FileTypes]]]:
+        """(name, project files) the import binding the target points at; None when the target isn't imported."""
+        imp = source_file.imports.get(rel.qualifier or rel.target)
+        if imp is None:
+            return None
+        if rel.qualifier is None:  # from pkg.base import Base as B
+            name = rel.target if imp.symbol in (None, "default") else imp.symbol
+            return name, modules.module_files(source_file, imp.module)
+        if imp.symbol is None:  # import pkg.base as base / import * as base
+            return rel.target, modules.module_files(source_file, imp.module)
+        if source_file.language == "python":  # from pkg import base
+            separator = "" if imp.module.endswith(".") else "."
+            return rel.target, modules.module_files(source_file, f"{imp.module}{separator}{imp.symbol}")
+        return rel.target, []
+
+    def target_node(source_file: FileTypes, rel: TypeRelation) -> str:
+        name, candidates = rel.target, defined.get(rel.target, [])
+        best = None if rel.qualifier else next((c for c in candidates if c[0] is source_file), None)
+        binding = None if best else imported(source_file, rel)
+        if binding:
+            name, targets = binding
+            if not targets:
+                return node(name, rel.target_kind, None)  # Imported from outside the project
+            paths = {f.path for f in targets}
+            # Not defined in the imported module itself: re-exported, look further below
+            best = next((c for c in defined.get(name, []) if c[0].path in paths), None)
+            candidates = defined.get(name, [])
+        if best is None:
+            # Nearest definition in the same language: the same package wins over a namesake elsewhere
+            same_language = [c for c in candidates if c[0].language == source_file.language]
+            best = max(same_language, key=lambda c: _shared_dirs(c[0].path, source_file.path), default=None)
+        if best:
+            return node(name, best[1], best[0])
+        return node(name, rel.target_kind, None)  # Outside the project (std, node_modules, site-packages)
+
+    pairs = [(f, rel) for f in files for rel in f.relations] + _go_satisfaction(files)
+    edges: Dict[str, Dict[str, Any]] = {}
+    for f, rel in pairs:
+        source_kind = f.types.get(rel.source) or next((k for ff, k in defined.get(rel.source, []) if ff.language == f.language), "struct")
+        source_file = f if rel.source in f.types else next(
+            (ff for ff, _ in defined.get(rel.source, []) if ff.language == f.language), f)
+        source_id = node(rel.source, source_kind, source_file)
+        target_id = target_node(f, rel)
+        if source_id == target_id:
+            continue
+        edge_id = relationship_id(source_id, target_id, rel.relation)
+        edges.setdefault(edge_id, {
+            "id": edge_id,
+            "project_id": project_id,
+            "source_id": source_id,
+            "target_id": target_id,
+            "relation_type": rel.relation,
+            "confidence": CONFIDENCE["structural" if rel.via == "structural" else "declared"],
+        })
+    return list(entities.values()), list(edges.values())
+
+
+# Parsed files per project root, keyed by absolute path and reused while (mtime, size) is unchanged
+_types_cache: Dict[Path, Dict[Path, Tuple[Tuple[int, int], FileTypes]]] = {}
+
+
+def _cached_types(root: Path, path: Path, previous: Dict[Path, Tuple[Tuple[int, int], FileTypes]]):
+    """(stat key, types) for `path`, re-extracted only when its (mtime, size) changed; None when unreadable."""
+    try:
+        stat = path.stat()
+        key = (stat.st_mtime_ns, stat.st_size)
+        cached = previous.get(path)
+        if cached and cached[0] == key:
+            return cached
+        types = extract_file_types(path, path.read_text(errors="ignore"), path.relative_to(root).as_posix())
+    except (OSError, ValueError) as e:
+        logger.debug(f"Type graph: skipping {path}: {e}")
+        return None
+    return (key, types) if types else None
+
+
+def _qualify_rust(root: Path, types: FileTypes) -> None:
+    path = root / types.path
+    types.qualified = {name: rust_modules.qualify(rust_modules.module_path(root, path, line), name)
+                       for name, line in types.lines.items()}
+
+
+def build_type_graph(root: Path) -> List[FileTypes]:
+    previous, cache = _types_cache.get(root, {}), {}
+    for path in iter_source_files(root, TYPE_GRAPH_LANGUAGES):
+        entry = _cached_types(root, path, previous)
+        if entry:
+            cache[path] = entry
+    _types_cache[root] = cache
+    files = [types for _, types in cache.values()]
+    for types in files:
+        if types.language == "rust":
+            _qualify_rust(root, types)
+    return files
+
+
+def update_type_graph(root: Path, changed: Iterable[Path]) -> List[FileTypes]:
+    """The last type graph built for `root` with only `changed` files re-extracted (a full build the first time)."""
+    if root not in _types_cache:
+        return build_type_graph(root)
+    cache = _types_cache[root]
+    extensions = {ext for lang in TYPE_GRAPH_LANGUAGES if (spec := languages.get(lang)) for ext in spec.extensions}
+    for path in changed:
+        entry = _cached_types(root, path, cache) if path.suffix.lower() in extensions and path.is_file() else None
+        if entry:
+            cache[path] = entry
+            if entry[1].language == "rust":
+                _qualify_rust(root, entry[1])
+        else:
+            cache.pop(path, None)
+    return [types for _, types in cache.values()]
+
+
+def index_type_graph(root: Path, schema_store, changed: Optional[List[Path]] = None) -> Dict[str, int]:
+    """
+    Rebuilds the project's heuristic `implements`/`inherits` edges (imported SCIP edges are kept).
+    With `changed` files, only edges from or to the types they define or relate (before and after the edit) are replaced.
+    """
+    from side.intel.scip_import import drop_superseded
+
+    project_id = schema_store.engine.get_project_id()
+    sources = None
+    if changed is None:
+        files = build_type_graph(root)
+        entities, edges = type_graph_records(files, project_id)
+    else:
+        cached = _types_cache.get(root, {})
+        before = [cached[p][1] for p in changed if p in cached]
+        files = update_type_graph(root, changed)
+        paths = relative_paths(root, changed)
+        touched = before + [f for f in files if f.path in paths]
+        names = {name for f in touched for name in (*f.types, *f.methods)}
+        names |= {rel.source for f in touched for rel in f.relations}
+
+        entities, edges = type_graph_records(files, project_id)
+        named = {e["id"] for e in entities if e["name"] in names}
+        sources = named | {e["source_id"] for e in edges if e["target_id"] in named}
+        sources |= {e["id"] for e in schema_store.list_entities_in_files(project_id, sorted(paths))}
+        edges = [e for e in edges if e["source_id"] in sources]
+        endpoints = {e["source_id"] for e in edges} | {e["target_id"] for e in edges}
+        entities = [e for e in entities if e["id"] in endpoints or e["file_path"] in paths]
+        sources = sorted(sources)
+    edges = drop_superseded(schema_store, project_id, edges)
+
+    schema_store.save_entities_batch(entities)
+    for relation in TYPE_RELATIONS:
+        schema_store.delete_relationships(project_id, relation_type=relation, origin="index", source_ids=sources)
+    schema_store.save_relationships_batch(edges)
+
+    stats = {"files": len(files), "types": len(entities), "edges": len(edges)}
+    logger.info(f"🧬 [TYPE GRAPH]: {stats}")
+    return stats
@@ -8,8 +8,8 @@ import asyncio
 import logging
 from dataclasses import dataclass, field
 from pathlib import Path
-from typing import List, Dict, Any, Callable, Optional
-from concurrent.futures import ThreadPoolExecutor, as_completed
+from typing import List, Dict, Any, Callable, Optional, Tuple
+from concurrent.futures import ProcessPoolExecutor, ThreadPoolExecutor, as_completed
 import time
 
 logger = logging.getLogger(__name__)
@@ -47,9 +47,16 @@ class TaskDecomposer:
     - Aggregates results for unified report
     """
     
-    def __init__(self, max_workers: int = 4):
+    def __init__(self, max_workers: int = 4, use_processes: bool = False):
+        """
+        Args:
+            max_workers: Pool size
+            use_processes: CPU-bound work (parsing) runs in a process pool to escape the GIL;
+                worker functions and payloads must then be picklable (module-level functions)
+        """
         self.max_workers = max_workers
-        self.executor = ThreadPoolExecutor(max_workers=max_workers)
+        self.use_processes = use_processes
+        self.executor = ProcessPoolExecutor(max_workers=max_workers) if use_processes else ThreadPoolExecutor(max_workers=max_workers)
         self._metrics = {
             "total_decompositions": 0,
             "total_chunks_processed": 0,
@@ -113,7 +120,7 @@ class TaskDecomposer:
         worker_fn: Callable[[TaskChunk], Any]
     ) -> DecompositionResult:
         """
-        Execute chunks in parallel using thread pool.
+        Execute chunks in parallel using the thread (or process) pool.
         
         Args:
             chunks: List of TaskChunk objects
@@ -134,14 +141,15 @@ class TaskDecomposer:
         errors = []
         
         futures = {
-            self.executor.submit(self._execute_chunk, chunk, worker_fn): chunk
+            self.executor.submit(_execute_chunk, chunk, worker_fn): chunk
             for chunk in chunks
         }
         
         for future in as_completed(futures):
             chunk = futures[future]
             try:
-                result = future.result()
+                # Timing comes back with the result: a process worker only sees a copy of the chunk
+                result, chunk.duration_ms = future.result()
                 chunk.result = result
                 results.append(result)
                 completed += 1
@@ -173,13 +181,6 @@ class TaskDecomposer:
             errors=errors
         )
     
-    def _execute_chunk(self, chunk: TaskChunk, worker_fn: Callable) -> Any:
-        """Execute a single chunk and track timing."""
-        start = time.time()
-        result = worker_fn(chunk)
-        chunk.duration_ms = (time.time() - start) * 1000
-        return result
-    
     async def execute_parallel_async(
         self,
         chunks: List[TaskChunk],
@@ -232,6 +233,13 @@ class TaskDecomposer:
         self.executor.shutdown(wait=True)
 
 
+def _execute_chunk(chunk: TaskChunk, worker_fn: Callable) -> Tuple[Any, float]:
+    """Execute a single chunk and track timing (module-level so process pools can pickle it)."""
+    start = time.time()
+    result = worker_fn(chunk)
+    return result, (time.time() - start) * 1000
+
+
 # Global singleton for easy access
 _decomposer: Optional[TaskDecomposer] = None
 
@@ -22,6 +22,7 @@ from .intel.pattern_analyzer import PatternAnalyzer
 from .intel.rule_generator import RuleGenerator
 from .intel.system_awareness import SystemAwareness
 from .intel.log_monitor import LogMonitor
+from .intel import graph_query
 from .prompts import DynamicPromptManager, register_prompt_handlers
 import json
 import asyncio
@@ -199,6 +200,127 @@ def query_patterns(topic: str) -> str:
     results = engine.plans.get_patterns(topic)
     return json.dumps(results, indent=2)
 
+@mcp.tool()
+def find_callers(symbol: str, depth: int = 2) -> str:
+    """
+    Call Graph: Who calls this function or method, ranked by confidence.
+    cfg-gated Rust items say when they exist (e.g. "only exists with feature `postgres`").
+    Args:
+        symbol: Function or method name (`process_order`, `Order.total`, `Order::new`)
+        depth: How many call hops to follow upwards (default 2)
+    """
+    results = graph_query.find_callers(engine.schema, engine.get_project_id(), symbol, depth=depth)
+    return json.dumps(results, indent=2)
+
+@mcp.tool()
+def find_callees(symbol: str, depth: int = 2) -> str:
+    """
+    Call Graph: What this function or method calls, ranked by confidence.
+    Args:
+        symbol: Function or method name (`process_order`, `Order.total`, `Order::new`)
+        depth: How many call hops to follow downwards (default 2)
+    """
+    results = graph_query.find_callees(engine.schema, engine.get_project_id(), symbol, depth=depth)
+    return json.dumps(results, indent=2)
+
+@mcp.tool()
+def find_tests(symbol: str, depth: int = 2) -> str:
+    """
+    Test Map: Which tests exercise this function or method (cargo `#[test]`s, pytest functions,
+    vitest/jest `it()` blocks), directly or through the functions they call.
+    Args:
+        symbol: Function or method name (`add_item`, `Order.add_item`, `Order::add_item`)
+        depth: How many call hops between a test and the symbol to allow (default 2)
+    """
+    results = graph_query.find_tests(engine.schema, engine.get_project_id(), symbol, depth=depth)
+    return json.dumps(results, indent=2)
+
+@mcp.tool()
+def find_implementations(symbol: str) -> str:
+    """
+    Type Graph: Which types implement a trait/interface or inherit from a class.
+    Args:
+        symbol: Trait, interface or class name (e.g., "Repository")
+    """
+    results = graph_query.find_implementations(engine.schema, engine.get_project_id(), symbol)
+    return json.dumps(results, indent=2)
+
+@mcp.tool()
+def impact_of_change(target: str, depth: int = 3) -> str:
+    """
+    Blast Radius: Everything that transitively calls into a file or symbol.
+    Check this before editing shared code.
+    Args:
+        target: Project-relative file path (`src/orders.rs`) or symbol name
+        depth: How many call hops to follow (default 3)
+    """
+    results = graph_query.impact_of_change(engine.schema, engine.get_project_id(), target, depth=depth)
+    return json.dumps(results, indent=2)
+
+@mcp.tool()
+def diff_revisions(base: str, head: str = "HEAD") -> str:
+    """
+    Index Diff: Entities added/removed/changed, signature changes and new call
+    edges between two git revisions. Use it to review a generated branch.
+    Args:
+        base: Base revision (e.g., "main")
+        head: Revision to compare (default HEAD)
+    """
+    from .intel.index_diff import diff_revisions as build_diff
+    try:
+        results = build_diff(Path.cwd(), base, head)
+    except ValueError as e:
+        return json.dumps({"error": str(e)})
+    return json.dumps(results, indent=2)
+
+@mcp.tool()
+def check_api_break(base: str = "HEAD", head: str = "") -> str:
+    """
+    API Surface: Would this change break downstream users? Compares the public API
+    (`pub` Rust items, exported TS symbols, Python `__all__`) and classifies the change
+    as major (removed item, changed signature, new required trait bound), minor or patch.
+    Run it before editing library code that other crates or packages depend on.
+    Args:
+        base: Revision with the published API (default HEAD)
+        head: Revision to compare (default: the uncommitted working tree)
+    """
+    from .intel.api_surface import check_api_break as check
+    try:
+        results = check(Path.cwd(), base, head or None)
+    except ValueError as e:
+        return json.dumps({"error": str(e)})
+    return json.dumps(results, indent=2)
+
+@mcp.tool()
+def test_impact(since: str = "HEAD", depth: int = 3) -> str:
+    """
+    Test Impact: The minimal set of tests affected by uncommitted changes (or everything since
+    a revision), with ready-to-run `cargo test` / `pytest` / `vitest` command lines.
+    Run these after an edit instead of the whole suite.
+    Args:
+        since: Revision to compare the working tree with (default HEAD)
+        depth: How many call hops between a test and a changed function to allow (default 3)
+    """
+    from .intel.impacted_tests import affected_tests
+    try:
+        results = affected_tests(Path.cwd(), engine.schema, since, depth=depth)
+    except ValueError as e:
+        return json.dumps({"error": str(e)})
+    return json.dumps(results, indent=2)
+
+@mcp.tool()
+def list_routes(method: str = "", prefix: str = "") -> str:
+    """
+    Route Map: Every HTTP route the project serves (FastAPI/Starlette/Flask, Next.js app router,
+    Express, axum, actix-web) with its method, path, handler function and auth middleware.
+    Args:
+        method: Only routes answering this method (e.g., "POST")
+        prefix: Only paths starting with this prefix (e.g., "/api/")
+    """
+    from .intel.route_map import list_routes as stored_routes
+    results = stored_routes(engine.schema, engine.get_project_id(), method=method, prefix=prefix)
+    return json.dumps(results, indent=2)
+
 # ---------------------------------------------------------------------
 # INFRASTRUCTURE (Deployment & Health & Dashboard API)
 # ---------------------------------------------------------------------
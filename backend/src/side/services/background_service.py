@@ -10,6 +10,7 @@ from pathlib import Path
 from typing import List, Dict, Any
 
 from side.storage.modules.strategy import DecisionStore
+from side.intel.languages import source_extensions
 from side.utils.llm_helpers import extract_json
 from side.prompts import Personas, StrategicFrictionPrompt, LLMConfigs
 
@@ -38,11 +39,11 @@ class BackgroundService:
         active_plans = self.plans.list_plans(project_id, status="active")
         
         # 2. Scan for Technical Signals (TODO, HACK, FIXME)
-        extensions = {'.py', '.js', '.ts', '.tsx', '.go', '.rs', '.java'}
+        extensions = source_extensions()
         excludes = {'.git', 'node_modules', '__pycache__', 'venv', 'env', 'dist', 'build'}
         
         for path in self.project_path.rglob('*'):
-            if path.is_file() and path.suffix in extensions:
+            if path.is_file() and path.suffix.lower() in extensions:
                 if any(part in excludes for part in path.parts):
                     continue
                     
@@ -5,7 +5,9 @@ Analyzes file changes and git activity to understand what user is working on.
 """
 
 import asyncio
+import codecs
 import logging
+import re
 import subprocess
 from datetime import datetime, timezone
 from pathlib import Path
@@ -272,6 +274,60 @@ class ContextTracker:
         except:
             return {}
 
+    @staticmethod
+    def get_changed_lines(project_path: str | Path, since: str = "HEAD") -> dict[str, list[tuple[int, int]] | None]:
+        """
+        Lines of the working tree that differ from `since` (staged, unstaged and untracked).
+
+        Returns:
+            Project-relative path -> (first, last) line ranges on the working-tree side;
+            None when the whole file is new or deleted. A pure deletion touches the line before it.
+        """
+        project_path = Path(project_path).resolve()
+
+        def git(*args: str) -> str:
+            result = subprocess.run(["git", *args], cwd=project_path, capture_output=True, text=True, timeout=30)
+            if result.returncode != 0:
+                raise ValueError(result.stderr.strip() or f"git {args[0]} failed")
+            return result.stdout
+
+        try:
+            git("rev-parse", "--verify", "--quiet", f"{since}^{{commit}}")
+        except ValueError:
+            raise ValueError(f"Unknown revision: {since}") from None
+
+        def header_path(header: str, prefix: str) -> str | None:
+            """Path of a `--- a/...` / `+++ b/...` header; None for /dev/null."""
+            header = header.rstrip("\t")  # git appends a tab to names with spaces
+            if header.startswith('"') and header.endswith('"'):
+                # Names with quotes, backslashes or control characters stay C-quoted
+                header = codecs.escape_decode(header[1:-1].encode())[0].decode(errors="replace")
+            return header[len(prefix):] if header.startswith(prefix) else None
+
+        changes: dict[str, list[tuple[int, int]] | None] = {}
+        old_path = path = None
+        diff = git("-c", "core.quotePath=false", "diff", "-U0", "--no-color", "--no-ext-diff", "--no-renames",
+                   "--relative", since, "--")
+        for line in diff.splitlines():
+            if line.startswith("--- "):
+                old_path = header_path(line[4:], "a/")
+            elif line.startswith("+++ "):
+                path = header_path(line[4:], "b/")
+                if path is None or old_path is None:
+                    if path or old_path:
+                        changes[path or old_path] = None  # Added or deleted file
+                    path = None
+                else:
+                    changes.setdefault(path, [])
+            elif path and (hunk := re.match(r"@@ -\d+(?:,\d+)? \+(\d+)(?:,(\d+))? @@", line)):
+                start, count = int(hunk.group(1)), int(hunk.group(2) or 1)
+                changes[path].append((start, start + count - 1) if count else (max(start, 1), max(start, 1)))
+
+        for untracked in git("ls-files", "-z", "--others", "--exclude-standard").split("\0"):
+            if untracked:
+                changes[untracked] = None
+        return changes
+
     def _get_current_branch(self, project_path: Path) -> str | None:
         """Get current git branch."""
         try:
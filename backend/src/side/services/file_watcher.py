@@ -22,6 +22,7 @@ from watchdog.events import FileSystemEventHandler, FileSystemEvent
 from datetime import datetime, timedelta
 
 from side.utils.event_optimizer import event_bus, FrictionPoint, EventPriority
+from side.intel.languages import source_extensions
 
 logger = logging.getLogger(__name__)
 
@@ -103,7 +104,7 @@ class SmartFileWatcher(FileSystemEventHandler):
         Significant changes:
         - File creation/deletion (structure change)
         - Config file changes (.json, .yaml, .toml, .env)
-        - Code file changes (.py, .js, .ts, .tsx, .jsx)
+        - Code file changes (any registered LanguageSpec extension)
         """
         # Always significant: creation/deletion
         if event_type in {'created', 'deleted'}:
@@ -114,7 +115,7 @@ class SmartFileWatcher(FileSystemEventHandler):
             return True
         
         # Significant: Code changes
-        if path.suffix in {'.py', '.js', '.ts', '.tsx', '.jsx', '.go', '.rs', '.java'}:
+        if path.suffix.lower() in source_extensions():
             return True
         
         # Not significant: Other files
@@ -135,7 +136,7 @@ class SmartFileWatcher(FileSystemEventHandler):
         if path.suffix in {'.json', '.yaml', '.yml', '.toml', '.env'}:
             return EventPriority.CRITICAL
         
-        if path.suffix in {'.py', '.js', '.ts', '.tsx', '.jsx'}:
+        if path.suffix.lower() in source_extensions():
             return EventPriority.HIGH
         
         return EventPriority.NORMAL
@@ -90,6 +90,10 @@ class SocketListenerService:
                     # DataBuffer.ingest is already async and uses a lock, but we can fire-and-forget
                     # or await it since it's just a buffer append.
                     asyncio.create_task(self.buffer.ingest(category, payload))
+
+                    # Cargo runs wrapped by the shell hook ship their captured output
+                    if category == "shell" and payload.get("payload", {}).get("output_file"):
+                        asyncio.create_task(self._ingest_cargo_output(payload["payload"]))
                 except json.JSONDecodeError:
                     logger.warning(f"📡 [SOCKET]: Received malformed JSON signal: {line[:50]}...")
                 except Exception as e:
@@ -102,3 +106,37 @@ class SocketListenerService:
                 await writer.wait_closed()
             except Exception:
                 pass
+
+    async def _ingest_cargo_output(self, signal: Dict[str, Any]):
+        """Turns a captured `cargo build/check/test` output into rustc friction events."""
+        from side.terminal.cargo_diagnostics import read_captured_output, cargo_friction_events, mistake_repeat_payload
+        from side.utils.event_optimizer import event_bus, FrictionPoint, EventPriority
+
+        output = await asyncio.to_thread(read_captured_output, Path(signal["output_file"]))
+        if output is None:
+            return
+
+        events = cargo_friction_events(signal.get("command", ""), output, signal.get("cwd", ""), str(signal.get("exit_code", "")))
+        if not events:
+            return
+        project_id = await asyncio.to_thread(project_for_cwd, signal.get("cwd", ""))
+        for event in events:
+            await self.buffer.ingest("activity", dict(event, project_id=project_id))
+            await event_bus.emit(
+                FrictionPoint.AI_MISTAKE_REPEAT,
+                mistake_repeat_payload(project_id, event),
+                EventPriority.HIGH
+            )
+
+
+def project_for_cwd(cwd: str) -> str:
+    """Project id of the repository a shell command ran in; 'global' outside any project."""
+    from side.storage.modules.base import ContextEngine
+    from side.utils.paths import get_repo_root
+
+    if not cwd or not Path(cwd).is_dir():
+        return "global"
+    root = get_repo_root(cwd)
+    if not (root / ".git").exists() and not (root / ".side").exists():
+        return "global"  # get_repo_root falls back to the directory itself
+    return ContextEngine.get_project_id(root)
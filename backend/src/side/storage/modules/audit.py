@@ -443,13 +443,25 @@ class AuditService:
 
     def prune_stale_assets(self, root_path: Path, days: int = 30) -> int:
         """
-        Asset Purging: Deletes old .side/local.json files that haven't been modified in 'days'.
+        Asset Purging: Deletes directory indexes that haven't been modified in 'days',
+        both scattered .side/local.json files and nodes of the central .side/tree store.
         """
         count = 0
         cutoff = datetime.now() - timedelta(days=days)
         
         try:
             import os
+            from side.intel.index_store import CENTRAL_DIR
+
+            tree = Path(root_path) / ".side" / CENTRAL_DIR
+            if tree.is_dir():
+                # The tree directory itself stays: it is what keeps the project central
+                for node in tree.iterdir():
+                    if node.is_file() and datetime.fromtimestamp(node.stat().st_mtime) < cutoff:
+                        logger.info(f"🧹 [PURGE]: Removing stale context at {node}")
+                        node.unlink()
+                        count += 1
+
             for dirpath, dirnames, filenames in os.walk(root_path):
                 if ".side" in dirnames:
                     side_dir = Path(dirpath) / ".side"
@@ -463,6 +475,7 @@ class AuditService:
                             # If .side is empty, remove it
                             if not any(side_dir.iterdir()):
                                 side_dir.rmdir()
+                    dirnames.remove(".side")
         except Exception as e:
             logger.error(f"Purge Error: {e}")
             
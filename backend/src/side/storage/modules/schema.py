@@ -20,7 +20,7 @@ class SchemaStore:
         # [MIGRATION]: Drop old relationships to relax constraints
         try:
             # Check if FK exists by looking at schema
-            res = conn.execute("PRAGMA table_info(relationships)").fetchall()
+            res = conn.execute("PRAGMA foreign_key_list(relationships)").fetchall()
             if res:
                 # Simple approach: Drop and recreate since it's Phase 52 early dev
                 conn.execute("DROP TABLE IF EXISTS relationships")
@@ -34,17 +34,30 @@ class SchemaStore:
                 id TEXT PRIMARY KEY,
                 project_id TEXT NOT NULL DEFAULT 'default',
                 name TEXT NOT NULL,
-                entity_type TEXT NOT NULL, -- 'class', 'function', 'module', 'table'
+                entity_type TEXT NOT NULL, -- 'class', 'function', 'module', 'table', 'crate', 'feature'
                 file_path TEXT,
                 signature TEXT,
+                visibility TEXT, -- 'pub', 'pub(crate)', 'export', 'private', ...
+                doc TEXT,
+                qualified_name TEXT, -- 'shop::orders::Order::total', 'pkg.models.Cart.total', 'web/cart.ts::Cart.total'; identifies the entity
+                cfg TEXT, -- 'feature = "postgres"', 'test', 'all(unix, ...)' (Rust conditional compilation)
                 parent_id TEXT,
                 created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
                 FOREIGN KEY (parent_id) REFERENCES entities(id)
             )
         """)
+        # Migration: declaration details for the API surface, crate-qualified paths, cfg gates
+        import sqlite3
+        for column in ("visibility", "doc", "qualified_name", "cfg"):
+            try:
+                conn.execute(f"ALTER TABLE entities ADD COLUMN {column} TEXT")
+            except sqlite3.OperationalError:
+                pass
         conn.execute("CREATE INDEX IF NOT EXISTS idx_entities_project ON entities(project_id)")
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_entities_parent ON entities(parent_id)")
         conn.execute("CREATE INDEX IF NOT EXISTS idx_entities_type ON entities(entity_type)")
         conn.execute("CREATE INDEX IF NOT EXISTS idx_entities_name ON entities(name)")
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_entities_qualified ON entities(qualified_name)")
 
         # ─────────────────────────────────────────────────────────────
         # CORE TABLE 2: RELATIONSHIPS - The "Semantic Shadow" (Call Graph/Usage)
@@ -55,11 +68,17 @@ class SchemaStore:
                 project_id TEXT NOT NULL DEFAULT 'default',
                 source_id TEXT NOT NULL,
                 target_id TEXT NOT NULL,
-                relation_type TEXT NOT NULL, -- 'calls', 'uses', 'inherits', 'references'
+                relation_type TEXT NOT NULL, -- 'calls', 'uses', 'inherits', 'references', 'depends_on'
                 confidence REAL DEFAULT 1.0,
+                origin TEXT DEFAULT 'index', -- 'index' (heuristic passes) | 'scip' (compiler-grade import)
                 created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP
             )
         """)
+        # Migration: provenance, so SCIP edges survive heuristic rebuilds
+        try:
+            conn.execute("ALTER TABLE relationships ADD COLUMN origin TEXT DEFAULT 'index'")
+        except sqlite3.OperationalError:
+            pass
         conn.execute("CREATE INDEX IF NOT EXISTS idx_relationships_project ON relationships(project_id)")
         conn.execute("CREATE INDEX IF NOT EXISTS idx_relationships_source ON relationships(source_id)")
         conn.execute("CREATE INDEX IF NOT EXISTS idx_relationships_target ON relationships(target_id)")
@@ -80,37 +99,69 @@ class SchemaStore:
         conn.execute("CREATE INDEX IF NOT EXISTS idx_concepts_topic ON concepts(topic)")
         conn.execute("CREATE INDEX IF NOT EXISTS idx_concepts_category ON concepts(category)")
 
+        # ─────────────────────────────────────────────────────────────
+        # CORE TABLE 4: COVERAGE - Line coverage per entity, per commit
+        # ─────────────────────────────────────────────────────────────
+        conn.execute("""
+            CREATE TABLE IF NOT EXISTS coverage (
+                project_id TEXT NOT NULL DEFAULT 'default',
+                entity_id TEXT NOT NULL,
+                commit_sha TEXT NOT NULL, -- HEAD when the report was ingested ('unversioned' outside git)
+                covered_lines INTEGER NOT NULL,
+                total_lines INTEGER NOT NULL,
+                percent REAL NOT NULL,
+                source TEXT, -- 'lcov' | 'cobertura' | 'llvm-cov'
+                recorded_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+                PRIMARY KEY (project_id, entity_id, commit_sha)
+            )
+        """)
+        conn.execute("CREATE INDEX IF NOT EXISTS idx_coverage_entity ON coverage(entity_id)")
+
     def save_entities_batch(self, entities: List[Dict[str, Any]]) -> None:
-        """Batch save structural entities."""
-        with self.engine.connection() as conn:
-            for ent in entities:
-                conn.execute(
-                    """
-                    INSERT INTO entities (id, project_id, name, entity_type, file_path, signature, parent_id)
-                    VALUES (?, ?, ?, ?, ?, ?, ?)
-                    ON CONFLICT(id) DO UPDATE SET
-                        name = excluded.name,
-                        entity_type = excluded.entity_type,
-                        file_path = excluded.file_path,
-                        signature = excluded.signature
-                    """,
-                    (ent['id'], ent.get('project_id', 'default'), ent['name'], 
-                     ent['entity_type'], ent.get('file_path'), ent.get('signature'), ent.get('parent_id')),
-                )
+        """
+        Batch save structural entities.
+        Declaration details (signature, visibility, doc, qualified name, parent) are only
+        overwritten when provided, so graph passes that know just the name don't erase them.
+        `cfg` is replaced whenever the entity carries the key (None ungates it), so a removed
+        `#[cfg]` clears the gate while passes that don't read attributes keep it.
+        """
+        with self.engine.connection() as conn:
+            conn.executemany(
+                """
+                INSERT INTO entities (id, project_id, name, entity_type, file_path, signature, visibility, doc,
+                                      qualified_name, cfg, parent_id)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO UPDATE SET
+                    name = excluded.name,
+                    entity_type = excluded.entity_type,
+                    file_path = excluded.file_path,
+                    signature = COALESCE(excluded.signature, entities.signature),
+                    visibility = COALESCE(excluded.visibility, entities.visibility),
+                    doc = COALESCE(excluded.doc, entities.doc),
+                    qualified_name = COALESCE(excluded.qualified_name, entities.qualified_name),
+                    cfg = CASE WHEN ? THEN excluded.cfg ELSE entities.cfg END,
+                    parent_id = COALESCE(excluded.parent_id, entities.parent_id)
+                """,
+                ((ent['id'], ent.get('project_id', 'default'), ent['name'],
+                  ent['entity_type'], ent.get('file_path'), ent.get('signature'),
+                  ent.get('visibility'), ent.get('doc'), ent.get('qualified_name'), ent.get('cfg'),
+                  ent.get('parent_id'), 'cfg' in ent)
+                 for ent in entities),
+            )
 
     def save_relationships_batch(self, relationships: List[Dict[str, Any]]) -> None:
-        """Batch save entity relationships."""
-        with self.engine.connection() as conn:
-            for rel in relationships:
-                conn.execute(
-                    """
-                    INSERT INTO relationships (id, project_id, source_id, target_id, relation_type, confidence)
-                    VALUES (?, ?, ?, ?, ?, ?)
-                    ON CONFLICT(id) DO NOTHING
-                    """,
-                    (rel['id'], rel.get('project_id', 'default'), rel['source_id'], 
-                     rel['target_id'], rel['relation_type'], rel.get('confidence', 1.0)),
-                )
+        """Batch save entity relationships (one transaction)."""
+        with self.engine.connection() as conn:
+            conn.executemany(
+                """
+                INSERT INTO relationships (id, project_id, source_id, target_id, relation_type, confidence, origin)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(id) DO NOTHING
+                """,
+                ((rel['id'], rel.get('project_id', 'default'), rel['source_id'],
+                  rel['target_id'], rel['relation_type'], rel.get('confidence', 1.0), rel.get('origin', 'index'))
+                 for rel in relationships),
+            )
 
     def get_entity_by_name(self, project_id: str, name: str, entity_type: Optional[str] = None) -> Dict[str, Any] | None:
         """Fetch entity details by name."""
@@ -123,8 +174,77 @@ class SchemaStore:
             row = conn.execute(query, params).fetchone()
             return dict(row) if row else None
 
-    def list_relationships(self, source_id: Optional[str] = None, target_id: Optional[str] = None) -> List[Dict[str, Any]]:
-        """List relationships for an entity."""
+    def find_entities(self, project_id: str, name: str, entity_type: Optional[str] = None) -> List[Dict[str, Any]]:
+        """Fetch every entity sharing a name (e.g. methods of different types)."""
+        with self.engine.connection() as conn:
+            query = "SELECT * FROM entities WHERE project_id = ? AND name = ?"
+            params = [project_id, name]
+            if entity_type:
+                query += " AND entity_type = ?"
+                params.append(entity_type)
+            return [dict(row) for row in conn.execute(query, params).fetchall()]
+
+    def list_qualified_entities(self, project_id: str) -> List[Dict[str, Any]]:
+        """Every entity with a qualified name (project items of every language, Rust modules, tests, routes)."""
+        with self.engine.connection() as conn:
+            rows = conn.execute(
+                "SELECT * FROM entities WHERE project_id = ? AND qualified_name IS NOT NULL", (project_id,)
+            ).fetchall()
+            return [dict(row) for row in rows]
+
+    def list_entities(self, project_id: str, entity_type: str) -> List[Dict[str, Any]]:
+        """Every entity of one type (e.g. all `route`s of a project)."""
+        with self.engine.connection() as conn:
+            rows = conn.execute(
+                "SELECT * FROM entities WHERE project_id = ? AND entity_type = ?", (project_id, entity_type)
+            ).fetchall()
+            return [dict(row) for row in rows]
+
+    def find_entities_in_file(self, project_id: str, file_path: str) -> List[Dict[str, Any]]:
+        """Entities defined in a file, given as a project-relative path or a trailing part of one."""
+        with self.engine.connection() as conn:
+            rows = conn.execute(
+                "SELECT * FROM entities WHERE project_id = ? AND (file_path = ? OR file_path LIKE ?)",
+                (project_id, file_path, f"%/{file_path}"),
+            ).fetchall()
+            return [dict(row) for row in rows]
+
+    def list_entities_in_files(self, project_id: str, file_paths: List[str],
+                               entity_type: Optional[str] = None) -> List[Dict[str, Any]]:
+        """Entities stored for exactly these project-relative paths (what a per-file rebuild replaces)."""
+        results = []
+        with self.engine.connection() as conn:
+            for i in range(0, len(file_paths), 500):  # Stay under SQLite's bound-parameter limit
+                chunk = file_paths[i:i + 500]
+                query = f"SELECT * FROM entities WHERE project_id = ? AND file_path IN ({', '.join('?' * len(chunk))})"
+                params = [project_id, *chunk]
+                if entity_type:
+                    query += " AND entity_type = ?"
+                    params.append(entity_type)
+                results.extend(dict(row) for row in conn.execute(query, params).fetchall())
+        return results
+
+    def get_entity_by_id(self, entity_id: str) -> Dict[str, Any] | None:
+        """Fetch entity details by id."""
+        with self.engine.connection() as conn:
+            row = conn.execute("SELECT * FROM entities WHERE id = ?", (entity_id,)).fetchone()
+            return dict(row) if row else None
+
+    def get_entities_by_ids(self, entity_ids: List[str]) -> Dict[str, Dict[str, Any]]:
+        """Fetch many entities in one go, keyed by id; unknown ids are simply absent."""
+        results = {}
+        ids = list(dict.fromkeys(entity_ids))
+        with self.engine.connection() as conn:
+            for i in range(0, len(ids), 500):  # Stay under SQLite's bound-parameter limit
+                chunk = ids[i:i + 500]
+                rows = conn.execute(f"SELECT * FROM entities WHERE id IN ({', '.join('?' * len(chunk))})", chunk)
+                results.update((row["id"], dict(row)) for row in rows.fetchall())
+        return results
+
+    def list_relationships(self, source_id: Optional[str] = None, target_id: Optional[str] = None,
+                           project_id: Optional[str] = None, relation_type: Optional[str] = None,
+                           origin: Optional[str] = None) -> List[Dict[str, Any]]:
+        """List relationships for an entity, or all of a project's edges of one type."""
         with self.engine.connection() as conn:
             query = "SELECT * FROM relationships WHERE 1=1"
             params = []
@@ -134,10 +254,140 @@ class SchemaStore:
             if target_id:
                 query += " AND target_id = ?"
                 params.append(target_id)
+            if project_id:
+                query += " AND project_id = ?"
+                params.append(project_id)
+            if relation_type:
+                query += " AND relation_type = ?"
+                params.append(relation_type)
+            if origin:
+                query += " AND origin = ?"
+                params.append(origin)
             
             rows = conn.execute(query, params).fetchall()
             return [dict(row) for row in rows]
 
+    def list_relationships_to(self, project_id: str, target_ids: List[str], relation_type: Optional[str] = None,
+                              origin: Optional[str] = None) -> List[Dict[str, Any]]:
+        """A project's edges pointing at any of these entities (optionally of one type or origin)."""
+        query = "SELECT * FROM relationships WHERE project_id = ?"
+        params = [project_id]
+        if relation_type:
+            query += " AND relation_type = ?"
+            params.append(relation_type)
+        if origin:
+            query += " AND origin = ?"
+            params.append(origin)
+        results = []
+        with self.engine.connection() as conn:
+            for i in range(0, len(target_ids), 500):  # Stay under SQLite's bound-parameter limit
+                chunk = target_ids[i:i + 500]
+                rows = conn.execute(f"{query} AND target_id IN ({', '.join('?' * len(chunk))})", params + chunk)
+                results.extend(dict(row) for row in rows.fetchall())
+        return results
+
+    def delete_relationships(self, project_id: str, relation_type: Optional[str] = None,
+                             origin: Optional[str] = None, source_ids: Optional[List[str]] = None) -> int:
+        """Drop a project's edges (optionally of one type, origin or set of sources) before a rebuild."""
+        with self.engine.connection() as conn:
+            query = "DELETE FROM relationships WHERE project_id = ?"
+            params = [project_id]
+            if relation_type:
+                query += " AND relation_type = ?"
+                params.append(relation_type)
+            if origin:
+                query += " AND origin = ?"
+                params.append(origin)
+            if source_ids is None:
+                return conn.execute(query, params).rowcount
+            deleted = 0
+            for i in range(0, len(source_ids), 500):  # Stay under SQLite's bound-parameter limit
+                chunk = source_ids[i:i + 500]
+                deleted += conn.execute(f"{query} AND source_id IN ({', '.join('?' * len(chunk))})",
+                                        params + chunk).rowcount
+            return deleted
+
+    def delete_entities(self, project_id: str, entity_type: str, file_paths: Optional[List[str]] = None) -> int:
+        """Drop a project's entities of one type (optionally only those of some files) before a pass rebuilds them."""
+        with self.engine.connection() as conn:
+            query = "DELETE FROM entities WHERE project_id = ? AND entity_type = ?"
+            params = [project_id, entity_type]
+            if file_paths is None:
+                return conn.execute(query, params).rowcount
+            deleted = 0
+            for i in range(0, len(file_paths), 500):
+                chunk = file_paths[i:i + 500]
+                deleted += conn.execute(f"{query} AND file_path IN ({', '.join('?' * len(chunk))})",
+                                        params + chunk).rowcount
+            return deleted
+
+    def delete_entities_by_ids(self, entity_ids: List[str]) -> int:
+        """Drop these entities and every edge from or to them."""
+        deleted = 0
+        ids = list(dict.fromkeys(entity_ids))
+        with self.engine.connection() as conn:
+            for i in range(0, len(ids), 250):  # Bound twice below: stay under SQLite's parameter limit
+                chunk = ids[i:i + 250]
+                marks = ", ".join("?" * len(chunk))
+                conn.execute(f"DELETE FROM relationships WHERE source_id IN ({marks}) OR target_id IN ({marks})",
+                             chunk + chunk)
+                deleted += conn.execute(f"DELETE FROM entities WHERE id IN ({marks})", chunk).rowcount
+        return deleted
+
+    def delete_unreferenced_entities(self, project_id: str, entity_type: str, relation_type: str) -> int:
+        """Drop entities of one type that no `relation_type` edge points at any more."""
+        with self.engine.connection() as conn:
+            return conn.execute(
+                """
+                DELETE FROM entities WHERE project_id = ? AND entity_type = ? AND id NOT IN (
+                    SELECT target_id FROM relationships WHERE project_id = ? AND relation_type = ?
+                )
+                """,
+                (project_id, entity_type, project_id, relation_type),
+            ).rowcount
+
+    def save_coverage_batch(self, rows: List[Dict[str, Any]]) -> None:
+        """Batch save entity coverage; re-ingesting a commit replaces its numbers."""
+        with self.engine.connection() as conn:
+            conn.executemany(
+                """
+                INSERT INTO coverage (project_id, entity_id, commit_sha, covered_lines, total_lines, percent, source)
+                VALUES (?, ?, ?, ?, ?, ?, ?)
+                ON CONFLICT(project_id, entity_id, commit_sha) DO UPDATE SET
+                    covered_lines = excluded.covered_lines,
+                    total_lines = excluded.total_lines,
+                    percent = excluded.percent,
+                    source = excluded.source,
+                    recorded_at = CURRENT_TIMESTAMP
+                """,
+                ((row.get('project_id', 'default'), row['entity_id'], row['commit_sha'], row['covered_lines'],
+                  row['total_lines'], row['percent'], row.get('source'))
+                 for row in rows),
+            )
+
+    def get_latest_coverage(self, entity_ids: List[str]) -> Dict[str, Dict[str, Any]]:
+        """Most recently ingested coverage of each entity (entities never covered by a report are absent)."""
+        latest: Dict[str, Dict[str, Any]] = {}
+        with self.engine.connection() as conn:
+            for i in range(0, len(entity_ids), 500):  # Stay under SQLite's bound-parameter limit
+                chunk = entity_ids[i:i + 500]
+                rows = conn.execute(
+                    f"SELECT * FROM coverage WHERE entity_id IN ({', '.join('?' * len(chunk))}) "
+                    "ORDER BY recorded_at, rowid",
+                    chunk,
+                ).fetchall()
+                latest.update((row["entity_id"], dict(row)) for row in rows)
+        return latest
+
+    def coverage_history(self, entity_id: str) -> List[Dict[str, Any]]:
+        """Coverage of an entity per ingested commit, oldest first."""
+        with self.engine.connection() as conn:
+            rows = conn.execute(
+                "SELECT * FROM coverage WHERE entity_id = ? ORDER BY recorded_at, rowid",
+                (entity_id,)
+            ).fetchall()
+            return [dict(row) for row in rows]
+
     def save_concept(self, topic: str, content: str, category: str = 'general') -> None:
         """Saves or updates a high-level concept/goal."""
         import uuid
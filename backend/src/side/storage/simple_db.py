@@ -31,7 +31,7 @@ InsufficientTokensError = InsufficientTokensError
 class SimplifiedDatabase:
     """
     Privacy-first SQLite storage for Sidelith.
-    Access services directly: db.plans, db.profile, db.auditss, etc.
+    Access services directly: db.plans, db.profile, db.audits, etc.
     """
 
     def __init__(self, db_path: str | Path | None = None):
@@ -41,7 +41,7 @@ class SimplifiedDatabase:
         # Initialize services (The Source of Truth)
         self.profile = IdentityService(self.engine)
         self.plans = DecisionStore(self.engine)
-        self.auditss = AuditService(self.engine)
+        self.audits = AuditService(self.engine)
         self.ledger = Ledger(self.engine)
         self.operational = SessionCache(self.engine)
         self.goal_tracker = GoalTracker(self.engine)
@@ -58,7 +58,7 @@ class SimplifiedDatabase:
             self.operational.init_schema(conn)
             self.plans.init_schema(conn)
             self.profile.init_schema(conn)
-            self.auditss.init_schema(conn)
+            self.audits.init_schema(conn)
 
     def _run_migrations(self) -> None:
         """Handle CTO-level schema resilience."""
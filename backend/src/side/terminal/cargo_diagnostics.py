@@ -0,0 +1,273 @@
+"""
+Cargo Diagnostics - Structured rustc errors from `cargo build/check/test` runs.
+
+Reads the `--message-format=json` stream when present and falls back to the
+human-readable format otherwise. Every coded rustc error (E0382, E0502, ...)
+becomes a friction event carrying its span and the compiler's suggestion.
+"""
+
+import os
+import json
+import re
+import shlex
+import logging
+import tempfile
+import time
+from pathlib import Path
+from dataclasses import dataclass, field, asdict
+from typing import Any, Dict, List, Optional
+
+from side.tools.audit_adapters.clippy import collect_suggestions
+
+logger = logging.getLogger(__name__)
+
+CARGO_SUBCOMMANDS = {
+    "build": "build", "b": "build",
+    "check": "check", "c": "check",
+    "test": "test", "t": "test",
+    "clippy": "clippy",
+    "run": "run", "r": "run",
+}
+
+# Colors and progress-bar control sequences the hook keeps for the terminal (CARGO_TERM_COLOR=always)
+ANSI_RE = re.compile(r"\x1b\[[0-9;?]*[A-Za-z]")
+
+# Files written by the shell hook's cargo wrapper (see hooks/side_shell_hook.sh)
+CAPTURE_PREFIX = "side-cargo."
+MAX_CAPTURE_BYTES = 8 * 1024 * 1024
+
+# error[E0382]: borrow of moved value: `order`
+HEADER_RE = re.compile(r"^(?P<level>error|warning)(?:\[(?P<code>[^\]]+)\])?: (?P<message>.+)$")
+#   --> src/main.rs:9:20
+LOCATION_RE = re.compile(r"^\s*--> (?P<file>.+?):(?P<line>\d+):(?P<column>\d+)\s*$")
+#    |                    ^^^^^^^^^^^ value borrowed here after move
+PRIMARY_MARK_RE = re.compile(r"^\s*\d*\s*\|(?P<pad>\s*)(?P<marks>\^+)\s*(?P<label>.*)$")
+#    = help: consider borrowing here
+HELP_RE = re.compile(r"^\s*(?:= )?help: (?P<message>.+)$")
+#  8 |     consume(order.clone());
+SOURCE_LINE_RE = re.compile(r"^\s*(?P<line>\d+)\s*[|~+-] ?(?P<code>.*)$")
+
+
+@dataclass
+class RustDiagnostic:
+    code: str
+    level: str
+    message: str
+    file: Optional[str] = None
+    line: Optional[int] = None
+    column: Optional[int] = None
+    line_end: Optional[int] = None
+    column_end: Optional[int] = None
+    label: Optional[str] = None
+    suggestion: Optional[Dict[str, Any]] = None
+    notes: List[str] = field(default_factory=list)
+    source_format: str = "human"
+
+    @property
+    def fingerprint(self) -> str:
+        """Stable identity of "the same mistake": code plus location."""
+        return f"{self.code}:{self.file}:{self.line}"
+
+    def to_payload(self) -> Dict[str, Any]:
+        payload = asdict(self)
+        payload["explain"] = f"rustc --explain {self.code}" if re.fullmatch(r"E\d{4}", self.code) else None
+        payload["fingerprint"] = self.fingerprint
+        return payload
+
+
+def cargo_subcommand(command: str) -> Optional[str]:
+    """Returns 'build'/'check'/'test'/... when the shell command is a cargo compile, else None."""
+    try:
+        tokens = shlex.split(command)
+    except ValueError:
+        tokens = command.split()
+    # Skip env assignments (RUSTFLAGS=... cargo test) and wrappers
+    while tokens and ("=" in tokens[0] or tokens[0] in ("time", "env", "command")):
+        tokens = tokens[1:]
+    if not tokens or tokens[0].rsplit("/", 1)[-1] != "cargo":
+        return None
+    args = [t for t in tokens[1:] if not t.startswith("+")]  # cargo +nightly build
+    if not args:
+        return None
+    return CARGO_SUBCOMMANDS.get(args[0])
+
+
+def read_captured_output(path: Path) -> Optional[str]:
+    """
+    Reads and deletes a cargo output capture.
+    Only hook-created captures in the temp dir owned by this user are accepted,
+    since the path arrives over a local socket.
+    """
+    try:
+        path = Path(path)
+        allowed_dirs = {Path(tempfile.gettempdir()).resolve(), Path(os.environ.get("TMPDIR", "/tmp")).resolve()}
+        if not path.name.startswith(CAPTURE_PREFIX) or path.resolve().parent not in allowed_dirs:
+            logger.warning(f"Ignoring cargo capture outside the temp dir: {path}")
+            return None
+        stat = path.lstat()
+        if not path.is_file() or path.is_symlink() or stat.st_uid != os.getuid():
+            return None
+        with open(path, "r", errors="ignore") as f:
+            output = f.read(MAX_CAPTURE_BYTES)
+        path.unlink(missing_ok=True)
+        return output
+    except OSError as e:
+        logger.debug(f"Cargo output unavailable ({path}): {e}")
+        return None
+
+
+def parse_json_diagnostics(output: str) -> List[RustDiagnostic]:
+    """Parses cargo's `--message-format=json` stream."""
+    diagnostics = []
+    for raw_line in output.splitlines():
+        if not raw_line.lstrip().startswith("{"):
+            continue
+        try:
+            record = json.loads(raw_line)
+        except json.JSONDecodeError:
+            continue
+        if record.get("reason") != "compiler-message":
+            continue
+
+        message = record.get("message") or {}
+        code = (message.get("code") or {}).get("code")
+        if message.get("level") != "error" or not code:
+            continue
+        primary = next((s for s in message.get("spans", []) if s.get("is_primary")), {})
+
+        suggestion = None
+        suggestions = collect_suggestions(message)
+        if suggestions:
+            s = suggestions[0]
+            suggestion = {
+                "message": s["message"],
+                "original": s["original"],
+                "replacement": s["replacement"],
+                "line": s["line_start"],
+                "applicability": s["applicability"],
+            }
+        else:
+            help_text = next((c.get("message") for c in message.get("children", []) if c.get("level") == "help"), None)
+            if help_text:
+                suggestion = {"message": help_text, "replacement": None, "applicability": "Unspecified"}
+
+        diagnostics.append(RustDiagnostic(
+            code=code,
+            level="error",
+            message=message.get("message", ""),
+            file=primary.get("file_name"),
+            line=primary.get("line_start"),
+            column=primary.get("column_start"),
+            line_end=primary.get("line_end"),
+            column_end=primary.get("column_end"),
+            label=primary.get("label"),
+            suggestion=suggestion,
+            notes=[c.get("message", "") for c in message.get("children", []) if c.get("level") == "note"],
+            source_format="json",
+        ))
+    return diagnostics
+
+
+def parse_human_diagnostics(output: str) -> List[RustDiagnostic]:
+    """Parses rustc's human-readable output (the default `cargo build` format)."""
+    diagnostics: List[RustDiagnostic] = []
+    current: Optional[RustDiagnostic] = None
+    in_help = False
+
+    for line in output.splitlines():
+        header = HEADER_RE.match(line)
+        if header:
+            in_help = False
+            code = header.group("code")
+            if header.group("level") == "error" and code:
+                current = RustDiagnostic(code=code, level="error", message=header.group("message").strip())
+                diagnostics.append(current)
+            else:
+                current = None  # "aborting due to...", uncoded warnings
+            continue
+        if current is None:
+            continue
+
+        location = LOCATION_RE.match(line)
+        if location and current.file is None:
+            current.file = location.group("file")
+            current.line = current.line_end = int(location.group("line"))
+            current.column = int(location.group("column"))
+            continue
+
+        mark = PRIMARY_MARK_RE.match(line)
+        if mark and current.label is None and not in_help and current.column:
+            current.column_end = current.column + len(mark.group("marks"))
+            current.label = mark.group("label").strip() or None
+            continue
+
+        help_line = HELP_RE.match(line)
+        if help_line and current.suggestion is None:
+            current.suggestion = {"message": help_line.group("message").strip(), "replacement": None,
+                                  "applicability": "Unspecified"}
+            in_help = not line.lstrip().startswith("=")
+            continue
+
+        if line.lstrip().startswith(("note:", "= note:")):
+            current.notes.append(line.split("note:", 1)[1].strip())
+            in_help = False
+            continue
+
+        source = SOURCE_LINE_RE.match(line)
+        if in_help and source and current.suggestion.get("replacement") is None:
+            current.suggestion["replacement"] = source.group("code").strip()
+            current.suggestion["line"] = int(source.group("line"))
+
+    return diagnostics
+
+
+def plain_output(output: str) -> str:
+    """Output without colors; a progress bar redrawn with `\\r` leaves only the line written last."""
+    return "\n".join(line.rstrip("\r").rsplit("\r", 1)[-1] for line in ANSI_RE.sub("", output).split("\n"))
+
+
+def parse_cargo_output(output: str) -> List[RustDiagnostic]:
+    """Structured diagnostics from a cargo run, preferring the JSON stream when present."""
+    output = plain_output(output)
+    if '"reason":"compiler-message"' in output.replace(" ", ""):
+        return parse_json_diagnostics(output)
+    return parse_human_diagnostics(output)
+
+
+def cargo_friction_events(command: str, output: str, cwd: str, exit_code: str = "") -> List[Dict[str, Any]]:
+    """Activity records (one per rustc error) for a finished cargo run."""
+    subcommand = cargo_subcommand(command)
+    if not subcommand:
+        return []
+
+    events = []
+    seen = set()
+    captured_at = time.time()
+    for diag in parse_cargo_output(output):
+        if diag.fingerprint in seen:
+            continue  # Same error reported for lib and test targets
+        seen.add(diag.fingerprint)
+        payload = diag.to_payload()
+        payload.update({
+            "command": command,
+            "cwd": cwd,
+            "subcommand": subcommand,
+            "exit_code": exit_code,
+            "captured_at": captured_at,
+        })
+        events.append({"tool": "rustc", "action": "rustc_error", "payload": payload})
+
+    if events:
+        logger.info(f"🦀 [CARGO]: {len(events)} rustc error(s) from `{command}`")
+    return events
+
+
+def mistake_repeat_payload(project_id: str, event: Dict[str, Any]) -> Dict[str, Any]:
+    """Payload for FrictionPoint.AI_MISTAKE_REPEAT describing a rustc error."""
+    diag = event["payload"]
+    return {
+        "project_id": project_id,
+        "pattern": diag["code"],
+        "diagnostic": diag,
+        "previous_rejection": {"reason": f"{diag['code']}: {diag['message']}"},
+    }
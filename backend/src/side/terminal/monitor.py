@@ -5,6 +5,13 @@ import asyncio
 from pathlib import Path
 from typing import Optional
 from side.storage.simple_db import SimplifiedDatabase
+from side.terminal.cargo_diagnostics import (
+    CAPTURE_PREFIX,
+    cargo_subcommand,
+    cargo_friction_events,
+    mistake_repeat_payload,
+    read_captured_output,
+)
 
 logger = logging.getLogger(__name__)
 
@@ -47,10 +54,17 @@ class TerminalMonitor:
     async def _process_message(self, message: str):
         """
         Parses the shell hook message.
-        Expected format: "CMD|EXIT_CODE|CWD"
+        Expected format: "CMD|EXIT_CODE|CWD[|OUTPUT_FILE]"
+        OUTPUT_FILE is a captured copy of the command's output (cargo runs only).
         """
         try:
-            parts = message.split("|")
+            output_file = None
+            head, _, tail = message.rpartition("|")
+            if Path(tail).name.startswith(CAPTURE_PREFIX):
+                message, output_file = head, tail
+
+            # rsplit keeps pipes inside the command itself intact
+            parts = message.rsplit("|", 2)
             if len(parts) < 3:
                 return
 
@@ -75,10 +89,32 @@ class TerminalMonitor:
             if exit_code != "0":
                 print(f"🚨 [RUNTIME ERROR]: Command '{command}' failed with code {exit_code}")
                 # Future: Trigger auto-diagnostic or 'ask side'
+
+            if output_file and cargo_subcommand(command):
+                await self._ingest_cargo_output(command, exit_code, cwd, Path(output_file))
             
         except Exception as e:
             logger.error(f"Failed to process terminal message: {e}")
 
+    async def _ingest_cargo_output(self, command: str, exit_code: str, cwd: str, output_file: Path):
+        """Stores each rustc error of a cargo run as a friction event."""
+        from side.utils.event_optimizer import event_bus, FrictionPoint, EventPriority
+
+        output = read_captured_output(output_file)
+        if output is None:
+            return
+
+        events = cargo_friction_events(command, output, cwd, exit_code)
+        if not events:
+            return
+        self.db.audits.log_activities_batch([dict(e, project_id=self.project_id) for e in events])
+        for event in events:
+            await event_bus.emit(
+                FrictionPoint.AI_MISTAKE_REPEAT,
+                mistake_repeat_payload(self.project_id, event),
+                EventPriority.HIGH
+            )
+
     def stop(self):
         self.is_running = False
 
@@ -26,15 +26,12 @@ async def handle_run_audit(arguments: dict[str, Any]) -> str:
     """
     from side.tools.core import get_engine
     from side.intel.language_detector import detect_primary_languages
+    from side.intel.languages import registry as language_registry
     from side.tools.audit_adapters import (
         SemgrepAdapter, 
-        BanditAdapter, 
-        ESLintAdapter,
-        GosecAdapter,
-        SwiftLintAdapter,
-        DetektAdapter,
         DocVerifyAdapter,
-        DebtAdapter
+        DebtAdapter,
+        CoverageAdapter
     )
     
     category = arguments.get('category', 'general')
@@ -59,21 +56,14 @@ async def handle_run_audit(arguments: dict[str, Any]) -> str:
         DocVerifyAdapter(project_path),
         DebtAdapter(project_path)
     ]
-    
-    if "python" in languages:
-        adapters.append(BanditAdapter(project_path))
-    
-    if "javascript" in languages or "typescript" in languages:
-        adapters.append(ESLintAdapter(project_path))
-    
-    if "go" in languages:
-        adapters.append(GosecAdapter(project_path))
-        
-    if "swift" in languages:
-        adapters.append(SwiftLintAdapter(project_path))
-        
-    if "kotlin" in languages:
-        adapters.append(DetektAdapter(project_path))
+    # Coverage only speaks once a report has been ingested; it has nothing to JIT-install
+    coverage = CoverageAdapter(project_path, db.schema)
+    if coverage.is_available():
+        adapters.append(coverage)
+    
+    # Language-specific probes are declared on each LanguageSpec
+    for adapter_cls in language_registry.audit_adapter_classes(sorted(languages)):
+        adapters.append(adapter_cls(project_path))
     
     print(f"🛡️  [AUDIT]: Initiating scan across {', '.join(languages)}...")
     print(f"🎯 [FILTER]: Severity in {severity_filter}")
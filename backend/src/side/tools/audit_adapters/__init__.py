@@ -5,6 +5,9 @@ This package integrates proven open-source security tools:
 - Semgrep (universal, primary)
 - Bandit (Python-specific)
 - ESLint (JavaScript/TypeScript)
+- RustSec advisory-db (Rust, offline Cargo.lock audit)
+- Clippy (Rust lints)
+- Coverage (edits to untested code)
 
 Sidelith's value-add: LLM synthesis for remediation, not detection.
 """
@@ -16,8 +19,11 @@ from .eslint import ESLintAdapter
 from .gosec import GosecAdapter
 from .swiftlint import SwiftLintAdapter
 from .detekt import DetektAdapter
+from .cargo_audit import CargoAuditAdapter
+from .clippy import ClippyAdapter
 from .doc_verify import DocVerifyAdapter
 from .debt import DebtAdapter
+from .coverage import CoverageAdapter
 from .synthesizer import AuditSynthesizer
 
 __all__ = [
@@ -30,7 +36,10 @@ __all__ = [
     "GosecAdapter",
     "SwiftLintAdapter",
     "DetektAdapter",
+    "CargoAuditAdapter",
+    "ClippyAdapter",
     "DocVerifyAdapter",
     "DebtAdapter",
+    "CoverageAdapter",
     "AuditSynthesizer"
 ]
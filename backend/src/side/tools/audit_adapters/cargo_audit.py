@@ -0,0 +1,436 @@
+"""
+CargoAuditAdapter: Offline RustSec audit of Cargo.lock.
+
+Matches locked crate versions against a local checkout of the RustSec
+advisory database (https://github.com/rustsec/advisory-db), so audits work
+without network access. Output mirrors `cargo audit --json`.
+"""
+
+import json
+import logging
+import math
+import os
+import re
+import subprocess
+import tomllib
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+from .base import Finding, AuditAdapter, Severity
+
+logger = logging.getLogger(__name__)
+
+ADVISORY_DB_REPO = "https://github.com/rustsec/advisory-db.git"
+DEFAULT_ADVISORY_DB = Path.home() / ".cargo" / "advisory-db"
+LOCKFILE_EXCLUDE_DIRS = {".git", "target", "node_modules", ".side"}
+
+# Informational advisories are not vulnerabilities; map them explicitly.
+INFORMATIONAL_SEVERITY = {
+    "unsound": Severity.MEDIUM,
+    "unmaintained": Severity.LOW,
+    "notice": Severity.INFO,
+}
+
+# --- SEMVER ---
+
+_VERSION_RE = re.compile(r'^(\d+)(?:\.(\d+))?(?:\.(\d+))?(?:-([0-9A-Za-z.-]+))?(?:\+[0-9A-Za-z.-]+)?$')
+
+
+def parse_version(text: str) -> Tuple[int, int, int, Tuple]:
+    """Parses `1.2.3-alpha.1` into a sortable tuple. Missing parts default to 0."""
+    m = _VERSION_RE.match(text.strip())
+    if not m:
+        raise ValueError(f"Invalid version: {text}")
+    major, minor, patch, pre = m.groups()
+    # Releases sort after any pre-release of the same version.
+    pre_key = (1,) if pre is None else (0,) + tuple(
+        (0, int(p), "") if p.isdigit() else (1, 0, p) for p in pre.split(".")
+    )
+    return int(major), int(minor or 0), int(patch or 0), pre_key
+
+
+def _caret_upper(parts: List[int], given: int) -> Tuple[int, int, int]:
+    major, minor, patch = parts
+    if major > 0 or given == 1:
+        return major + 1, 0, 0
+    if minor > 0 or given == 2:
+        return 0, minor + 1, 0
+    return 0, 0, patch + 1
+
+
+def _comparator_matches(version: Tuple, comparator: str) -> bool:
+    m = re.match(r'^\s*(>=|<=|>|<|=|\^|~)?\s*([0-9][0-9A-Za-z.+-]*|\*)\s*$', comparator)
+    if not m:
+        raise ValueError(f"Invalid requirement: {comparator}")
+    op, raw = m.group(1) or "^", m.group(2)
+    if raw == "*":
+        return True
+
+    bound = parse_version(raw)
+    given = len(raw.split("-")[0].split("."))
+    base = version[:3]
+
+    if op == ">=":
+        return version >= bound
+    if op == ">":
+        return version > bound
+    if op == "<=":
+        return version <= bound
+    if op == "<":
+        return version < bound
+    if op == "=":
+        return base[:given] == bound[:given] if given < 3 else version == bound
+    if op == "~":
+        upper = (bound[0] + 1, 0, 0) if given == 1 else (bound[0], bound[1] + 1, 0)
+        return version >= bound and base < upper
+    # Caret (also the default for bare versions)
+    return version >= bound and base < _caret_upper(list(bound[:3]), given)
+
+
+def version_matches(version: str, requirement: str) -> bool:
+    """True if `version` satisfies a comma-separated Cargo requirement like `>= 1.2, < 2`."""
+    parsed = parse_version(version)
+    return all(_comparator_matches(parsed, c) for c in requirement.split(",") if c.strip())
+
+
+# --- CVSS ---
+
+_CVSS_WEIGHTS = {
+    "AV": {"N": 0.85, "A": 0.62, "L": 0.55, "P": 0.2},
+    "AC": {"L": 0.77, "H": 0.44},
+    "UI": {"N": 0.85, "R": 0.62},
+    "C": {"H": 0.56, "L": 0.22, "N": 0.0},
+    "I": {"H": 0.56, "L": 0.22, "N": 0.0},
+    "A": {"H": 0.56, "L": 0.22, "N": 0.0},
+}
+
+
+def cvss_base_score(vector: str) -> Optional[float]:
+    """Computes a CVSS v3.x base score from its vector string."""
+    try:
+        metrics = dict(part.split(":", 1) for part in vector.split("/")[1:])
+        changed = metrics["S"] == "C"
+        pr = {"N": 0.85, "L": 0.68 if changed else 0.62, "H": 0.5 if changed else 0.27}[metrics["PR"]]
+        w = {k: _CVSS_WEIGHTS[k][metrics[k]] for k in _CVSS_WEIGHTS}
+    except (KeyError, ValueError):
+        return None
+
+    iss = 1 - (1 - w["C"]) * (1 - w["I"]) * (1 - w["A"])
+    impact = 7.52 * (iss - 0.029) - 3.25 * (iss - 0.02) ** 15 if changed else 6.42 * iss
+    exploitability = 8.22 * w["AV"] * w["AC"] * pr * w["UI"]
+    if impact <= 0:
+        return 0.0
+    raw = min(1.08 * (impact + exploitability), 10) if changed else min(impact + exploitability, 10)
+    return math.ceil(raw * 10 - 1e-9) / 10
+
+
+def severity_from_cvss(vector: Optional[str]) -> Severity:
+    score = cvss_base_score(vector) if vector else None
+    if score is None:
+        return Severity.HIGH  # Unscored vulnerabilities are treated as HIGH until triaged
+    if score >= 9.0:
+        return Severity.CRITICAL
+    if score >= 7.0:
+        return Severity.HIGH
+    if score >= 4.0:
+        return Severity.MEDIUM
+    if score > 0:
+        return Severity.LOW
+    return Severity.INFO
+
+
+# --- ADVISORY DATABASE ---
+
+def load_advisory(path: Path) -> Optional[Dict[str, Any]]:
+    """
+    Loads a RustSec advisory. Supports the Markdown format (TOML front matter
+    in a ```toml fence, then `# Title` and description) and legacy `.toml` files.
+    """
+    text = path.read_text(errors="ignore")
+    title, description = "", ""
+    if path.suffix == ".md":
+        m = re.search(r'```toml\s*\n(.*?)\n```\s*(.*)', text, re.DOTALL)
+        if not m:
+            return None
+        front, body = m.group(1), m.group(2).strip()
+        heading = re.match(r'#\s+(.+?)\s*\n(.*)', body + "\n", re.DOTALL)
+        if heading:
+            title, description = heading.group(1), heading.group(2).strip()
+        data = tomllib.loads(front)
+    else:
+        data = tomllib.loads(text)
+
+    advisory = data.get("advisory", {})
+    if not advisory.get("id") or advisory.get("withdrawn"):
+        return None
+
+    versions = data.get("versions", {})
+    return {
+        "id": advisory["id"],
+        "package": advisory.get("package"),
+        "title": advisory.get("title", title),
+        "description": advisory.get("description", description),
+        "date": str(advisory.get("date", "")),
+        "aliases": list(advisory.get("aliases", [])),
+        "cvss": advisory.get("cvss"),
+        "url": advisory.get("url"),
+        "categories": list(advisory.get("categories", [])),
+        "informational": advisory.get("informational"),
+        "patched": list(versions.get("patched", advisory.get("patched_versions", []))),
+        "unaffected": list(versions.get("unaffected", advisory.get("unaffected_versions", []))),
+    }
+
+
+def load_advisory_db(db_path: Path, packages: Optional[set] = None) -> Dict[str, List[Dict[str, Any]]]:
+    """Indexes advisories by crate name. Restricts the walk to `packages` when given."""
+    crates_dir = db_path / "crates"
+    index: Dict[str, List[Dict[str, Any]]] = {}
+    if not crates_dir.is_dir():
+        return index
+
+    crate_dirs = [crates_dir / p for p in packages] if packages else list(crates_dir.iterdir())
+    for crate_dir in crate_dirs:
+        if not crate_dir.is_dir():
+            continue
+        for adv_file in sorted(crate_dir.glob("RUSTSEC-*")):
+            try:
+                advisory = load_advisory(adv_file)
+            except (OSError, tomllib.TOMLDecodeError) as e:
+                logger.debug(f"Skipping malformed advisory {adv_file}: {e}")
+                continue
+            if advisory:
+                index.setdefault(advisory["package"] or crate_dir.name, []).append(advisory)
+    return index
+
+
+def unparseable_requirements(version: str, advisory: Dict[str, Any]) -> List[str]:
+    """Patched/unaffected requirements that cannot be checked against `version` (all of them if it is unparseable)."""
+    failed = []
+    for req in advisory["patched"] + advisory["unaffected"]:
+        try:
+            version_matches(version, req)
+        except ValueError:
+            failed.append(req)
+    return failed
+
+
+def is_affected(version: str, advisory: Dict[str, Any]) -> bool:
+    """
+    A version is affected unless it matches a patched or unaffected requirement.
+    Requirements that can't be parsed don't clear it: the advisory is reported at
+    low confidence instead of being dropped.
+    """
+    for req in advisory["patched"] + advisory["unaffected"]:
+        try:
+            if version_matches(version, req):
+                return False
+        except ValueError as e:
+            logger.debug(f"Unparseable version data in {advisory['id']}: {e}")
+    return True
+
+
+def describe_affected(advisory: Dict[str, Any]) -> str:
+    safe = advisory["patched"] + advisory["unaffected"]
+    return f"all versions except {' | '.join(safe)}" if safe else "all versions"
+
+
+# --- LOCKFILE ---
+
+def read_lockfile(lock_path: Path) -> List[Dict[str, Any]]:
+    """Registry packages from Cargo.lock with the line of their `name = ...` entry."""
+    text = lock_path.read_text(errors="ignore")
+    data = tomllib.loads(text)
+    lines = text.splitlines()
+
+    packages = []
+    cursor = 0
+    for pkg in data.get("package", []):
+        name, version = pkg.get("name"), pkg.get("version")
+        line = 0
+        for i in range(cursor, len(lines)):
+            if lines[i].strip() == f'name = "{name}"' and i + 1 < len(lines) and version in lines[i + 1]:
+                line, cursor = i + 1, i + 1
+                break
+        # Workspace and path crates have no `source`; advisories only cover registry crates.
+        if not str(pkg.get("source", "")).startswith("registry+"):
+            continue
+        packages.append({"name": name, "version": version, "line": line})
+    return packages
+
+
+def audit_lockfile(lock_path: Path, db_path: Path, project_path: Optional[Path] = None) -> Dict[str, Any]:
+    """Builds a `cargo audit --json` shaped report for one lockfile."""
+    packages = read_lockfile(lock_path)
+    advisories = load_advisory_db(db_path, {p["name"] for p in packages})
+    try:
+        rel_lock = str(lock_path.relative_to(project_path)) if project_path else str(lock_path)
+    except ValueError:
+        rel_lock = str(lock_path)
+
+    vulnerabilities, warnings = [], []
+    for pkg in packages:
+        for advisory in advisories.get(pkg["name"], []):
+            if not is_affected(pkg["version"], advisory):
+                continue
+            entry = {
+                "advisory": {k: v for k, v in advisory.items() if k not in ("patched", "unaffected")},
+                "versions": {"patched": advisory["patched"], "unaffected": advisory["unaffected"]},
+                "package": {"name": pkg["name"], "version": pkg["version"]},
+                "location": {"file": rel_lock, "line": pkg["line"]},
+            }
+            unparsed = unparseable_requirements(pkg["version"], advisory)
+            if unparsed:
+                entry["versions"]["unparsed"] = unparsed
+            (warnings if advisory["informational"] else vulnerabilities).append(entry)
+
+    return {
+        "lockfile": {"path": rel_lock, "dependency-count": len(packages)},
+        "vulnerabilities": {"found": bool(vulnerabilities), "count": len(vulnerabilities), "list": vulnerabilities},
+        "warnings": {"advisories": warnings},
+    }
+
+
+class CargoAuditAdapter(AuditAdapter):
+    """
+    Adapter for RustSec (Rust Dependency Advisories).
+    Audits every Cargo.lock in the project against a local advisory-db checkout.
+    """
+
+    def __init__(self, project_path: Path, db_path: Optional[Path] = None):
+        super().__init__(project_path)
+        env_db = os.environ.get("SIDE_RUSTSEC_DB")
+        self.db_path = db_path or (Path(env_db).expanduser() if env_db else DEFAULT_ADVISORY_DB)
+
+    def get_tool_name(self) -> str:
+        return "cargo-audit"
+
+    def is_available(self) -> bool:
+        return (self.db_path / "crates").is_dir()
+
+    def get_install_instructions(self) -> str:
+        return f"git clone {ADVISORY_DB_REPO} {self.db_path}  (or set SIDE_RUSTSEC_DB)"
+
+    def install(self) -> bool:
+        """Clone the RustSec advisory database (one-time, then works offline)."""
+        try:
+            logger.info(f"📦 [INSTALL]: Cloning RustSec advisory-db into {self.db_path}...")
+            self.db_path.parent.mkdir(parents=True, exist_ok=True)
+            subprocess.run(["git", "clone", "--depth", "1", ADVISORY_DB_REPO, str(self.db_path)], check=True)
+            return self.is_available()
+        except Exception as e:
+            logger.error(f"❌ Failed to clone advisory-db: {e}")
+            return False
+
+    def _find_lockfiles(self) -> List[Path]:
+        lockfiles = []
+        for root, dirs, files in os.walk(self.project_path):
+            dirs[:] = [d for d in dirs if d not in LOCKFILE_EXCLUDE_DIRS]
+            if "Cargo.lock" in files:
+                lockfiles.append(Path(root) / "Cargo.lock")
+        return lockfiles
+
+    def parse_output(self, output: str) -> List[Finding]:
+        """Parses a `cargo audit --json` report into normalized Finding objects."""
+        if not output.strip():
+            return []
+
+        try:
+            data = json.loads(output)
+        except json.JSONDecodeError as e:
+            logger.error(f"❌ Failed to parse cargo-audit JSON: {e}")
+            return []
+
+        lock_path = data.get("lockfile", {}).get("path", "Cargo.lock")
+        entries = data.get("vulnerabilities", {}).get("list", [])
+        for kind_entries in data.get("warnings", {}).values():
+            if isinstance(kind_entries, list):
+                entries = entries + kind_entries
+
+        findings = []
+        for entry in entries:
+            try:
+                advisory = entry.get("advisory") or {}
+                package = entry.get("package", {})
+                versions = entry.get("versions", {})
+                location = entry.get("location", {})
+                patched = versions.get("patched", [])
+                unaffected = versions.get("unaffected", [])
+                unparsed = versions.get("unparsed", [])
+                aliases = advisory.get("aliases", [])
+                cves = [a for a in aliases if a.startswith("CVE-")]
+
+                informational = advisory.get("informational")
+                if informational:
+                    severity = INFORMATIONAL_SEVERITY.get(informational, Severity.INFO)
+                else:
+                    severity = severity_from_cvss(advisory.get("cvss"))
+
+                name, version = package.get("name"), package.get("version")
+                ids = ", ".join([advisory.get("id", "RUSTSEC")] + cves)
+                fix = (
+                    f"Upgrade {name} to a version matching: {' or '.join(patched)}"
+                    if patched else
+                    f"No patched release of {name}; replace the crate or vendor a fix."
+                )
+
+                finding = Finding(
+                    id=f"{advisory.get('id', 'RUSTSEC')}:{name}@{version}",
+                    project_id="default",
+                    category="dependency",
+                    title=advisory.get("title") or advisory.get("id", "RustSec advisory"),
+                    description=advisory.get("description", ""),
+                    tool="cargo-audit",
+                    rule_id=advisory.get("id", "unknown"),
+                    file_path=location.get("file", lock_path),
+                    line=location.get("line", 1) or 1,
+                    column=1,
+                    severity=severity,
+                    message=f"{name} {version}: {advisory.get('title', '')} ({ids})",
+                    code_snippet=f'name = "{name}"\nversion = "{version}"',
+                    cwe_id=None,
+                    owasp_category="A06:2021-Vulnerable and Outdated Components",
+                    # Version data that could not be checked may hide a patched release
+                    confidence="LOW" if unparsed else "HIGH",
+                    suggested_fix=fix,
+                    metadata={
+                        "advisory_id": advisory.get("id"),
+                        "aliases": aliases,
+                        "cve_ids": cves,
+                        "package": name,
+                        "installed_version": version,
+                        "patched_versions": patched,
+                        "unaffected_versions": unaffected,
+                        "unparsed_versions": unparsed,
+                        "affected_versions": describe_affected({"patched": patched, "unaffected": unaffected}),
+                        "informational": informational,
+                        "categories": advisory.get("categories", []),
+                        "cvss": advisory.get("cvss"),
+                        "url": advisory.get("url"),
+                    }
+                )
+                findings.append(finding)
+            except Exception as e:
+                logger.warning(f"⚠️ Failed to parse cargo-audit entry: {e}")
+                continue
+
+        return findings
+
+    async def scan(self, target_paths: Optional[List[Path]] = None) -> List[Finding]:
+        """Audits Cargo.lock files against the local advisory database."""
+        if not self.is_available():
+            logger.error(f"❌ RustSec advisory-db not available. {self.get_install_instructions()}")
+            return []
+
+        lockfiles = [p for p in (target_paths or []) if p.name == "Cargo.lock"] or self._find_lockfiles()
+        logger.info(f"🔍 [CARGO-AUDIT] Auditing {len(lockfiles)} lockfile(s) against {self.db_path}")
+
+        findings = []
+        for lock_path in lockfiles:
+            try:
+                report = audit_lockfile(lock_path, self.db_path, self.project_path)
+                findings.extend(self.parse_output(json.dumps(report)))
+            except Exception as e:
+                logger.error(f"❌ cargo-audit failed for {lock_path}: {e}")
+
+        logger.info(f"✅ [CARGO-AUDIT] Found {len(findings)} advisories")
+        return findings
@@ -0,0 +1,299 @@
+"""
+ClippyAdapter: Rust lint diagnostics via `cargo clippy --message-format=json`.
+
+Maps lint levels and lint groups onto Severity, turns rustc spans into
+file/line/column/snippet, and keeps rustc's suggested replacements in
+`metadata["suggestions"]` so fixes can be applied verbatim.
+"""
+
+import json
+import logging
+import re
+import subprocess
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from .base import Finding, AuditAdapter, Severity
+
+logger = logging.getLogger(__name__)
+
+GROUP_SEVERITY = {
+    "correctness": Severity.HIGH,
+    "suspicious": Severity.MEDIUM,
+    "perf": Severity.MEDIUM,
+    "complexity": Severity.LOW,
+    "style": Severity.LOW,
+    "pedantic": Severity.INFO,
+    "nursery": Severity.INFO,
+    "restriction": Severity.INFO,
+    "cargo": Severity.INFO,
+}
+
+# Groups for common lints whose diagnostics don't name their group.
+# Anything unlisted falls back to the group implied by the lint level.
+KNOWN_LINT_GROUPS = {
+    "correctness": [
+        "absurd_extreme_comparisons", "approx_constant", "eq_op", "erasing_op", "if_same_then_else",
+        "ifs_same_cond", "infinite_iter", "iter_next_loop", "let_underscore_lock", "never_loop",
+        "nonsensical_open_options", "not_unsafe_ptr_arg_deref", "out_of_bounds_indexing",
+        "panicking_unwrap", "self_assignment", "uninit_assumed_init", "unit_cmp", "while_immutable_condition",
+        "wrong_transmute", "zst_offset", "overly_complex_bool_expr", "mem_replace_with_uninit",
+    ],
+    "suspicious": [
+        "await_holding_lock", "await_holding_refcell_ref", "blanket_clippy_restriction_lints",
+        "empty_loop", "float_equality_without_abs", "suspicious_arithmetic_impl", "suspicious_else_formatting",
+        "suspicious_map", "suspicious_op_assign_impl", "suspicious_unary_op_formatting", "mut_range_bound",
+        "let_underscore_future", "unconditional_recursion", "suspicious_command_arg_space",
+    ],
+    "perf": [
+        "box_collection", "boxed_local", "cmp_owned", "expect_fun_call", "extend_with_drain",
+        "iter_nth", "large_enum_variant", "manual_memcpy", "manual_str_repeat", "map_entry",
+        "or_fun_call", "redundant_clone", "single_char_pattern", "slow_vector_initialization",
+        "to_string_in_format_args", "unnecessary_to_owned", "useless_vec", "vec_init_then_push",
+    ],
+    "complexity": [
+        "bind_instead_of_map", "bool_comparison", "clone_on_copy", "explicit_counter_loop",
+        "needless_borrow", "needless_lifetimes", "too_many_arguments", "type_complexity",
+        "unnecessary_cast", "unnecessary_unwrap", "useless_conversion", "manual_filter_map",
+    ],
+    "style": [
+        "needless_return", "needless_range_loop", "len_zero", "redundant_field_names",
+        "collapsible_if", "collapsible_else_if", "single_match", "new_without_default",
+        "question_mark", "manual_map", "match_like_matches_macro", "redundant_closure",
+        "let_and_return", "ptr_arg", "write_with_newline", "println_empty_string",
+    ],
+    "pedantic": [
+        "cast_possible_truncation", "cast_possible_wrap", "cast_sign_loss", "cast_precision_loss",
+        "doc_markdown", "missing_errors_doc", "missing_panics_doc", "module_name_repetitions",
+        "must_use_candidate", "needless_pass_by_value", "redundant_closure_for_method_calls",
+        "similar_names", "too_many_lines", "unreadable_literal", "wildcard_imports",
+        "items_after_statements", "match_same_arms", "inline_always", "used_underscore_binding",
+    ],
+    "restriction": [
+        "unwrap_used", "expect_used", "panic", "todo", "unimplemented", "indexing_slicing",
+        "print_stdout", "dbg_macro", "shadow_unrelated", "missing_docs_in_private_items",
+    ],
+}
+LINT_GROUPS = {lint: group for group, lints in KNOWN_LINT_GROUPS.items() for lint in lints}
+
+# e.g. "`#[warn(clippy::cast_lossless)]` implied by `#[warn(clippy::pedantic)]`"
+#      "`-W clippy::similar-names` implied by `-W clippy::pedantic`"
+IMPLIED_GROUP_RE = re.compile(r'implied by `(?:#\[\w+\(|-[WDF]\s*)clippy::([\w-]+)')
+
+
+def lint_group(lint: str, level: str, notes: List[str]) -> str:
+    """Resolves a clippy lint's group from its diagnostic notes, the known table, or its level."""
+    for note in notes:
+        m = IMPLIED_GROUP_RE.search(note)
+        if m and m.group(1).replace("-", "_") in GROUP_SEVERITY:
+            return m.group(1).replace("-", "_")
+    if lint in LINT_GROUPS:
+        return LINT_GROUPS[lint]
+    # correctness is the only group that is deny-by-default
+    return "correctness" if level == "error" else "style"
+
+
+def collect_suggestions(message: Dict[str, Any]) -> List[Dict[str, Any]]:
+    """Flattens rustc suggestions (children spans with `suggested_replacement`)."""
+    suggestions = []
+    for child in [message] + message.get("children", []):
+        for span in child.get("spans", []):
+            if span.get("suggested_replacement") is None:
+                continue
+            suggestions.append({
+                "message": child.get("message", ""),
+                "file": span.get("file_name"),
+                "line_start": span.get("line_start"),
+                "line_end": span.get("line_end"),
+                "column_start": span.get("column_start"),
+                "column_end": span.get("column_end"),
+                "byte_start": span.get("byte_start"),
+                "byte_end": span.get("byte_end"),
+                "original": "\n".join(t.get("text", "")[t.get("highlight_start", 1) - 1:t.get("highlight_end", 1) - 1]
+                                      for t in span.get("text", [])),
+                "replacement": span["suggested_replacement"],
+                "applicability": span.get("suggestion_applicability") or "Unspecified",
+            })
+    return suggestions
+
+
+def format_suggestion(suggestion: Dict[str, Any]) -> str:
+    """Human-readable exact replacement, e.g. for verify_fix output."""
+    location = f"{suggestion['file']}:{suggestion['line_start']}:{suggestion['column_start']}"
+    if suggestion.get("original"):
+        return f"{location}: replace `{suggestion['original']}` with `{suggestion['replacement']}`"
+    return f"{location}: insert `{suggestion['replacement']}`"
+
+
+class ClippyAdapter(AuditAdapter):
+    """
+    Adapter for Clippy (Rust Linter).
+    Catches correctness bugs, suspicious constructs and performance traps in Rust code.
+    """
+
+    def get_tool_name(self) -> str:
+        return "clippy"
+
+    def is_available(self) -> bool:
+        import shutil
+        if shutil.which("cargo") is None:
+            return False
+        try:
+            return subprocess.run(["cargo", "clippy", "--version"], capture_output=True).returncode == 0
+        except Exception:
+            return False
+
+    def get_install_instructions(self) -> str:
+        return "rustup component add clippy"
+
+    def install(self) -> bool:
+        """Attempt to install clippy via rustup."""
+        try:
+            logger.info("📦 [INSTALL]: Installing clippy via rustup...")
+            subprocess.run(["rustup", "component", "add", "clippy"], check=True)
+            return self.is_available()
+        except Exception as e:
+            logger.error(f"❌ Failed to install clippy: {e}")
+            return False
+
+    def _relative(self, file_name: str) -> Optional[str]:
+        """Project-relative path, or None for spans outside the project (registry, std)."""
+        path = Path(file_name)
+        if not path.is_absolute():
+            return file_name
+        try:
+            return str(path.resolve().relative_to(self.project_path.resolve()))
+        except ValueError:
+            return None
+
+    def parse_output(self, output: str) -> List[Finding]:
+        """Parses cargo's JSON-lines stream into normalized Finding objects."""
+        findings = []
+        seen = set()
+
+        for raw_line in output.splitlines():
+            if not raw_line.strip().startswith("{"):
+                continue
+            try:
+                record = json.loads(raw_line)
+            except json.JSONDecodeError:
+                continue
+            if record.get("reason") != "compiler-message":
+                continue
+
+            message = record.get("message") or {}
+            level = message.get("level", "warning")
+            code = (message.get("code") or {}).get("code")
+            if level not in ("error", "warning") or not code:
+                continue  # Skip summaries ("N warnings emitted") and uncoded notes
+
+            primary = next((s for s in message.get("spans", []) if s.get("is_primary")), None)
+            if not primary:
+                continue
+            rel_path = self._relative(primary.get("file_name", ""))
+            if rel_path is None:
+                continue
+
+            line, column = primary.get("line_start", 1), primary.get("column_start", 1)
+            key = (code, rel_path, line, column)
+            if key in seen:
+                continue  # Same diagnostic reported for lib and test targets
+            seen.add(key)
+
+            try:
+                notes = [c.get("message", "") for c in message.get("children", [])]
+                if code.startswith("clippy::"):
+                    lint = code.split("::", 1)[1]
+                    group = lint_group(lint, level, notes)
+                    severity = GROUP_SEVERITY.get(group, Severity.LOW)
+                    if level == "error" and severity not in (Severity.CRITICAL, Severity.HIGH):
+                        severity = Severity.HIGH  # Explicitly denied by the project
+                else:
+                    lint, group = code, "rustc"
+                    severity = Severity.HIGH if level == "error" else Severity.LOW
+
+                suggestions = collect_suggestions(message)
+                for s in suggestions:
+                    s["file"] = self._relative(s["file"] or "") or s["file"]
+                machine = [s for s in suggestions if s["applicability"] == "MachineApplicable"]
+                snippet = "\n".join(t.get("text", "") for t in primary.get("text", []))
+                help_text = next((c.get("message") for c in message.get("children", []) if c.get("level") == "help"), None)
+
+                finding = Finding(
+                    id=f"{code}:{rel_path}:{line}:{column}",
+                    project_id="default",
+                    category="lint",
+                    title=message.get("message", code),
+                    description=message.get("rendered") or message.get("message", ""),
+                    tool="clippy",
+                    rule_id=code,
+                    file_path=rel_path,
+                    line=line,
+                    column=column,
+                    severity=severity,
+                    message=message.get("message", ""),
+                    code_snippet=snippet,
+                    cwe_id=None,
+                    owasp_category=None,
+                    confidence="HIGH" if group in ("correctness", "rustc") else "MEDIUM",
+                    suggested_fix="\n".join(format_suggestion(s) for s in machine) or help_text,
+                    metadata={
+                        "lint": lint,
+                        "group": group,
+                        "level": level,
+                        "line_end": primary.get("line_end"),
+                        "column_end": primary.get("column_end"),
+                        "label": primary.get("label"),
+                        "notes": notes,
+                        "suggestions": suggestions,
+                        "machine_applicable": bool(machine),
+                        "rendered": message.get("rendered"),
+                    }
+                )
+                findings.append(finding)
+            except Exception as e:
+                logger.warning(f"⚠️ Failed to parse clippy diagnostic: {e}")
+                continue
+
+        return findings
+
+    async def scan(self, target_paths: Optional[List[Path]] = None) -> List[Finding]:
+        """Runs clippy across the workspace (all targets) and parses the JSON stream."""
+        if not self.is_available():
+            logger.error(f"❌ Clippy not available. {self.get_install_instructions()}")
+            return []
+
+        cmd = [
+            "cargo", "clippy",
+            "--workspace",
+            "--all-targets",
+            "--message-format=json",
+            "--quiet",
+        ]
+
+        logger.info(f"🔍 [CLIPPY] Running scan: {' '.join(cmd)}")
+
+        try:
+            result = subprocess.run(
+                cmd,
+                capture_output=True,
+                text=True,
+                cwd=self.project_path
+            )
+
+            # Cargo exits 101 when compilation (or a denied lint) fails; diagnostics are still emitted.
+            if result.returncode not in [0, 101]:
+                logger.error(f"❌ Clippy failed with exit code {result.returncode}: {result.stderr}")
+                return []
+
+            findings = self.parse_output(result.stdout)
+            if target_paths:
+                targets = {self._relative(str(p)) for p in target_paths}
+                findings = [f for f in findings if any(f.file_path.startswith(t) for t in targets if t)]
+            logger.info(f"✅ [CLIPPY] Found {len(findings)} issues")
+
+            return findings
+
+        except Exception as e:
+            logger.error(f"❌ Clippy execution failed: {e}")
+            return []
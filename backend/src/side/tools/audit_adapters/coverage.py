@@ -0,0 +1,95 @@
+"""
+CoverageAdapter: flags edits to code that no test exercises.
+
+Uses the coverage ingested by `side coverage` (lcov, Cobertura, llvm-cov JSON)
+and the working tree's changes since HEAD: every function or method edited
+whose latest recorded coverage is below the threshold becomes a finding.
+"""
+
+import logging
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from .base import Finding, AuditAdapter, Severity
+
+logger = logging.getLogger(__name__)
+
+
+class CoverageAdapter(AuditAdapter):
+    """
+    Adapter for Sidelith's coverage map.
+    Catches untested code being changed, before the change ships without a test.
+    """
+
+    def __init__(self, project_path: Path, schema_store=None, since: str = "HEAD"):
+        super().__init__(project_path)
+        self.schema_store = schema_store
+        self.since = since
+
+    def _schema(self):
+        if self.schema_store is None:
+            from side.tools.core import get_engine
+            self.schema_store = get_engine().schema
+        return self.schema_store
+
+    def get_tool_name(self) -> str:
+        return "sidelith-coverage"
+
+    def is_available(self) -> bool:
+        """Only meaningful once a coverage report has been ingested."""
+        try:
+            with self._schema().engine.connection() as conn:
+                return conn.execute("SELECT 1 FROM coverage LIMIT 1").fetchone() is not None
+        except Exception:
+            return False
+
+    def get_install_instructions(self) -> str:
+        return "cargo llvm-cov --lcov --output-path lcov.info (or pytest --cov --cov-report=xml), then: side coverage"
+
+    def install(self) -> bool:
+        return False
+
+    async def scan(self, target_paths: Optional[List[Path]] = None) -> List[Finding]:
+        from side.intel.coverage import untested_changes
+        try:
+            warnings = untested_changes(self.project_path, self._schema(), self.since)
+        except ValueError as e:
+            logger.warning(f"⚠️ Coverage audit skipped: {e}")
+            return []
+        if target_paths:
+            wanted = {Path(p).resolve() for p in target_paths}
+            warnings = [w for w in warnings if (self.project_path / w["file"]).resolve() in wanted]
+        return self.parse_output(warnings)
+
+    def parse_output(self, warnings: List[Dict[str, Any]]) -> List[Finding]:
+        findings = []
+        for w in warnings:
+            untested = w["covered_lines"] == 0
+            title = (f"Untested {w['type']} `{w['symbol']}` changed" if untested
+                     else f"Low-coverage {w['type']} `{w['symbol']}` changed ({w['percent']:g}%)")
+            findings.append(Finding(
+                id=f"UNTESTED_CHANGE:{w['file']}:{w['symbol']}",
+                project_id="default",
+                category="testing",
+                title=title,
+                description=(f"{w['covered_lines']} of {w['total_lines']} instrumented lines of `{w['symbol']}` "
+                             f"were covered as of {w['commit'][:7]}, and it has uncommitted changes."),
+                tool=self.get_tool_name(),
+                rule_id="UNTESTED_CHANGE",
+                file_path=w["file"],
+                line=w.get("line"),
+                column=1,
+                severity=Severity.MEDIUM if untested else Severity.LOW,
+                message=title,
+                confidence="MEDIUM",
+                suggested_fix=f"Add or extend a test exercising `{w['symbol']}`, then re-run coverage and `side coverage`.",
+                metadata={
+                    "symbol": w["symbol"],
+                    "entity_type": w["type"],
+                    "percent": w["percent"],
+                    "covered_lines": w["covered_lines"],
+                    "total_lines": w["total_lines"],
+                    "commit": w["commit"],
+                },
+            ))
+        return findings
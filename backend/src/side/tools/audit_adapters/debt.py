@@ -40,7 +40,8 @@ class DebtAdapter(AuditAdapter):
         }
         
         for category, items in raw_results.items():
-            if category == "scanned_at": continue
+            # Skip metadata and aggregate reports (scanned_at, rust_summary, rust_trend)
+            if not isinstance(items, list): continue
             
             severity = sev_map.get(category, Severity.INFO)
             
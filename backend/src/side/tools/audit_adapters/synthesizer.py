@@ -54,6 +54,7 @@ class AuditSynthesizer:
         # Prepare the context for the LLM
         findings_context = []
         for i, f in enumerate(findings):
+            exact_fix = self._exact_fix(f)
             findings_context.append(f"""
 FINDING #{i}:
 Tool: {f.tool}
@@ -64,7 +65,7 @@ Code:
 ```
 {f.code_snippet or "Snippet not available"}
 ```
-""")
+""" + (f"Exact Fix (machine-applicable, from {f.tool}):\n{exact_fix}\n" if exact_fix else ""))
 
         system_prompt = AuditSynthesisPrompt
 
@@ -86,7 +87,9 @@ Code:
             for i, data in enumerate(synthesis_data):
                 if i < len(findings):
                     findings[i].explanation = data.get("explanation")
-                    findings[i].suggested_fix = data.get("suggested_fix")
+                    # Tool-provided exact replacements beat LLM-written fixes
+                    if not self._exact_fix(findings[i]):
+                        findings[i].suggested_fix = data.get("suggested_fix")
                     
                     impact = data.get("strategic_impact")
                     if impact:
@@ -99,3 +102,12 @@ Code:
         except Exception as e:
             logger.warning(f"⚠️ Failed to parse LLM response for synthesis: {e}")
             return findings
+
+    @staticmethod
+    def _exact_fix(finding: Finding) -> str:
+        """Machine-applicable replacements carried in finding metadata (e.g. clippy)."""
+        from .clippy import format_suggestion
+        suggestions = (finding.metadata or {}).get("suggestions") or []
+        return "\n".join(
+            format_suggestion(s) for s in suggestions if s.get("applicability") == "MachineApplicable"
+        )
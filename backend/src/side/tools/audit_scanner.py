@@ -1,13 +1,35 @@
 import os
+import re
 import json
 import logging
 from pathlib import Path
-from typing import Dict, List, Any
+from typing import Dict, List, Any, Optional
 from datetime import datetime
 from side.utils.fast_ast import get_ast
+from side.intel.lexing import rust_module_path, strip_rust_noise
 
 logger = logging.getLogger(__name__)
 
+# Rust panic-surface and unsafe patterns, matched against comment/string-stripped code.
+# (type, result bucket, applies inside test code)
+RUST_DEBT_PATTERNS = [
+    ("UNSAFE_BLOCK", "technical_debt", True, re.compile(r'\bunsafe\s*\{')),
+    ("UNSAFE_FN", "technical_debt", True, re.compile(r'\bunsafe\s+(?:extern\s+"[^"]*"\s+)?fn\b')),
+    ("UNSAFE_IMPL", "technical_debt", True, re.compile(r'\bunsafe\s+impl\b')),
+    ("UNWRAP", "technical_debt", False, re.compile(r'\.unwrap\(\s*\)')),
+    ("EXPECT", "technical_debt", False, re.compile(r'\.expect\(')),
+    ("PANIC", "technical_debt", False, re.compile(r'\bpanic!\s*[(\[{]')),
+    ("TODO_MACRO", "placeholders", True, re.compile(r'\btodo!\s*[(\[{]')),
+    ("UNIMPLEMENTED", "placeholders", True, re.compile(r'\bunimplemented!\s*[(\[{]')),
+    ("ALLOW_SUPPRESSION", "technical_debt", True, re.compile(r'#!?\[\s*allow\s*\(([^\]]*)\)\s*\]')),
+]
+RUST_TEST_ATTR = re.compile(r'#\[\s*(?:cfg\s*\(\s*test\s*\)|(?:\w+::)*test\b)')
+RUST_CFG_ATTR = re.compile(r'#!?\[\s*cfg(?:_attr)?\s*\(|\bcfg!\s*\(')
+RUST_FEATURE_RE = re.compile(r'\bfeature\s*=\s*"([^"]+)"')
+RUST_SKIP_DIRS = {"target", ".git", "node_modules", ".side"}
+RUST_HISTORY_LIMIT = 50
+
+
 class DebtScanner:
     """
     Programmatic Technical Debt & Marker Auditor.
@@ -16,8 +38,9 @@ class DebtScanner:
     
     MARKERS = ["TODO", "FIXME", "HACK", "BUG", "XXX", "TEMP"]
     
-    def __init__(self, project_path: Path):
+    def __init__(self, project_path: Path, history_path: Optional[Path] = None):
         self.project_path = project_path
+        self.history_path = history_path or project_path / ".side" / "debt_history.json"
         self.results = {
             "critical": [],
             "technical_debt": [],
@@ -40,9 +63,166 @@ class DebtScanner:
             for path in full_path.rglob("*"):
                 if path.is_file() and path.suffix in [".py", ".ts", ".tsx", ".js"]:
                     self._audit_file(path)
+
+        # Rust pass: crate-aware, wherever the crates live
+        self._scan_rust_crates()
                     
         return self.results
 
+    def _find_rust_crates(self) -> List[Dict[str, Any]]:
+        """Every `[package]` manifest in the project (workspace members and standalone crates)."""
+        from side.intel.cargo_manifest import parse_crate
+
+        crates = []
+        for root, dirs, files in os.walk(self.project_path):
+            dirs[:] = [d for d in dirs if d not in RUST_SKIP_DIRS]
+            if "Cargo.toml" not in files:
+                continue
+            try:
+                crate = parse_crate(Path(root) / "Cargo.toml")
+            except Exception as e:
+                logger.debug(f"Scanner: Skip manifest in {root}: {e}")
+                continue
+            if crate:
+                from side.intel.rust_cfg import declared_features
+                crates.append({"name": crate.name, "root": Path(root), "features": declared_features(crate)})
+        return crates
+
+    def _scan_rust_crates(self):
+        """
+        Counts unsafe code, panic surface and lint suppressions per crate and module.
+        Files of `#[cfg(test)]`-gated modules count as test code.
+        """
+        from side.intel import rust_cfg, rust_modules
+
+        rust_modules.clear()  # Module gates come from the current `mod` items
+        crates = self._find_rust_crates()
+        if not crates:
+            return
+
+        summary: Dict[str, Any] = {}
+        crate_roots = {c["root"] for c in crates}
+        for crate in crates:
+            crate_summary = summary.setdefault(crate["name"], {"totals": {}, "modules": {}})
+            for root, dirs, files in os.walk(crate["root"]):
+                # Nested crates are scanned on their own
+                dirs[:] = [d for d in dirs if d not in RUST_SKIP_DIRS and Path(root) / d not in crate_roots]
+                for name in files:
+                    if not name.endswith(".rs"):
+                        continue
+                    path = Path(root) / name
+                    rel = path.relative_to(crate["root"])
+                    is_test_file = rel.parts[0] in ("tests", "benches", "examples")
+                    module = rust_module_path(crate["root"] / "src", path) if not is_test_file else f"{rel.parts[0]}::{path.stem}"
+                    test_only = is_test_file or rust_cfg.is_test_only(rust_modules.module_cfg(self.project_path, path))
+
+                    self._audit_file(path)
+                    counts = self._audit_rust(path, crate["name"], module, test_only, crate["features"])
+                    module_counts = crate_summary["modules"].setdefault(module, {})
+                    for kind, count in counts.items():
+                        module_counts[kind] = module_counts.get(kind, 0) + count
+                        crate_summary["totals"][kind] = crate_summary["totals"].get(kind, 0) + count
+
+        self.results["rust_summary"] = summary
+        self.results["rust_trend"] = self._track_rust_trend(summary)
+
+    def _audit_rust(self, path: Path, crate: str, module: str, is_test_file: bool = False,
+                    features: Optional[set] = None) -> Dict[str, int]:
+        """
+        Lexical Rust pass. unwrap/expect/panic! inside test code is not debt.
+        With the crate's `features`, cfg checks on features Cargo.toml doesn't declare are flagged.
+        """
+        counts: Dict[str, int] = {}
+        try:
+            original = path.read_text(errors='ignore').splitlines()
+            code = strip_rust_noise("\n".join(original)).splitlines()
+        except Exception as e:
+            logger.debug(f"Scanner: Skip {path}: {e}")
+            return counts
+
+        depth = 0
+        test_depth = None      # brace depth at which the current test region closes
+        pending_test = False   # saw #[cfg(test)] / #[test]; the next item opens a test region
+        for i, line in enumerate(code):
+            if RUST_TEST_ATTR.search(line):
+                pending_test = True
+            in_test = is_test_file or test_depth is not None or pending_test
+
+            if features is not None and RUST_CFG_ATTR.search(line) and i < len(original):
+                # Feature names are string literals, blanked in `code`
+                for feature in RUST_FEATURE_RE.findall(original[i]):
+                    if feature in features:
+                        continue
+                    counts["UNDECLARED_FEATURE"] = counts.get("UNDECLARED_FEATURE", 0) + 1
+                    self.results["technical_debt"].append({
+                        "file": str(path.relative_to(self.project_path)),
+                        "line": i + 1,
+                        "type": "UNDECLARED_FEATURE",
+                        "snippet": original[i].strip()[:100],
+                        "crate": crate,
+                        "module": module,
+                        "feature": feature,
+                    })
+
+            for kind, bucket, applies_in_tests, pattern in RUST_DEBT_PATTERNS:
+                if in_test and not applies_in_tests:
+                    continue
+                for m in pattern.finditer(line):
+                    counts[kind] = counts.get(kind, 0) + 1
+                    snippet = original[i].strip()[:100] if i < len(original) else ""
+                    self.results[bucket].append({
+                        "file": str(path.relative_to(self.project_path)),
+                        "line": i + 1,
+                        "type": kind,
+                        "snippet": snippet,
+                        "crate": crate,
+                        "module": module,
+                    })
+
+            opens, closes = line.count("{"), line.count("}")
+            if pending_test and opens:
+                test_depth, pending_test = depth, False
+            elif pending_test and line.strip().endswith(";"):
+                pending_test = False  # e.g. `#[cfg(test)] mod tests;` lives in another file
+            depth += opens - closes
+            if test_depth is not None and depth <= test_depth:
+                test_depth = None
+        return counts
+
+    def _track_rust_trend(self, summary: Dict[str, Any]) -> Dict[str, Any]:
+        """Appends crate totals to the scan history and returns deltas against the previous scan."""
+        history: List[Dict[str, Any]] = []
+        try:
+            if self.history_path.exists():
+                history = json.loads(self.history_path.read_text())
+        except Exception as e:
+            logger.debug(f"Scanner: Unreadable debt history {self.history_path}: {e}")
+
+        previous = history[-1]["crates"] if history else {}
+        trend = {}
+        for crate, data in summary.items():
+            before = previous.get(crate, {})
+            kinds = set(data["totals"]) | set(before)
+            trend[crate] = {
+                kind: {
+                    "current": data["totals"].get(kind, 0),
+                    "previous": before.get(kind, 0),
+                    "delta": data["totals"].get(kind, 0) - before.get(kind, 0),
+                }
+                for kind in sorted(kinds)
+            }
+
+        history.append({
+            "scanned_at": self.results["scanned_at"],
+            "crates": {crate: data["totals"] for crate, data in summary.items()},
+        })
+        try:
+            self.history_path.parent.mkdir(parents=True, exist_ok=True)
+            self.history_path.write_text(json.dumps(history[-RUST_HISTORY_LIMIT:], indent=2))
+        except Exception as e:
+            logger.debug(f"Scanner: Could not persist debt history: {e}")
+        return trend
+
     def _audit_file(self, path: Path):
         """Audits a single file for debt markers and structural placeholders."""
         try:
@@ -5,6 +5,7 @@ from typing import List, Dict, Any, Optional
 from side.tools.recursive_utils import partition, peek, grep, chunk_list
 from side.llm.client import LLMClient
 from side.intel.audit_allowlist import allowlist
+from side.intel.languages import source_extensions
 
 logger = logging.getLogger(__name__)
 
@@ -98,14 +99,15 @@ class AuditTool:
         return report, findings
 
     def _find_files(self, patterns: List[str] = None) -> List[Path]:
-        """Return a list of source code files."""
+        """Return a list of source code files (registered languages, plus docs)."""
         # Standard excludes
         excludes = {'.git', 'node_modules', '__pycache__', 'venv', 'env', '.DS_Store'}
+        suffixes = tuple(source_extensions() | {'.md'})
         files = []
         for root, dirs, filenames in os.walk(self.project_path):
             dirs[:] = [d for d in dirs if d not in excludes]
             for name in filenames:
-                if name.endswith(('.py', '.js', '.ts', '.tsx', '.go', '.rs', '.md')):
+                if name.lower().endswith(suffixes):
                     files.append(Path(root) / name)
         return files
 
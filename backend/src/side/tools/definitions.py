@@ -182,7 +182,7 @@ Returns: Stats on processed nodes.""",
             "properties": {
                 "finding_type": {
                     "type": "string",
-                    "description": "Type of finding to verify (e.g. 'Password Handling', 'File Length Limits')"
+                    "description": "Type of finding to verify (e.g. 'Password Handling', 'File Length Limits'), or a lint code such as 'clippy::needless_range_loop'"
                 },
                 "file_path": {
                     "type": "string",
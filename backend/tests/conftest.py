@@ -0,0 +1,120 @@
+"""
+Shared fixtures: throwaway projects built from a {relative path: content} dict,
+optionally committed to a real git repo and indexed into a fresh database.
+`project` lays out the requesting test module's `FILES`, `repo` commits its `BASE` then `HEAD`.
+"""
+import subprocess
+
+import pytest
+
+from side.intel import rust_modules
+from side.intel.tree_indexer import run_context_scan
+
+IGNORED = (".side/", ".sideignore", "graph.db*")
+
+# Two directory levels of Python plus a TypeScript file, with one cross-file call
+SOURCE_TREE = {
+    "src/orders.py": "class Order:\n    def total(self):\n        return 0\n",
+    "src/billing/invoice.py": "class Invoice:\n    def issue(self, order):\n        return order.total()\n",
+    "src/billing/tax.py": "def tax(amount):\n    return amount * 0.2\n",
+    "web/app.ts": "export function render(): void {}\n",
+}
+
+# The usual pytest layout: the same test name in several files
+SAME_NAMED_TESTS = {
+    "pkg/__init__.py": "",
+    "pkg/a.py": "def foo():\n    return 1\n",
+    "pkg/b.py": "def bar():\n    return 2\n",
+    "tests/test_a.py": "from pkg.a import foo\n\n\ndef test_basic():\n    assert foo() == 1\n",
+    "tests/test_b.py": "from pkg.b import bar\n\n\ndef test_basic():\n    assert bar() == 2\n",
+}
+
+
+def run_git(root, *args):
+    return subprocess.run(["git", *args], cwd=root, check=True, capture_output=True, text=True).stdout.strip()
+
+
+def write_files(root, files):
+    for rel, content in files.items():
+        path = root / rel
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+
+
+@pytest.fixture
+def git():
+    """`git(root, *args)` runs git in `root` and returns its stripped stdout."""
+    return run_git
+
+
+@pytest.fixture
+def commit():
+    """`commit(root, files, message)` writes and commits `files`, returning the new HEAD."""
+    def commit(root, files, message):
+        write_files(root, files)
+        run_git(root, "add", "-A")
+        run_git(root, "-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-qm", message)
+        return run_git(root, "rev-parse", "HEAD")
+    return commit
+
+
+@pytest.fixture
+def make_project(tmp_path, commit):
+    """`make_project(files, repo=False, ignore=(), name="project")` lays out `tmp_path/<name>`.
+
+    A bare `.git` marker makes it a project root. With `repo=True` it is a real
+    repo instead, the files committed as "base" and the index database (plus any
+    `ignore` patterns) kept out of `git status`.
+    """
+    def make(files, repo=False, ignore=(), name="project"):
+        root = tmp_path / name
+        if repo:
+            root.mkdir(parents=True)
+            run_git(root, "init", "-q")
+            gitignore = "".join(f"{pattern}\n" for pattern in (*IGNORED, *ignore))
+            commit(root, {**files, ".gitignore": gitignore}, "base")
+        else:
+            (root / ".git").mkdir(parents=True)
+            write_files(root, files)
+        rust_modules.clear()
+        return root
+    return make
+
+
+@pytest.fixture
+def project(request, make_project):
+    """The requesting test module's `FILES`, laid out by `make_project`."""
+    return make_project(request.module.FILES)
+
+
+@pytest.fixture
+def repo(request, make_project, git, commit):
+    """(root, base sha, head sha): the requesting test module's `BASE` committed, then its `HEAD` on top."""
+    root = make_project(request.module.BASE, repo=True)
+    base = git(root, "rev-parse", "HEAD")
+    return root, base, commit(root, request.module.HEAD, "head")
+
+
+@pytest.fixture
+def index_project():
+    """`index_project(root)` runs a full scan into `root/graph.db` and returns the engine."""
+    def index(root):
+        from side.storage.modules.base import ContextEngine
+
+        engine = ContextEngine(root / "graph.db")
+        run_context_scan(root, schema_store=engine.schema, workers=1)
+        return engine
+    return index
+
+
+@pytest.fixture
+def indexed(project, index_project):
+    """`project` after a full scan: (root, engine)."""
+    return project, index_project(project)
+
+
+@pytest.fixture
+def same_named_tests(make_project, index_project):
+    """An indexed repo where `tests/test_a.py` and `tests/test_b.py` both define `test_basic`: (root, engine)."""
+    root = make_project(SAME_NAMED_TESTS, repo=True)
+    return root, index_project(root)
@@ -0,0 +1,192 @@
+"""
+Test: Public API Surface
+
+Verifies the public surface of library crates (`pub` items behind `pub mod`s),
+TS packages (exports) and Python packages (`__all__`) is computed, that surfaces
+are stored per commit, and that `check_api_break` classifies changes as
+major/minor/patch.
+"""
+import pytest
+from unittest.mock import patch
+
+from side.intel import api_surface
+from side.intel.api_surface import check_api_break, split_bounds
+
+BASE = {
+    "storage/Cargo.toml": '[package]\nname = "storage"\nversion = "1.0.0"\n',
+    "storage/src/lib.rs": """pub mod repo;
+mod internal;
+
+pub fn connect(url: &str) -> Pool { Pool }
+
+pub fn legacy() {}
+
+pub struct Pool;
+
+impl Pool {
+    pub fn get(&self) -> u32 { 0 }
+    fn recycle(&self) {}
+}
+
+pub fn save<T: Serialize>(item: T) {}
+
+pub fn load<T: Clone + Send>(id: u32) -> Option<T> { None }
+
+#[cfg(test)]
+pub fn fixture() {}
+""",
+    "storage/src/repo.rs": """pub trait Repository: Send {
+    fn find(&self, id: u32) -> Option<u32>;
+    fn count(&self) -> usize { 0 }
+}
+""",
+    "storage/src/internal.rs": "pub fn helper() {}\n",
+    "tool/Cargo.toml": '[package]\nname = "tool"\nversion = "0.1.0"\n',
+    "tool/src/main.rs": "pub fn run() {}\nfn main() {}\n",
+    "web/package.json": '{"name": "@shop/web"}\n',
+    "web/src/client.ts": """export class Client {
+  public send(body: string): void {}
+  private retry(): void {}
+}
+
+export function connect(url: string): Client { return new Client(); }
+
+function local(): void {}
+""",
+    "web/src/client.test.ts": "export function fakeClient(): void {}\n",
+    "shop/__init__.py": 'from .orders import Order, total\n\n__all__ = ["Order", "total"]\n',
+    "shop/orders.py": """class Order:
+    def refund(self):
+        return 0
+
+    def _audit(self):
+        return 1
+
+
+def total(order):
+    return 0
+
+
+def internal():
+    return 0
+""",
+}
+
+HEAD = {
+    "storage/src/lib.rs": """pub mod repo;
+mod internal;
+
+pub fn connect(url: &str, timeout: u64) -> Pool { Pool }
+
+pub struct Pool;
+
+impl Pool {
+    pub fn get(&self) -> u32 { 0 }
+    fn recycle(&self) {}
+}
+
+pub fn save<T: Serialize + Send>(item: T) {}
+
+pub fn load<T: Clone + Send>(id: u32) -> Option<T> { None }
+""",
+    "storage/src/repo.rs": """pub trait Repository: Send + Sync {
+    fn find(&self, id: u32) -> Option<u32>;
+    fn count(&self) -> usize { 0 }
+    fn delete(&self, id: u32);
+}
+""",
+    "web/src/client.ts": """export class Client {
+  public send(body: string): void {}
+  private retry(): void {}
+}
+
+export function connect(url: string): Client { return new Client(); }
+
+export function disconnect(client: Client): void {}
+""",
+    "shop/__init__.py": 'from .orders import Order, total\n\n__all__ = ["Order"]\n',
+}
+
+
+def reasons(report):
+    return {c["path"]: (c["level"], c["reason"]) for c in report["changes"]}
+
+
+class TestApiSurface:
+    """Tests for API surface snapshots and semver classification."""
+
+    def test_surface(self, repo):
+        """Only items downstream users can name are part of the surface."""
+        root, base, _ = repo
+        items = api_surface.load_snapshot(root, base)["items"]
+        assert sorted(items) == [
+            "shop.Order", "shop.Order.refund", "shop.total",
+            "storage::Pool", "storage::Pool::get", "storage::connect", "storage::legacy", "storage::load",
+            "storage::repo::Repository", "storage::repo::Repository::count", "storage::repo::Repository::find",
+            "storage::save",
+            "web/src/client.ts::Client", "web/src/client.ts::Client.send", "web/src/client.ts::connect",
+        ]
+        assert items["storage::repo::Repository::find"]["required"] is True
+        assert items["storage::repo::Repository::count"]["required"] is False
+        assert items["shop.total"]["file"] == "shop/orders.py"
+        assert {items[k]["package"] for k in items} == {"storage", "@shop/web", "shop"}
+
+    def test_breaking_changes_are_major(self, repo):
+        """Removals, signature changes, new bounds and new required trait items are major."""
+        root, base, head = repo
+        report = check_api_break(root, base, head)
+        assert report["level"] == "major"
+        assert report["packages"] == {"@shop/web": "minor", "shop": "major", "storage": "major"}
+        assert reasons(report) == {
+            "storage::legacy": ("major", "removed"),
+            "shop.total": ("major", "removed"),
+            "storage::connect": ("major", "changed signature"),
+            "storage::save": ("major", "new required trait bound"),
+            "storage::repo::Repository": ("major", "new required trait bound"),
+            "storage::repo::Repository::delete": ("major", "new required trait item"),
+            "web/src/client.ts::disconnect": ("minor", "added"),
+        }
+        bounds = {c["path"]: c.get("bounds") for c in report["changes"]}
+        assert bounds["storage::save"] == ["T: Send"] and bounds["storage::repo::Repository"] == ["Self: Sync"]
+        assert "storage" in report["warning"] and "@shop/web" not in report["warning"]
+
+    def test_working_tree_minor_and_patch(self, repo):
+        """Uncommitted changes are compared with HEAD: additions and relaxed bounds are minor, bodies patch."""
+        root, _, _ = repo
+        lib = root / "storage/src/lib.rs"
+        lib.write_text(lib.read_text().replace("Option<T> { None }", "Option<T> { let _ = id; None }"))
+        assert check_api_break(root)["level"] == "patch"
+
+        lib.write_text(lib.read_text().replace("<T: Clone + Send>", "<T: Clone>") + "\npub fn ping() {}\n")
+        report = check_api_break(root)
+        assert report["head"] == "working tree" and report["level"] == "minor" and "warning" not in report
+        assert reasons(report) == {"storage::load": ("minor", "relaxed trait bound"), "storage::ping": ("minor", "added")}
+
+    def test_snapshots_stored_per_commit(self, repo):
+        """Scans leave snapshots alone; the first check stores them and later checks reuse them."""
+        from side.intel import rust_modules
+        from side.intel.tree_indexer import run_context_scan
+
+        root, base, head = repo
+        run_context_scan(root, workers=1)
+        assert not list((root / ".side/cache/api").glob("v*/*"))
+        trees = dict(rust_modules._trees)
+
+        check_api_break(root, base, head)
+        stored = sorted(p.name.split(".")[0] for p in (root / ".side/cache/api").glob("v*/*"))
+        assert stored == sorted([base, head])
+        assert rust_modules._trees == trees      # The indexer's trees survive, scratch checkouts leave none
+        with patch.object(api_surface, "build_snapshot") as build:
+            again = check_api_break(root, base, head)
+        build.assert_not_called()
+        assert again["level"] == "major"
+
+    def test_bounds_and_unknown_revision(self, repo):
+        """Bounds come from generics, supertraits and `where` clauses; bad revisions are errors."""
+        assert split_bounds("pub fn save<T>(item: T) where T: Serialize + Send") == (
+            "pub fn save<T> (item: T)", {"T: Serialize", "T: Send"},
+        )
+        assert split_bounds("pub struct Cache<K: Hash, V = String>")[1] == {"K: Hash"}
+        root, _, _ = repo
+        with pytest.raises(ValueError, match="Unknown revision: nope"):
+            check_api_break(root, "nope")
@@ -0,0 +1,279 @@
+"""
+Test: Call Graph Resolution
+
+Verifies calls are attributed to their enclosing function and resolved across
+files through imports / `use` paths for Python, TypeScript and Rust.
+"""
+import pytest
+from pathlib import Path
+
+from side.intel import call_graph, rust_modules
+from side.intel.call_graph import CONFIDENCE, build_call_graph, index_call_graph
+
+PYTHON_FILES = {
+    "app/__init__.py": "",
+    "app/models.py": """
+class Order:
+    def total(self):
+        return self._sum()
+
+    def _sum(self):
+        return 0
+
+    @classmethod
+    def create(cls):
+        return cls()
+""",
+    "app/billing.py": """
+from .models import Order
+from app import util as helpers
+
+
+def charge(order):
+    amount = order.total()
+    helpers.log_charge(amount)
+    return Order.create()
+""",
+    "app/util.py": """
+import os
+
+
+def log_charge(amount):
+    print(os.path.join("a", "b"))
+    return fmt(amount)
+
+
+def fmt(value):
+    return str(value)
+""",
+}
+
+TS_FILES = {
+    "web/src/api.ts": """
+export function fetchOrders(limit: number) {
+  return request('/orders', limit);
+}
+
+function request(url: string, limit: number) {
+  return fetch(url);
+}
+
+export default class Client {
+  send(path: string): Promise<void> {
+    return this.retry(path);
+  }
+
+  private retry(path: string) {
+    return fetchOrders(1);
+  }
+}
+""",
+    "web/src/page.tsx": """
+import Client, { fetchOrders as loadOrders } from './api';
+import * as api from "./api";
+
+const refresh = async () => {
+  await loadOrders(10);
+  return api.fetchOrders(5);
+};
+
+export function Page() {
+  const client = new Client();
+  client.send('/x');
+  return refresh();
+}
+""",
+}
+
+RUST_FILES = {
+    "shop/Cargo.toml": '[package]\nname = "shop"\nversion = "0.1.0"\n',
+    "shop/src/lib.rs": """
+pub mod model;
+pub mod pricing;
+
+use crate::model::{Order, Item as LineItem};
+use pricing::*;
+
+pub fn checkout(items: Vec<LineItem>) -> u32 {
+    let order = Order::new(items);
+    apply_discount(order.total())
+}
+""",
+    "shop/src/model.rs": """
+pub struct Item { pub price: u32 }
+pub struct Order { items: Vec<Item> }
+
+impl Order {
+    pub fn new(items: Vec<Item>) -> Self {
+        Self { items }
+    }
+
+    pub fn total(&self) -> u32 {
+        // helper() in a comment is not a call
+        self.items.iter().map(|i| i.price).sum::<u32>() + self.fees()
+    }
+
+    fn fees(&self) -> u32 { crate::pricing::base_fee() }
+}
+""",
+    "shop/src/pricing.rs": """
+pub fn apply_discount(total: u32) -> u32 {
+    total - base_fee()
+}
+
+pub fn base_fee() -> u32 { 2 }
+""",
+    "shop/tests/flow.rs": """
+use shop::checkout;
+
+#[test]
+fn checkout_empty() {
+    assert_eq!(checkout(vec![]), 0);
+}
+""",
+}
+
+SAME_NAMES = """
+class Cart:
+    def total(self):
+        return 1
+
+
+class Order:
+    def total(self):
+        return 2
+
+
+def checkout():
+    return Cart.total(None)
+"""
+
+
+def write_tree(root: Path, files):
+    for rel, content in files.items():
+        path = root / rel
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+
+
+def edges(graph):
+    """{(caller qualname, target qualname): confidence} from the resolved graph."""
+    result = {}
+    for call in graph.resolve():
+        caller = call.caller.qualname if call.caller else call.caller_file
+        key = (caller, call.target.qualname)
+        result[key] = max(result.get(key, 0), call.confidence)
+    return result
+
+
+class TestCallGraph:
+    """Tests for scope attribution and cross-file resolution."""
+
+    def test_python(self, tmp_path):
+        """Relative/aliased imports, classmethods and self calls resolve; stdlib calls are dropped."""
+        write_tree(tmp_path, PYTHON_FILES)
+        graph = edges(build_call_graph(tmp_path))
+
+        assert graph[("charge", "Order.create")] == CONFIDENCE["import"]
+        assert graph[("charge", "log_charge")] == CONFIDENCE["import"]
+        assert graph[("charge", "Order.total")] == CONFIDENCE["receiver"]
+        assert graph[("Order.total", "Order._sum")] == CONFIDENCE["scope"]
+        assert graph[("log_charge", "fmt")] == CONFIDENCE["scope"]
+        assert not any(target == "join" for _, target in graph)
+
+    def test_typescript(self, tmp_path):
+        """Named, default and namespace imports; arrow functions and methods are callers."""
+        write_tree(tmp_path, TS_FILES)
+        graph = edges(build_call_graph(tmp_path))
+
+        assert graph[("refresh", "fetchOrders")] == CONFIDENCE["import"]
+        assert graph[("fetchOrders", "request")] == CONFIDENCE["scope"]
+        assert graph[("Client.send", "Client.retry")] == CONFIDENCE["scope"]
+        assert graph[("Client.retry", "fetchOrders")] == CONFIDENCE["scope"]
+        assert graph[("Page", "refresh")] == CONFIDENCE["scope"]
+        assert graph[("Page", "Client.send")] == CONFIDENCE["receiver"]
+        assert ("request", "fetch") not in graph
+
+    def test_rust(self, tmp_path):
+        """`use` groups, aliases, globs, crate paths and integration tests resolve to the right items."""
+        write_tree(tmp_path, RUST_FILES)
+        graph = edges(build_call_graph(tmp_path))
+
+        assert graph[("checkout", "Order.new")] == CONFIDENCE["import"]
+        assert graph[("checkout", "apply_discount")] == CONFIDENCE["import"]
+        assert graph[("Order.total", "Order.fees")] == CONFIDENCE["scope"]
+        assert graph[("Order.fees", "base_fee")] == CONFIDENCE["import"]
+        assert graph[("apply_discount", "base_fee")] == CONFIDENCE["scope"]
+        assert graph[("checkout_empty", "checkout")] == CONFIDENCE["import"]
+        assert not any(target == "helper" for _, target in graph)
+
+    def test_persisted_graph(self, tmp_path):
+        """list_relationships returns function-to-function edges that point at stored entities."""
+        from side.storage.modules.base import ContextEngine
+
+        write_tree(tmp_path, RUST_FILES)
+        store = ContextEngine(tmp_path / "graph.db").schema
+        project_id = store.engine.get_project_id()
+        store.save_relationships_batch([{"id": "stale", "project_id": project_id, "source_id": "a",
+                                         "target_id": "b", "relation_type": "calls"}])
+
+        stats = index_call_graph(tmp_path, store)
+        assert stats["edges"] > 0
+
+        checkout = store.get_entity_by_name(project_id, "checkout", "function")
+        assert checkout["file_path"] == "shop/src/lib.rs"
+        targets = {store.get_entity_by_id(r["target_id"])["name"]: r["confidence"]
+                   for r in store.list_relationships(source_id=checkout["id"])}
+        assert targets == {"new": CONFIDENCE["import"], "apply_discount": CONFIDENCE["import"],
+                           "total": CONFIDENCE["receiver"]}
+        assert store.list_relationships(source_id="a") == []
+
+    def test_caches_follow_the_tree(self, tmp_path):
+        """Deleted files leave the symbol cache; an edited Cargo.toml renames the crate without a clear()."""
+        write_tree(tmp_path, RUST_FILES)
+        build_call_graph(tmp_path)
+        (tmp_path / "shop/src/pricing.rs").unlink()
+        graph = build_call_graph(tmp_path)
+        assert "shop/src/pricing.rs" not in graph.files
+        assert tmp_path / "shop/src/pricing.rs" not in call_graph._symbol_cache[tmp_path]
+
+        assert rust_modules.crate_name(tmp_path / "shop") == "shop"
+        (tmp_path / "shop/Cargo.toml").write_text('[package]\nname = "store-front"\nversion = "0.1.0"\n')
+        assert rust_modules.crate_name(tmp_path / "shop") == "store_front"
+
+    def test_same_names_stay_apart(self, make_project, index_project):
+        """Same-named methods of two classes are two entities, and calls land on the right one."""
+        root = make_project({"pkg/models.py": SAME_NAMES})
+        store = index_project(root).schema
+        project_id = store.engine.get_project_id()
+
+        totals = {e["qualified_name"]: e for e in store.find_entities(project_id, "total", "method")}
+        assert sorted(totals) == ["pkg.models.Cart.total", "pkg.models.Order.total"]
+        owners = {store.get_entity_by_id(e["parent_id"])["qualified_name"] for e in totals.values()}
+        assert owners == {"pkg.models.Cart", "pkg.models.Order"}
+
+        [checkout] = store.find_entities(project_id, "checkout", "function")
+        targets = [r["target_id"] for r in store.list_relationships(source_id=checkout["id"], relation_type="calls")]
+        assert targets == [totals["pkg.models.Cart.total"]["id"]]
+
+    def test_update_reresolves_callers(self, make_project, index_project):
+        """Editing a file relinks calls into it from files that did not change."""
+        from side.intel.tree_indexer import update_branch
+
+        root = make_project({
+            "pkg/__init__.py": "",
+            "pkg/a.py": "def foo():\n    return 1\n",
+            "pkg/b.py": "from pkg import a\n\n\ndef use_it():\n    a.foo()\n    a.bar()\n",
+        })
+        store = index_project(root).schema
+        project_id = store.engine.get_project_id()
+
+        def callees():
+            [use_it] = store.find_entities(project_id, "use_it", "function")
+            ids = [r["target_id"] for r in store.list_relationships(source_id=use_it["id"], relation_type="calls")]
+            return {e["qualified_name"] for e in store.get_entities_by_ids(ids).values()}
+
+        assert callees() == {"pkg.a.foo"}
+        (root / "pkg/a.py").write_text("def foo2():\n    return 1\n\n\ndef bar():\n    return 2\n")
+        update_branch(root, root / "pkg/a.py", schema_store=store)
+        assert callees() == {"pkg.a.bar"}
@@ -0,0 +1,156 @@
+"""
+Test: Offline RustSec Audit
+
+Verifies Cargo.lock matching against a local advisory-db checkout.
+"""
+import json
+import pytest
+from pathlib import Path
+from side.tools.audit_adapters.cargo_audit import (
+    CargoAuditAdapter,
+    audit_lockfile,
+    cvss_base_score,
+    version_matches,
+)
+
+LOCKFILE = """
+version = 3
+
+[[package]]
+name = "my-app"
+version = "0.1.0"
+dependencies = ["smallvec", "time"]
+
+[[package]]
+name = "smallvec"
+version = "1.6.0"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+
+[[package]]
+name = "time"
+version = "0.1.45"
+source = "registry+https://github.com/rust-lang/crates.io-index"
+"""
+
+SMALLVEC_ADVISORY = """```toml
+[advisory]
+id = "RUSTSEC-2021-0003"
+package = "smallvec"
+date = "2021-01-08"
+url = "https://github.com/servo/rust-smallvec/issues/252"
+categories = ["memory-corruption"]
+aliases = ["CVE-2021-25900", "GHSA-43w2-9j62-hq99"]
+cvss = "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"
+
+[versions]
+patched = [">= 0.6.14, < 1.0.0", ">= 1.6.1"]
+```
+
+# Buffer overflow in SmallVec::insert_many
+
+A bug in `SmallVec::insert_many` caused it to allocate a buffer that was too small.
+"""
+
+TIME_ADVISORY = """```toml
+[advisory]
+id = "RUSTSEC-2020-0071"
+package = "time"
+date = "2020-11-18"
+aliases = ["CVE-2020-26235"]
+cvss = "CVSS:3.1/AV:L/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H"
+
+[versions]
+patched = [">= 0.2.23"]
+unaffected = ["= 0.2.0", "= 0.2.1", "= 0.2.2"]
+```
+
+# Potential segfault in the time crate
+"""
+
+
+@pytest.fixture
+def advisory_db(tmp_path):
+    db = tmp_path / "advisory-db"
+    for crate, adv_id, text in [
+        ("smallvec", "RUSTSEC-2021-0003", SMALLVEC_ADVISORY),
+        ("time", "RUSTSEC-2020-0071", TIME_ADVISORY),
+    ]:
+        (db / "crates" / crate).mkdir(parents=True)
+        (db / "crates" / crate / f"{adv_id}.md").write_text(text)
+    return db
+
+
+class TestRustSecMatching:
+    """Tests for version requirements and advisory matching."""
+
+    def test_version_requirements(self):
+        """Cargo requirement operators should follow semver semantics."""
+        assert version_matches("1.6.1", ">= 1.6.1")
+        assert not version_matches("1.6.0", ">= 1.6.1")
+        assert version_matches("0.6.14", ">= 0.6.14, < 1.0.0")
+        assert version_matches("0.2.1", "= 0.2.1")
+        assert version_matches("0.3.9", "^0.3")
+        assert not version_matches("0.4.0", "^0.3")
+        assert version_matches("1.2.9", "~1.2.3")
+        assert not version_matches("1.0.0-rc.1", ">= 1.0.0")
+
+    def test_cvss_scores(self):
+        """CVSS v3.1 vectors should compute the published base scores."""
+        assert cvss_base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H") == 9.8
+        assert cvss_base_score("CVSS:3.1/AV:L/AC:H/PR:N/UI:N/S:U/C:N/I:N/A:H") == 5.1
+
+    def test_audit_lockfile_report(self, advisory_db, tmp_path):
+        """Affected registry packages are reported with ids, ranges and lockfile lines."""
+        lock = tmp_path / "Cargo.lock"
+        lock.write_text(LOCKFILE)
+
+        report = audit_lockfile(lock, advisory_db, tmp_path)
+        vulns = {v["advisory"]["id"]: v for v in report["vulnerabilities"]["list"]}
+
+        assert report["lockfile"]["dependency-count"] == 2  # my-app has no registry source
+        assert set(vulns) == {"RUSTSEC-2021-0003", "RUSTSEC-2020-0071"}
+        smallvec = vulns["RUSTSEC-2021-0003"]
+        assert smallvec["advisory"]["aliases"][0] == "CVE-2021-25900"
+        assert smallvec["advisory"]["title"] == "Buffer overflow in SmallVec::insert_many"
+        assert smallvec["versions"]["patched"] == [">= 0.6.14, < 1.0.0", ">= 1.6.1"]
+        assert LOCKFILE.splitlines()[smallvec["location"]["line"] - 1] == 'name = "smallvec"'
+
+    def test_patched_version_is_clean(self, advisory_db, tmp_path):
+        """A lockfile on patched versions should produce no vulnerabilities."""
+        lock = tmp_path / "Cargo.lock"
+        lock.write_text(LOCKFILE.replace('"1.6.0"', '"1.6.1"').replace('"0.1.45"', '"0.3.36"'))
+
+        report = audit_lockfile(lock, advisory_db, tmp_path)
+        assert report["vulnerabilities"]["count"] == 0
+
+    def test_parse_output_findings(self, advisory_db, tmp_path):
+        """The adapter turns the report into Findings with severity and fix metadata."""
+        lock = tmp_path / "Cargo.lock"
+        lock.write_text(LOCKFILE)
+        adapter = CargoAuditAdapter(tmp_path, db_path=advisory_db)
+
+        findings = adapter.parse_output(json.dumps(audit_lockfile(lock, advisory_db, tmp_path)))
+        by_rule = {f.rule_id: f for f in findings}
+
+        assert by_rule["RUSTSEC-2021-0003"].severity == "CRITICAL"
+        assert by_rule["RUSTSEC-2020-0071"].severity == "MEDIUM"
+        assert by_rule["RUSTSEC-2021-0003"].metadata["cve_ids"] == ["CVE-2021-25900"]
+        assert by_rule["RUSTSEC-2020-0071"].metadata["unaffected_versions"] == ["= 0.2.0", "= 0.2.1", "= 0.2.2"]
+        assert by_rule["RUSTSEC-2021-0003"].confidence == "HIGH"
+        assert "too small" in by_rule["RUSTSEC-2021-0003"].description
+
+    def test_unparseable_versions_stay_reported(self, advisory_db, tmp_path):
+        """Version data that can't be checked keeps the advisory, at low confidence."""
+        advisory = advisory_db / "crates" / "smallvec" / "RUSTSEC-2021-0003.md"
+        advisory.write_text(SMALLVEC_ADVISORY.replace('">= 1.6.1"', '">= 1.6.1.post"'))
+        lock = tmp_path / "Cargo.lock"
+        lock.write_text(LOCKFILE.replace('"1.6.0"', '"1.6.1"').replace('"0.1.45"', '"0.1.45.1"'))
+
+        report = audit_lockfile(lock, advisory_db, tmp_path)
+        findings = CargoAuditAdapter(tmp_path, db_path=advisory_db).parse_output(json.dumps(report))
+        by_rule = {f.rule_id: f for f in findings}
+
+        assert set(by_rule) == {"RUSTSEC-2021-0003", "RUSTSEC-2020-0071"}
+        assert by_rule["RUSTSEC-2021-0003"].confidence == "LOW"
+        assert by_rule["RUSTSEC-2021-0003"].metadata["unparsed_versions"] == [">= 1.6.1.post"]
+        assert by_rule["RUSTSEC-2020-0071"].metadata["unparsed_versions"] == [">= 0.2.23", "= 0.2.0", "= 0.2.1", "= 0.2.2"]
@@ -0,0 +1,219 @@
+"""
+Test: Cargo Diagnostic Ingestion
+
+Verifies rustc errors from captured cargo runs become structured friction events
+and that repeated error codes reach handle_ai_mistake_repeat.
+"""
+import asyncio
+import json
+import os
+import subprocess
+import tempfile
+import pytest
+from pathlib import Path
+from unittest.mock import patch
+
+from side.terminal.cargo_diagnostics import (
+    cargo_subcommand,
+    parse_human_diagnostics,
+    parse_json_diagnostics,
+    cargo_friction_events,
+    mistake_repeat_payload,
+    read_captured_output,
+)
+
+HUMAN_OUTPUT = """\
+   Compiling orders v0.1.0 (/work/orders)
+error[E0382]: borrow of moved value: `order`
+ --> src/main.rs:9:20
+  |
+7 |     let order = Order::new();
+  |         ----- move occurs because `order` has type `Order`, which does not implement the `Copy` trait
+8 |     consume(order);
+  |             ----- value moved here
+9 |     println!("{}", order.total);
+  |                    ^^^^^^^^^^^ value borrowed here after move
+  |
+note: consider changing this parameter type in function `consume` to borrow instead if owning the value isn't necessary
+ --> src/main.rs:3:15
+  |
+3 | fn consume(o: Order) {}
+  |    -------    ^^^^^ this parameter takes ownership of the value
+help: consider cloning the value if the performance cost is acceptable
+  |
+8 |     consume(order.clone());
+  |                  ++++++++
+
+error[E0502]: cannot borrow `items` as mutable because it is also borrowed as immutable
+  --> src/lib.rs:14:9
+   |
+13 |     let first = &items[0];
+   |                  ----- immutable borrow occurs here
+14 |     items.push(4);
+   |     ^^^^^^^^^^^^^ mutable borrow occurs here
+15 |     println!("{}", first);
+   |                    ----- immutable borrow later used here
+
+warning: unused variable: `x`
+ --> src/lib.rs:2:9
+
+error: aborting due to 2 previous errors
+
+Some errors have detailed explanations: E0382, E0502.
+For more information about an error, try `rustc --explain E0382`.
+"""
+
+JSON_OUTPUT = "\n".join([
+    json.dumps({"reason": "compiler-artifact", "target": {"name": "orders"}}),
+    json.dumps({
+        "reason": "compiler-message",
+        "message": {
+            "message": "borrow of moved value: `order`",
+            "code": {"code": "E0382", "explanation": "..."},
+            "level": "error",
+            "spans": [{
+                "file_name": "src/main.rs", "is_primary": True, "label": "value borrowed here after move",
+                "line_start": 9, "line_end": 9, "column_start": 20, "column_end": 31,
+                "text": [], "suggested_replacement": None,
+            }],
+            "children": [{
+                "message": "consider cloning the value if the performance cost is acceptable",
+                "level": "help", "children": [],
+                "spans": [{
+                    "file_name": "src/main.rs", "is_primary": True, "label": None,
+                    "line_start": 8, "line_end": 8, "column_start": 18, "column_end": 18,
+                    "byte_start": 120, "byte_end": 120,
+                    "text": [{"text": "    consume(order);", "highlight_start": 18, "highlight_end": 18}],
+                    "suggested_replacement": ".clone()", "suggestion_applicability": "MachineApplicable",
+                }],
+            }],
+            "rendered": "error[E0382]: borrow of moved value: `order`\n",
+        },
+    }),
+    json.dumps({
+        "reason": "compiler-message",
+        "message": {"message": "aborting due to 1 previous error", "code": None, "level": "error",
+                    "spans": [], "children": []},
+    }),
+    json.dumps({"reason": "build-finished", "success": False}),
+])
+
+
+class TestCargoDiagnostics:
+    """Tests for cargo output parsing and the friction event path."""
+
+    def test_cargo_subcommand(self):
+        """Only cargo compile runs are recognised, including env prefixes and toolchains."""
+        assert cargo_subcommand("cargo build --release") == "build"
+        assert cargo_subcommand("RUSTFLAGS=-Dwarnings cargo +nightly t -p orders") == "test"
+        assert cargo_subcommand("/usr/bin/cargo check") == "check"
+        assert cargo_subcommand("cargo fmt") is None
+        assert cargo_subcommand("npm test") is None
+
+    def test_preexec_leaves_wrapped_cargo_to_the_wrapper(self):
+        """zsh `preexec` skips the cargo runs `cargo()` reports with their output, and sends the rest."""
+        import side
+
+        hook = Path(side.__file__).parent / "hooks" / "side_shell_hook.sh"
+        script = 'ZSH_VERSION=5.9; source "$0"; _side_send_signal() { echo "$1"; }; for c in "${@}"; do preexec "$c"; done'
+        commands = ["cargo build --release", "RUSTFLAGS=-Dwarnings cargo t -p orders", "cargo clippy",
+                    "cargo bench", "cargo +nightly build", "command cargo test", "ls -la"]
+        sent = subprocess.run(["bash", "-c", script, str(hook), *commands], capture_output=True, text=True, check=True)
+        assert sent.stdout.splitlines() == ["cargo bench", "cargo +nightly build", "command cargo test", "ls -la"]
+
+    def test_human_format(self):
+        """Coded errors carry span, label, notes and the suggested replacement line."""
+        moved, borrowed = parse_human_diagnostics(HUMAN_OUTPUT)
+
+        assert (moved.code, moved.file, moved.line, moved.column) == ("E0382", "src/main.rs", 9, 20)
+        assert moved.column_end == 31
+        assert moved.label == "value borrowed here after move"
+        assert moved.suggestion["message"] == "consider cloning the value if the performance cost is acceptable"
+        assert moved.suggestion["replacement"] == "consume(order.clone());"
+        assert moved.notes and moved.notes[0].startswith("consider changing this parameter")
+
+        assert (borrowed.code, borrowed.file, borrowed.line) == ("E0502", "src/lib.rs", 14)
+        assert borrowed.label == "mutable borrow occurs here"
+        assert borrowed.suggestion is None
+
+    def test_json_format(self):
+        """The JSON stream keeps rustc's exact replacement."""
+        diag, = parse_json_diagnostics(JSON_OUTPUT)
+        assert (diag.code, diag.line, diag.column_end) == ("E0382", 9, 31)
+        assert diag.suggestion["replacement"] == ".clone()"
+        assert diag.suggestion["applicability"] == "MachineApplicable"
+        assert diag.source_format == "json"
+
+    def test_friction_events(self):
+        """One rustc_error activity per distinct error; non-cargo commands are ignored."""
+        events = cargo_friction_events("cargo build", HUMAN_OUTPUT + HUMAN_OUTPUT, "/work/orders", "101")
+        assert [e["payload"]["code"] for e in events] == ["E0382", "E0502"]
+        assert events[0]["action"] == "rustc_error"
+        assert events[0]["payload"]["explain"] == "rustc --explain E0382"
+        assert events[0]["payload"]["subcommand"] == "build"
+        assert cargo_friction_events("make", HUMAN_OUTPUT, "/work") == []
+
+    def test_terminal_output(self):
+        """Colors and progress-bar redraws kept for the terminal don't hide errors from the parser."""
+        from side.terminal.cargo_diagnostics import parse_cargo_output
+
+        colored = HUMAN_OUTPUT.replace("error[E0382]", "\x1b[0m\x1b[1m\x1b[38;5;9merror[E0382]\x1b[0m")
+        colored = "    Building [=====>   ] 3/7: orders\r\x1b[K" + colored
+        assert [d.code for d in parse_cargo_output(colored)] == ["E0382", "E0502"]
+
+    def test_project_for_cwd(self, tmp_path):
+        """Cargo friction is filed under the repository the command ran in."""
+        from side.services.socket_listener import project_for_cwd
+        from side.storage.modules.base import ContextEngine
+
+        (tmp_path / "repo" / ".git").mkdir(parents=True)
+        (tmp_path / "repo" / "crates").mkdir()
+        assert project_for_cwd(str(tmp_path / "repo" / "crates")) == ContextEngine.get_project_id(tmp_path / "repo")
+        assert project_for_cwd(str(tmp_path / "missing")) == "global"
+
+    def test_read_captured_output(self, tmp_path):
+        """Captures are read once and deleted; arbitrary paths are refused."""
+        fd, name = tempfile.mkstemp(prefix="side-cargo.")
+        os.close(fd)
+        Path(name).write_text(HUMAN_OUTPUT)
+        assert read_captured_output(Path(name)) == HUMAN_OUTPUT
+        assert not Path(name).exists()
+
+        stray = tmp_path / "notes.txt"
+        stray.write_text("keep me")
+        assert read_captured_output(stray) is None
+        assert stray.exists()
+
+    def test_repeat_detection(self):
+        """handle_ai_mistake_repeat only injects context once the same code recurs."""
+        from side.handlers.friction_points import handle_ai_mistake_repeat
+        from side.models.core import Activity
+        from side.utils.event_optimizer import Event, FrictionPoint, EventPriority
+
+        first, = [e for e in cargo_friction_events("cargo check", HUMAN_OUTPUT, "/w") if e["payload"]["code"] == "E0382"]
+        second = json.loads(json.dumps(first))
+        second["payload"]["captured_at"] += 5
+
+        class Ledger:
+            def __init__(self, history):
+                self.history, self.logged = history, []
+
+            def get_recent_activities(self, project_id, limit=20):
+                return [Activity(project_id=project_id, tool=e["tool"], action=e["action"], payload=e["payload"])
+                        for e in self.history]
+
+            def log_activity(self, **kwargs):
+                self.logged.append(kwargs)
+
+        def run(event, history):
+            ledger = Ledger(history)
+            with patch("side.storage.get_activity_ledger", return_value=ledger):
+                asyncio.run(handle_ai_mistake_repeat(Event(
+                    FrictionPoint.AI_MISTAKE_REPEAT, EventPriority.HIGH, mistake_repeat_payload("proj", event))))
+            return ledger.logged
+
+        assert run(first, [first]) == []
+        logged, = run(second, [second, first])
+        assert logged["payload"]["pattern"] == "E0382"
+        assert logged["payload"]["occurrence_count"] == 2
+        assert "consume(order.clone());" in logged["payload"]["advice"]
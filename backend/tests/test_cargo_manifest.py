@@ -0,0 +1,149 @@
+"""
+Test: Cargo Workspace Model
+
+Verifies manifest parsing, member globs, path dependencies and the crate graph in SchemaStore.
+"""
+import pytest
+from pathlib import Path
+from side.intel.cargo_manifest import (
+    load_cargo_workspace,
+    persist_cargo_workspace,
+    crates_depending_on,
+)
+
+
+def _write(path: Path, text: str):
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(text)
+
+
+@pytest.fixture
+def workspace_root(tmp_path):
+    """A virtual workspace with two members and one out-of-tree path dependency."""
+    _write(tmp_path / "Cargo.toml", """
+[workspace]
+members = ["crates/*"]
+exclude = ["crates/scratch"]
+
+[workspace.package]
+version = "0.3.0"
+
+[workspace.dependencies]
+serde = { version = "1", features = ["derive"] }
+""")
+    _write(tmp_path / "crates" / "core" / "Cargo.toml", """
+[package]
+name = "orders-core"
+version.workspace = true
+
+[dependencies]
+serde = { workspace = true }
+vendored = { path = "../../vendor/vendored" }
+
+[features]
+default = ["std"]
+std = []
+postgres = ["dep:sqlx"]
+
+[lib]
+name = "orders_core"
+""")
+    _write(tmp_path / "crates" / "api" / "Cargo.toml", """
+[package]
+name = "orders-api"
+version = "0.1.0"
+
+[dependencies]
+orders-core = { path = "../core" }
+tokio = "1"
+
+[dev-dependencies]
+insta = "1"
+
+[[bin]]
+name = "orders-server"
+path = "src/bin/server.rs"
+""")
+    _write(tmp_path / "crates" / "scratch" / "Cargo.toml", '[package]\nname = "scratch"\n')
+    _write(tmp_path / "vendor" / "vendored" / "Cargo.toml", '[package]\nname = "vendored"\nversion = "0.0.1"\n')
+    return tmp_path
+
+
+class TestCargoWorkspace:
+    """Tests for the Cargo project model."""
+
+    def test_members_and_path_dependencies(self, workspace_root):
+        """Globbed members are loaded; excluded dirs are skipped; path deps become non-members."""
+        ws = load_cargo_workspace(workspace_root)
+
+        assert ws.is_virtual
+        assert sorted(c.name for c in ws.members()) == ["orders-api", "orders-core"]
+        assert "scratch" not in ws.crates
+        assert ws.crates["vendored"].is_member is False
+
+    def test_crate_details(self, workspace_root):
+        """Inherited versions, features, targets and dependency kinds are resolved."""
+        ws = load_cargo_workspace(workspace_root)
+        core = ws.crates["orders-core"]
+        api = ws.crates["orders-api"]
+
+        assert core.version == "0.3.0"
+        assert core.features["postgres"] == ["dep:sqlx"]
+        assert [(t.kind, t.name) for t in core.targets] == [("lib", "orders_core")]
+        assert ("bin", "orders-server") in [(t.kind, t.name) for t in api.targets]
+
+        serde = next(d for d in core.dependencies if d.name == "serde")
+        assert serde.version == "1" and serde.features == ["derive"]
+        kinds = {d.name: d.kind for d in api.dependencies}
+        assert kinds == {"orders-core": "normal", "tokio": "normal", "insta": "dev"}
+        assert ws.dependents_of("orders-core") == ["orders-api"]
+
+    def test_persisted_crate_graph(self, workspace_root, tmp_path):
+        """Crates and depends_on edges land in SchemaStore."""
+        from side.storage.modules.base import ContextEngine
+
+        store = ContextEngine(tmp_path / "graph.db").schema
+        persist_cargo_workspace(load_cargo_workspace(workspace_root), store, "proj")
+
+        assert crates_depending_on(store, "proj", "orders-core") == ["orders-api"]
+        assert crates_depending_on(store, "proj", "serde") == ["orders-core"]
+        feature = store.get_entity_by_name("proj", "postgres", "feature")
+        assert feature["signature"] == "postgres = [dep:sqlx]"
+
+    def test_dependency_kinds_and_rebuild(self, workspace_root, tmp_path):
+        """Dev dependencies are their own relation, and a dependency dropped from Cargo.toml loses its edge."""
+        from side.storage.modules.base import ContextEngine
+
+        store = ContextEngine(tmp_path / "graph.db").schema
+        persist_cargo_workspace(load_cargo_workspace(workspace_root), store, "proj")
+        assert crates_depending_on(store, "proj", "insta") == ["orders-api"]
+        assert crates_depending_on(store, "proj", "insta", kinds=["normal"]) == []
+        [edge] = store.list_relationships(target_id=store.get_entity_by_name("proj", "insta", "crate")["id"])
+        assert edge["relation_type"] == "dev_depends_on"
+
+        manifest = workspace_root / "crates" / "api" / "Cargo.toml"
+        manifest.write_text(manifest.read_text().replace('insta = "1"\n', ""))
+        persist_cargo_workspace(load_cargo_workspace(workspace_root), store, "proj")
+        assert crates_depending_on(store, "proj", "insta") == []
+        assert crates_depending_on(store, "proj", "orders-core") == ["orders-api"]
+
+    def test_rebuild_drops_removed_crates_and_features(self, workspace_root, tmp_path):
+        """A feature, target or dependency dropped from Cargo.toml leaves the store with its edges."""
+        from side.storage.modules.base import ContextEngine
+
+        store = ContextEngine(tmp_path / "graph.db").schema
+        persist_cargo_workspace(load_cargo_workspace(workspace_root), store, "proj")
+        assert store.get_entity_by_name("proj", "postgres", "feature")
+        assert store.get_entity_by_name("proj", "orders-server", "target")
+
+        core = workspace_root / "crates" / "core" / "Cargo.toml"
+        core.write_text(core.read_text().replace('postgres = ["dep:sqlx"]\n', ""))
+        api = workspace_root / "crates" / "api" / "Cargo.toml"
+        api.write_text(api.read_text().replace('tokio = "1"\n', "").split("[[bin]]")[0])
+        persist_cargo_workspace(load_cargo_workspace(workspace_root), store, "proj")
+
+        assert store.get_entity_by_name("proj", "postgres", "feature") is None
+        assert store.get_entity_by_name("proj", "orders-server", "target") is None
+        assert store.get_entity_by_name("proj", "tokio", "crate") is None
+        assert store.get_entity_by_name("proj", "std", "feature")
+        assert crates_depending_on(store, "proj", "orders-core") == ["orders-api"]
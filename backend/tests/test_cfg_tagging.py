@@ -0,0 +1,124 @@
+"""
+Test: Rust cfg Tagging
+
+Verifies `#[cfg(...)]` gates on items, impl blocks and modules are stored on
+entities, that feature gates are linked to the crate's `[features]` table (and
+undeclared features reported), that graph answers say when an item exists, and
+that debt scans treat `#[cfg(test)]` modules as test code.
+"""
+from side.intel import rust_cfg
+from side.intel.graph_query import find_callers
+from side.intel.tree_indexer import update_branch
+from side.tools.audit_scanner import DebtScanner
+
+FILES = {
+    "Cargo.toml": """[package]
+name = "storefront"
+version = "0.1.0"
+
+[dependencies]
+serde = { version = "1", optional = true }
+
+[features]
+default = []
+postgres = []
+""",
+    "src/lib.rs": """pub mod db;
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "postgres")]
+pub fn connect_pg() {}
+
+#[cfg(all(unix, feature="serde"))]
+pub fn export() {}
+
+#[cfg(feature = "mysql")]
+pub fn connect_mysql() {}
+
+pub fn always() { connect_pg(); }
+
+pub struct Store;
+
+#[cfg(target_os = "linux")]
+impl Store {
+    /// Reclaims space.
+    #[cfg(
+        feature = "postgres"
+    )]
+    pub fn vacuum(&self) {}
+    pub fn sync(&self) {}
+}
+""",
+    "src/db.rs": '#![cfg(feature = "postgres")]\n\npub fn pool() {}\n',
+    "src/tests.rs": "use super::*;\n\nfn helper() {\n    Some(1).unwrap();\n}\n",
+}
+
+
+def cfg_of(store, project_id, name):
+    [entity] = store.find_entities(project_id, name)
+    return entity["cfg"]
+
+
+class TestCfgTagging:
+    """Tests for cfg predicates on Rust entities."""
+
+    def test_predicates(self):
+        """Predicates are normalized, flattened when combined and described in plain words."""
+        assert rust_cfg.cfg_predicates('#[cfg(all( unix,feature="x" ))]\n#[cfg_attr(test, derive(Debug))]') == [
+            'all(unix, feature = "x")',
+        ]
+        combined = rust_cfg.combine(['all(unix, feature = "x")', "test", "unix"])
+        assert combined == 'all(unix, feature = "x", test)'
+        assert rust_cfg.is_test_only(combined) and not rust_cfg.is_test_only("not(test)")
+        assert rust_cfg.features('any(feature = "a", not(feature = "b"))') == ["a"]
+        assert rust_cfg.describe('feature = "postgres"') == "only exists with feature `postgres`"
+        assert rust_cfg.describe(combined) == 'only exists with feature `x`, in test builds, when `unix`'
+        assert rust_cfg.describe('any(unix, windows)') == "only exists when `cfg(any(unix, windows))` holds"
+
+    def test_entities_carry_gates(self, indexed):
+        """Item, impl-block and module gates combine; a removed attribute ungates the item."""
+        project, engine = indexed
+        store, project_id = engine.schema, engine.get_project_id()
+        assert cfg_of(store, project_id, "connect_pg") == 'feature = "postgres"'
+        assert cfg_of(store, project_id, "vacuum") == 'all(target_os = "linux", feature = "postgres")'
+        assert cfg_of(store, project_id, "sync") == 'target_os = "linux"'
+        assert cfg_of(store, project_id, "pool") == 'feature = "postgres"'   # Inner `#![cfg]`
+        assert cfg_of(store, project_id, "helper") == "test"                 # `#[cfg(test)] mod tests;`
+        assert cfg_of(store, project_id, "always") is None
+
+        lib = project / "src/lib.rs"
+        lib.write_text(lib.read_text().replace('#[cfg(feature = "postgres")]\npub fn connect_pg', "pub fn connect_pg"))
+        update_branch(project, lib, schema_store=store)
+        assert cfg_of(store, project_id, "connect_pg") is None
+
+    def test_feature_edges_and_undeclared(self, indexed):
+        """Gated items link to their feature; optional dependencies count, unknown features are reported."""
+        project, engine = indexed
+        store, project_id = engine.schema, engine.get_project_id()
+        edges = store.list_relationships(relation_type="requires_feature")
+        linked = {(store.get_entity_by_id(r["source_id"])["name"], store.get_entity_by_id(r["target_id"])["name"])
+                  for r in edges}
+        assert linked == {("connect_pg", "postgres"), ("vacuum", "postgres"), ("pool", "postgres"), ("db", "postgres")}
+
+        stats = rust_cfg.index_cfg_features(project, store)
+        assert stats["undeclared"] == [
+            {"crate": "storefront", "feature": "mysql", "entities": ["storefront::connect_mysql"]},
+        ]
+
+    def test_graph_answers_say_when_items_exist(self, indexed):
+        """Query results for gated items carry the predicate and a readable availability note."""
+        _, engine = indexed
+        store, project_id = engine.schema, engine.get_project_id()
+        [match] = find_callers(store, project_id, "connect_pg")["matches"]
+        assert match["cfg"] == 'feature = "postgres"'
+        assert match["availability"] == "only exists with feature `postgres`"
+        [always] = find_callers(store, project_id, "always")["matches"]
+        assert "cfg" not in always
+
+    def test_debt_scan_skips_test_modules(self, project):
+        """unwrap() in a `#[cfg(test)]` module file is not debt; undeclared features are."""
+        results = DebtScanner(project, history_path=project / "history.json").scan()
+        assert not [d for d in results["technical_debt"] if d["type"] == "UNWRAP"]
+        undeclared = [d for d in results["technical_debt"] if d["type"] == "UNDECLARED_FEATURE"]
+        assert [(d["file"], d["line"], d["feature"]) for d in undeclared] == [("src/lib.rs", 11, "mysql")]
@@ -0,0 +1,101 @@
+"""
+Test: Clippy JSON Diagnostics
+
+Verifies ClippyAdapter maps cargo's --message-format=json stream onto Findings.
+"""
+import json
+import pytest
+from pathlib import Path
+from side.tools.audit_adapters.clippy import ClippyAdapter, lint_group
+
+
+def _compiler_message(code, level, message, span, children=(), file_name="src/main.rs"):
+    return json.dumps({
+        "reason": "compiler-message",
+        "package_id": "demo 0.1.0 (path+file:///work/demo)",
+        "message": {
+            "message": message,
+            "code": {"code": code, "explanation": None},
+            "level": level,
+            "spans": [dict(span, file_name=file_name, is_primary=True)],
+            "children": list(children),
+            "rendered": f"{level}: {message}\n",
+        },
+    })
+
+
+RANGE_LOOP_SPAN = {
+    "line_start": 4, "line_end": 4, "column_start": 14, "column_end": 29,
+    "byte_start": 60, "byte_end": 75, "label": None,
+    "text": [{"text": "    for i in 0..items.len() {", "highlight_start": 14, "highlight_end": 29}],
+    "suggested_replacement": None, "suggestion_applicability": None,
+}
+
+LEN_ZERO_SPAN = {
+    "line_start": 9, "line_end": 9, "column_start": 8, "column_end": 24,
+    "byte_start": 120, "byte_end": 135, "label": None,
+    "text": [{"text": "    if items.len() == 0 {", "highlight_start": 8, "highlight_end": 24}],
+    "suggested_replacement": None, "suggestion_applicability": None,
+}
+
+STREAM = "\n".join([
+    json.dumps({"reason": "compiler-artifact", "target": {"name": "demo"}}),
+    _compiler_message("clippy::len_zero", "warning", "length comparison to zero", LEN_ZERO_SPAN, children=[
+        {"message": "`#[warn(clippy::len_zero)]` on by default", "level": "note", "spans": [], "children": []},
+        {"message": "using `is_empty` is clearer and more explicit", "level": "help", "children": [], "spans": [
+            dict(LEN_ZERO_SPAN, file_name="src/main.rs", is_primary=True,
+                 suggested_replacement="items.is_empty()", suggestion_applicability="MachineApplicable"),
+        ]},
+    ]),
+    _compiler_message("clippy::cast_possible_truncation", "warning", "casting `u64` to `u32` may truncate the value",
+                      RANGE_LOOP_SPAN, children=[
+        {"message": "`#[warn(clippy::cast_possible_truncation)]` implied by `#[warn(clippy::pedantic)]`",
+         "level": "note", "spans": [], "children": []},
+    ]),
+    _compiler_message("E0382", "error", "borrow of moved value: `order`", LEN_ZERO_SPAN),
+    # Diagnostics in dependencies live outside the project and are dropped
+    _compiler_message("clippy::len_zero", "warning", "length comparison to zero", LEN_ZERO_SPAN,
+                      file_name="/home/u/.cargo/registry/src/dep-1.0/src/lib.rs"),
+    # Duplicate emitted for the test target
+    _compiler_message("E0382", "error", "borrow of moved value: `order`", LEN_ZERO_SPAN),
+    json.dumps({"reason": "build-finished", "success": False}),
+])
+
+
+class TestClippyAdapter:
+    """Tests for clippy diagnostic parsing."""
+
+    def test_parse_stream(self, tmp_path):
+        """Lints and rustc errors become Findings; foreign and duplicate diagnostics are dropped."""
+        findings = ClippyAdapter(tmp_path).parse_output(STREAM)
+        by_rule = {f.rule_id: f for f in findings}
+
+        assert sorted(by_rule) == ["E0382", "clippy::cast_possible_truncation", "clippy::len_zero"]
+        assert by_rule["E0382"].severity == "HIGH"
+        assert by_rule["clippy::cast_possible_truncation"].metadata["group"] == "pedantic"
+        assert by_rule["clippy::cast_possible_truncation"].severity == "INFO"
+
+        len_zero = by_rule["clippy::len_zero"]
+        assert (len_zero.file_path, len_zero.line_number, len_zero.column) == ("src/main.rs", 9, 8)
+        assert len_zero.code_snippet == "    if items.len() == 0 {"
+        assert len_zero.metadata["group"] == "style"
+        assert len_zero.severity == "LOW"
+
+    def test_machine_applicable_suggestion(self, tmp_path):
+        """Exact replacements are preserved in metadata and the suggested fix."""
+        findings = ClippyAdapter(tmp_path).parse_output(STREAM)
+        len_zero = next(f for f in findings if f.rule_id == "clippy::len_zero")
+
+        assert len_zero.metadata["machine_applicable"] is True
+        suggestion = len_zero.metadata["suggestions"][0]
+        assert suggestion["original"] == "items.len() == 0"
+        assert suggestion["replacement"] == "items.is_empty()"
+        assert (suggestion["byte_start"], suggestion["byte_end"]) == (120, 135)
+        assert "replace `items.len() == 0` with `items.is_empty()`" in len_zero.recommendation
+
+    def test_lint_group_resolution(self):
+        """Groups come from notes first, then the known table, then the lint level."""
+        assert lint_group("cast_lossless", "warning", ["`-W clippy::cast-lossless` implied by `-W clippy::pedantic`"]) == "pedantic"
+        assert lint_group("needless_return", "warning", ["`#[warn(clippy::style)]` implied by `#[warn(clippy::all)]`"]) == "style"
+        assert lint_group("redundant_clone", "warning", []) == "perf"
+        assert lint_group("brand_new_lint", "error", []) == "correctness"
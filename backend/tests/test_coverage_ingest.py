@@ -0,0 +1,195 @@
+"""
+Test: Coverage Ingestion
+
+Verifies that lcov, Cobertura XML and llvm-cov JSON reports map onto function,
+method and type entities as percent-covered, that coverage is kept per commit,
+and that PromptBuilder and the coverage audit flag edits to untested code.
+"""
+import json
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+import pytest
+
+from side.intel import coverage
+from side.intel.handlers.context import PromptBuilder
+from side.tools.audit_adapters import CoverageAdapter, Severity
+
+FILES = {
+    "Cargo.toml": '[package]\nname = "shop"\nversion = "0.1.0"\n',
+    "src/lib.rs": "pub mod order;\n",
+    "src/order.rs": """pub struct Order { items: Vec<u32> }
+
+impl Order {
+    pub fn new() -> Order { Order { items: Vec::new() } }
+    pub fn add_item(&mut self, id: u32) { self.items.push(id); }
+    pub fn total(&self) -> u32 {
+        0
+    }
+}
+
+pub fn checkout(order: &mut Order) -> u32 {
+    order.total()
+}
+""",
+    "pkg/__init__.py": "",
+    "pkg/billing.py": """def invoice(amount):
+    return amount
+
+
+def charge(amount):
+    if amount < 0:
+        raise ValueError(amount)
+    return invoice(amount)
+""",
+}
+
+# `total` never ran; everything else in order.rs did
+LCOV = """TN:
+SF:{root}/src/order.rs
+FN:4,_RNvMNtCs1_4shop5orderNtB2_5Order3new
+DA:4,3
+DA:5,2
+DA:6,0
+DA:7,0
+DA:8,0
+DA:11,1
+DA:12,1
+DA:13,1
+LF:8
+LH:5
+end_of_record
+SF:/home/ci/.cargo/registry/src/serde-1.0.0/src/lib.rs
+DA:1,1
+end_of_record
+"""
+
+COBERTURA = """<?xml version="1.0" ?>
+<coverage version="7.4" line-rate="0.83">
+  <sources><source>/ci/checkout</source></sources>
+  <packages><package name="pkg"><classes>
+    <class name="billing.py" filename="pkg/billing.py">
+      <lines>
+        <line number="1" hits="1"/><line number="2" hits="1"/>
+        <line number="5" hits="1"/><line number="6" hits="1"/>
+        <line number="7" hits="0"/><line number="8" hits="1"/>
+      </lines>
+    </class>
+  </classes></package></packages>
+</coverage>
+"""
+
+
+def llvm_export(root):
+    segments = [
+        [4, 5, 3, True, True, False], [4, 60, 0, False, False, False],     # new
+        [6, 32, 0, True, True, False], [8, 6, 0, False, False, False],     # total
+        [11, 40, 1, True, True, False], [13, 2, 0, False, False, False],   # checkout
+    ]
+    return json.dumps({"type": "llvm.coverage.json.export", "version": "2.0.1",
+                       "data": [{"files": [{"filename": f"{root}/src/order.rs", "segments": segments}]}]})
+
+
+@pytest.fixture
+def project(make_project, index_project):
+    root = make_project(FILES, repo=True, ignore=("*.info", "*.xml", "*.json"))
+    return root, index_project(root)
+
+
+def write(root, name, text):
+    (root / name).write_text(text)
+    return root / name
+
+
+def percents(engine, *names):
+    """Latest percent covered per symbol name."""
+    store = engine.schema
+    ids = {e["id"]: e["name"] for n in names for e in store.find_entities(engine.get_project_id(), n)
+           if e["entity_type"] != "file"}
+    return {ids[ent_id]: row["percent"] for ent_id, row in store.get_latest_coverage(list(ids)).items()}
+
+
+class TestCoverageIngest:
+    """Tests for coverage ingestion and untested-edit warnings."""
+
+    def test_lcov_functions_and_types(self, project):
+        """Functions get their own lines; a type is credited with its methods' lines."""
+        root, engine = project
+        report = write(root, "lcov.info", LCOV.format(root=root))
+        stats = coverage.ingest_coverage(root, engine.schema, [report], commit="c1")
+        assert stats["formats"] == ["lcov"] and stats["files"] == 1
+        assert stats["unmatched"] == ["/home/ci/.cargo/registry/src/serde-1.0.0/src/lib.rs"]
+        assert percents(engine, "new", "add_item", "total", "checkout", "Order") == {
+            "new": 100.0, "add_item": 100.0, "total": 0.0, "checkout": 100.0, "Order": 40.0,
+        }
+
+    def test_cobertura_and_llvm_cov(self, project):
+        """Cobertura paths from another checkout match by suffix; llvm-cov segments become line counts."""
+        root, engine = project
+        write(root, "coverage.xml", COBERTURA)
+        write(root, "llvm-cov.json", llvm_export(root))
+        stats = coverage.ingest_coverage(root, engine.schema, commit="c1")     # Default report locations
+        assert stats["formats"] == ["cobertura", "llvm-cov"] and stats["files"] == 2
+        assert percents(engine, "invoice", "charge") == {"invoice": 100.0, "charge": 75.0}
+        assert percents(engine, "new", "add_item", "total", "checkout", "Order") == {
+            "new": 100.0, "total": 0.0, "checkout": 100.0, "Order": 25.0,
+        }
+        assert coverage.parse_llvm_cov(llvm_export(root))[f"{root}/src/order.rs"] == {
+            4: 3, 6: 0, 7: 0, 8: 0, 11: 1, 12: 1, 13: 1,
+        }
+        with pytest.raises(ValueError, match="Unrecognized coverage report"):
+            coverage.detect_format("not a report")
+
+    def test_history_per_commit(self, project, git):
+        """Each commit keeps its own numbers; re-ingesting a commit replaces them."""
+        root, engine = project
+        report = write(root, "lcov.info", LCOV.format(root=root))
+        coverage.ingest_coverage(root, engine.schema, [report], commit="c1")
+        write(root, "lcov.info", LCOV.format(root=root).replace("DA:6,0\nDA:7,0", "DA:6,1\nDA:7,1"))
+        coverage.ingest_coverage(root, engine.schema, [report], commit="c2")
+        coverage.ingest_coverage(root, engine.schema, [report], commit="c2")
+        [total] = engine.schema.find_entities(engine.get_project_id(), "total")
+        history = engine.schema.coverage_history(total["id"])
+        assert [(h["commit_sha"], h["percent"]) for h in history] == [("c1", 0.0), ("c2", 66.7)]
+        assert percents(engine, "total") == {"total": 66.7}
+
+        default = coverage.ingest_coverage(root, engine.schema, [report])
+        assert default["commit"] == git(root, "rev-parse", "HEAD")
+
+    def test_prompt_and_audit_flag_untested_edits(self, project):
+        """Context for a file lists its poorly covered symbols; the audit flags edited ones."""
+        root, engine = project
+        coverage.ingest_coverage(root, engine.schema, [write(root, "lcov.info", LCOV.format(root=root))])
+        strategic = MagicMock()
+        strategic.search_wisdom.return_value = []
+        strategic.recall_facts.return_value = []
+        prompt = PromptBuilder(root, engine, strategic).gather_context(active_file="src/order.rs", include_code=False)
+        assert "🧪 [COVERAGE]" in prompt
+        assert "`total` (method, src/order.rs): 0% of 3 lines covered" in prompt
+        assert "`Order` (struct, src/order.rs): 40% of 5 lines covered" in prompt
+        assert "`checkout`" not in prompt
+
+        adapter = CoverageAdapter(root, engine.schema)
+        assert adapter.is_available()
+        order = root / "src/order.rs"
+        order.write_text(order.read_text().replace("        0\n", "        1\n").replace("order.total()", "order.total() + 1"))
+        findings = adapter.parse_output(coverage.untested_changes(root, engine.schema))
+        assert [(f.rule_id, f.file_path, f.line_number, f.severity) for f in findings] == [
+            ("UNTESTED_CHANGE", "src/order.rs", 6, Severity.MEDIUM),
+        ]
+        assert findings[0].title == "Untested method `total` changed"
+
+    def test_cli(self, project):
+        """`side coverage` finds the report, stores it and lists edited code that stays untested."""
+        from side.cli_handlers import intel
+
+        root, engine = project
+        write(root, "lcov.info", LCOV.format(root=root))
+        order = root / "src/order.rs"
+        order.write_text(order.read_text().replace("        0\n", "        2\n"))
+        with patch.object(intel, "get_engine", return_value=engine), patch.object(intel, "ux") as ux, \
+                patch.object(intel, "Path", SimpleNamespace(cwd=lambda: root)):
+            intel.handle_coverage(SimpleNamespace(reports=[], commit=None))
+        [(title, headers, rows)] = [call.args for call in ux.render_table.call_args_list]
+        assert title == "Untested Changes"
+        assert rows == [["total", "method", "src/order.rs", "0% of 3 lines"]]
@@ -0,0 +1,204 @@
+"""
+Test: Declaration Details
+
+Verifies entities carry full signatures, visibility, parent linkage and leading
+doc comments for Rust, Python and TypeScript, and that they are persisted.
+"""
+import pytest
+from pathlib import Path
+from unittest.mock import patch
+
+from side.intel import tree_indexer
+from side.intel.tree_indexer import get_file_semantics, generate_local_index
+
+RUST_SOURCE = """
+/// A customer order.
+#[derive(Debug)]
+pub struct Order<'a, T: Clone> {
+    items: Vec<&'a T>,
+}
+
+impl<'a, T: Clone> Order<'a, T> {
+    /// Builds an empty order.
+    ///
+    /// Never allocates.
+    pub fn new() -> Self {
+        Order { items: Vec::new() }
+    }
+
+    pub(crate) fn add_item(
+        &mut self,
+        item: &'a T,
+    ) -> Result<(), String>
+    where
+        T: Send,
+    {
+        Ok(())
+    }
+
+    fn recount(&self) {}
+}
+
+/** Persistence boundary. */
+pub trait Repository {
+    fn get(&self, id: u32) -> Option<u32>;
+}
+
+impl Repository for Order<'_, u32> {
+    fn get(&self, id: u32) -> Option<u32> { None }
+}
+"""
+
+PYTHON_SOURCE = '''
+class Order:
+    """A customer order."""
+
+    def __init__(self, items: list[str] | None = None):
+        self.items = items or []
+
+    @property
+    def _total(self) -> int:
+        """Sum of item prices."""
+        return 0
+
+    def __reset(self): pass
+
+
+async def checkout(order: Order, *, dry_run: bool = False) -> dict[str, int]:
+    return {}
+'''
+
+TS_SOURCE = """
+/**
+ * Order storage.
+ */
+export class OrderStore<T extends Order> {
+  private cache = new Map<string, T>();
+
+  /** Loads one order. */
+  public async load(id: string, opts?: LoadOptions): Promise<T> {
+    return this.cache.get(id);
+  }
+
+  protected evict(id: string): void {
+    this.cache.delete(id);
+  }
+}
+
+function helper(a: number, b: number): number {
+  return a + b;
+}
+"""
+
+
+@pytest.fixture
+def semantics():
+    """Entity lookup by (name, type) through the regex/ast fallback path."""
+    with patch.object(tree_indexer, "TS_AVAILABLE", False):
+        def scan(name, content):
+            entities = get_file_semantics(Path(name), content)["entities"]
+            return {(e["name"], e["type"]): e for e in entities}
+        yield scan
+
+
+class TestDeclarations:
+    """Tests for signatures, visibility, parents and docs."""
+
+    def test_rust(self, semantics):
+        """Generics, lifetimes and where clauses survive; methods point at their impl or trait."""
+        items = semantics("lib.rs", RUST_SOURCE)
+
+        order = items[("Order", "struct")]
+        assert order["signature"] == "pub struct Order<'a, T: Clone>"
+        assert order["visibility"] == "pub"
+        assert order["doc"] == "A customer order."
+
+        new = items[("new", "method")]
+        assert new["signature"] == "pub fn new() -> Self"
+        assert new["doc"] == "Builds an empty order.\n\nNever allocates."
+        assert (new["parent"], new["parent_type"]) == ("Order", "impl")
+
+        add = items[("add_item", "method")]
+        assert add["signature"] == "pub(crate) fn add_item(&mut self, item: &'a T) -> Result<(), String> where T: Send"
+        assert add["visibility"] == "pub(crate)"
+        assert add["doc"] is None
+
+        assert items[("recount", "method")]["visibility"] == "private"
+        assert items[("Repository", "trait")]["doc"] == "Persistence boundary."
+        # `get` appears in the trait and in the trait impl; both inherit the trait's visibility
+        assert items[("get", "method")]["visibility"] == "inherited"
+        assert items[("get", "method")]["signature"] == "fn get(&self, id: u32) -> Option<u32>"
+
+    def test_python(self, semantics):
+        """Methods attach to their class; leading underscores map to visibility."""
+        items = semantics("orders.py", PYTHON_SOURCE)
+
+        assert items[("Order", "class")]["doc"] == "A customer order."
+        init = items[("__init__", "method")]
+        assert init["signature"] == "def __init__(self, items: list[str] | None = None)"
+        assert (init["parent"], init["visibility"]) == ("Order", "public")
+
+        total = items[("_total", "method")]
+        assert (total["visibility"], total["doc"]) == ("internal", "Sum of item prices.")
+        assert items[("__reset", "method")]["visibility"] == "private"
+
+        checkout = items[("checkout", "function")]
+        assert checkout["signature"] == "async def checkout(order: Order, *, dry_run: bool = False) -> dict[str, int]"
+        assert "parent" not in checkout
+
+    def test_typescript(self, semantics):
+        """Exports, member modifiers and JSDoc."""
+        items = semantics("store.ts", TS_SOURCE)
+
+        store = items[("OrderStore", "class")]
+        assert store["signature"] == "export class OrderStore<T extends Order>"
+        assert (store["visibility"], store["doc"]) == ("export", "Order storage.")
+
+        load = items[("load", "method")]
+        assert load["signature"] == "public async load(id: string, opts?: LoadOptions): Promise<T>"
+        assert (load["parent"], load["visibility"], load["doc"]) == ("OrderStore", "public", "Loads one order.")
+        assert items[("evict", "method")]["visibility"] == "protected"
+        assert items[("helper", "function")]["visibility"] == "module"
+
+    def test_persisted(self, tmp_path):
+        """generate_local_index stores declaration details and parent_id; later graph passes keep them."""
+        from side.storage.modules.base import ContextEngine
+
+        (tmp_path / ".git").mkdir()
+        src = tmp_path / "src"
+        src.mkdir()
+        (src / "lib.rs").write_text(RUST_SOURCE)
+        store = ContextEngine(tmp_path / "graph.db").schema
+        project_id = store.engine.get_project_id()
+
+        with patch.object(tree_indexer, "TS_AVAILABLE", False):
+            generate_local_index(src, store)
+
+        new = store.find_entities(project_id, "new", "method")[0]
+        assert new["signature"] == "pub fn new() -> Self"
+        assert new["visibility"] == "pub"
+        assert new["file_path"] == "src/lib.rs"
+        parent = store.get_entity_by_id(new["parent_id"])
+        assert (parent["name"], parent["entity_type"]) == ("Order", "impl")
+
+        # A name-only upsert (as the call graph does) must not wipe the details
+        store.save_entities_batch([{"id": new["id"], "project_id": project_id, "name": "new",
+                                    "entity_type": "method", "file_path": "src/lib.rs"}])
+        again = store.find_entities(project_id, "new", "method")[0]
+        assert again["doc"] == new["doc"] and again["parent_id"] == new["parent_id"]
+
+    def test_same_names_keep_their_owners(self, make_project, index_project):
+        """Same-named methods of two classes, and same-named classes in two files, each keep their own parent."""
+        two_classes = "class Cart:\n    def total(self):\n        return 1\n\n\nclass Order:\n    def total(self):\n        return 2\n"
+        legacy = "class Order:\n    def total(self):\n        return 3\n"
+        root = make_project({"pkg/models.py": two_classes, "pkg/legacy.py": legacy})
+        store = index_project(root).schema
+        project_id = store.engine.get_project_id()
+
+        owners = set()
+        for total in store.find_entities(project_id, "total", "method"):
+            parent = store.get_entity_by_id(total["parent_id"])
+            owners.add((total["file_path"], parent["name"], parent["file_path"]))
+        assert owners == {("pkg/models.py", "Cart", "pkg/models.py"), ("pkg/models.py", "Order", "pkg/models.py"),
+                          ("pkg/legacy.py", "Order", "pkg/legacy.py")}
+        assert len(store.find_entities(project_id, "Order", "class")) == 2
@@ -0,0 +1,105 @@
+"""
+Test: Call Graph Queries
+
+Verifies find_callers, find_callees and impact_of_change walk stored `calls`
+edges transitively and rank results by path confidence.
+"""
+import pytest
+
+from side.intel.graph_query import find_callers, find_callees, impact_of_change
+
+PROJECT = "proj"
+
+ENTITIES = [
+    ("base_fee", "function", "shop/src/pricing.rs"),
+    ("apply_discount", "function", "shop/src/pricing.rs"),
+    ("fees", "method", "shop/src/model.rs"),
+    ("total", "method", "shop/src/model.rs"),
+    ("checkout", "function", "shop/src/lib.rs"),
+    ("checkout_empty", "function", "shop/tests/flow.rs"),
+]
+
+EDGES = [
+    ("apply_discount", "base_fee", 0.8),
+    ("fees", "base_fee", 0.9),
+    ("total", "fees", 0.8),
+    ("checkout", "apply_discount", 0.9),
+    ("checkout", "total", 0.3),
+    ("checkout_empty", "checkout", 0.9),
+]
+
+
+@pytest.fixture
+def store(tmp_path):
+    from side.storage.modules.base import ContextEngine
+
+    schema = ContextEngine(tmp_path / "graph.db").schema
+    schema.save_entities_batch([
+        {"id": name, "project_id": PROJECT, "name": name, "entity_type": kind, "file_path": path}
+        for name, kind, path in ENTITIES
+    ])
+    schema.save_relationships_batch([
+        {"id": f"{src}->{dst}", "project_id": PROJECT, "source_id": src, "target_id": dst,
+         "relation_type": "calls", "confidence": conf}
+        for src, dst, conf in EDGES
+    ] + [{"id": "dep", "project_id": PROJECT, "source_id": "checkout_empty", "target_id": "base_fee",
+          "relation_type": "depends_on", "confidence": 1.0}])
+    return schema
+
+
+class TestGraphQuery:
+    """Tests for transitive call graph queries."""
+
+    def test_find_callers(self, store):
+        """Direct callers come first; deeper ones carry the product of edge confidences."""
+        result = find_callers(store, PROJECT, "pricing::base_fee", depth=2)
+        assert [m["symbol"] for m in result["matches"]] == ["base_fee"]
+
+        ranked = [(c["symbol"], c["depth"], c["confidence"]) for c in result["callers"]]
+        assert ranked == [
+            ("fees", 1, 0.9),
+            ("apply_discount", 1, 0.8),
+            ("checkout", 2, 0.72),
+            ("total", 2, 0.72),
+        ]
+        assert result["callers"][2]["via"] == "apply_discount"
+
+    def test_find_callees(self, store):
+        """Callees follow edges forwards and ignore non-call relations."""
+        result = find_callees(store, PROJECT, "checkout_empty", depth=1)
+        assert [(c["symbol"], c["file"]) for c in result["callees"]] == [("checkout", "shop/src/lib.rs")]
+
+        deep = find_callees(store, PROJECT, "checkout", depth=5)
+        assert {c["symbol"]: c["confidence"] for c in deep["callees"]} == {
+            "apply_discount": 0.9, "base_fee": 0.72, "total": 0.3, "fees": 0.24,
+        }
+
+    def test_impact_of_file(self, store):
+        """A file resolves to its definitions; affected code elsewhere is grouped by file."""
+        result = impact_of_change(store, PROJECT, "src/pricing.rs", depth=3)
+        assert {r["symbol"] for r in result["roots"]} == {"base_fee", "apply_discount"}
+        assert [f["file"] for f in result["files"]] == ["shop/src/model.rs", "shop/src/lib.rs", "shop/tests/flow.rs"]
+        assert result["risk"] == "medium"
+
+    def test_unknown_symbol(self, store):
+        """Unknown symbols yield empty, well-formed results."""
+        result = impact_of_change(store, PROJECT, "does_not_exist")
+        assert result["roots"] == [] and result["affected"] == [] and result["risk"] == "low"
+
+    def test_owner_qualifier(self, make_project, index_project):
+        """`Type.method` keeps only the method of that type, in an indexed file with two owners."""
+        two_owners = ("class Cart:\n    def total(self):\n        return 1\n\n\n"
+                      "class Order:\n    def total(self):\n        return 2\n\n\n"
+                      "def checkout():\n    return Cart.total(None)\n")
+        root = make_project({"pkg/models.py": two_owners})
+        store = index_project(root).schema
+        project_id = store.engine.get_project_id()
+
+        result = find_callers(store, project_id, "Cart.total", depth=1)
+        assert [m["file"] for m in result["matches"]] == ["pkg/models.py"]
+        assert [c["symbol"] for c in result["callers"]] == ["checkout"]
+        assert find_callers(store, project_id, "Order.total", depth=1)["callers"] == []
+        assert find_callers(store, project_id, "models.Order.total")["matches"] == \
+            find_callers(store, project_id, "Order.total")["matches"]
+        assert len(find_callers(store, project_id, "total")["matches"]) == 2
+        assert len(find_callers(store, project_id, "Basket.total")["matches"]) == 2  # Nothing owned by Basket: no narrowing
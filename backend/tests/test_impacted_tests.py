@@ -0,0 +1,206 @@
+"""
+Test: Impacted Tests
+
+Verifies that changed lines since a revision map to the entities containing
+them, that only the tests reaching those entities are selected, and that each
+runner gets a ready-to-run command line (`cargo test` filters, pytest node ids,
+vitest `-t` patterns).
+"""
+from types import SimpleNamespace
+from unittest.mock import patch
+
+import pytest
+
+from side.intel.impacted_tests import affected_tests
+from side.services.context_tracker import ContextTracker
+
+FILES = {
+    "Cargo.toml": '[package]\nname = "shop"\nversion = "0.1.0"\n',
+    "src/lib.rs": "pub mod order;\n",
+    "src/order.rs": """pub struct Order { items: Vec<u32> }
+
+impl Order {
+    pub fn new() -> Order { Order { items: Vec::new() } }
+    pub fn add_item(&mut self, id: u32) { self.items.push(id); }
+    pub fn total(&self) -> u32 { 0 }
+}
+
+pub fn checkout(order: &mut Order) -> u32 {
+    order.total()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_items() {
+        let mut order = Order::new();
+        order.add_item(7);
+    }
+
+    #[test]
+    fn checks_out() {
+        checkout(&mut Order::new());
+    }
+}
+""",
+    "pkg/__init__.py": "",
+    "pkg/billing.py": """RATE = 1
+
+
+def invoice(amount):
+    return amount
+
+
+def charge(amount):
+    return invoice(amount)
+
+
+def refund(amount):
+    return -amount
+""",
+    "tests/test_billing.py": """from pkg.billing import charge, invoice
+
+
+def test_invoice():
+    assert invoice(1) == 1
+
+
+class TestCharge:
+    def test_charge(self):
+        assert charge(2) == 2
+""",
+    "web/package.json": '{"name": "web", "devDependencies": {"vitest": "^1.0.0"}}\n',
+    "web/src/cart.ts": """export function addToCart(id: string): void {}
+
+export function clearCart(): void {}
+""",
+    "web/src/cart.test.ts": """import { addToCart, clearCart } from './cart';
+
+describe('cart', () => {
+  it('adds (one) item', () => {
+    addToCart("a");
+  });
+
+  it('clears', () => {
+    clearCart();
+  });
+});
+""",
+}
+
+def edit(root, rel, old, new):
+    path = root / rel
+    path.write_text(path.read_text().replace(old, new))
+
+
+@pytest.fixture
+def project(make_project, index_project):
+    root = make_project(FILES, repo=True)
+    return root, index_project(root)
+
+
+def selected(report):
+    return [(t["test"], t["depth"], t["via"]) for t in report["tests"]]
+
+
+class TestTestImpact:
+    """Tests for affected-test selection."""
+
+    def test_changed_lines(self, project):
+        """Hunks map to working-tree lines; new and deleted files are whole-file changes."""
+        root, _ = project
+        edit(root, "pkg/billing.py", "    return -amount", "    return 0 - amount")
+        (root / "pkg/notes.py").write_text("x = 1\n")
+        (root / "tests/test_billing.py").unlink()
+        assert ContextTracker.get_changed_lines(root) == {
+            "pkg/billing.py": [(13, 13)], "pkg/notes.py": None, "tests/test_billing.py": None,
+        }
+        with pytest.raises(ValueError, match="Unknown revision: nope"):
+            ContextTracker.get_changed_lines(root, "nope")
+
+    def test_changed_lines_with_unusual_names(self, project, git):
+        """Non-ASCII, spaced and quoted file names come back as paths, never as a None key."""
+        root, engine = project
+        names = ["pkg/é.py", "pkg/with space.py", 'pkg/q"t.py']
+        for name in names:
+            (root / name).write_text("x = 1\n")
+        git(root, "add", "-A")
+        git(root, "-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-qm", "names")
+        for name in names:
+            (root / name).write_text("x = 2\n")
+        (root / "pkg/ü new.py").write_text("y = 1\n")
+
+        assert ContextTracker.get_changed_lines(root) == {
+            "pkg/é.py": [(1, 1)], "pkg/with space.py": [(1, 1)], 'pkg/q"t.py': [(1, 1)], "pkg/ü new.py": None,
+        }
+        assert affected_tests(root, engine.schema)["tests"] == []
+
+    def test_rust_change_selects_reaching_tests(self, project):
+        """Editing a method selects only the tests that reach it, as `cargo test --exact` filters."""
+        root, engine = project
+        edit(root, "src/order.rs", "pub fn total(&self) -> u32 { 0 }", "pub fn total(&self) -> u32 { 1 }")
+        report = affected_tests(root, engine.schema)
+        assert report["changed"] == [{"symbol": "total", "type": "method", "file": "src/order.rs"}]
+        assert selected(report) == [("order::tests::checks_out", 2, "checkout")]
+        assert [c["command"] for c in report["commands"]] == ["cargo test -- order::tests::checks_out --exact"]
+        assert report["summary"] == "1 of 6 tests affected by 1 changed entities"
+
+    def test_python_and_vitest_commands(self, project):
+        """pytest gets node ids; vitest gets the test files and an anchored full-name pattern."""
+        root, engine = project
+        edit(root, "pkg/billing.py", "    return amount", "    return amount * RATE")
+        edit(root, "web/src/cart.ts", "addToCart(id: string): void {}", "addToCart(id: string): void { void id; }")
+        report = affected_tests(root, engine.schema)
+        assert selected(report) == [
+            ("tests/test_billing.py::test_invoice", 1, "invoice"),
+            ("cart adds (one) item", 1, "addToCart"),
+            ("tests/test_billing.py::TestCharge::test_charge", 2, "charge"),
+        ]
+        commands = {c["runner"]: (c["cwd"], c["command"]) for c in report["commands"]}
+        assert commands["pytest"] == (".", "pytest tests/test_billing.py::TestCharge::test_charge "
+                                            "tests/test_billing.py::test_invoice")
+        assert commands["vitest"] == ("web", "npx vitest run src/cart.test.ts -t '^(?:cart adds \\(one\\) item)$'")
+
+    def test_test_files_and_untraced_changes(self, project):
+        """Editing a test selects it; module-level test changes select the file; other module code is reported."""
+        root, engine = project
+        edit(root, "web/src/cart.test.ts", "clearCart();", "clearCart();\n    clearCart();")
+        edit(root, "tests/test_billing.py", "from pkg.billing import charge, invoice",
+             "import pytest\nfrom pkg.billing import charge, invoice")
+        edit(root, "pkg/billing.py", "RATE = 1", "RATE = 2")
+        report = affected_tests(root, engine.schema)
+        assert {(t["test"], t["depth"]) for t in report["tests"]} == {
+            ("cart clears", 0), ("tests/test_billing.py::test_invoice", 0),
+            ("tests/test_billing.py::TestCharge::test_charge", 0),
+        }
+        assert report["unmapped"] == ["pkg/billing.py"] and "pkg/billing.py" in report["warning"]
+
+        clean = affected_tests(root, engine.schema, changes={})
+        assert clean["tests"] == [] and clean["commands"] == [] and "warning" not in clean
+
+    def test_since_revision_and_cli(self, project, git):
+        """`side test-impact --since <rev>` includes committed changes after the revision."""
+        from side.cli_handlers import intel
+
+        root, engine = project
+        base = git(root, "rev-parse", "HEAD")
+        edit(root, "src/order.rs", "self.items.push(id);", "self.items.push(id + 1);")
+        git(root, "-c", "user.name=test", "-c", "user.email=test@example.com", "commit", "-qam", "tweak")
+        assert affected_tests(root, engine.schema)["tests"] == []
+
+        with patch.object(intel, "get_engine", return_value=engine), patch.object(intel, "ux") as ux, \
+                patch.object(intel, "Path", SimpleNamespace(cwd=lambda: root)):
+            intel.handle_test_impact(SimpleNamespace(since=base, depth=3))
+        tables = {call.args[0]: call.args[2] for call in ux.render_table.call_args_list}
+        assert [row[0] for row in tables["Affected Tests"]] == ["order::tests::adds_items"]
+        assert tables["Run"] == [[".", "cargo test -- order::tests::adds_items --exact"]]
+
+    def test_same_test_name_in_two_files(self, same_named_tests):
+        """`test_basic` in two files are two tests: a change selects only the one reaching it."""
+        root, engine = same_named_tests
+        edit(root, "pkg/b.py", "    return 2", "    return 3")
+        report = affected_tests(root, engine.schema)
+        assert selected(report) == [("tests/test_b.py::test_basic", 1, "bar")]
+        assert [c["command"] for c in report["commands"]] == ["pytest tests/test_b.py::test_basic"]
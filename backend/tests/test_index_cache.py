@@ -0,0 +1,121 @@
+"""
+Test: Incremental Index Cache
+
+Verifies unchanged files are served from the content-addressed cache in `.side`
+without re-parsing, that Merkle checksums stay stable, and that stale entries are pruned.
+"""
+import json
+from pathlib import Path
+from unittest.mock import patch
+
+from side.intel import tree_indexer
+from side.intel.tree_indexer import run_context_scan, update_branch
+from side.utils.crypto import shield
+from tests.conftest import SOURCE_TREE
+
+FILES = SOURCE_TREE
+
+
+def parsed_during(action):
+    """Names of source files whose semantics were extracted while running `action`."""
+    parsed = []
+    original = tree_indexer.get_file_semantics
+
+    def spy(path, content):
+        if not path.name.startswith("."):
+            parsed.append(path.name)
+        return original(path, content)
+
+    with patch.object(tree_indexer, "get_file_semantics", spy):
+        action()
+    return sorted(parsed)
+
+
+def root_checksum(root: Path) -> str:
+    return json.loads(shield.unseal_file(root / ".side" / "local.json"))["checksum"]
+
+
+class TestIndexCache:
+    """Tests for content-addressed incremental indexing."""
+
+    def test_rescan_parses_nothing(self, project):
+        """A second full scan is served entirely from the cache with identical checksums."""
+        assert parsed_during(lambda: run_context_scan(project)) == ["app.ts", "invoice.py", "orders.py", "tax.py"]
+        before = root_checksum(project)
+
+        assert parsed_during(lambda: run_context_scan(project)) == []
+        assert root_checksum(project) == before
+
+    def test_update_branch_parses_only_the_edit(self, project):
+        """Saving one file re-parses that file alone; ancestors are rebuilt from cached DNA."""
+        run_context_scan(project)
+        before = root_checksum(project)
+
+        edited = project / "src/billing/tax.py"
+        edited.write_text("def tax(amount, rate=0.2):\n    return amount * rate\n")
+        assert parsed_during(lambda: update_branch(project, edited)) == ["tax.py"]
+        assert root_checksum(project) != before
+
+        # Reverting restores the old content: found by hash, no parse, original checksum
+        edited.write_text(FILES["src/billing/tax.py"])
+        assert parsed_during(lambda: update_branch(project, edited)) == []
+        assert root_checksum(project) == before
+
+    def test_copy_hits_content_cache(self, project):
+        """A new file with already-indexed content is not parsed, but its DNA carries its own name."""
+        run_context_scan(project)
+        copy = project / "src/billing/vat.py"
+        copy.write_text(FILES["src/billing/tax.py"])
+        assert parsed_during(lambda: update_branch(project, copy)) == []
+
+        local = json.loads(shield.unseal_file(project / "src/billing/.side/local.json"))
+        names = {f["name"]: f["semantics"]["functions"] for f in local["context"]["files"]}
+        assert names["vat.py"] == names["tax.py"] == ["tax"]
+
+    def test_prune_after_full_scan(self, project):
+        """Objects for deleted content are removed by the next full scan."""
+        run_context_scan(project)
+        objects = project / ".side" / "cache" / "index"
+
+        def cached_languages():
+            return sorted(p.suffix for p in objects.glob("v*/objects/*/*") if p.suffix != ".plain")
+
+        assert cached_languages() == [".python", ".python", ".python", ".typescript"]
+        (project / "web/app.ts").unlink()
+        run_context_scan(project)
+        assert cached_languages() == [".python", ".python", ".python"]
+
+    def test_cached_files_reach_a_new_database(self, project):
+        """Stat hits skip persistence only for the database a full scan already filled."""
+        from side.storage.modules.base import ContextEngine
+
+        first = ContextEngine(project / ".side" / "first.db").schema
+        run_context_scan(project, schema_store=first, workers=1)
+        second = ContextEngine(project / ".side" / "second.db").schema
+        assert parsed_during(lambda: run_context_scan(project, schema_store=second, workers=1)) == []
+
+        project_id = second.engine.get_project_id()
+        assert [e["name"] for e in second.find_entities(project_id, "Order", "class")] == ["Order"]
+        assert [e["name"] for e in second.find_entities(project_id, "tax")] == ["tax"]
+
+    def test_removed_items_leave_the_database(self, project):
+        """Items deleted from a re-persisted file are dropped with their edges, by update_branch and full scans."""
+        from side.storage.modules.base import ContextEngine
+
+        store = ContextEngine(project / ".side" / "graph.db").schema
+        project_id = store.engine.get_project_id()
+        run_context_scan(project, schema_store=store, workers=1)
+        [total] = store.find_entities(project_id, "total", "method")
+        assert store.list_relationships(target_id=total["id"], relation_type="calls")
+
+        orders = project / "src/orders.py"
+        orders.write_text(FILES["src/orders.py"].replace("total", "subtotal"))
+        update_branch(project, orders, schema_store=store)
+        assert store.find_entities(project_id, "total", "method") == []
+        assert store.list_relationships(target_id=total["id"]) == []
+        assert [e["file_path"] for e in store.find_entities(project_id, "Order", "class")] == ["src/orders.py"]
+
+        (project / "src/billing/tax.py").write_text("def levy(amount):\n    return amount * 0.2\n")
+        run_context_scan(project, schema_store=store, workers=1)
+        assert store.find_entities(project_id, "tax", "function") == []
+        assert len(store.find_entities(project_id, "levy", "function")) == 1
@@ -0,0 +1,137 @@
+"""
+Test: Index Diff Between Revisions
+
+Verifies two git revisions are indexed from the object store without a checkout
+and compared for added/removed/changed entities, signature changes and new call
+edges, that revision snapshots are cached by tree, and that `side index --diff`
+reports them.
+"""
+import sys
+
+import pytest
+from unittest.mock import patch
+
+from side.intel import index_diff
+from side.intel.index_diff import diff_revisions
+
+BASE = {
+    "shop/Cargo.toml": '[package]\nname = "shop"\nversion = "0.1.0"\n',
+    "shop/src/lib.rs": """pub mod pricing;
+
+pub fn checkout(total: u32) -> u32 {
+    pricing::with_tax(total)
+}
+
+pub fn legacy() {}
+""",
+    "shop/src/pricing.rs": """pub fn with_tax(total: u32) -> u32 {
+    total * 2
+}
+
+pub fn discount(total: u32) -> u32 {
+    total - 1
+}
+""",
+    "app/orders.py": """class Order:
+    def total(self):
+        return 0
+""",
+}
+
+HEAD = {
+    "shop/src/lib.rs": """pub mod pricing;
+
+pub fn checkout(total: u32, coupon: bool) -> u32 {
+    let price = pricing::with_tax(total);
+    if coupon { pricing::discount(price) } else { price }
+}
+""",
+    "shop/src/pricing.rs": """pub fn with_tax(total: u32) -> u32 {
+    total * 2 + 1
+}
+
+pub fn discount(total: u32) -> u32 {
+    total - 1
+}
+""",
+    "app/orders.py": """class Order:
+    def total(self):
+        return 0
+
+    def refund(self):
+        return self.total()
+""",
+}
+
+
+def symbols(items):
+    return sorted((i["symbol"], i["file"]) for i in items)
+
+
+class TestIndexDiff:
+    """Tests for comparing the index of two git revisions."""
+
+    def test_entities(self, repo):
+        """Added, removed and body-changed entities are reported per file."""
+        root, base, head = repo
+        report = diff_revisions(root, base, head)
+        assert symbols(report["added"]) == [("Order.refund", "app/orders.py")]
+        assert symbols(report["removed"]) == [("legacy", "shop/src/lib.rs")]
+        assert symbols(report["changed"]) == [("checkout", "shop/src/lib.rs"), ("with_tax", "shop/src/pricing.rs")]
+
+    def test_signature_changes(self, repo):
+        """Only declaration changes count as signature changes, with before and after."""
+        root, base, head = repo
+        report = diff_revisions(root, base, head)
+        assert report["signature_changes"] == [{
+            "symbol": "checkout", "type": "function", "file": "shop/src/lib.rs", "line": 3,
+            "before": "pub fn checkout(total: u32) -> u32",
+            "after": "pub fn checkout(total: u32, coupon: bool) -> u32",
+        }]
+        changed = {c["symbol"]: c["signature_changed"] for c in report["changed"]}
+        assert changed == {"checkout": True, "with_tax": False}
+
+    def test_new_call_edges(self, repo):
+        """Calls present only in head are listed with their resolution confidence."""
+        root, base, head = repo
+        report = diff_revisions(root, base, head)
+        calls = {(c["caller"], c["callee"]): c["confidence"] for c in report["new_calls"]}
+        assert calls[("checkout", "discount")] >= 0.7
+        assert calls[("Order.refund", "Order.total")] >= 0.8
+        assert report["removed_calls"] == []
+        assert report["summary"]["new_calls"] == len(report["new_calls"])
+
+    def test_no_checkout_and_cached_snapshots(self, repo, git):
+        """The working tree is untouched, and a repeated diff reuses both snapshots."""
+        root, base, head = repo
+        (root / "app/orders.py").write_text("# local edit\n")
+        diff_revisions(root, base, head)
+        assert (root / "app/orders.py").read_text() == "# local edit\n"
+        assert git(root, "rev-parse", "HEAD") == head
+
+        with patch.object(index_diff, "build_snapshot") as build:
+            again = diff_revisions(root, base, head)
+        build.assert_not_called()
+        assert symbols(again["removed"]) == [("legacy", "shop/src/lib.rs")]
+
+    def test_unknown_revision(self, repo):
+        root, base, _ = repo
+        with pytest.raises(ValueError, match="Unknown revision: nope"):
+            diff_revisions(root, base, "nope")
+
+    def test_cli(self, repo):
+        """`side index PATH --diff BASE HEAD` diffs instead of indexing; a stray positional revision is rejected."""
+        from side import cli
+        from side.cli_handlers import intel
+
+        root, base, head = repo
+        with patch.object(sys, "argv", ["side", "index", str(root), "--diff", base, head]), \
+                patch.object(intel, "ux") as ux, patch.object(intel, "get_engine") as get_engine:
+            cli.main()
+        get_engine.assert_not_called()
+        ux.display_header.assert_called_once_with("Index Diff", subtitle=f"{base}..{head}")
+
+        with patch.object(sys, "argv", ["side", "index", "diff", base]), patch.object(cli, "handle_index") as index:
+            with pytest.raises(SystemExit):
+                cli.main()
+        index.assert_not_called()
@@ -0,0 +1,127 @@
+"""
+Test: Central Index Store
+
+Verifies the central layout keeps the sparse Merkle tree under `<root>/.side/tree`
+without touching the working copy, produces the same checksums as per-directory
+files, prunes vanished directories, and that migration collects existing files.
+"""
+import os
+import shutil
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import AsyncMock, MagicMock, patch
+
+from side.config import config
+from side.intel.index_store import CentralIndexStore, ScatteredIndexStore, migrate_to_central, open_index_store
+from side.intel.tree_indexer import run_context_scan, update_branch
+from tests.conftest import SOURCE_TREE
+
+FILES = SOURCE_TREE
+
+
+def layout(value):
+    return patch.object(config, "index_store", value)
+
+
+def scattered_files(root: Path):
+    return sorted(p.relative_to(root).as_posix() for p in root.rglob(".side/local.json"))
+
+
+class TestIndexStore:
+    """Tests for the central sparse-index layout and its migration."""
+
+    def test_central_scan_leaves_working_copy_clean(self, project):
+        """Only `<root>/.side` is written, and checksums match the scattered layout."""
+        scattered = project.parent / "scattered"
+        shutil.copytree(project, scattered)
+        with layout("scattered"):
+            run_context_scan(scattered)
+        with layout("central"):
+            run_context_scan(project)
+
+        assert scattered_files(project) == []
+        assert not (project / "src" / ".side").exists()
+        central, legacy = CentralIndexStore(project), ScatteredIndexStore(scattered)
+        for rel in ("", "src", "src/billing"):
+            assert central.read(project / rel)["checksum"] == legacy.read(scattered / rel)["checksum"]
+
+    def test_update_branch_central(self, project):
+        """Incremental updates rewrite the branch in the central tree."""
+        with layout("central"):
+            run_context_scan(project)
+            store = open_index_store(project)
+            before = store.read(project)["checksum"]
+            billing = store.read(project / "src/billing")["checksum"]
+
+            edited = project / "src/billing/tax.py"
+            edited.write_text("def tax(amount, rate=0.2):\n    return amount * rate\n")
+            update_branch(project, edited)
+
+        assert store.read(project)["checksum"] != before
+        assert store.read(project / "src/billing")["checksum"] != billing
+        assert scattered_files(project) == []
+
+    def test_prune_vanished_directories(self, project):
+        """A full scan drops nodes of directories that no longer exist."""
+        with layout("central"):
+            run_context_scan(project)
+            store = open_index_store(project)
+            assert store.read(project / "src/billing") is not None
+
+            shutil.rmtree(project / "src/billing")
+            run_context_scan(project)
+        assert store.read(project / "src/billing") is None
+        assert "billing" not in store.read(project / "src")["context"]["children"]
+
+    def test_migration(self, project):
+        """Existing per-directory files move into the central tree, which then becomes the default."""
+        with layout("scattered"):
+            run_context_scan(project)
+        legacy = ScatteredIndexStore(project)
+        checksums = {rel: legacy.read(project / rel)["checksum"] for rel in ("", "src", "src/billing")}
+        assert scattered_files(project) == [".side/local.json", "src/.side/local.json", "src/billing/.side/local.json"]
+
+        assert migrate_to_central(project) == 3
+        assert scattered_files(project) == []
+        assert not (project / "src/billing/.side").exists()
+
+        with layout(""):
+            store = open_index_store(project)
+            assert store.layout == "central"
+            assert {rel: store.read(project / rel)["checksum"] for rel in checksums} == checksums
+            run_context_scan(project)
+        assert scattered_files(project) == []
+        assert store.read(project)["checksum"] == checksums[""]
+
+    def test_cli_migrate_store(self, project):
+        """`side index --migrate-store` moves existing per-directory files before indexing."""
+        from side.cli_handlers import intel
+        from side.intel import context_service
+
+        with layout("scattered"):
+            run_context_scan(project)
+        service = MagicMock()
+        service.return_value.feed = AsyncMock(return_value={})
+        args = SimpleNamespace(path=str(project), migrate_store=True, import_scip=None, export=None)
+        with patch.object(intel, "get_engine"), patch.object(intel, "ux") as ux, \
+                patch.object(context_service, "ContextService", service):
+            intel.handle_index(args)
+        assert scattered_files(project) == []
+        assert CentralIndexStore(project).read(project / "src/billing") is not None
+        ux.display_status.assert_any_call(f"Moved 3 directory indexes into {project / '.side' / 'tree'}.",
+                                          level="success")
+
+    def test_purge_stale_central_nodes(self, project):
+        """The asset purge ages out central nodes as it does scattered files, keeping the tree itself."""
+        from side.storage.modules.base import ContextEngine
+
+        with layout("central"):
+            run_context_scan(project)
+        store = CentralIndexStore(project)
+        stale, fresh = store.node_path(project / "src/billing"), store.node_path(project / "src")
+        os.utime(stale, (0, 0))
+
+        audit = ContextEngine(project / ".side" / "graph.db").audits
+        assert audit.prune_stale_assets(project) == 1
+        assert not stale.exists() and fresh.exists()
+        assert store.base.is_dir() and scattered_files(project) == []
@@ -0,0 +1,103 @@
+"""
+Test: Language Registry
+
+Verifies LanguageSpec lookups, entry-point plugins and that detection reads from the registry.
+"""
+import re
+import pytest
+from pathlib import Path
+from unittest.mock import patch
+
+from side.intel.languages import LanguageRegistry, LanguageSpec, BUILTIN_LANGUAGES
+
+ZIG = LanguageSpec(
+    name="zig",
+    extensions=(".zig",),
+    manifests={"build.zig": "zig_build"},
+    regex={"class": re.compile(r"const\s+(\w+)\s*=\s*struct"), "def": re.compile(r"\bfn\s+(\w+)")},
+    audit_adapters=("side.tools.audit_adapters.clippy:ClippyAdapter",),
+    log_label="NATIVE",
+    log_patterns=("reached unreachable code",),
+)
+
+
+class FakeEntryPoint:
+    def __init__(self, name, obj):
+        self.name, self._obj = name, obj
+
+    def load(self):
+        if isinstance(self._obj, Exception):
+            raise self._obj
+        return self._obj
+
+
+@pytest.fixture
+def registry():
+    reg = LanguageRegistry(BUILTIN_LANGUAGES)
+    reg._plugins_loaded = True  # Isolate from whatever is installed
+    return reg
+
+
+class TestLanguageRegistry:
+    """Tests for the LanguageSpec registry."""
+
+    def test_builtin_lookups(self, registry):
+        """Extensions, manifests and globs resolve to one normalized language."""
+        assert registry.for_path("src/lib.rs").name == "rust"
+        assert registry.for_path("App.TSX").name == "typescript"
+        assert registry.for_path("main.jsx").name == "javascript"
+        assert registry.for_path("README.md") is None
+        assert registry.for_manifest("Cargo.toml").name == "rust"
+        assert registry.for_manifest("Orders.csproj").name == "dotnet"
+        assert registry.get("dotnet").grammar == "c_sharp"
+        assert {".py", ".rs", ".go", ".kt", ".swift"} <= registry.source_extensions()
+
+    def test_log_families_keep_priority(self, registry):
+        """PHP is checked before JS_NODE; NATIVE merges Go and Rust signatures."""
+        families = registry.log_error_patterns()
+        labels = list(families)
+        assert labels.index("PHP") < labels.index("JS_NODE")
+        assert {"panic:", "segfault"} <= set(families["NATIVE"])
+
+    def test_audit_adapters(self, registry):
+        """Adapters are resolved per language and deduplicated."""
+        names = [cls.__name__ for cls in registry.audit_adapter_classes(["javascript", "typescript", "rust"])]
+        assert names == ["ESLintAdapter", "CargoAuditAdapter", "ClippyAdapter"]
+
+    def test_entry_point_plugins(self, registry):
+        """Plugins register specs (directly, in lists or via factories); broken ones are skipped."""
+        registry._plugins_loaded = False
+        points = [
+            FakeEntryPoint("zig", ZIG),
+            FakeEntryPoint("nim", lambda: [LanguageSpec(name="nim", extensions=(".nim",))]),
+            FakeEntryPoint("broken", ImportError("missing dependency")),
+        ]
+        with patch("importlib.metadata.entry_points", return_value=points):
+            assert registry.load_plugins() == ["zig", "nim"]
+
+        assert registry.for_path("build.zig").name == "zig"
+        assert registry.for_manifest("build.zig").name == "zig"
+        assert registry.for_path("a.nim").name == "nim"
+        assert "reached unreachable code" in registry.log_error_patterns()["NATIVE"]
+
+    def test_detector_and_indexer_use_registry(self, tmp_path):
+        """A registered plugin language is detected and indexed without touching call sites."""
+        from side.intel import languages
+        from side.intel.language_detector import detect_primary_languages
+        from side.intel.tree_indexer import get_file_semantics
+
+        (tmp_path / "build.zig").write_text("")
+        (tmp_path / "src").mkdir()
+        source = tmp_path / "src" / "main.zig"
+        source.write_text("const Order = struct {};\npub fn main() void {}\n")
+
+        with patch.object(languages.registry, "_specs", dict(languages.registry._specs)), \
+             patch.object(languages.registry, "_by_ext", dict(languages.registry._by_ext)), \
+             patch.object(languages.registry, "_by_manifest", dict(languages.registry._by_manifest)), \
+             patch.object(languages.registry, "_manifest_globs", list(languages.registry._manifest_globs)):
+            languages.register_language(ZIG)
+            assert "zig" in detect_primary_languages(tmp_path)
+            semantics = get_file_semantics(source, source.read_text())
+
+        assert semantics["classes"] == ["Order"]
+        assert semantics["functions"] == ["main"]
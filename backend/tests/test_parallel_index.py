@@ -0,0 +1,81 @@
+"""
+Test: Multi-Process Indexing
+
+Verifies parsing is sharded across worker processes without changing the
+bottom-up Merkle checksums, that entities are written in one batch, that
+worker-written cache objects survive pruning, and that throughput is reported.
+"""
+from pathlib import Path
+from unittest.mock import patch
+
+from side.intel import tree_indexer
+from side.intel.index_store import ScatteredIndexStore
+from side.intel.tree_indexer import run_context_scan
+from tests.conftest import SOURCE_TREE
+
+FILES = {**SOURCE_TREE, "web/util.ts": "export function pad(s: string): string { return s; }\n"}
+
+
+def processes():
+    """Forces the process pool even for a handful of files."""
+    return patch.object(tree_indexer, "PROCESS_POOL_MIN_FILES", 0)
+
+
+def checksums(root: Path):
+    store = ScatteredIndexStore(root)
+    return {rel: store.read(root / rel)["checksum"] for rel in ("", "src", "src/billing")}
+
+
+class TestParallelIndex:
+    """Tests for process-sharded scanning."""
+
+    def test_same_merkle_tree_as_serial(self, project, make_project):
+        """Process-parsed DNA yields exactly the checksums of an in-process scan."""
+        serial = make_project(FILES, name="serial")
+        run_context_scan(serial, workers=1)
+        with processes():
+            stats = run_context_scan(project, workers=2)
+        assert stats["workers"] == 2 and stats["parsed"] == stats["files"]
+        assert checksums(project) == checksums(serial)
+
+    def test_worker_cache_objects_survive_prune(self, project):
+        """Objects stored by worker processes are marked live, so the scan's own prune keeps them."""
+        objects = project / ".side" / "cache" / "index"
+        with processes():
+            run_context_scan(project, workers=2)
+        assert sorted(p.suffix for p in objects.glob("v*/objects/*/*") if p.suffix != ".plain") == [
+            ".python", ".python", ".python", ".typescript", ".typescript",
+        ]
+
+        stats = run_context_scan(project, workers=1)
+        assert stats["parsed"] == 0 and stats["cached"] == stats["files"]
+
+    def test_entities_written_in_one_batch(self, project):
+        """All tree entities reach SchemaStore in a single save, owners before members."""
+        from side.storage.modules.base import ContextEngine
+
+        store = ContextEngine(project / "graph.db").schema
+        batches = []
+        original = store.save_entities_batch
+
+        def spy(entities):
+            batches.append(list(entities))
+            return original(entities)
+
+        with processes(), patch.object(store, "save_entities_batch", spy), \
+                patch("side.intel.call_graph.index_call_graph"), patch("side.intel.type_graph.index_type_graph"), \
+                patch("side.intel.test_links.index_test_links"), patch("side.intel.route_map.index_route_map"):
+            stats = run_context_scan(project, schema_store=store, workers=2)
+
+        assert len(batches) == 1 and len(batches[0]) == stats["entities"]
+        names = [e["name"] for e in batches[0]]
+        assert names.index("Order") < names.index("total")
+        total = store.get_entity_by_name(store.engine.get_project_id(), "total", "method")
+        assert total["parent_id"] == store.get_entity_by_name(store.engine.get_project_id(), "Order", "class")["id"]
+
+    def test_throughput_stats(self, project):
+        """The scan reports files, directories, cache split and files per second."""
+        stats = run_context_scan(project, workers=1)
+        assert stats["files"] == len(FILES) + 1 and stats["directories"] == 4  # + the generated .sideignore
+        assert stats["parsed"] + stats["cached"] == stats["files"]
+        assert stats["files_per_second"] > 0 and stats["seconds"] >= stats["parse_seconds"]
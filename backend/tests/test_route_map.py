@@ -0,0 +1,271 @@
+"""
+Test: Route Map
+
+Verifies that HTTP routes are extracted from FastAPI/Starlette decorators and
+route tables, Next.js app-router files, Express routers and axum/actix-web
+routers, that each is linked to its handler function and the auth in front of
+it, and that `list_routes` answers from the stored `route` entities.
+"""
+from side.intel import route_map
+from side.intel.call_graph import build_call_graph
+from side.intel.tree_indexer import update_branch
+
+FILES = {
+    "api/users.py": """from fastapi import APIRouter, Depends, FastAPI
+from mcp.server.fastmcp import FastMCP
+from starlette.routing import Route
+
+app = FastAPI()
+router = APIRouter(prefix="/users", dependencies=[Depends(verify_api_key)])
+mcp = FastMCP("demo")
+
+
+def verify_api_key():
+    return True
+
+
+def get_current_user():
+    return None
+
+
+def get_db():
+    return None
+
+
+@router.get("/{user_id}")
+async def read_user(user_id: int, user=Depends(get_current_user), db=Depends(get_db)):
+    return user_id
+
+
+@app.post("/login")
+def login(form: dict):
+    return create_token(form)
+
+
+def create_token(form):
+    return "t"
+
+
+@mcp.custom_route("/health", methods=["GET", "HEAD"])
+async def health(request):
+    return {}
+
+
+async def homepage(request):
+    return {}
+
+
+routes = [Route("/", homepage)]
+app.include_router(router, prefix="/v1")
+""",
+    "web/src/middleware.ts": """import { withAuth } from "next-auth/middleware";
+
+export default withAuth({});
+
+export const config = { matcher: ["/dashboard/:path*"] };
+""",
+    "web/src/app/(shop)/users/[id]/route.ts": """import { NextResponse } from "next/server";
+
+export async function GET(req: Request) {
+  return NextResponse.json({});
+}
+
+export const DELETE = async (req: Request) => NextResponse.json({});
+""",
+    "web/src/app/api/auth/route.ts": """import NextAuth from "next-auth";
+
+const handler = NextAuth({});
+
+export { handler as GET, handler as POST };
+""",
+    "web/src/app/dashboard/page.tsx": """export default function DashboardPage() {
+  return <div />;
+}
+""",
+    "web/src/app/_components/page.tsx": """export default function Hidden() {
+  return null;
+}
+""",
+    "server/app.js": """const express = require('express');
+const { requireAuth, listOrders, createOrder } = require('./orders');
+
+const app = express();
+const orders = express.Router();
+
+app.use(express.json());
+app.get('env');
+
+orders.get('/', listOrders);
+orders.post('/', requireAuth, createOrder);
+orders.route('/:id').get((req, res) => res.json({})).delete(requireAuth, asyncHandler(removeOrder));
+
+app.use('/orders', orders);
+""",
+    "server/orders.js": """function requireAuth(req, res, next) { next(); }
+function listOrders(req, res) { res.json([]); }
+function createOrder(req, res) { res.json({}); }
+function removeOrder(req, res) { res.json({}); }
+module.exports = { requireAuth, listOrders, createOrder, removeOrder };
+""",
+    "Cargo.toml": '[package]\nname = "shop"\nversion = "0.1.0"\n',
+    "src/main.rs": """mod handlers;
+use axum::{middleware, routing::{get, post}, Router};
+
+fn app() -> Router {
+    let api = Router::new()
+        .route("/orders", get(handlers::list_orders).post(handlers::create_order))
+        .route_layer(middleware::from_fn(require_auth));
+    Router::new()
+        .route("/", get(|| async { "ok" }))
+        .nest("/api", api)
+}
+
+async fn require_auth() {}
+
+fn main() {}
+""",
+    "src/handlers.rs": """use actix_web::{get, web, App};
+
+pub async fn list_orders() {}
+
+pub async fn create_order(claims: Claims) {}
+
+#[get("/items/{id}")]
+async fn item(path: web::Path<u32>) -> String { String::new() }
+
+async fn stats() {}
+
+async fn admin_index() {}
+
+fn config() {
+    App::new()
+        .service(item)
+        .service(web::resource("/stats").route(web::get().to(stats)))
+        .service(web::scope("/admin").wrap(HttpAuthentication::bearer(validator)).route("/", web::get().to(admin_index)));
+}
+""",
+}
+
+
+def extracted(root, framework):
+    routes = route_map.extract_routes(root, build_call_graph(root))
+    return [(r.method, r.path, r.handler, r.handler_file, r.auth) for r in routes if r.framework == framework]
+
+
+class TestRouteMap:
+    """Tests for route extraction and `route` entities."""
+
+    def test_python_routes(self, project):
+        """Router prefixes and `Depends` guards apply; `custom_route` and `Route` tables count; auth is checked, not issued."""
+        assert extracted(project, "fastapi") == [
+            ("GET", "/v1/users/{user_id}", "read_user", "api/users.py", ["verify_api_key", "get_current_user"]),
+            ("POST", "/login", "login", "api/users.py", []),
+        ]
+        assert extracted(project, "starlette") == [
+            ("GET", "/health", "health", "api/users.py", []),
+            ("HEAD", "/health", "health", "api/users.py", []),
+            ("GET", "/", "homepage", "api/users.py", []),
+        ]
+
+    def test_generic_words_need_a_guard_position(self, project):
+        """`token`/`role`/`login` mark a dependency or decorator, not a handler's own calls or types."""
+        assert route_map.is_auth("require_role", guard=True) and route_map.is_auth("requireAuth")
+        assert not any(route_map.is_auth(n) for n in ("tokenize", "CreateTokenRequest", "LoginForm", "RoleList"))
+        assert not route_map.is_auth("tokenize", guard=True)
+        (project / "api/tokens.py").write_text('''from fastapi import Depends, FastAPI
+
+app = FastAPI()
+
+
+def verify_token():
+    return True
+
+
+def login_required(fn):
+    return fn
+
+
+def tokenize(text):
+    return text.split()
+
+
+@app.post("/tokens")
+def split(body: "CreateTokenRequest"):
+    return tokenize(body)
+
+
+@app.get("/me")
+@login_required
+def me(user=Depends(verify_token)):
+    return user
+''')
+        assert [r for r in extracted(project, "fastapi") if r[3] == "api/tokens.py"] == [
+            ("POST", "/tokens", "split", "api/tokens.py", []),
+            ("GET", "/me", "me", "api/tokens.py", ["login_required", "verify_token"]),
+        ]
+
+    def test_nextjs_routes(self, project):
+        """Folders become the path (minus route groups and private folders); middleware covers matched paths."""
+        assert extracted(project, "nextjs") == [
+            ("GET", "/users/[id]", "GET", "web/src/app/(shop)/users/[id]/route.ts", []),
+            ("DELETE", "/users/[id]", "DELETE", "web/src/app/(shop)/users/[id]/route.ts", []),
+            ("GET", "/api/auth", "handler", None, []),
+            ("POST", "/api/auth", "handler", None, []),
+            ("GET", "/dashboard", "DashboardPage", "web/src/app/dashboard/page.tsx", ["withAuth"]),
+        ]
+
+    def test_nextjs_handlers_per_file(self, make_project, index_project):
+        """Every app-router `route.ts` exports `GET`: each route is handled by the one in its own file."""
+        get = 'export async function GET() {\n  return Response.json({});\n}\n'
+        root = make_project({"web/app/users/[id]/route.ts": get, "web/app/orders/route.ts": get})
+        engine = index_project(root)
+        routes = route_map.list_routes(engine.schema, engine.get_project_id())
+        assert sorted((r["path"], r["handler"], r["handler_file"]) for r in routes) == [
+            ("/orders", "GET", "web/app/orders/route.ts"),
+            ("/users/[id]", "GET", "web/app/users/[id]/route.ts"),
+        ]
+        assert len(engine.schema.find_entities(engine.get_project_id(), "GET", "function")) == 2
+
+    def test_express_routes(self, project):
+        """Mount prefixes, per-route middleware, `.route()` chains and wrapped handlers in another file."""
+        assert extracted(project, "express") == [
+            ("GET", "/orders/", "listOrders", "server/orders.js", []),
+            ("POST", "/orders/", "createOrder", "server/orders.js", ["requireAuth"]),
+            ("DELETE", "/orders/:id", "removeOrder", "server/orders.js", ["requireAuth"]),
+            ("GET", "/orders/:id", None, None, []),
+        ]
+
+    def test_rust_routes(self, project):
+        """axum `nest` through a binding, `route_layer` and auth extractors; actix attributes, resources and scopes."""
+        assert extracted(project, "axum") == [
+            ("GET", "/api/orders", "handlers::list_orders", "src/handlers.rs", ["require_auth"]),
+            ("POST", "/api/orders", "handlers::create_order", "src/handlers.rs", ["require_auth", "Claims"]),
+            ("GET", "/", None, None, []),
+        ]
+        assert extracted(project, "actix") == [
+            ("GET", "/items/{id}", "item", "src/handlers.rs", []),
+            ("GET", "/stats", "stats", "src/handlers.rs", []),
+            ("GET", "/admin/", "admin_index", "src/handlers.rs", ["HttpAuthentication"]),
+        ]
+
+    def test_stored_routes(self, indexed):
+        """Indexing stores `route` entities with `handles`/`guarded_by` edges; removed routes disappear."""
+        project, engine = indexed
+        pid = engine.get_project_id()
+        [posted] = route_map.list_routes(engine.schema, pid, method="post", prefix="/orders")
+        assert posted == {
+            "method": "POST", "path": "/orders/", "handler": "createOrder", "handler_file": "server/orders.js",
+            "auth": ["requireAuth"], "file": "server/app.js", "declaration": "orders.post('/', requireAuth, createOrder);",
+        }
+        [user] = route_map.list_routes(engine.schema, pid, prefix="/v1/")
+        assert (user["handler"], user["auth"]) == ("read_user", ["get_current_user", "verify_api_key"])
+        guard = engine.schema.get_entity_by_name(pid, "HttpAuthentication", entity_type="middleware")
+        assert guard and guard["file_path"] == "src/handlers.rs"
+        assert len(route_map.list_routes(engine.schema, pid)) == 20
+
+        app = project / "server/app.js"
+        app.write_text(app.read_text().replace("orders.get('/', listOrders);\n", ""))
+        update_branch(project, app, schema_store=engine.schema)
+        assert [r["method"] for r in route_map.list_routes(engine.schema, pid, prefix="/orders")] == [
+            "POST", "DELETE", "GET",
+        ]
@@ -0,0 +1,109 @@
+"""
+Test: Rust Debt Scan
+
+Verifies DebtScanner's unsafe / panic-surface pass, per-crate grouping and trend tracking.
+"""
+import pytest
+from pathlib import Path
+from side.tools.audit_scanner import DebtScanner, strip_rust_noise, rust_module_path
+
+LIB_RS = '''
+pub mod store;
+
+#[allow(dead_code, clippy::too_many_arguments)]
+pub fn load(path: &str) -> String {
+    // a comment mentioning .unwrap() is not code
+    let msg = "neither is this: panic!(\\"x\\")";
+    std::fs::read_to_string(path).unwrap()
+}
+
+pub unsafe fn raw(ptr: *const u8) -> u8 {
+    unsafe { *ptr }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn loads() {
+        super::load("x").len();
+        Some(1).unwrap();
+        panic!("tests may panic");
+    }
+}
+'''
+
+STORE_RS = '''
+pub struct Store;
+
+unsafe impl Send for Store {}
+
+impl Store {
+    pub fn get(&self) -> u8 {
+        let v: Option<u8> = None;
+        v.expect("present")
+    }
+
+    pub fn put(&self) {
+        todo!()
+    }
+}
+'''
+
+
+@pytest.fixture
+def rust_project(tmp_path):
+    crate = tmp_path / "engine"
+    (crate / "src").mkdir(parents=True)
+    (crate / "Cargo.toml").write_text('[package]\nname = "engine"\nversion = "0.1.0"\n')
+    (crate / "src" / "lib.rs").write_text(LIB_RS)
+    (crate / "src" / "store.rs").write_text(STORE_RS)
+    return tmp_path
+
+
+def _types(results, bucket):
+    return [(i["type"], i["module"]) for i in results[bucket] if "crate" in i]
+
+
+class TestRustDebtScan:
+    """Tests for the Rust pass of DebtScanner."""
+
+    def test_strip_rust_noise(self):
+        """Comments, strings and char literals are blanked; code and line count are kept."""
+        src = 'let a = "x.unwrap()"; // y.unwrap()\nlet c = \'{\'; /* unsafe { */ z.unwrap()'
+        stripped = strip_rust_noise(src)
+        assert stripped.count("\n") == 1
+        assert stripped.count("unwrap") == 1
+        assert "{" not in stripped and "unsafe" not in stripped
+
+    def test_module_paths(self, tmp_path):
+        """Files map onto Rust module paths."""
+        src = tmp_path / "src"
+        assert rust_module_path(src, src / "lib.rs") == "crate"
+        assert rust_module_path(src, src / "net" / "mod.rs") == "net"
+        assert rust_module_path(src, src / "net" / "tcp.rs") == "net::tcp"
+
+    def test_counts_per_crate_and_module(self, rust_project):
+        """Debt is attributed to crate/module; unwrap/panic in #[cfg(test)] code is ignored."""
+        results = DebtScanner(rust_project).scan()
+
+        debt = _types(results, "technical_debt")
+        assert ("UNWRAP", "crate") in debt
+        assert debt.count(("UNWRAP", "crate")) == 1
+        assert ("PANIC", "crate") not in debt
+        assert ("UNSAFE_FN", "crate") in debt and ("UNSAFE_BLOCK", "crate") in debt
+        assert ("ALLOW_SUPPRESSION", "crate") in debt
+        assert ("UNSAFE_IMPL", "store") in debt and ("EXPECT", "store") in debt
+        assert ("TODO_MACRO", "store") in _types(results, "placeholders")
+
+        totals = results["rust_summary"]["engine"]["totals"]
+        assert totals["UNWRAP"] == 1 and totals["EXPECT"] == 1
+        assert results["rust_summary"]["engine"]["modules"]["store"]["UNSAFE_IMPL"] == 1
+
+    def test_trend_across_scans(self, rust_project):
+        """A second scan reports deltas against the persisted history."""
+        DebtScanner(rust_project).scan()
+        (rust_project / "engine" / "src" / "store.rs").write_text(STORE_RS.replace('v.expect("present")', "v.unwrap()"))
+
+        trend = DebtScanner(rust_project).scan()["rust_trend"]["engine"]
+        assert trend["UNWRAP"] == {"current": 2, "previous": 1, "delta": 1}
+        assert trend["EXPECT"]["delta"] == -1
@@ -0,0 +1,156 @@
+"""
+Test: Rust Module Tree
+
+Verifies module paths follow `mod` declarations, `#[path]` attributes, mod.rs/foo.rs
+layouts and inline modules, that Rust entities get crate-qualified paths and unique
+ids, and that `use` statements (through re-exports and globs) resolve to entities.
+"""
+
+from side.intel import rust_modules
+from side.intel.graph_query import resolve_symbol
+
+FILES = {
+    "Cargo.toml": '[package]\nname = "shop-core"\nversion = "0.1.0"\n',
+    "src/lib.rs": """pub mod orders;
+pub mod net;
+mod billing;
+
+#[path = "generated/api_v1.rs"]
+pub mod api;
+
+pub use orders::model::Line as OrderLine;
+
+mod util {
+    pub mod strings;
+
+    pub fn new() -> u32 { 0 }
+}
+""",
+    "src/orders.rs": """pub mod model;
+pub use self::model::*;
+
+pub struct Order;
+
+impl Order {
+    pub fn new() -> Order { Order }
+}
+""",
+    "src/orders/model.rs": """pub struct Line;
+
+pub fn parse() -> Line { Line }
+""",
+    "src/net/mod.rs": "mod tcp;\n",
+    "src/net/tcp.rs": "pub fn connect() {}\n",
+    "src/billing.rs": """use std::collections::HashMap;
+use crate::orders::{Order, model::parse};
+use crate::OrderLine;
+use super::orders::Line;
+
+pub struct Invoice;
+
+impl Invoice {
+    pub fn new() -> Invoice {
+        let _ = Order::new();
+        Invoice
+    }
+}
+
+pub fn parse_all() { parse(); }
+""",
+    "src/generated/api_v1.rs": "pub fn parse() {}\n",
+    "src/util/strings.rs": "pub fn trim() {}\n",
+    "src/bin/tool/main.rs": "mod cli;\nfn main() {}\n",
+    "src/bin/tool/cli.rs": "pub fn run() {}\n",
+    "src/orphan.rs": "pub fn lost() {}\n",
+}
+
+
+def qualified(store, project_id, name, entity_type=None):
+    return sorted(e["qualified_name"] for e in store.find_entities(project_id, name, entity_type))
+
+
+class TestRustModules:
+    """Tests for the Rust module tree and crate-path identities."""
+
+    def test_module_paths(self, project):
+        """Files map to the module that loads them, not to where they sit on disk."""
+        path = lambda rel, line=0: rust_modules.module_path(project, project / rel, line)
+        assert path("src/lib.rs") == "shop_core"
+        assert path("src/orders/model.rs") == "shop_core::orders::model"
+        assert path("src/net/tcp.rs") == "shop_core::net::tcp"
+        assert path("src/generated/api_v1.rs") == "shop_core::api"
+        assert path("src/util/strings.rs") == "shop_core::util::strings"
+        assert path("src/bin/tool/cli.rs") == "tool::cli"
+        assert path("src/orphan.rs") == "shop_core::orphan"   # Unreached: directory layout
+
+    def test_inline_modules(self, project):
+        """Items inside `mod util { ... }` belong to `util`; items after the block don't."""
+        assert rust_modules.inline_modules(project, project / "src/lib.rs") == [(10, 14, "shop_core::util")]
+        assert rust_modules.module_path(project, project / "src/lib.rs", 13) == "shop_core::util"
+        assert rust_modules.module_path(project, project / "src/lib.rs", 8) == "shop_core"
+
+    def test_unique_ids(self, indexed):
+        """Same-named items in different modules are distinct entities with their crate paths."""
+        _, engine = indexed
+        store, project_id = engine.schema, engine.get_project_id()
+        assert qualified(store, project_id, "new") == [
+            "shop_core::billing::Invoice::new", "shop_core::orders::Order::new", "shop_core::util::new",
+        ]
+        assert qualified(store, project_id, "parse", "function") == ["shop_core::api::parse", "shop_core::orders::model::parse"]
+        assert len({e["id"] for e in store.find_entities(project_id, "new")}) == 3
+
+        tcp = store.find_entities(project_id, "tcp", "module")[0]
+        parents = []
+        while tcp["parent_id"]:
+            tcp = store.get_entity_by_id(tcp["parent_id"])
+            parents.append(tcp["qualified_name"])
+        assert parents == ["shop_core::net", "shop_core"]
+
+    def test_use_edges(self, indexed):
+        """`use` paths resolve through `crate`/`super`, renaming re-exports and globs; std is skipped."""
+        _, engine = indexed
+        store, project_id = engine.schema, engine.get_project_id()
+        billing = store.find_entities(project_id, "billing", "module")[0]
+        targets = {store.get_entity_by_id(r["target_id"])["qualified_name"]
+                   for r in store.list_relationships(source_id=billing["id"], relation_type="uses")}
+        assert targets == {"shop_core::orders::Order", "shop_core::orders::model::parse", "shop_core::orders::model::Line"}
+
+    def test_calls_and_lookup_share_ids(self, indexed):
+        """Call edges land on the qualified entities, and qualified queries pick the right one."""
+        _, engine = indexed
+        store, project_id = engine.schema, engine.get_project_id()
+        [order_new] = resolve_symbol(store, project_id, "Order::new")
+        assert order_new["qualified_name"] == "shop_core::orders::Order::new"
+        callers = {store.get_entity_by_id(r["source_id"])["qualified_name"]
+                   for r in store.list_relationships(target_id=order_new["id"], relation_type="calls")}
+        assert callers == {"shop_core::billing::Invoice::new"}
+        assert [e["qualified_name"] for e in resolve_symbol(store, project_id, "crate::util::new")] == ["shop_core::util::new"]
+
+    def test_update_branch_replaces_only_the_edited_file(self, indexed):
+        """A save rebuilds the edges its own items are the source of; other files' edges stay."""
+        from side.intel.tree_indexer import update_branch
+
+        project, engine = indexed
+        store, project_id = engine.schema, engine.get_project_id()
+        [util_new] = resolve_symbol(store, project_id, "crate::util::new")
+        [trim] = resolve_symbol(store, project_id, "crate::util::strings::trim")
+        store.save_relationships_batch([{
+            "id": "kept-edge", "project_id": project_id, "source_id": util_new["id"],
+            "target_id": trim["id"], "relation_type": "calls", "confidence": 0.5,
+        }])
+
+        billing = project / "src/billing.rs"
+        billing.write_text("\n" + FILES["src/billing.rs"].replace("        let _ = Order::new();\n", ""))
+        update_branch(project, billing, schema_store=store)
+
+        [order_new] = resolve_symbol(store, project_id, "Order::new")
+        assert store.list_relationships(target_id=order_new["id"], relation_type="calls") == []
+        assert [r["id"] for r in store.list_relationships(source_id=util_new["id"], relation_type="calls")] == ["kept-edge"]
+        module = store.find_entities(project_id, "billing", "module")[0]
+        assert len(store.list_relationships(source_id=module["id"], relation_type="uses")) == 3
+
+        lib = project / "src/lib.rs"
+        lib.write_text("\n\n" + FILES["src/lib.rs"])
+        update_branch(project, lib, schema_store=store)
+        assert rust_modules.inline_modules(project, lib) == [(12, 16, "shop_core::util")]
+        assert store.list_relationships(source_id=util_new["id"], relation_type="calls") == []
@@ -0,0 +1,135 @@
+"""
+Test: Rust Panic Capture
+
+Verifies panic/backtrace parsing, the cargo test summary and linking panics to SchemaStore entities.
+"""
+import pytest
+from pathlib import Path
+from side.intel.rust_panic import parse_rust_panics, parse_test_summary, link_panic_to_entity
+
+BACKTRACE_LOG = """\
+   Compiling orders v0.1.0 (/work/orders)
+thread 'main' panicked at src/lib.rs:18:37:
+called `Option::unwrap()` on a `None` value
+stack backtrace:
+   0: rust_begin_unwind
+             at /rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/std/src/panicking.rs:645:5
+   1: core::panicking::panic_fmt
+             at /rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/core/src/panicking.rs:72:14
+   2: core::option::Option<T>::unwrap
+             at /rustc/90b35a6239c3d8bdabc530a6a0816f7ff89a0aaf/library/core/src/option.rs:931:21
+   3: orders::Order::add_item
+             at ./src/lib.rs:18:37
+   4: orders::main
+             at ./src/main.rs:6:5
+note: Some details are omitted, run with `RUST_BACKTRACE=full` for a verbose backtrace.
+"""
+
+LEGACY_LOG = "thread '<unnamed>' panicked at 'index out of bounds: the len is 3 but the index is 5', src/worker.rs:40:9\n"
+
+FULL_BACKTRACE_LOG = """\
+thread 'main' panicked at src/main.rs:3:5:
+boom
+stack backtrace:
+   0:     0x55d1c2a4b1e0 - std::backtrace_rs::backtrace::libunwind::trace::h5f3c0a4b2d1e9f87
+   1:     0x55d1c2a4c2f0 - orders::process_order::h0123456789abcdef
+   2:     0x55d1c2a4c3a0 - orders::main::{{closure}}::hfedcba9876543210
+"""
+
+CARGO_TEST_OUTPUT = """\
+running 3 tests
+test tests::adds_item ... FAILED
+test tests::totals ... ok
+test tests::empty ... FAILED
+
+failures:
+
+---- tests::adds_item stdout ----
+thread 'tests::adds_item' panicked at src/lib.rs:31:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+---- tests::empty stdout ----
+thread 'tests::empty' panicked at src/lib.rs:38:9:
+explicit panic
+
+
+failures:
+    tests::adds_item
+    tests::empty
+
+test result: FAILED. 1 passed; 2 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+"""
+
+LIB_RS = """pub struct Order { items: Vec<u32> }
+
+impl Order {
+    pub fn add_item(&mut self, id: Option<u32>) {
+        self.items.push(id.unwrap());
+    }
+}
+"""
+
+
+class TestRustPanicParsing:
+    """Tests for the Rust panic parser."""
+
+    def test_modern_panic_with_backtrace(self):
+        """Thread, message, location and the first in-crate frame are extracted."""
+        panic, = parse_rust_panics(BACKTRACE_LOG)
+
+        assert panic.thread == "main"
+        assert panic.message == "called `Option::unwrap()` on a `None` value"
+        assert (panic.file, panic.line, panic.column) == ("src/lib.rs", 18, 37)
+        assert len(panic.frames) == 5
+        assert panic.first_crate_frame.function == "orders::Order::add_item"
+        assert (panic.first_crate_frame.file, panic.first_crate_frame.line) == ("src/lib.rs", 18)
+        assert BACKTRACE_LOG.splitlines()[panic.end - 1].startswith("note:")
+
+    def test_legacy_panic_header(self):
+        """The pre-1.73 single-line format keeps the quoted message."""
+        panic, = parse_rust_panics(LEGACY_LOG)
+        assert panic.thread == "<unnamed>"
+        assert panic.message == "index out of bounds: the len is 3 but the index is 5"
+        assert (panic.file, panic.line) == ("src/worker.rs", 40)
+
+    def test_full_backtrace_symbols(self):
+        """RUST_BACKTRACE=full frames lose addresses and hashes; runtime frames are skipped."""
+        panic, = parse_rust_panics(FULL_BACKTRACE_LOG)
+        assert panic.first_crate_frame.function == "orders::process_order"
+        assert panic.frames[2].symbol_path == ["orders", "main"]
+
+    def test_cargo_test_failures(self):
+        """Each failing test panic is tagged with its test; the summary lists failures."""
+        panics = parse_rust_panics(CARGO_TEST_OUTPUT)
+        assert [(p.test, p.line) for p in panics] == [("tests::adds_item", 31), ("tests::empty", 38)]
+        assert panics[0].message.startswith("assertion `left == right` failed\n  left: 1")
+
+        summary = parse_test_summary(CARGO_TEST_OUTPUT)
+        assert (summary.passed, summary.failed, summary.ignored) == (1, 2, 0)
+        assert summary.failures == ["tests::adds_item", "tests::empty"]
+
+    def test_passing_run_has_no_summary(self):
+        """Only failed runs produce a summary."""
+        assert parse_test_summary("test result: ok. 3 passed; 0 failed; 0 ignored; 0 measured") is None
+
+    def test_links_panic_to_entity(self, tmp_path):
+        """The panic location resolves to the method entity in SchemaStore."""
+        from side.storage.modules.base import ContextEngine
+
+        store = ContextEngine(tmp_path / "graph.db").schema
+        store.save_entities_batch([
+            {"id": "e1", "project_id": "proj", "name": "add_item", "entity_type": "method", "file_path": "lib.rs"},
+            {"id": "e2", "project_id": "proj", "name": "add_item", "entity_type": "function", "file_path": "cart.rs"},
+        ])
+        panic, = parse_rust_panics(BACKTRACE_LOG)
+        entity = link_panic_to_entity(panic, store, "proj", tmp_path)
+        assert entity["id"] == "e1"
+
+        # Without a backtrace the enclosing fn is read from the source file
+        (tmp_path / "src").mkdir()
+        (tmp_path / "src" / "lib.rs").write_text(LIB_RS)
+        bare, = parse_rust_panics("thread 'main' panicked at src/lib.rs:5:33:\nboom\n")
+        assert link_panic_to_entity(bare, store, "proj", tmp_path)["name"] == "add_item"
@@ -0,0 +1,75 @@
+"""
+Test: Rust Structural Extraction
+
+Verifies get_file_semantics extracts Rust items (tree-sitter and regex fallback).
+"""
+import pytest
+from pathlib import Path
+from unittest.mock import patch
+from side.intel import tree_indexer
+from side.intel.tree_indexer import get_file_semantics
+
+FIXTURE = Path(__file__).parent / "test_polyglot.rs"
+
+RUST_SOURCE = """
+pub mod orders;
+
+macro_rules! log_event {
+    ($e:expr) => {};
+}
+
+pub trait Repository {
+    fn find(&self, id: u64) -> Option<u64>;
+}
+
+pub enum Status {
+    Open,
+    Closed,
+}
+
+impl<T: Clone> Repository for Store<T> {
+    fn find(&self, id: u64) -> Option<u64> {
+        Some(id)
+    }
+}
+"""
+
+
+@pytest.fixture(params=[True, False], ids=["tree-sitter", "regex"])
+def ts_mode(request):
+    """Runs each test with and without the tree-sitter backend."""
+    if request.param and not tree_indexer.TS_AVAILABLE:
+        pytest.skip("tree-sitter not installed")
+    with patch.object(tree_indexer, "TS_AVAILABLE", request.param):
+        yield request.param
+
+
+class TestRustSemantics:
+    """Tests for Rust entity extraction."""
+
+    def test_polyglot_fixture_entities(self, ts_mode):
+        """test_polyglot.rs should yield Order, its methods and the free fn."""
+        semantics = get_file_semantics(FIXTURE, FIXTURE.read_text())
+        entities = {(e["name"], e["type"]): e for e in semantics["entities"]}
+
+        assert ("Order", "struct") in entities
+        assert ("Order", "impl") in entities
+        assert entities[("new", "method")]["parent"] == "Order"
+        assert entities[("add_item", "method")]["parent"] == "Order"
+        assert ("process_order", "function") in entities
+        assert "Order" in semantics["classes"]
+        assert {"new", "add_item", "process_order"} <= set(semantics["functions"])
+
+    def test_traits_enums_macros_modules(self, ts_mode):
+        """Traits, enums, macro_rules and modules should all be extracted."""
+        semantics = get_file_semantics(Path("lib.rs"), RUST_SOURCE)
+        entities = {(e["name"], e["type"]): e for e in semantics["entities"]}
+
+        assert ("orders", "module") in entities
+        assert ("log_event", "macro") in entities
+        assert ("Repository", "trait") in entities
+        assert ("Status", "enum") in entities
+        assert ("Store", "impl") in entities
+
+        owners = {e.get("parent") for e in semantics["entities"] if e["name"] == "find"}
+        assert owners == {"Repository", "Store"}
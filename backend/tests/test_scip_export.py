@@ -0,0 +1,176 @@
+"""
+Test: SCIP / LSIF Export
+
+Verifies the exported SCIP protobuf carries documents, definition ranges,
+stable symbols, implementation relationships and call references, and that
+LSIF JSON lines link ranges to monikers and definitions.
+"""
+import json
+import pytest
+from pathlib import Path
+from unittest.mock import patch
+
+from side.intel import tree_indexer
+from side.intel.scip_export import build_export, export_index
+
+FILES = {
+    "shop/Cargo.toml": '[package]\nname = "shop"\nversion = "0.1.0"\n',
+    "shop/src/lib.rs": """pub mod model;
+
+pub fn checkout(order: &model::Order) -> u32 {
+    order.total() + model::shipping(order)
+}
+""",
+    "shop/src/model.rs": """/// A customer order.
+pub struct Order { id: u32 }
+
+impl Order {
+    pub fn total(&self) -> u32 { self.id }
+}
+
+impl Clone for Order {
+    fn clone(&self) -> Self { Order { id: self.id } }
+}
+
+pub fn shipping(order: &Order) -> u32 { 5 }
+""",
+    "app/models.py": """class Base:
+    pass
+
+class User(Base):
+    def name(self) -> str:
+        return "x"
+""",
+}
+
+
+def decode(data: bytes) -> dict:
+    """Minimal protobuf decoder: field number -> list of raw values (ints or bytes)."""
+    fields, pos = {}, 0
+
+    def varint():
+        nonlocal pos
+        shift = value = 0
+        while True:
+            byte = data[pos]
+            pos += 1
+            value |= (byte & 0x7F) << shift
+            shift += 7
+            if not byte & 0x80:
+                return value
+
+    while pos < len(data):
+        key = varint()
+        number, wire = key >> 3, key & 7
+        if wire == 0:
+            fields.setdefault(number, []).append(varint())
+        else:
+            length = varint()
+            fields.setdefault(number, []).append(data[pos:pos + length])
+            pos += length
+    return fields
+
+
+def text(fields, number):
+    return [v.decode() for v in fields.get(number, [])]
+
+
+@pytest.fixture
+def project(tmp_path):
+    from side.storage.modules.base import ContextEngine
+    from side.intel.type_graph import index_type_graph
+
+    for rel, content in FILES.items():
+        path = tmp_path / rel
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+    store = ContextEngine(tmp_path / "graph.db").schema
+    index_type_graph(tmp_path, store)
+    with patch.object(tree_indexer, "TS_AVAILABLE", False):
+        yield tmp_path, store
+
+
+class TestScipExport:
+    """Tests for SCIP protobuf and LSIF output."""
+
+    def test_symbols_and_ranges(self, project):
+        """Symbols are built from crate/module/owner paths; definition ranges point at the name."""
+        root, store = project
+        index = build_export(root, store)
+        docs = {d.relative_path: d for d in index.documents}
+        assert set(docs) == {"shop/src/lib.rs", "shop/src/model.rs", "app/models.py"}
+
+        model = {s.display_name: s for s in docs["shop/src/model.rs"].symbols}
+        assert model["Order"].symbol == "side cargo shop . model/Order#"
+        assert model["total"].symbol == "side cargo shop . model/Order#total()."
+        assert model["total"].enclosing == "side cargo shop . model/Order#"
+        assert model["Order"].documentation == ["```rust\npub struct Order\n```", "A customer order."]
+
+        definition = next(o for o in docs["shop/src/model.rs"].occurrences if o.symbol.endswith("total()."))
+        assert (definition.line, definition.start, definition.end, definition.roles) == (4, 11, 16, 1)
+
+        python = {s.display_name: s.symbol for s in docs["app/models.py"].symbols}
+        assert python["name"] == f"side pip {root.name} . app/models/User#name()."
+
+    def test_relationships_and_references(self, project):
+        """implements/inherits edges become relationships; resolved calls become references."""
+        root, store = project
+        index = build_export(root, store)
+        docs = {d.relative_path: d for d in index.documents}
+
+        user = next(s for s in docs["app/models.py"].symbols if s.display_name == "User")
+        assert user.implements == [f"side pip {root.name} . app/models/Base#"]
+
+        order = next(s for s in docs["shop/src/model.rs"].symbols if s.display_name == "Order")
+        assert order.implements == ["side cargo . . Clone#"]
+        assert [s.symbol for s in index.external_symbols] == ["side cargo . . Clone#"]
+
+        # `order.total()` only resolves at receiver confidence, below the reference threshold
+        refs = [o for o in docs["shop/src/lib.rs"].occurrences if not o.roles]
+        assert [(o.line, o.start, o.end, o.symbol) for o in refs] == [
+            (3, 27, 35, "side cargo shop . model/shipping()."),
+        ]
+
+    def test_scip_protobuf(self, project, tmp_path):
+        """The written file decodes as scip.Index with metadata, documents and occurrences."""
+        root, store = project
+        output = tmp_path / "out" / "index.scip"
+        stats = export_index(root, store, "scip", output)
+        assert stats["documents"] == 3 and stats["references"] == 1
+
+        index = decode(output.read_bytes())
+        metadata = decode(index[1][0])
+        assert text(decode(metadata[2][0]), 1) == ["sidelith"]
+        assert text(metadata, 3) == [root.resolve().as_uri()]
+
+        docs = {text(d, 1)[0]: d for d in map(decode, index[2])}
+        model = docs["shop/src/model.rs"]
+        assert text(model, 4) == ["Rust"]
+        symbols = {text(s, 1)[0]: s for s in map(decode, model[3])}
+        total = symbols["side cargo shop . model/Order#total()."]
+        assert total[5] == [26] and text(total, 8) == ["side cargo shop . model/Order#"]
+
+        occurrence = next(o for o in map(decode, model[2]) if text(o, 2) == ["side cargo shop . model/Order#total()."])
+        assert list(occurrence[1][0]) == [4, 11, 16] and occurrence[3] == [1]
+        assert len(index[3]) == 1  # external Clone
+
+    def test_lsif(self, project, tmp_path):
+        """Each reference range resolves through its resultSet to a moniker and a definition."""
+        root, store = project
+        output = tmp_path / "index.lsif"
+        export_index(root, store, "lsif", output)
+        items = [json.loads(line) for line in output.read_text().splitlines()]
+        by_id = {i["id"]: i for i in items}
+        edges = [i for i in items if i["type"] == "edge"]
+
+        assert items[0]["label"] == "metaData"
+        ref_range = next(i for i in items if i["label"] == "range" and i["start"] == {"line": 3, "character": 27})
+        result_set = next(e["inV"] for e in edges if e["label"] == "next" and e["outV"] == ref_range["id"])
+        moniker = next(by_id[e["inV"]] for e in edges if e["label"] == "moniker" and e["outV"] == result_set)
+        assert moniker["identifier"] == "side cargo shop . model/shipping()."
+        assert any(e["label"] == "textDocument/definition" and e["outV"] == result_set for e in edges)
+
+    def test_unknown_format(self, project, tmp_path):
+        root, store = project
+        with pytest.raises(ValueError, match="Unknown export format"):
+            export_index(root, store, "ctags", tmp_path / "tags")
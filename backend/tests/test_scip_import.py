@@ -0,0 +1,213 @@
+"""
+Test: SCIP Import
+
+Verifies `.scip` indexes are decoded into definitions, references and
+implementation relationships at confidence 1.0, that they replace heuristic
+edges for the symbols they cover, and that heuristic rebuilds keep them.
+"""
+import pytest
+from pathlib import Path
+
+from side.intel.call_graph import index_call_graph
+from side.intel.scip_import import import_scip, parse_symbol
+from side.intel.type_graph import index_type_graph
+
+FILES = {
+    "shop/Cargo.toml": '[package]\nname = "shop"\nversion = "0.1.0"\n',
+    "shop/src/lib.rs": """pub mod repo;
+use repo::Repository;
+
+pub fn load(store: &dyn Repository) -> u32 {
+    store.get(1)
+}
+""",
+    "shop/src/repo.rs": """pub trait Repository {
+    fn get(&self, id: u32) -> u32;
+}
+
+pub struct Store;
+
+impl Repository for Store {
+    fn get(&self, id: u32) -> u32 { id }
+}
+""",
+}
+
+PKG = "rust-analyzer cargo shop 0.1.0 "
+CLONE = "rust-analyzer cargo std 1.0.0 clone/Clone#"
+
+
+# --- Minimal protobuf encoder ---
+
+def varint(n: int) -> bytes:
+    out = bytearray()
+    while True:
+        byte, n = n & 0x7F, n >> 7
+        out.append(byte | (0x80 if n else 0))
+        if not n:
+            return bytes(out)
+
+
+def message(*fields) -> bytes:
+    """fields: (number, value) with value an int, str, bytes or list of ints (packed)."""
+    out = bytearray()
+    for number, value in fields:
+        if isinstance(value, int):
+            out += varint(number << 3) + varint(value)
+            continue
+        if isinstance(value, list):
+            value = b"".join(varint(v) for v in value)
+        elif isinstance(value, str):
+            value = value.encode()
+        out += varint(number << 3 | 2) + varint(len(value)) + value
+    return bytes(out)
+
+
+def occurrence(symbol, range_, definition=False, enclosing=None):
+    fields = [(1, range_), (2, symbol)]
+    if definition:
+        fields.append((3, 1))
+    if enclosing:
+        fields.append((7, enclosing))
+    return (2, message(*fields))
+
+
+def symbol_info(symbol, kind=0, docs=(), implements=()):
+    fields = [(1, symbol)] + [(3, d) for d in docs]
+    fields += [(4, message((1, target), (3, 1))) for target in implements]
+    if kind:
+        fields.append((5, kind))
+    return message(*fields)
+
+
+def build_index(root: Path, enclosing: bool = True) -> bytes:
+    load_body = [3, 0, 5, 1] if enclosing else None
+    lib = message(
+        (1, "src/lib.rs"), (4, "rust"),
+        occurrence(PKG + "load().", [3, 7, 11], definition=True, enclosing=load_body),
+        occurrence(PKG + "repo/Repository#", [3, 24, 34]),
+        occurrence(PKG + "repo/Repository#get().", [4, 10, 13]),
+        (3, symbol_info(PKG + "load().", kind=17)),
+    )
+    repo = message(
+        (1, "src/repo.rs"), (4, "rust"),
+        occurrence(PKG + "repo/Repository#", [0, 10, 20], definition=True),
+        occurrence(PKG + "repo/Repository#get().", [1, 7, 10], definition=True),
+        occurrence(PKG + "repo/Store#", [4, 11, 16], definition=True),
+        occurrence(PKG + "repo/Repository#", [6, 5, 15]),
+        occurrence(PKG + "repo/Store#get().", [7, 7, 10], definition=True, enclosing=[7, 4, 40]),
+        (3, symbol_info(PKG + "repo/Repository#", kind=53)),
+        (3, symbol_info(PKG + "repo/Repository#get().", kind=70,
+                        docs=["```rust\nfn get(&self, id: u32) -> u32\n```", "Fetch by id."])),
+        (3, symbol_info(PKG + "repo/Store#", kind=49, implements=[PKG + "repo/Repository#", CLONE])),
+        (3, symbol_info(PKG + "repo/Store#get().", implements=[PKG + "repo/Repository#get()."])),
+    )
+    metadata = message((2, message((1, "rust-analyzer"))), (3, (root / "shop").as_uri()))
+    return message((1, metadata), (2, lib), (2, repo), (3, symbol_info(CLONE, kind=53)))
+
+
+@pytest.fixture
+def project(tmp_path):
+    from side.storage.modules.base import ContextEngine
+
+    for rel, content in FILES.items():
+        path = tmp_path / rel
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+    store = ContextEngine(tmp_path / "graph.db").schema
+    scip = tmp_path / "index.scip"
+    scip.write_bytes(build_index(tmp_path))
+    return tmp_path, store, scip
+
+
+def entity(store, name, entity_type):
+    project_id = store.engine.get_project_id()
+    return store.get_entity_by_name(project_id, name, entity_type)
+
+
+def edges(store, source, relation):
+    return [(r["target_id"], r["confidence"], r["origin"])
+            for r in store.list_relationships(source_id=source["id"], relation_type=relation)]
+
+
+class TestScipImport:
+    """Tests for ingesting compiler-grade SCIP indexes."""
+
+    def test_parse_symbol(self):
+        """Header fields honour double-space escapes; descriptors keep their suffix kinds."""
+        header, _, descriptors = parse_symbol("scip-python python my  pkg 1.0 `app.models`/User#name().")
+        assert header == "scip-python python my  pkg 1.0 "
+        assert [(d.name, d.suffix) for d in descriptors] == [
+            ("app.models", "namespace"), ("User", "type"), ("name", "method"),
+        ]
+        assert parse_symbol("local 4") is None
+
+    def test_definitions(self, project):
+        """Definitions land under the project-relative path with kinds, signatures, docs and parents."""
+        root, store, scip = project
+        stats = import_scip(root, scip, store)
+        assert stats["documents"] == 2 and stats["definitions"] == 5 and stats["tool"] == "rust-analyzer"
+
+        trait = entity(store, "Repository", "trait")
+        get = entity(store, "get", "method")
+        assert trait["file_path"] == "shop/src/repo.rs"
+        assert entity(store, "Store", "struct")["file_path"] == "shop/src/repo.rs"
+        assert get["signature"] == "fn get(&self, id: u32) -> u32"
+        assert get["doc"] == "Fetch by id."
+        assert get["parent_id"] == trait["id"]
+
+    def test_calls_replace_heuristic_edges(self, project):
+        """Trait-object dispatch resolves precisely; the heuristic guess for the same caller is dropped."""
+        root, store, scip = project
+        index_call_graph(root, store)
+        load = entity(store, "load", "function")
+        assert all(conf < 1.0 for _, conf, _ in edges(store, load, "calls"))
+
+        import_scip(root, scip, store)
+        get = entity(store, "get", "method")
+        assert edges(store, load, "calls") == [(get["id"], 1.0, "scip")]
+        assert edges(store, load, "references") == [(entity(store, "Repository", "trait")["id"], 1.0, "scip")]
+
+        # A later heuristic rebuild leaves the covered caller alone
+        index_call_graph(root, store)
+        assert edges(store, load, "calls") == [(get["id"], 1.0, "scip")]
+
+    def test_implementations(self, project):
+        """is_implementation relationships become implements edges, external traits included."""
+        root, store, scip = project
+        index_type_graph(root, store)
+        import_scip(root, scip, store)
+        index_type_graph(root, store)
+
+        store_type = entity(store, "Store", "struct")
+        clone = entity(store, "Clone", "trait")
+        assert clone["file_path"] is None
+        assert sorted(edges(store, store_type, "implements")) == sorted([
+            (entity(store, "Repository", "trait")["id"], 1.0, "scip"),
+            (clone["id"], 1.0, "scip"),
+        ])
+
+    def test_heuristic_spans_without_enclosing_ranges(self, project):
+        """Indexers that omit enclosing_range still attribute references to the enclosing function."""
+        root, store, scip = project
+        scip.write_bytes(build_index(root, enclosing=False))
+        import_scip(root, scip, store)
+
+        load = entity(store, "load", "function")
+        assert edges(store, load, "calls") == [(entity(store, "get", "method")["id"], 1.0, "scip")]
+
+    def test_migrates_relationships_without_origin(self, tmp_path):
+        """Databases from before SCIP import gain the origin column and keep their edges as heuristic ones."""
+        import sqlite3
+        from side.storage.modules.base import ContextEngine
+
+        with sqlite3.connect(tmp_path / "graph.db") as conn:
+            conn.execute("""CREATE TABLE relationships (id TEXT PRIMARY KEY, project_id TEXT NOT NULL DEFAULT 'default',
+                source_id TEXT NOT NULL, target_id TEXT NOT NULL, relation_type TEXT NOT NULL,
+                confidence REAL DEFAULT 1.0, created_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP)""")
+            conn.execute("INSERT INTO relationships (id, project_id, source_id, target_id, relation_type) "
+                         "VALUES ('r1', 'p', 'a', 'b', 'calls')")
+        store = ContextEngine(tmp_path / "graph.db").schema
+        assert [r["origin"] for r in store.list_relationships(source_id="a")] == ["index"]
+        store.delete_relationships("p", relation_type="calls", origin="index")
+        assert store.list_relationships(source_id="a") == []
@@ -9,6 +9,11 @@ from pathlib import Path
 from side.parallel.task_decomposer import TaskDecomposer, TaskChunk, get_decomposer
 
 
+def _sum_with_pid(chunk: TaskChunk):
+    import os
+    return sum(chunk.payload), os.getpid()
+
+
 class TestTaskDecomposer:
     """Tests for TaskDecomposer functionality."""
     
@@ -112,6 +117,21 @@ class TestTaskDecomposer:
         
         decomposer.shutdown()
     
+    def test_process_pool_runs_module_level_workers(self):
+        """use_processes runs picklable workers in other processes and still reports timings."""
+        import os
+        decomposer = TaskDecomposer(max_workers=2, use_processes=True)
+
+        chunks = decomposer.decompose_by_count(list(range(20)), num_chunks=4)
+        result = decomposer.execute_parallel(chunks, _sum_with_pid)
+
+        assert result.completed_chunks == 4
+        assert sum(total for total, _ in result.results) == sum(range(20))
+        assert all(pid != os.getpid() for _, pid in result.results)
+        assert all(chunk.duration_ms >= 0 and chunk.result for chunk in chunks)
+
+        decomposer.shutdown()
+    
     def test_global_singleton_works(self):
         """get_decomposer() should return singleton."""
         d1 = get_decomposer()
@@ -0,0 +1,181 @@
+"""
+Test: Test Links
+
+Verifies that Rust `#[test]`/`#[tokio::test]` functions, pytest functions and
+vitest/jest `it()` blocks are discovered, that `tests` edges link them to the
+code they call, and that `find_tests` answers "which tests cover X" directly
+and through helpers.
+"""
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from side.intel import test_links
+from side.intel.call_graph import build_call_graph
+from side.intel.graph_query import find_tests
+from side.intel.tree_indexer import update_branch
+
+FILES = {
+    "Cargo.toml": '[package]\nname = "shop"\nversion = "0.1.0"\n',
+    "src/lib.rs": """pub mod order;
+""",
+    "src/order.rs": """pub struct Order { items: Vec<u32> }
+
+impl Order {
+    pub fn new() -> Order { Order { items: Vec::new() } }
+    pub fn add_item(&mut self, id: u32) { self.items.push(id); }
+    pub fn total(&self) -> u32 { 0 }
+}
+
+pub fn checkout(order: &mut Order) -> u32 {
+    order.add_item(0);
+    order.total()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adds_items() {
+        let mut order = Order::new();
+        order.add_item(7);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn checks_out() {
+        let mut order = Order::new();
+        checkout(&mut order);
+    }
+
+    fn not_a_test() {
+        Order::new();
+    }
+}
+""",
+    "pkg/__init__.py": "",
+    "pkg/billing.py": """def invoice(amount):
+    return amount
+
+
+def charge(amount):
+    return invoice(amount)
+""",
+    "tests/test_billing.py": """from pkg.billing import charge, invoice
+
+
+def test_invoice():
+    assert invoice(1) == 1
+
+
+class TestCharge:
+    def test_charge(self):
+        assert charge(2) == 2
+
+    def helper(self):
+        return invoice(3)
+""",
+    "web/cart.ts": """export function addToCart(id: string): void {}
+
+export function clearCart(): void {}
+""",
+    "web/cart.test.ts": """import { addToCart, clearCart } from './cart';
+
+describe('cart', () => {
+  it('adds an item', () => {
+    addToCart("a");
+  });
+
+  test.skip("clears (later)", () => {
+    clearCart();
+  });
+});
+""",
+}
+
+
+def covering(engine, symbol, depth=2):
+    report = find_tests(engine.schema, engine.get_project_id(), symbol, depth=depth)
+    return {(t["symbol"], t["depth"], t["via"]) for t in report["tests"]}
+
+
+class TestTestMap:
+    """Tests for test discovery and `tests` edges."""
+
+    def test_discovery(self, project):
+        """Attributes, pytest naming and it()/test() blocks (nested under describe) mark tests."""
+        tests = test_links.discover_tests(project, build_call_graph(project))
+        found = {(t.framework, t.name, t.file) for t in tests}
+        assert found == {
+            ("cargo", "adds_items", "src/order.rs"),
+            ("cargo", "checks_out", "src/order.rs"),
+            ("pytest", "test_invoice", "tests/test_billing.py"),
+            ("pytest", "test_charge", "tests/test_billing.py"),
+            ("vitest", "cart > adds an item", "web/cart.test.ts"),
+            ("vitest", "cart > clears (later)", "web/cart.test.ts"),
+        }
+        block = next(t for t in tests if t.name == "cart > adds an item")
+        assert (block.line, block.end_line) == (4, 6)
+
+    def test_edges_from_tests(self, indexed):
+        """Every test links to what its body calls; JS blocks become `test` entities."""
+        _, engine = indexed
+        store = engine.schema
+        linked = {(store.get_entity_by_id(r["source_id"])["name"], store.get_entity_by_id(r["target_id"])["name"])
+                  for r in store.list_relationships(relation_type="tests")}
+        assert linked == {
+            ("adds_items", "new"), ("adds_items", "add_item"),
+            ("checks_out", "new"), ("checks_out", "checkout"),
+            ("test_invoice", "invoice"), ("test_charge", "charge"),
+            ("cart > adds an item", "addToCart"), ("cart > clears (later)", "clearCart"),
+        }
+        [block] = store.find_entities(engine.get_project_id(), "cart > adds an item")
+        assert block["entity_type"] == "test"
+        assert block["qualified_name"] == "web/cart.test.ts::cart > adds an item"
+
+    def test_which_tests_cover(self, indexed):
+        """Direct tests come first; tests reaching the symbol through a caller name it in `via`."""
+        _, engine = indexed
+        assert covering(engine, "Order::add_item") == {("adds_items", 1, "add_item"), ("checks_out", 2, "checkout")}
+        assert covering(engine, "Order::add_item", depth=1) == {("adds_items", 1, "add_item")}
+        assert covering(engine, "invoice") == {("test_invoice", 1, "invoice"), ("test_charge", 2, "charge")}
+        assert covering(engine, "addToCart") == {("cart > adds an item", 1, "addToCart")}
+        assert covering(engine, "Order::total") == {("checks_out", 2, "checkout")}
+
+    def test_edit_updates_map(self, indexed):
+        """Re-indexing a changed test file replaces its old edges."""
+        project, engine = indexed
+        spec = project / "web/cart.test.ts"
+        spec.write_text(spec.read_text().replace('addToCart("a");', "clearCart();"))
+        update_branch(project, spec, schema_store=engine.schema)
+        assert covering(engine, "addToCart") == set()
+        assert covering(engine, "clearCart") == {("cart > adds an item", 1, "clearCart"),
+                                                 ("cart > clears (later)", 1, "clearCart")}
+
+    def test_edit_relinks_callers(self, indexed):
+        """Re-indexing changed code relinks the unchanged tests that call into it."""
+        project, engine = indexed
+        code = project / "web/cart.ts"
+        code.write_text(code.read_text().replace("clearCart", "emptyCart"))
+        update_branch(project, code, schema_store=engine.schema)
+        assert covering(engine, "clearCart") == set()
+        assert covering(engine, "addToCart") == {("cart > adds an item", 1, "addToCart")}
+
+    def test_cli(self, indexed):
+        """`side tests <symbol>` lists covering tests, direct ones first."""
+        from side.cli_handlers import intel
+
+        _, engine = indexed
+        with patch.object(intel, "get_engine", return_value=engine), patch.object(intel, "ux") as ux:
+            intel.handle_tests(SimpleNamespace(symbol="Order::add_item", depth=2))
+        [(title, _, rows), _] = ux.render_table.call_args
+        assert title == "Covering Tests"
+        assert [row[0] for row in rows] == ["adds_items", "checks_out"]
+
+    def test_same_test_name_in_two_files(self, same_named_tests):
+        """`test_basic` in two files are two test entities, each linked to what it calls."""
+        _, engine = same_named_tests
+        [a] = find_tests(engine.schema, engine.get_project_id(), "foo")["tests"]
+        [b] = find_tests(engine.schema, engine.get_project_id(), "bar")["tests"]
+        assert (a["symbol"], a["file"]) == ("test_basic", "tests/test_a.py")
+        assert (b["symbol"], b["file"]) == ("test_basic", "tests/test_b.py")
+        assert len(engine.schema.find_entities(engine.get_project_id(), "test_basic", "function")) == 2
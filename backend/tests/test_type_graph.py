@@ -0,0 +1,224 @@
+"""
+Test: Type Graph
+
+Verifies `implements`/`inherits` edges for Rust impls, derives and supertraits,
+Python bases, TypeScript extends/implements and Go implicit interfaces.
+"""
+import pytest
+from pathlib import Path
+
+from side.intel.type_graph import CONFIDENCE, build_type_graph, type_graph_records, index_type_graph
+
+FILES = {
+    "repo/src/lib.rs": """
+pub trait Repository: Send + Sync + 'static {
+    fn get(&self, id: u32) -> Option<Order>;
+}
+
+#[derive(Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+#[derive(serde::Serialize)]
+pub struct Order { id: u32 }
+
+pub struct PgRepository;
+
+// impl Repository for Commented {}
+impl<T: Send> Repository for Cached<T> where T: Sync {
+    fn get(&self, id: u32) -> Option<Order> { None }
+}
+
+impl Repository for PgRepository {
+    fn get(&self, id: u32) -> Option<Order> { None }
+}
+
+impl !Sync for PgRepository {}
+
+impl PgRepository {
+    pub fn connect() -> Self { PgRepository }
+}
+""",
+    "app/models.py": """
+from typing import Generic, TypeVar
+from app import base
+
+T = TypeVar("T")
+
+class Repository(Generic[T]):
+    pass
+
+class SqlRepository(Repository[int], metaclass=base.Meta):
+    pass
+
+class CachedRepository(SqlRepository):
+    pass
+""",
+    "web/store.ts": """
+export interface Readable<T> { read(): T }
+export interface Store<T> extends Readable<T>, Disposable {}
+
+export class MemoryStore<T> extends BaseStore<T> implements Store<T>, Iterable<T> {
+  read(): T { return this.value; }
+}
+""",
+    "svc/store.go": """
+package svc
+
+import "io"
+
+type Getter interface {
+    Get(id int) (string, error)
+}
+
+type Repository interface {
+    Getter
+    Put(id int, v string) error
+}
+
+type ReadCloser interface {
+    io.Reader
+    Close() error
+}
+
+type memRepo struct{ data map[int]string }
+
+func (m *memRepo) Get(id int) (string, error) { return m.data[id], nil }
+func (m memRepo) Put(id int, v string) error   { return nil }
+
+type readOnly struct{}
+
+func (r readOnly) Get(id int) (string, error) { return "", nil }
+func (r readOnly) Close() error                { return nil }
+""",
+}
+
+
+def write_tree(root: Path):
+    for rel, content in FILES.items():
+        path = root / rel
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(content)
+
+
+def relations(files, language):
+    return {(r.source, r.relation, r.target) for f in files if f.language == language for r in f.relations}
+
+
+class TestTypeGraph:
+    """Tests for type relationship extraction."""
+
+    def test_rust(self, tmp_path):
+        """impl-for, stacked derives and supertraits; inherent and negative impls are not edges."""
+        write_tree(tmp_path)
+        rust = relations(build_type_graph(tmp_path), "rust")
+        assert rust == {
+            ("Repository", "inherits", "Send"),
+            ("Repository", "inherits", "Sync"),
+            ("Order", "implements", "Debug"),
+            ("Order", "implements", "Clone"),
+            ("Order", "implements", "Serialize"),
+            ("Cached", "implements", "Repository"),
+            ("PgRepository", "implements", "Repository"),
+        }
+
+    def test_python_and_typescript(self, tmp_path):
+        """Generic and dotted bases reduce to names; keyword arguments are not bases."""
+        write_tree(tmp_path)
+        files = build_type_graph(tmp_path)
+        assert relations(files, "python") == {
+            ("SqlRepository", "inherits", "Repository"),
+            ("CachedRepository", "inherits", "SqlRepository"),
+            ("Repository", "inherits", "Generic"),
+        }
+        assert relations(files, "typescript") == {
+            ("Store", "inherits", "Readable"),
+            ("Store", "inherits", "Disposable"),
+            ("MemoryStore", "inherits", "BaseStore"),
+            ("MemoryStore", "implements", "Store"),
+            ("MemoryStore", "implements", "Iterable"),
+        }
+
+    def test_go_structural(self, tmp_path):
+        """Method sets (value + pointer receivers, embedded interfaces) decide satisfaction."""
+        write_tree(tmp_path)
+        files = build_type_graph(tmp_path)
+        entities, edges = type_graph_records(files, "proj")
+        names = {e["id"]: e["name"] for e in entities}
+        go = {(names[e["source_id"]], e["relation_type"], names[e["target_id"]], e["confidence"]) for e in edges
+              if names[e["source_id"]] in {"memRepo", "readOnly", "Repository"} and e["confidence"] < 1}
+        assert go == {
+            ("memRepo", "implements", "Getter", CONFIDENCE["structural"]),
+            ("memRepo", "implements", "Repository", CONFIDENCE["structural"]),
+            ("readOnly", "implements", "Getter", CONFIDENCE["structural"]),
+        }
+
+    def test_who_implements(self, tmp_path):
+        """Stored edges answer "who implements Repository?" transitively, with file paths."""
+        from side.storage.modules.base import ContextEngine
+        from side.intel.graph_query import find_implementations
+
+        write_tree(tmp_path)
+        store = ContextEngine(tmp_path / "graph.db").schema
+        project_id = store.engine.get_project_id()
+        index_type_graph(tmp_path, store)
+
+        result = find_implementations(store, project_id, "Repository")
+        found = {(i["symbol"], i["file"]) for i in result["implementations"]}
+        assert ("PgRepository", "repo/src/lib.rs") in found
+        assert ("CachedRepository", "app/models.py") in found
+        assert ("memRepo", "svc/store.go") in found
+        assert ("Order", "repo/src/lib.rs") not in found
+
+    def test_bases_resolve_through_imports(self, tmp_path):
+        """Same-named bases in several files: the one the defining file imports wins, not the first or nearest."""
+        files = {
+            "py/__init__.py": "",
+            "py/base.py": "class Base:\n    pass\n",
+            "py/other.py": "class Base:\n    pass\n\nclass Model:\n    pass\n",
+            "py/models.py": (
+                "from django.db import models\n"
+                "from py import other\n"
+                "from py.base import Base as Root\n\n"
+                "class Order(Root):\n    pass\n\n"
+                "class Refund(other.Base):\n    pass\n\n"
+                "class Item(models.Model):\n    pass\n"
+            ),
+            "web/a/base.ts": "export class Base {}\n",
+            "web/b/base.ts": "export class Base {}\n",
+            "web/b/order.ts": 'import { Base } from "../a/base";\n\nexport class Order extends Base {}\n',
+        }
+        for rel, content in files.items():
+            (tmp_path / rel).parent.mkdir(parents=True, exist_ok=True)
+            (tmp_path / rel).write_text(content)
+
+        entities, edges = type_graph_records(build_type_graph(tmp_path), "proj")
+        by_id = {e["id"]: e for e in entities}
+        found = {(by_id[e["source_id"]]["file_path"], by_id[e["source_id"]]["name"],
+                  by_id[e["target_id"]]["name"], by_id[e["target_id"]]["file_path"]) for e in edges}
+        assert found == {
+            ("py/models.py", "Order", "Base", "py/base.py"),
+            ("py/models.py", "Refund", "Base", "py/other.py"),
+            ("py/models.py", "Item", "Model", None),
+            ("web/b/order.ts", "Order", "Base", "web/a/base.ts"),
+        }
+        bases = {e["qualified_name"] for e in entities if e["name"] == "Base"}
+        assert bases == {"py.base.Base", "py.other.Base", "web/a/base.ts::Base"}
+
+    def test_update_relinks_subtypes(self, tmp_path):
+        """Renaming a base relinks the unchanged subclasses that inherit it."""
+        from side.storage.modules.base import ContextEngine
+
+        (tmp_path / "pkg").mkdir()
+        (tmp_path / "pkg/a.py").write_text("class Base:\n    pass\n")
+        (tmp_path / "pkg/b.py").write_text("from pkg.a import Base\n\n\nclass Order(Base):\n    pass\n")
+        store = ContextEngine(tmp_path / "graph.db").schema
+        project_id = store.engine.get_project_id()
+        index_type_graph(tmp_path, store)
+
+        def bases():
+            [order] = store.find_entities(project_id, "Order", "class")
+            return [store.get_entity_by_id(r["target_id"]) for r in store.list_relationships(source_id=order["id"])]
+
+        assert [b["file_path"] for b in bases()] == ["pkg/a.py"]
+        (tmp_path / "pkg/a.py").write_text("class Root:\n    pass\n")
+        index_type_graph(tmp_path, store, changed=[tmp_path / "pkg/a.py"])
+        assert [(b["name"], b["file_path"]) for b in bases()] == [("Base", None)]